@@ -10,12 +10,25 @@ pub struct ContainerAttributes {
     /// Examples of valid instances (as tokenstreams)
     pub examples: Vec<proc_macro2::TokenStream>,
 
+    /// Most likely variant of a simple enum, emitted as a single `example`
+    pub example: Option<String>,
+
     /// Serde rename_all case style (from serde attribute)
     pub serde_rename_all: Option<String>,
 
     /// Custom validation function path (e.g., "validate_product" or "my_module::validate")
     pub validate: Option<String>,
 
+    /// Custom normalization function path, called with `&mut Self` after validation
+    pub normalize: Option<String>,
+
+    /// Reject fields without a description, from `#[llm(require_descriptions)]`
+    pub require_descriptions: bool,
+
+    /// List each field's `#[serde(alias)]` names in its description, from
+    /// `#[llm(document_aliases)]`
+    pub document_aliases: bool,
+
     /// Serde tag field name for internally/adjacently tagged enums
     pub serde_tag: Option<String>,
 
@@ -32,8 +45,12 @@ pub struct ContainerAttributesBuilder {
     description: Option<String>,
     title: Option<String>,
     examples: Vec<proc_macro2::TokenStream>,
+    example: Option<String>,
     serde_rename_all: Option<String>,
     validate: Option<String>,
+    normalize: Option<String>,
+    require_descriptions: bool,
+    document_aliases: bool,
     serde_tag: Option<String>,
     serde_content: Option<String>,
     serde_untagged: bool,
@@ -55,6 +72,11 @@ impl ContainerAttributesBuilder {
         self
     }
 
+    pub fn example(mut self, example: Option<String>) -> Self {
+        self.example = example;
+        self
+    }
+
     pub fn serde_rename_all(mut self, rename_all: Option<String>) -> Self {
         self.serde_rename_all = rename_all;
         self
@@ -65,6 +87,21 @@ impl ContainerAttributesBuilder {
         self
     }
 
+    pub fn normalize(mut self, normalize: Option<String>) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
+    pub fn require_descriptions(mut self, require: bool) -> Self {
+        self.require_descriptions = require;
+        self
+    }
+
+    pub fn document_aliases(mut self, document: bool) -> Self {
+        self.document_aliases = document;
+        self
+    }
+
     pub fn serde_tag(mut self, tag: Option<String>) -> Self {
         self.serde_tag = tag;
         self
@@ -85,8 +122,12 @@ impl ContainerAttributesBuilder {
             description: self.description,
             title: self.title,
             examples: self.examples,
+            example: self.example,
             serde_rename_all: self.serde_rename_all,
             validate: self.validate,
+            normalize: self.normalize,
+            require_descriptions: self.require_descriptions,
+            document_aliases: self.document_aliases,
             serde_tag: self.serde_tag,
             serde_content: self.serde_content,
             serde_untagged: self.serde_untagged,
@@ -102,12 +143,17 @@ impl ContainerAttributes {
 
     /// Returns true if there are no attributes set - currently unused but kept for future use
     #[cfg(test)]
+    #[allow(dead_code)]
     pub fn is_empty(&self) -> bool {
         self.description.is_none()
             && self.title.is_none()
             && self.examples.is_empty()
+            && self.example.is_none()
             && self.serde_rename_all.is_none()
             && self.validate.is_none()
+            && self.normalize.is_none()
+            && !self.require_descriptions
+            && !self.document_aliases
             && self.serde_tag.is_none()
             && self.serde_content.is_none()
             && !self.serde_untagged
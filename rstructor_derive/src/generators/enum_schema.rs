@@ -1,15 +1,16 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{DataEnum, Fields, Ident, Type};
+use syn::{DataEnum, Field, Fields, Ident, Type, Variant};
 
 use crate::container_attrs::ContainerAttributes;
-use crate::generators::struct_schema::apply_rename_all;
-use crate::parsers::field_parser::parse_field_attributes;
+use crate::generators::struct_schema::{apply_rename_all, timestamp_schema};
+use crate::parsers::field_parser::{describe_aliases, parse_field_attributes};
 use crate::parsers::variant_parser::parse_variant_attributes;
 use crate::type_utils::{
     get_array_inner_type, get_box_inner_type, get_map_types, get_option_inner_type,
-    get_schema_type_from_rust_type, get_tuple_element_types, is_array_type, is_box_type,
-    is_json_value_type, is_map_type, is_option_type, is_tuple_type,
+    get_schema_type_from_rust_type, get_tuple_element_types, integer_key_pattern, is_array_type,
+    is_box_type, is_date_type, is_json_value_type, is_map_type, is_option_type, is_tuple_type,
+    json_shape,
 };
 
 /// Generate the schema implementation for an enum
@@ -18,27 +19,105 @@ pub fn generate_enum_schema(
     data_enum: &DataEnum,
     container_attrs: &ContainerAttributes,
 ) -> TokenStream {
+    // A catch-all `#[llm(other)]` variant keeps the enum "simple": known values plus a
+    // free-form fallback
+    let other_variants: Vec<&Variant> = data_enum
+        .variants
+        .iter()
+        .filter(|v| parse_variant_attributes(v).other)
+        .collect();
+    if let Some(error) = check_other_variant(data_enum, &other_variants) {
+        return error;
+    }
+
     // Check if it's a simple enum (no data)
-    let all_simple = data_enum.variants.iter().all(|v| v.fields.is_empty());
+    let all_simple = data_enum
+        .variants
+        .iter()
+        .all(|v| v.fields.is_empty() || other_variants.contains(&v));
 
     if all_simple {
         // Generate implementation for simple enum as before
-        generate_simple_enum_schema(name, data_enum, container_attrs)
+        generate_simple_enum_schema(
+            name,
+            data_enum,
+            container_attrs,
+            other_variants.first().copied(),
+        )
     } else {
         // Generate implementation for enum with associated data
         generate_complex_enum_schema(name, data_enum, container_attrs)
     }
 }
 
-/// Generate schema for a simple enum (no associated data)
+/// Validate `#[llm(other)]`: at most one, on a single-field tuple variant marked
+/// `#[serde(untagged)]`, in an enum whose remaining variants are unit variants
+fn check_other_variant(data_enum: &DataEnum, other_variants: &[&Variant]) -> Option<TokenStream> {
+    let error = |variant: &Variant, message: String| {
+        Some(syn::Error::new_spanned(&variant.ident, message).to_compile_error())
+    };
+    let other = other_variants.first()?;
+    if let Some(second) = other_variants.get(1) {
+        return error(
+            second,
+            format!(
+                "only one variant can be `#[llm(other)]`, but `{}` already is",
+                other.ident
+            ),
+        );
+    }
+    if !matches!(&other.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1) {
+        return error(
+            other,
+            format!(
+                "`#[llm(other)]` variant `{}` must hold exactly one value, e.g. `{}(String)`",
+                other.ident, other.ident
+            ),
+        );
+    }
+    if !parse_variant_attributes(other).serde_untagged {
+        return error(
+            other,
+            format!(
+                "`#[llm(other)]` variant `{}` needs `#[serde(untagged)]` so serde routes \
+                 unknown values into it",
+                other.ident
+            ),
+        );
+    }
+    if let Some(variant) = data_enum
+        .variants
+        .iter()
+        .find(|v| !v.fields.is_empty() && v.ident != other.ident)
+    {
+        return error(
+            variant,
+            format!(
+                "`#[llm(other)]` requires the remaining variants to be unit variants, but `{}` \
+                 holds data",
+                variant.ident
+            ),
+        );
+    }
+    None
+}
+
+/// Generate schema for a simple enum (no associated data), optionally with a
+/// catch-all `#[llm(other)]` variant
 fn generate_simple_enum_schema(
     name: &Ident,
     data_enum: &DataEnum,
     container_attrs: &ContainerAttributes,
+    other: Option<&Variant>,
 ) -> TokenStream {
-    // Generate implementation for simple enum with serde rename support
-    let variant_values: Vec<_> = data_enum
+    let known_variants: Vec<&Variant> = data_enum
         .variants
+        .iter()
+        .filter(|v| other.is_none_or(|o| o.ident != v.ident))
+        .collect();
+
+    // Generate implementation for simple enum with serde rename support
+    let variant_values: Vec<String> = known_variants
         .iter()
         .map(|v| {
             let attrs = parse_variant_attributes(v);
@@ -82,6 +161,30 @@ fn generate_simple_enum_schema(
         });
     }
 
+    // Most likely value, named by variant or by its serialized value
+    let mut example_error = TokenStream::new();
+    if let Some(example) = &container_attrs.example {
+        let position = known_variants
+            .iter()
+            .zip(&variant_values)
+            .position(|(v, value)| v.ident == example || value == example);
+        match position {
+            Some(index) => {
+                let value = &variant_values[index];
+                container_setters.push(quote! {
+                    schema_obj["example"] = ::serde_json::Value::String(#value.to_string());
+                });
+            }
+            None => {
+                let message = format!(
+                    "`example = \"{}\"` does not name a variant of `{}`",
+                    example, name
+                );
+                example_error = syn::Error::new_spanned(name, message).to_compile_error();
+            }
+        }
+    }
+
     // Combine all container attribute setters
     let container_setter = if !container_setters.is_empty() {
         quote! {
@@ -91,7 +194,39 @@ fn generate_simple_enum_schema(
         quote! {}
     };
 
+    // The catch-all is offered after the known values, which the model should prefer
+    let other_branch = match other {
+        Some(variant) => {
+            let Fields::Unnamed(fields) = &variant.fields else {
+                unreachable!("checked by check_other_variant")
+            };
+            let other_ty = &fields.unnamed.first().unwrap().ty;
+            let other_desc = parse_variant_attributes(variant)
+                .description
+                .unwrap_or_else(|| {
+                    "Any other value, only when none of the listed values fit".to_string()
+                });
+            quote! {
+                let mut other_schema = <#other_ty as ::rstructor::schema::SchemaType>::schema().into_value();
+                if let ::serde_json::Value::Object(map) = &mut other_schema {
+                    map.insert("description".to_string(), ::serde_json::Value::String(#other_desc.to_string()));
+                }
+                let known_schema = ::serde_json::json!({
+                    "type": "string",
+                    "enum": schema_obj["enum"].take()
+                });
+                schema_obj = ::serde_json::json!({
+                    "anyOf": [known_schema, other_schema],
+                    "title": stringify!(#name)
+                });
+            }
+        }
+        None => quote! {},
+    };
+
     quote! {
+        #example_error
+
         impl ::rstructor::schema::SchemaType for #name {
             fn schema() -> ::rstructor::schema::Schema {
                 // Create array of enum values
@@ -104,6 +239,7 @@ fn generate_simple_enum_schema(
                     "enum": enum_values,
                     "title": stringify!(#name)
                 });
+                #other_branch
 
                 // Add container attributes if available
                 #container_setter
@@ -114,6 +250,8 @@ fn generate_simple_enum_schema(
             fn schema_name() -> Option<String> {
                 Some(stringify!(#name).to_string())
             }
+
+            const FLATTENABLE: bool = false;
         }
     }
 }
@@ -275,37 +413,18 @@ fn generate_externally_tagged_enum_schema(
                 let mut required_fields = Vec::new();
 
                 for field in &fields.named {
-                    if let Some(field_ident) = &field.ident {
-                        let original_field_name = field_ident.to_string();
-                        let field_attrs = parse_field_attributes(field);
-
-                        // Apply serde rename if present
-                        let field_name_str = if let Some(ref rename) = field_attrs.serde_rename {
-                            rename.clone()
-                        } else {
-                            original_field_name.clone()
-                        };
-
-                        let field_desc = field_attrs
-                            .description
-                            .unwrap_or_else(|| format!("Field {}", field_name_str));
-
-                        let is_optional = is_option_type(&field.ty);
-                        let field_schema = generate_field_schema(&field.ty, &Some(field_desc));
-
-                        let field_name_str_owned = field_name_str.clone();
-                        prop_setters.push(quote! {
-                            {
-                                let field_schema_value = #field_schema;
-                                properties_map.insert(#field_name_str_owned.to_string(), field_schema_value);
-                            }
+                    let Some((field_name_str, field_schema, required)) =
+                        variant_field_schema(field, container_attrs)
+                    else {
+                        continue;
+                    };
+                    prop_setters.push(quote! {
+                        properties_map.insert(#field_name_str.to_string(), #field_schema);
+                    });
+                    if required {
+                        required_fields.push(quote! {
+                            ::serde_json::Value::String(#field_name_str.to_string())
                         });
-
-                        if !is_optional {
-                            required_fields.push(quote! {
-                                ::serde_json::Value::String(#field_name_str.to_string())
-                            });
-                        }
                     }
                 }
 
@@ -413,10 +532,47 @@ fn generate_externally_tagged_enum_schema(
             fn schema_name() -> Option<String> {
                 Some(stringify!(#name).to_string())
             }
+
+            const FLATTENABLE: bool = false;
         }
     }
 }
 
+/// The schema of a struct variant's named field, shared by every tagging style.
+///
+/// Returns the property name, the schema tokens and whether the field is required, or
+/// `None` for fields left out of the schema (`#[serde(skip)]`, and `Option`s skipped
+/// when `None`).
+fn variant_field_schema(
+    field: &Field,
+    container_attrs: &ContainerAttributes,
+) -> Option<(String, TokenStream, bool)> {
+    let field_ident = field.ident.as_ref()?;
+    let field_attrs = parse_field_attributes(field);
+    if (field_attrs.skip_if_none && is_option_type(&field.ty)) || field_attrs.serde_skip {
+        return None;
+    }
+
+    let field_name = field_attrs
+        .serde_rename
+        .clone()
+        .unwrap_or_else(|| field_ident.to_string());
+    let description = if container_attrs.document_aliases {
+        describe_aliases(field_attrs.description, &field_attrs.serde_aliases)
+    } else {
+        field_attrs.description
+    };
+    let field_desc = description.unwrap_or_else(|| format!("Field {}", field_name));
+    let field_schema = match &field_attrs.timestamp {
+        Some(timestamp) => timestamp_schema(&format!("{} ({})", field_desc, timestamp)),
+        None => generate_field_schema(&field.ty, &Some(field_desc)),
+    };
+    let required = !is_option_type(&field.ty)
+        && !field_attrs.serde_default
+        && !field_attrs.serde_skip_serializing;
+    Some((field_name, field_schema, required))
+}
+
 /// Generate schema for a field based on its type
 fn generate_field_schema(field_type: &Type, description: &Option<String>) -> TokenStream {
     let schema_type = get_schema_type_from_rust_type(field_type);
@@ -444,15 +600,20 @@ fn generate_field_schema(field_type: &Type, description: &Option<String>) -> Tok
 
     // Handle HashMap/BTreeMap
     if is_map_type(actual_type)
-        && let Some((_key_ty, val_ty)) = get_map_types(actual_type)
+        && let Some((key_ty, val_ty)) = get_map_types(actual_type)
     {
+        // JSON keys are strings, so integer keys are described by their digits
+        let property_names = integer_key_pattern(key_ty).map(|pattern| {
+            quote! { "propertyNames": { "type": "string", "pattern": #pattern }, }
+        });
         let val_schema_type = get_schema_type_from_rust_type(val_ty);
-        if val_schema_type == "object" {
+        if val_schema_type == "object" || is_date_type(val_ty) {
             return quote! {
                 {
                     let mut schema = ::serde_json::json!({
                         "type": "object",
                         #desc_prop
+                        #property_names
                     });
                     let value_schema = <#val_ty as ::rstructor::schema::SchemaType>::schema();
                     if let ::serde_json::Value::Object(map) = &mut schema {
@@ -466,6 +627,7 @@ fn generate_field_schema(field_type: &Type, description: &Option<String>) -> Tok
                 ::serde_json::json!({
                     "type": "object",
                     #desc_prop
+                    #property_names
                     "additionalProperties": {
                         "type": #val_schema_type
                     }
@@ -490,9 +652,9 @@ fn generate_field_schema(field_type: &Type, description: &Option<String>) -> Tok
             .iter()
             .map(|elem_ty| {
                 let elem_schema_type = get_schema_type_from_rust_type(elem_ty);
-                if elem_schema_type == "object" {
+                if elem_schema_type == "object" || is_date_type(elem_ty) {
                     quote! {
-                        <#elem_ty as ::rstructor::schema::SchemaType>::schema().to_json()
+                        <#elem_ty as ::rstructor::schema::SchemaType>::schema().into_value()
                     }
                 } else {
                     quote! {
@@ -522,11 +684,11 @@ fn generate_field_schema(field_type: &Type, description: &Option<String>) -> Tok
         if let Some(inner_type) = get_array_inner_type(actual_type) {
             let inner_schema_type = get_schema_type_from_rust_type(inner_type);
 
-            if inner_schema_type == "object" {
+            if inner_schema_type == "object" || is_date_type(inner_type) {
                 // For arrays of complex types
                 return quote! {
                     {
-                        let items_schema = <#inner_type as ::rstructor::schema::SchemaType>::schema().to_json();
+                        let items_schema = <#inner_type as ::rstructor::schema::SchemaType>::schema().into_value();
                         ::serde_json::json!({
                             "type": "array",
                             #desc_prop
@@ -558,8 +720,8 @@ fn generate_field_schema(field_type: &Type, description: &Option<String>) -> Tok
         }
     }
 
-    // Handle custom object types
-    if schema_type == "object"
+    // Handle custom object types and chrono dates (which carry `format: date-time`)
+    if (schema_type == "object" || is_date_type(actual_type))
         && let Type::Path(type_path) = actual_type
         && type_path.path.segments.last().is_some()
     {
@@ -567,7 +729,7 @@ fn generate_field_schema(field_type: &Type, description: &Option<String>) -> Tok
             let desc_str = desc.clone();
             return quote! {
                 {
-                    let mut obj = <#type_path as ::rstructor::schema::SchemaType>::schema().to_json().clone();
+                    let mut obj = <#type_path as ::rstructor::schema::SchemaType>::schema().into_value();
                     if let ::serde_json::Value::Object(map) = &mut obj {
                         map.insert("description".to_string(), ::serde_json::Value::String(#desc_str.to_string()));
                     }
@@ -576,7 +738,7 @@ fn generate_field_schema(field_type: &Type, description: &Option<String>) -> Tok
             };
         } else {
             return quote! {
-                <#type_path as ::rstructor::schema::SchemaType>::schema().to_json()
+                <#type_path as ::rstructor::schema::SchemaType>::schema().into_value()
             };
         }
     }
@@ -651,36 +813,18 @@ fn generate_internally_tagged_enum_schema(
                 }];
 
                 for field in &fields.named {
-                    if let Some(field_ident) = &field.ident {
-                        let original_field_name = field_ident.to_string();
-                        let field_attrs = parse_field_attributes(field);
-
-                        let field_name_str = if let Some(ref rename) = field_attrs.serde_rename {
-                            rename.clone()
-                        } else {
-                            original_field_name.clone()
-                        };
-
-                        let field_desc = field_attrs
-                            .description
-                            .unwrap_or_else(|| format!("Field {}", field_name_str));
-
-                        let is_optional = is_option_type(&field.ty);
-                        let field_schema = generate_field_schema(&field.ty, &Some(field_desc));
-
-                        let field_name_str_owned = field_name_str.clone();
-                        prop_setters.push(quote! {
-                            {
-                                let field_schema_value = #field_schema;
-                                properties.insert(#field_name_str_owned.to_string(), field_schema_value);
-                            }
+                    let Some((field_name_str, field_schema, required)) =
+                        variant_field_schema(field, container_attrs)
+                    else {
+                        continue;
+                    };
+                    prop_setters.push(quote! {
+                        properties.insert(#field_name_str.to_string(), #field_schema);
+                    });
+                    if required {
+                        required_fields.push(quote! {
+                            ::serde_json::Value::String(#field_name_str.to_string())
                         });
-
-                        if !is_optional {
-                            required_fields.push(quote! {
-                                ::serde_json::Value::String(#field_name_str.to_string())
-                            });
-                        }
                     }
                 }
 
@@ -766,6 +910,8 @@ fn generate_internally_tagged_enum_schema(
             fn schema_name() -> Option<String> {
                 Some(stringify!(#name).to_string())
             }
+
+            const FLATTENABLE: bool = false;
         }
     }
 }
@@ -927,38 +1073,19 @@ fn generate_adjacently_tagged_enum_schema(
                 let mut field_names = Vec::new();
 
                 for field in &fields.named {
-                    if let Some(field_ident) = &field.ident {
-                        let original_field_name = field_ident.to_string();
-                        let field_attrs = parse_field_attributes(field);
-
-                        let field_name_str = if let Some(ref rename) = field_attrs.serde_rename {
-                            rename.clone()
-                        } else {
-                            original_field_name.clone()
-                        };
-
-                        field_names.push(field_name_str.clone());
-
-                        let field_desc = field_attrs
-                            .description
-                            .unwrap_or_else(|| format!("Field {}", field_name_str));
-
-                        let is_optional = is_option_type(&field.ty);
-                        let field_schema = generate_field_schema(&field.ty, &Some(field_desc));
-
-                        let field_name_str_owned = field_name_str.clone();
-                        prop_setters.push(quote! {
-                            {
-                                let field_schema_value = #field_schema;
-                                content_properties.insert(#field_name_str_owned.to_string(), field_schema_value);
-                            }
+                    let Some((field_name_str, field_schema, required)) =
+                        variant_field_schema(field, container_attrs)
+                    else {
+                        continue;
+                    };
+                    field_names.push(field_name_str.clone());
+                    prop_setters.push(quote! {
+                        content_properties.insert(#field_name_str.to_string(), #field_schema);
+                    });
+                    if required {
+                        required_content_fields.push(quote! {
+                            ::serde_json::Value::String(#field_name_str.to_string())
                         });
-
-                        if !is_optional {
-                            required_content_fields.push(quote! {
-                                ::serde_json::Value::String(#field_name_str.to_string())
-                            });
-                        }
                     }
                 }
 
@@ -1057,10 +1184,71 @@ fn generate_adjacently_tagged_enum_schema(
             fn schema_name() -> Option<String> {
                 Some(stringify!(#name).to_string())
             }
+
+            const FLATTENABLE: bool = false;
         }
     }
 }
 
+/// Structural fingerprint of an untagged variant's payload.
+///
+/// Two variants with the same fingerprint serialize to JSON of the same shape, so
+/// neither serde nor the model can tell them apart. Fields serde never reads
+/// (`#[serde(skip)]`) don't count towards the shape.
+fn untagged_variant_shape(fields: &Fields) -> String {
+    match fields {
+        Fields::Unit => "null".to_string(),
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            json_shape(&fields.unnamed.first().unwrap().ty)
+        }
+        Fields::Unnamed(fields) => {
+            let types: Vec<String> = fields
+                .unnamed
+                .iter()
+                .map(|field| json_shape(&field.ty))
+                .collect();
+            format!("[{}]", types.join(", "))
+        }
+        Fields::Named(fields) => {
+            let mut entries: Vec<String> = fields
+                .named
+                .iter()
+                .filter_map(|field| {
+                    let field_attrs = parse_field_attributes(field);
+                    if field_attrs.serde_skip {
+                        return None;
+                    }
+                    let field_name = field_attrs
+                        .serde_rename
+                        .unwrap_or_else(|| field.ident.as_ref().unwrap().to_string());
+                    Some(format!("{}: {}", field_name, json_shape(&field.ty)))
+                })
+                .collect();
+            entries.sort();
+            format!("{{{}}}", entries.join(", "))
+        }
+    }
+}
+
+/// Reject untagged enums where two variants have structurally identical payloads
+fn check_untagged_ambiguity(data_enum: &DataEnum) -> Option<TokenStream> {
+    let mut seen: Vec<(String, &Ident)> = Vec::new();
+    for variant in &data_enum.variants {
+        let shape = untagged_variant_shape(&variant.fields);
+        if let Some((_, previous)) = seen.iter().find(|(s, _)| *s == shape) {
+            let message = format!(
+                "untagged variants `{}` and `{}` have the same shape ({}), so the LLM \
+                 cannot reliably choose between them; give them distinct payloads or use \
+                 a tagged representation",
+                previous, variant.ident, shape
+            );
+            return Some(syn::Error::new_spanned(&variant.ident, message).to_compile_error());
+        }
+        seen.push((shape, &variant.ident));
+    }
+    None
+}
+
 /// Generate schema for untagged enums
 /// Format: Just the data, discriminated by structure
 fn generate_untagged_enum_schema(
@@ -1068,6 +1256,9 @@ fn generate_untagged_enum_schema(
     data_enum: &DataEnum,
     container_attrs: &ContainerAttributes,
 ) -> TokenStream {
+    // Emitted alongside the impl so the ambiguity is the only error reported
+    let ambiguity_error = check_untagged_ambiguity(data_enum).unwrap_or_default();
+
     let mut variant_schemas = Vec::new();
 
     for variant in &data_enum.variants {
@@ -1128,36 +1319,18 @@ fn generate_untagged_enum_schema(
                 let mut required_fields = Vec::new();
 
                 for field in &fields.named {
-                    if let Some(field_ident) = &field.ident {
-                        let original_field_name = field_ident.to_string();
-                        let field_attrs = parse_field_attributes(field);
-
-                        let field_name_str = if let Some(ref rename) = field_attrs.serde_rename {
-                            rename.clone()
-                        } else {
-                            original_field_name.clone()
-                        };
-
-                        let field_desc = field_attrs
-                            .description
-                            .unwrap_or_else(|| format!("Field {}", field_name_str));
-
-                        let is_optional = is_option_type(&field.ty);
-                        let field_schema = generate_field_schema(&field.ty, &Some(field_desc));
-
-                        let field_name_str_owned = field_name_str.clone();
-                        prop_setters.push(quote! {
-                            {
-                                let field_schema_value = #field_schema;
-                                properties.insert(#field_name_str_owned.to_string(), field_schema_value);
-                            }
+                    let Some((field_name_str, field_schema, required)) =
+                        variant_field_schema(field, container_attrs)
+                    else {
+                        continue;
+                    };
+                    prop_setters.push(quote! {
+                        properties.insert(#field_name_str.to_string(), #field_schema);
+                    });
+                    if required {
+                        required_fields.push(quote! {
+                            ::serde_json::Value::String(#field_name_str.to_string())
                         });
-
-                        if !is_optional {
-                            required_fields.push(quote! {
-                                ::serde_json::Value::String(#field_name_str.to_string())
-                            });
-                        }
                     }
                 }
 
@@ -1195,6 +1368,8 @@ fn generate_untagged_enum_schema(
     let container_setter = generate_container_setters(container_attrs);
 
     quote! {
+        #ambiguity_error
+
         impl ::rstructor::schema::SchemaType for #name {
             fn schema() -> ::rstructor::schema::Schema {
                 let variant_schemas = vec![
@@ -1214,6 +1389,8 @@ fn generate_untagged_enum_schema(
             fn schema_name() -> Option<String> {
                 Some(stringify!(#name).to_string())
             }
+
+            const FLATTENABLE: bool = false;
         }
     }
 }
@@ -1,13 +1,14 @@
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote, quote_spanned};
+use syn::spanned::Spanned;
 use syn::{DataStruct, Fields, Ident, Type};
 
 use crate::container_attrs::ContainerAttributes;
-use crate::parsers::field_parser::parse_field_attributes;
+use crate::parsers::field_parser::{describe_aliases, parse_field_attributes};
 use crate::type_utils::{
-    get_array_inner_type, get_box_inner_type, get_map_types, get_option_inner_type,
-    get_schema_type_from_rust_type, get_tuple_element_types, is_array_type, is_box_type,
-    is_json_value_type, is_map_type, is_option_type, is_self_reference, is_tuple_type,
+    TypeCategory, get_array_inner_type, get_box_inner_type, get_map_types, get_option_inner_type,
+    get_tuple_element_types, get_type_category, integer_key_pattern, is_array_type, is_map_type,
+    is_option_type, is_self_reference, is_set_type,
 };
 
 /// Generate the schema implementation for a struct
@@ -18,6 +19,11 @@ pub fn generate_struct_schema(
 ) -> TokenStream {
     let mut property_setters = Vec::new();
     let mut required_setters = Vec::new();
+    // Names of the properties listed directly, and the fields whose type's properties
+    // are inlined by `#[serde(flatten)]`
+    let mut property_names = Vec::new();
+    let mut flattened_fields = Vec::new();
+    let mut flatten_checks = Vec::new();
     let mut has_self_reference = false;
     let struct_name_str = name.to_string();
 
@@ -34,6 +40,59 @@ pub fn generate_struct_schema(
             for field in &fields.named {
                 // Parse field attributes first to check for serde rename
                 let attrs = parse_field_attributes(field);
+                // Non-Option fields are rejected in `skip_if_none_errors`. Fields serde
+                // never deserializes would be dropped from any response, so they're left
+                // out too
+                if (attrs.skip_if_none && is_option_type(&field.ty)) || attrs.serde_skip {
+                    continue;
+                }
+
+                // Flattened fields contribute their type's properties instead of one of
+                // their own, matching the JSON serde reads. Under `Option` none of them
+                // are required
+                if attrs.serde_flatten {
+                    let ident = field.ident.as_ref().unwrap();
+                    let inner_ty = get_option_inner_type(&field.ty);
+                    let flattened = format_ident!("__flattened_{}", ident);
+                    let inner_schema = generate_type_schema(inner_ty, &struct_name_str);
+                    property_setters.push(quote! {
+                        let #flattened = #inner_schema;
+                        if let ::std::option::Option::Some(::serde_json::Value::Object(inner)) = #flattened.get("properties") {
+                            if let ::serde_json::Value::Object(obj) = schema_obj.get_mut("properties").unwrap() {
+                                for (key, value) in inner {
+                                    obj.insert(key.clone(), value.clone());
+                                }
+                            }
+                        }
+                        // Flattened maps collect any remaining keys
+                        if let ::std::option::Option::Some(additional) = #flattened.get("additionalProperties") {
+                            schema_obj["additionalProperties"] = additional.clone();
+                        }
+                    });
+                    if !is_option_type(&field.ty) {
+                        required_setters.push(quote! {
+                            if let ::std::option::Option::Some(::serde_json::Value::Array(inner)) = #flattened.get("required") {
+                                required.extend(inner.iter().cloned());
+                            }
+                        });
+                    }
+                    // Types without top-level properties (enums, primitives, recursive
+                    // structs) would silently add nothing, so they're a compile error
+                    let message = format!(
+                        "`#[serde(flatten)]` field `{}` has a type whose schema has no top-level properties to inline; only structs and maps can be flattened",
+                        ident
+                    );
+                    flatten_checks.push(quote_spanned! {inner_ty.span()=>
+                        const _: () = ::rstructor::schema::assert_flattenable(
+                            <#inner_ty as ::rstructor::schema::SchemaType>::FLATTENABLE,
+                            #message,
+                        );
+                    });
+                    if !is_map_type(inner_ty) {
+                        flattened_fields.push(field);
+                    }
+                    continue;
+                }
 
                 let original_field_name = field.ident.as_ref().unwrap().to_string();
                 // Priority: 1) field-level #[serde(rename)], 2) container #[serde(rename_all)], 3) original name
@@ -46,351 +105,55 @@ pub fn generate_struct_schema(
                     original_field_name
                 };
                 let is_optional = is_option_type(&field.ty);
+                property_names.push(field_name.clone());
 
-                // Get schema type
-                let schema_type = get_schema_type_from_rust_type(&field.ty);
+                // Build the field schema, delegating to SchemaType wherever an impl exists.
+                // Dates serialized as Unix timestamps are integers on the wire
+                let type_schema = match &attrs.timestamp {
+                    Some(timestamp) => timestamp_schema(timestamp),
+                    None => generate_type_schema(&field.ty, &struct_name_str),
+                };
+                property_setters.push(quote! {
+                    let mut props = match #type_schema {
+                        ::serde_json::Value::Object(m) => m,
+                        _ => ::serde_json::Map::new(),
+                    };
+                });
 
-                // Extract type name for well-known library types only (exact matches, no heuristics)
-                let type_name = if let Type::Path(type_path) = &field.ty {
-                    type_path
-                        .path
-                        .segments
-                        .first()
-                        .map(|segment| segment.ident.to_string())
+                // Add title if available
+                if let Some(title) = &attrs.title {
+                    property_setters.push(quote! {
+                        props.insert("title".to_string(), ::serde_json::Value::String(#title.to_string()));
+                    });
+                }
+
+                // Add description if available. The field description replaces the
+                // type's own description, except for the "Keys: [..]" hint on maps and
+                // the unit of timestamps
+                let description = match (attrs.description, &attrs.timestamp) {
+                    (Some(desc), Some(timestamp)) => Some(format!("{} ({})", desc, timestamp)),
+                    (desc, _) => desc,
+                };
+                let description = if container_attrs.document_aliases {
+                    describe_aliases(description, &attrs.serde_aliases)
                 } else {
-                    None
+                    description
                 };
-
-                // Check for well-known library types by exact match only (no contains checks)
-                let is_date_type = matches!(
-                    type_name.as_deref(),
-                    Some("DateTime") | Some("NaiveDateTime") | Some("NaiveDate") | Some("Date")
-                );
-                let is_uuid_type = matches!(type_name.as_deref(), Some("Uuid"));
-
-                // Create field property
-                // IMPORTANT: Default to treating unknown types as structs (objects)
-                // Structs are far more common than enums, and this is the safest default
-                let field_prop = if is_date_type {
-                    // For date types
-                    quote! {
-                        // Create property for this date field
-                        let mut props = ::serde_json::Map::new();
-                        props.insert("type".to_string(), ::serde_json::Value::String("string".to_string()));
-                        props.insert("format".to_string(), ::serde_json::Value::String("date-time".to_string()));
-                        props.insert("description".to_string(),
-                                    ::serde_json::Value::String("ISO-8601 formatted date and time".to_string()));
-                    }
-                } else if is_uuid_type {
-                    // For UUID types
-                    quote! {
-                        // Create property for this UUID field
-                        let mut props = ::serde_json::Map::new();
-                        props.insert("type".to_string(), ::serde_json::Value::String("string".to_string()));
-                        props.insert("format".to_string(), ::serde_json::Value::String("uuid".to_string()));
-                        props.insert("description".to_string(),
-                                    ::serde_json::Value::String("UUID identifier string".to_string()));
-                    }
-                } else if is_array_type(&field.ty)
-                    || (is_optional && is_array_type(get_option_inner_type(&field.ty)))
-                {
-                    // For array types (including Option<Vec<T>>), we need to add the 'items' property
-                    let actual_array_type =
-                        if is_optional && is_array_type(get_option_inner_type(&field.ty)) {
-                            get_option_inner_type(&field.ty)
-                        } else {
-                            &field.ty
-                        };
-                    if let Some(inner_type) = get_array_inner_type(actual_array_type) {
-                        // Get the inner schema type
-                        let inner_schema_type = get_schema_type_from_rust_type(inner_type);
-
-                        // Extract inner type name for well-known library types only
-                        let inner_type_name = if let Type::Path(type_path) = inner_type {
-                            type_path
-                                .path
-                                .segments
-                                .first()
-                                .map(|segment| segment.ident.to_string())
-                        } else {
-                            None
-                        };
-
-                        // Check for well-known library types by exact match only
-                        let is_date = matches!(
-                            inner_type_name.as_deref(),
-                            Some("DateTime")
-                                | Some("NaiveDateTime")
-                                | Some("NaiveDate")
-                                | Some("Date")
-                        );
-                        let is_uuid = matches!(inner_type_name.as_deref(), Some("Uuid"));
-
-                        // Generate items schema
-                        if is_date {
-                            // Handle array of dates
-                            quote! {
-                                // Create property for this array field with date items
-                                let mut props = ::serde_json::Map::new();
-                                props.insert("type".to_string(), ::serde_json::Value::String(#schema_type.to_string()));
-
-                                // Add items schema for dates
-                                let mut items_schema = ::serde_json::Map::new();
-                                items_schema.insert("type".to_string(), ::serde_json::Value::String("string".to_string()));
-                                items_schema.insert("format".to_string(), ::serde_json::Value::String("date-time".to_string()));
-                                items_schema.insert("description".to_string(),
-                                    ::serde_json::Value::String("ISO-8601 formatted date and time".to_string()));
-
-                                props.insert("items".to_string(), ::serde_json::Value::Object(items_schema));
-                            }
-                        } else if is_uuid {
-                            // Handle array of UUIDs
-                            quote! {
-                                // Create property for this array field with UUID items
-                                let mut props = ::serde_json::Map::new();
-                                props.insert("type".to_string(), ::serde_json::Value::String(#schema_type.to_string()));
-
-                                // Add items schema for UUIDs
-                                let mut items_schema = ::serde_json::Map::new();
-                                items_schema.insert("type".to_string(), ::serde_json::Value::String("string".to_string()));
-                                items_schema.insert("format".to_string(), ::serde_json::Value::String("uuid".to_string()));
-                                items_schema.insert("description".to_string(),
-                                    ::serde_json::Value::String("UUID identifier string".to_string()));
-
-                                props.insert("items".to_string(), ::serde_json::Value::Object(items_schema));
-                            }
-                        } else if inner_schema_type == "object" {
-                            // Check if this is a self-reference (recursive type)
-                            let struct_name_str = name.to_string();
-                            if is_self_reference(inner_type, &struct_name_str) {
-                                // For self-referential types, use $ref to prevent infinite recursion
-                                quote! {
-                                    // Create property for this array field with recursive reference
-                                    let mut props = ::serde_json::Map::new();
-                                    props.insert("type".to_string(), ::serde_json::Value::String(#schema_type.to_string()));
-
-                                    // Use $ref for recursive type
-                                    let mut items_schema = ::serde_json::Map::new();
-                                    items_schema.insert("$ref".to_string(), ::serde_json::Value::String(format!("#/$defs/{}", #struct_name_str)));
-                                    props.insert("items".to_string(), ::serde_json::Value::Object(items_schema));
-                                }
-                            } else {
-                                // For arrays of nested structs, embed the inner type's schema directly
-                                // This requires the inner type to implement SchemaType
-                                quote! {
-                                    // Create property for this array field with nested struct items
-                                    let mut props = ::serde_json::Map::new();
-                                    props.insert("type".to_string(), ::serde_json::Value::String(#schema_type.to_string()));
-
-                                    // Get the inner type's schema directly
-                                    // This embeds the full schema with properties at compile time
-                                    let inner_schema = <#inner_type as ::rstructor::schema::SchemaType>::schema();
-                                    let items_schema = inner_schema.to_json();
-
-                                    props.insert("items".to_string(), items_schema);
-                                }
-                            }
-                        } else {
-                            // Standard handling for primitive types
-                            quote! {
-                                // Create property for this array field
-                                let mut props = ::serde_json::Map::new();
-                                props.insert("type".to_string(), ::serde_json::Value::String(#schema_type.to_string()));
-
-                                // Add items schema
-                                let mut items_schema = ::serde_json::Map::new();
-                                items_schema.insert("type".to_string(), ::serde_json::Value::String(#inner_schema_type.to_string()));
-                                props.insert("items".to_string(), ::serde_json::Value::Object(items_schema));
-                            }
-                        }
-                    } else {
-                        // Fallback for array without detectable item type
-                        quote! {
-                            // Create property for this array field (fallback)
-                            let mut props = ::serde_json::Map::new();
-                            props.insert("type".to_string(), ::serde_json::Value::String(#schema_type.to_string()));
-
-                            // Add default items schema
-                            let mut items_schema = ::serde_json::Map::new();
-                            items_schema.insert("type".to_string(), ::serde_json::Value::String("string".to_string()));
-                            props.insert("items".to_string(), ::serde_json::Value::Object(items_schema));
-                        }
-                    }
-                } else if is_json_value_type(&field.ty)
-                    || (is_optional && is_json_value_type(get_option_inner_type(&field.ty)))
-                {
-                    // For serde_json::Value, use an empty schema (any JSON is valid)
-                    quote! {
-                        let mut props = ::serde_json::Map::new();
-                        // Empty object schema means any JSON value is accepted
-                    }
-                } else if is_map_type(&field.ty)
-                    || (is_optional && is_map_type(get_option_inner_type(&field.ty)))
-                {
-                    // For HashMap<K, V> or BTreeMap<K, V>
-                    let actual_type = if is_optional {
-                        get_option_inner_type(&field.ty)
-                    } else {
-                        &field.ty
-                    };
-                    if let Some((key_ty, val_ty)) = get_map_types(actual_type) {
-                        // Use SchemaType::schema() for all value types to get complete schema
-                        // This ensures arrays get proper `items`, objects get properties, etc.
-                        // For enum keys, extract the enum variants and add them to description
-                        // so that Gemini can use the correct keys instead of generic placeholders
+                if let Some(desc) = description {
+                    let is_map_field = is_map_type(get_option_inner_type(&field.ty));
+                    let desc_prop = if is_map_field {
                         quote! {
-                            let mut props = ::serde_json::Map::new();
-                            props.insert("type".to_string(), ::serde_json::Value::String("object".to_string()));
-                            let value_schema = <#val_ty as ::rstructor::schema::SchemaType>::schema();
-                            props.insert("additionalProperties".to_string(), value_schema.to_json());
-
-                            // Try to extract enum keys from key type schema (for enum keys)
-                            let key_schema = <#key_ty as ::rstructor::schema::SchemaType>::schema();
-                            if let Some(enum_values) = key_schema.to_json().get("enum")
-                                .and_then(|e| e.as_array())
+                            if let Some(existing_desc) = props.get("description")
+                                .and_then(|d| d.as_str())
                             {
-                                let keys: Vec<String> = enum_values
-                                    .iter()
-                                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
-                                    .collect();
-                                if !keys.is_empty() {
-                                    let keys_hint = format!("Keys: [{}]", keys.join(", "));
-                                    props.insert("description".to_string(), ::serde_json::Value::String(keys_hint));
-                                }
-                            }
-                        }
-                    } else {
-                        // Fallback for map without detectable types
-                        quote! {
-                            let mut props = ::serde_json::Map::new();
-                            props.insert("type".to_string(), ::serde_json::Value::String("object".to_string()));
-                        }
-                    }
-                } else if is_box_type(&field.ty)
-                    || (is_optional && is_box_type(get_option_inner_type(&field.ty)))
-                {
-                    // For Box<T>, unwrap and use the inner type's schema
-                    let actual_type = if is_optional {
-                        get_option_inner_type(&field.ty)
-                    } else {
-                        &field.ty
-                    };
-                    if let Some(inner_ty) = get_box_inner_type(actual_type) {
-                        let inner_schema_type = get_schema_type_from_rust_type(inner_ty);
-                        if inner_schema_type == "object" {
-                            // Inner type is a complex type, use its schema
-                            quote! {
-                                let nested_schema = <#inner_ty as ::rstructor::schema::SchemaType>::schema();
-                                let props_json = nested_schema.to_json();
-                                let mut props = if let ::serde_json::Value::Object(m) = props_json {
-                                    m
-                                } else {
-                                    let mut m = ::serde_json::Map::new();
-                                    m.insert("type".to_string(), ::serde_json::Value::String("object".to_string()));
-                                    m
-                                };
-                            }
-                        } else {
-                            // Inner type is a primitive
-                            quote! {
-                                let mut props = ::serde_json::Map::new();
-                                props.insert("type".to_string(), ::serde_json::Value::String(#inner_schema_type.to_string()));
+                                let merged_desc = format!("{}. {}", #desc, existing_desc);
+                                props.insert("description".to_string(), ::serde_json::Value::String(merged_desc));
+                            } else {
+                                props.insert("description".to_string(), ::serde_json::Value::String(#desc.to_string()));
                             }
                         }
                     } else {
-                        // Fallback
                         quote! {
-                            let mut props = ::serde_json::Map::new();
-                            props.insert("type".to_string(), ::serde_json::Value::String("object".to_string()));
-                        }
-                    }
-                } else if is_tuple_type(&field.ty)
-                    || (is_optional && is_tuple_type(get_option_inner_type(&field.ty)))
-                {
-                    // For tuples, generate array with prefixItems
-                    let actual_type = if is_optional {
-                        get_option_inner_type(&field.ty)
-                    } else {
-                        &field.ty
-                    };
-                    if let Some(element_types) = get_tuple_element_types(actual_type) {
-                        let element_count = element_types.len();
-                        // Generate schema for each element
-                        let element_schemas: Vec<TokenStream> = element_types
-                            .iter()
-                            .map(|elem_ty| {
-                                let elem_schema_type = get_schema_type_from_rust_type(elem_ty);
-                                if elem_schema_type == "object" {
-                                    quote! {
-                                        <#elem_ty as ::rstructor::schema::SchemaType>::schema().to_json()
-                                    }
-                                } else {
-                                    quote! {
-                                        ::serde_json::json!({"type": #elem_schema_type})
-                                    }
-                                }
-                            })
-                            .collect();
-                        quote! {
-                            let mut props = ::serde_json::Map::new();
-                            props.insert("type".to_string(), ::serde_json::Value::String("array".to_string()));
-                            let prefix_items = vec![
-                                #(#element_schemas),*
-                            ];
-                            props.insert("prefixItems".to_string(), ::serde_json::Value::Array(prefix_items));
-                            props.insert("minItems".to_string(), ::serde_json::Value::Number(::serde_json::Number::from(#element_count)));
-                            props.insert("maxItems".to_string(), ::serde_json::Value::Number(::serde_json::Number::from(#element_count)));
-                        }
-                    } else {
-                        // Fallback for tuple without detectable elements
-                        quote! {
-                            let mut props = ::serde_json::Map::new();
-                            props.insert("type".to_string(), ::serde_json::Value::String("array".to_string()));
-                        }
-                    }
-                } else if type_name.is_some() && schema_type == "object" {
-                    // For nested struct fields, embed the inner type's schema directly
-                    // This requires the inner type to implement SchemaType
-                    // If it's an Option<T>, unwrap to get T first
-                    let actual_type = if is_optional {
-                        get_option_inner_type(&field.ty)
-                    } else {
-                        &field.ty
-                    };
-                    quote! {
-                        // Get the nested type's schema directly
-                        let nested_schema = <#actual_type as ::rstructor::schema::SchemaType>::schema();
-                        let props_json = nested_schema.to_json();
-                        // Convert to a mutable map so we can add to it
-                        let mut props = if let ::serde_json::Value::Object(m) = props_json {
-                            m
-                        } else {
-                            let mut m = ::serde_json::Map::new();
-                            m.insert("type".to_string(), ::serde_json::Value::String("object".to_string()));
-                            m
-                        };
-                    }
-                } else {
-                    // Regular primitive type
-                    quote! {
-                        // Create property for this field
-                        let mut props = ::serde_json::Map::new();
-                        props.insert("type".to_string(), ::serde_json::Value::String(#schema_type.to_string()));
-                    }
-                };
-                property_setters.push(field_prop);
-
-                // Add description if available
-                if let Some(desc) = attrs.description {
-                    let desc_prop = quote! {
-                        // Check if there's already a description (e.g., from enum keys hint)
-                        // If so, merge them; otherwise, use the user's description
-                        if let Some(existing_desc) = props.get("description")
-                            .and_then(|d| d.as_str())
-                        {
-                            let merged_desc = format!("{}. {}", #desc, existing_desc);
-                            props.insert("description".to_string(), ::serde_json::Value::String(merged_desc));
-                        } else {
                             props.insert("description".to_string(), ::serde_json::Value::String(#desc.to_string()));
                         }
                     };
@@ -406,6 +169,88 @@ pub fn generate_struct_schema(
                     property_setters.push(ex_prop);
                 }
 
+                // Add schema default if available
+                if let Some(default_val) = &attrs.default_value {
+                    property_setters.push(quote! {
+                        let default_value = #default_val;
+                        props.insert("default".to_string(), default_value);
+                    });
+                }
+
+                // Add map cardinality constraints (only meaningful on map fields)
+                if is_map_type(get_option_inner_type(&field.ty)) {
+                    if let Some(min) = attrs.min_properties {
+                        property_setters.push(quote! {
+                            props.insert("minProperties".to_string(), ::serde_json::json!(#min));
+                        });
+                    }
+                    if let Some(max) = attrs.max_properties {
+                        property_setters.push(quote! {
+                            props.insert("maxProperties".to_string(), ::serde_json::json!(#max));
+                        });
+                    }
+                }
+
+                // Add array cardinality constraints (only meaningful on array and set
+                // fields; elsewhere they're rejected in `array_constraint_errors`). Sets
+                // already carry `uniqueItems`
+                if is_array_type(get_option_inner_type(&field.ty)) {
+                    if let Some(min) = attrs.min_items {
+                        property_setters.push(quote! {
+                            props.insert("minItems".to_string(), ::serde_json::json!(#min));
+                        });
+                    }
+                    if let Some(max) = attrs.max_items {
+                        property_setters.push(quote! {
+                            props.insert("maxItems".to_string(), ::serde_json::json!(#max));
+                        });
+                    }
+                    if attrs.unique_items == Some(true) {
+                        property_setters.push(quote! {
+                            props.insert("uniqueItems".to_string(), ::serde_json::Value::Bool(true));
+                        });
+                    }
+                }
+
+                // Add length bounds, formats, patterns and embedded content annotations
+                // (only meaningful on string fields; bounds, formats and patterns elsewhere
+                // are rejected in `string_constraint_errors`)
+                if matches!(
+                    get_type_category(get_option_inner_type(&field.ty)),
+                    TypeCategory::String
+                ) {
+                    if let Some(min) = attrs.min_length {
+                        property_setters.push(quote! {
+                            props.insert("minLength".to_string(), ::serde_json::json!(#min));
+                        });
+                    }
+                    if let Some(max) = attrs.max_length {
+                        property_setters.push(quote! {
+                            props.insert("maxLength".to_string(), ::serde_json::json!(#max));
+                        });
+                    }
+                    if let Some(format) = &attrs.format {
+                        property_setters.push(quote! {
+                            props.insert("format".to_string(), ::serde_json::Value::String(#format.to_string()));
+                        });
+                    }
+                    if let Some(pattern) = &attrs.pattern {
+                        property_setters.push(quote! {
+                            props.insert("pattern".to_string(), ::serde_json::Value::String(#pattern.to_string()));
+                        });
+                    }
+                    if let Some(media_type) = &attrs.content_media_type {
+                        property_setters.push(quote! {
+                            props.insert("contentMediaType".to_string(), ::serde_json::Value::String(#media_type.to_string()));
+                        });
+                    }
+                    if let Some(encoding) = &attrs.content_encoding {
+                        property_setters.push(quote! {
+                            props.insert("contentEncoding".to_string(), ::serde_json::Value::String(#encoding.to_string()));
+                        });
+                    }
+                }
+
                 // Add multiple examples if available
                 if !attrs.examples_array.is_empty() {
                     let examples_tokens = attrs.examples_array.iter().collect::<Vec<_>>();
@@ -428,8 +273,9 @@ pub fn generate_struct_schema(
                 };
                 property_setters.push(add_prop);
 
-                // Add to required fields if not Optional type
-                if !is_optional {
+                // Add to required fields unless the field is an `Option`, has a serde
+                // default, or may be left out of serialized output
+                if !is_optional && !attrs.serde_default && !attrs.serde_skip_serializing {
                     let required_field = quote! {
                         required.push(::serde_json::Value::String(#field_name.to_string()));
                     };
@@ -477,6 +323,62 @@ pub fn generate_struct_schema(
         quote! {}
     };
 
+    // A proc macro can't see the fields of other types, so property clashes between
+    // flattened types are caught in const evaluation using their `PROPERTY_NAMES`
+    for (i, field) in flattened_fields.iter().enumerate() {
+        let ty = get_option_inner_type(&field.ty);
+        let field_ident = field.ident.as_ref().unwrap();
+        let message = format!(
+            "`#[serde(flatten)]` field `{}` has a property with the same name as a field of `{}`",
+            field_ident, name
+        );
+        flatten_checks.push(quote_spanned! {field.ty.span()=>
+            const _: () = ::rstructor::schema::assert_disjoint_properties(
+                &[#(#property_names),*],
+                <#ty as ::rstructor::schema::SchemaType>::PROPERTY_NAMES,
+                #message,
+            );
+        });
+        for other in &flattened_fields[i + 1..] {
+            let other_ty = get_option_inner_type(&other.ty);
+            let message = format!(
+                "`#[serde(flatten)]` fields `{}` and `{}` share a property name",
+                field_ident,
+                other.ident.as_ref().unwrap()
+            );
+            flatten_checks.push(quote_spanned! {other.ty.span()=>
+                const _: () = ::rstructor::schema::assert_disjoint_properties(
+                    <#ty as ::rstructor::schema::SchemaType>::PROPERTY_NAMES,
+                    <#other_ty as ::rstructor::schema::SchemaType>::PROPERTY_NAMES,
+                    #message,
+                );
+            });
+        }
+    }
+
+    // The names inlined from flattened fields count as this type's own, so a clash
+    // through a further level of flattening is caught too
+    let property_names_const = if flattened_fields.is_empty() {
+        quote! {
+            const PROPERTY_NAMES: &'static [&'static str] = &[#(#property_names),*];
+        }
+    } else {
+        let flattened_types = flattened_fields
+            .iter()
+            .map(|field| get_option_inner_type(&field.ty));
+        quote! {
+            const PROPERTY_NAMES: &'static [&'static str] = {
+                const PARTS: &[&[&str]] = &[
+                    &[#(#property_names),*],
+                    #(<#flattened_types as ::rstructor::schema::SchemaType>::PROPERTY_NAMES),*
+                ];
+                const NAMES: [&str; ::rstructor::schema::property_names_len(PARTS)] =
+                    ::rstructor::schema::concat_property_names(PARTS);
+                &NAMES
+            };
+        }
+    };
+
     // Generate implementation with $defs support for recursive types
     if has_self_reference {
         quote! {
@@ -515,7 +417,14 @@ pub fn generate_struct_schema(
                 fn schema_name() -> Option<String> {
                     Some(stringify!(#name).to_string())
                 }
+
+                #property_names_const
+
+                // Its properties live under `$defs`, behind a `$ref`
+                const FLATTENABLE: bool = false;
             }
+
+            #(#flatten_checks)*
         }
     } else {
         quote! {
@@ -545,12 +454,170 @@ pub fn generate_struct_schema(
                 fn schema_name() -> Option<String> {
                     Some(stringify!(#name).to_string())
                 }
+
+                #property_names_const
             }
+
+            #(#flatten_checks)*
         }
     }
 }
 
+/// Schema for a date serialized as a Unix timestamp (an integer, not an ISO-8601 string)
+pub fn timestamp_schema(description: &str) -> TokenStream {
+    quote! {
+        ::serde_json::json!({
+            "type": "integer",
+            "description": #description
+        })
+    }
+}
+
+/// Check whether a type is the struct currently being derived
+fn is_direct_self_reference(ty: &Type, struct_name: &str) -> bool {
+    if let Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.first()
+    {
+        return segment.ident == struct_name;
+    }
+    false
+}
+
+/// Check whether a type must be built structurally rather than by calling
+/// `<T as SchemaType>::schema()`, because it (or something nested inside it)
+/// is a self-reference or a map (which carries the enum key hint and may use
+/// non-String keys)
+fn needs_structural_schema(ty: &Type, struct_name: &str) -> bool {
+    if is_direct_self_reference(ty, struct_name) || is_map_type(ty) {
+        return true;
+    }
+    if is_option_type(ty) {
+        return needs_structural_schema(get_option_inner_type(ty), struct_name);
+    }
+    if let Some(inner) = get_box_inner_type(ty) {
+        return needs_structural_schema(inner, struct_name);
+    }
+    if let Some(inner) = get_array_inner_type(ty) {
+        return needs_structural_schema(inner, struct_name);
+    }
+    if let Some(elements) = get_tuple_element_types(ty) {
+        return elements
+            .iter()
+            .any(|elem| needs_structural_schema(elem, struct_name));
+    }
+    false
+}
+
+/// Generate an expression evaluating to the JSON schema of a field type.
+///
+/// Types are delegated to `<T as SchemaType>::schema()` so field schemas match the
+/// standalone impls in `rstructor::schema`. Wrappers are only unpacked here when
+/// something nested inside them cannot be delegated (see `needs_structural_schema`).
+pub fn generate_type_schema(ty: &Type, struct_name: &str) -> TokenStream {
+    if !needs_structural_schema(ty, struct_name) {
+        return quote! {
+            <#ty as ::rstructor::schema::SchemaType>::schema().into_value()
+        };
+    }
+
+    if is_direct_self_reference(ty, struct_name) {
+        // Use $ref for recursive types to prevent infinite recursion
+        return quote! {
+            ::serde_json::json!({ "$ref": format!("#/$defs/{}", #struct_name) })
+        };
+    }
+
+    if is_option_type(ty) {
+        return generate_type_schema(get_option_inner_type(ty), struct_name);
+    }
+
+    if let Some(inner) = get_box_inner_type(ty) {
+        return generate_type_schema(inner, struct_name);
+    }
+
+    if let Some((key_ty, val_ty)) = get_map_types(ty) {
+        let value_schema = generate_type_schema(val_ty, struct_name);
+        // JSON keys are strings, so integer keys are described by their digits
+        let property_names = integer_key_pattern(key_ty).map(|pattern| {
+            quote! {
+                props.insert(
+                    "propertyNames".to_string(),
+                    ::serde_json::json!({ "type": "string", "pattern": #pattern }),
+                );
+            }
+        });
+        // For enum keys, extract the enum variants and add them to description
+        // so that Gemini can use the correct keys instead of generic placeholders
+        return quote! {
+            {
+                let mut props = ::serde_json::Map::new();
+                props.insert("type".to_string(), ::serde_json::Value::String("object".to_string()));
+                #property_names
+                props.insert("additionalProperties".to_string(), #value_schema);
+
+                let key_schema = <#key_ty as ::rstructor::schema::SchemaType>::schema();
+                if let Some(enum_values) = key_schema.to_json().get("enum")
+                    .and_then(|e| e.as_array())
+                {
+                    let keys: Vec<String> = enum_values
+                        .iter()
+                        .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                        .collect();
+                    if !keys.is_empty() {
+                        let keys_hint = format!("Keys: [{}]", keys.join(", "));
+                        props.insert("description".to_string(), ::serde_json::Value::String(keys_hint));
+                    }
+                }
+                ::serde_json::Value::Object(props)
+            }
+        };
+    }
+
+    if let Some(inner) = get_array_inner_type(ty) {
+        let items_schema = generate_type_schema(inner, struct_name);
+        // Sets serialize as arrays of unique items, mirroring the HashSet/BTreeSet impls
+        let unique_items = if is_set_type(ty) {
+            quote! { props.insert("uniqueItems".to_string(), ::serde_json::Value::Bool(true)); }
+        } else {
+            quote! {}
+        };
+        return quote! {
+            {
+                let mut props = ::serde_json::Map::new();
+                props.insert("type".to_string(), ::serde_json::Value::String("array".to_string()));
+                props.insert("items".to_string(), #items_schema);
+                #unique_items
+                ::serde_json::Value::Object(props)
+            }
+        };
+    }
+
+    if let Some(elements) = get_tuple_element_types(ty) {
+        let element_count = elements.len();
+        let element_schemas: Vec<TokenStream> = elements
+            .iter()
+            .map(|elem| generate_type_schema(elem, struct_name))
+            .collect();
+        return quote! {
+            ::serde_json::json!({
+                "type": "array",
+                "prefixItems": [#(#element_schemas),*],
+                "minItems": #element_count,
+                "maxItems": #element_count
+            })
+        };
+    }
+
+    quote! {
+        <#ty as ::rstructor::schema::SchemaType>::schema().into_value()
+    }
+}
+
 /// Apply serde rename_all transformation to a field/variant name
+///
+/// Supports every case style serde accepts: `lowercase`, `UPPERCASE`, `camelCase`,
+/// `PascalCase`, `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case` and
+/// `SCREAMING-KEBAB-CASE`. Unknown values leave the name unchanged.
 pub fn apply_rename_all(name: &str, rename_all: &str) -> String {
     match rename_all {
         "lowercase" => name.to_lowercase(),
@@ -568,8 +635,9 @@ pub fn apply_rename_all(name: &str, rename_all: &str) -> String {
                         if !part.is_empty() {
                             let mut chars = part.chars();
                             if let Some(first) = chars.next() {
+                                // Like serde, only the first letter of each word changes case
                                 result.push(first.to_ascii_uppercase());
-                                result.extend(chars.map(|c| c.to_ascii_lowercase()));
+                                result.extend(chars);
                             }
                         }
                     }
@@ -71,6 +71,53 @@ use syn::{Data, DeriveInput, parse_macro_input};
 ///
 /// The validation function is called automatically when the LLM response is deserialized.
 ///
+/// Individual fields can have their own validator, called with a reference to the
+/// field's value before the container validator runs. Errors are prefixed with the
+/// field name, so the LLM knows which value to fix on retry:
+///
+/// ```
+/// use rstructor::{Instructor, RStructorError};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// struct Contact {
+///     #[llm(validate = "check_email")]
+///     email: String,
+/// }
+///
+/// fn check_email(email: &str) -> rstructor::Result<()> {
+///     if !email.contains('@') {
+///         return Err(RStructorError::ValidationError("must contain '@'".into()));
+///     }
+///     Ok(())
+/// }
+///
+/// let contact = Contact { email: "nobody".into() };
+/// assert_eq!(
+///     contact.validate().unwrap_err().to_string(),
+///     "Validation error: email: must contain '@'"
+/// );
+/// ```
+///
+/// To clean up a value after it passes validation, use `normalize` with a function
+/// taking `&mut Self`:
+///
+/// ```
+/// use rstructor::Instructor;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// #[llm(normalize = "normalize_product")]
+/// struct Product {
+///     name: String,
+/// }
+///
+/// fn normalize_product(product: &mut Product) -> rstructor::Result<()> {
+///     product.name = product.name.trim().to_string();
+///     Ok(())
+/// }
+/// ```
+///
 /// # Examples
 ///
 /// ## Field-level attributes
@@ -140,12 +187,373 @@ use syn::{Data, DeriveInput, parse_macro_input};
 /// - `description`: A description of the struct or enum
 /// - `title`: A custom title for the JSON Schema (defaults to the type name)
 /// - `examples`: Example instances of the struct or enum
+/// - `example`: For enums without data, the most likely variant (by Rust name or
+///   serialized value). It is emitted as a single `example` to bias the model toward
+///   the common case; providers that strip examples ignore it:
+///
+/// ```
+/// use rstructor::{Instructor, SchemaType};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize, Debug)]
+/// #[llm(example = "Neutral")]
+/// #[serde(rename_all = "lowercase")]
+/// enum Sentiment {
+///     #[llm(description = "Clearly favourable")]
+///     Positive,
+///     #[llm(description = "Neither favourable nor unfavourable (most reviews)")]
+///     Neutral,
+///     #[llm(description = "Clearly unfavourable")]
+///     Negative,
+/// }
+///
+/// assert_eq!(Sentiment::schema().to_json()["example"], "neutral");
+/// ```
+///
+/// ### Catch-all variants
+///
+/// A simple enum can keep one single-value variant as a fallback for values the
+/// listed variants don't cover. Mark it `#[llm(other)]` and `#[serde(untagged)]`: the
+/// schema becomes an `anyOf` of the known values and a free-form string, and serde
+/// routes any unknown string into the variant:
+///
+/// ```
+/// use rstructor::Instructor;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+/// enum Genre {
+///     Action,
+///     Comedy,
+///     #[llm(other)]
+///     #[serde(untagged)]
+///     Other(String),
+/// }
+///
+/// let genre: Genre = serde_json::from_str(r#""Western""#).unwrap();
+/// assert_eq!(genre, Genre::Other("Western".to_string()));
+/// ```
+///
+/// Without `#[serde(untagged)]` serde would expect `{"Other": "..."}`, so the derive
+/// rejects it:
+///
+/// ```compile_fail
+/// use rstructor::Instructor;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// enum Genre {
+///     Action,
+///     #[llm(other)]
+///     Other(String), // error: needs `#[serde(untagged)]`
+/// }
+/// ```
+///
+/// ### Omitting optional fields
+///
+/// On a large type, `#[llm(skip_if_none)]` leaves an `Option` field out of the schema
+/// entirely, so the model only sees the fields that matter for the extraction. The
+/// field still deserializes, as `None`:
+///
+/// ```
+/// use rstructor::{Instructor, SchemaType};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize, Debug)]
+/// struct Invoice {
+///     number: String,
+///     #[llm(skip_if_none)]
+///     internal_notes: Option<String>,
+/// }
+///
+/// assert!(Invoice::schema().to_json()["properties"].get("internal_notes").is_none());
+/// let invoice: Invoice = serde_json::from_str(r#"{"number": "INV-7"}"#).unwrap();
+/// assert!(invoice.internal_notes.is_none());
+/// ```
+///
+/// A required field can't be skipped, since responses would never contain it:
+///
+/// ```compile_fail
+/// use rstructor::Instructor;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// struct Invoice {
+///     #[llm(skip_if_none)]
+///     number: String, // error: only supported on `Option` fields
+/// }
+/// ```
+///
+/// ### String length bounds
+///
+/// `#[llm(min_length = ..., max_length = ...)]` on a `String` (or `Option<String>`)
+/// field emits JSON Schema `minLength`/`maxLength`, which both OpenAI strict mode and
+/// Gemini accept:
+///
+/// ```
+/// use rstructor::{Instructor, SchemaType};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize, Debug)]
+/// struct Tweet {
+///     #[llm(min_length = 1, max_length = 280)]
+///     text: String,
+/// }
+///
+/// assert_eq!(Tweet::schema().to_json()["properties"]["text"]["maxLength"], 280);
+/// ```
+///
+/// Other field types have no length, so the bounds are rejected there:
+///
+/// ```compile_fail
+/// use rstructor::Instructor;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// struct Tweet {
+///     #[llm(max_length = 280)]
+///     likes: u32, // error: only supported on string fields
+/// }
+/// ```
+///
+/// ### Array length bounds
+///
+/// `#[llm(min_items = ..., max_items = ...)]` on a `Vec`, `HashSet` or `BTreeSet` field
+/// (optionally inside an `Option`) emits `minItems`/`maxItems`, and `#[llm(unique_items)]`
+/// emits `uniqueItems` on a `Vec`. Sets are always unique, so `unique_items = false` is
+/// rejected on them:
+///
+/// ```
+/// use rstructor::{Instructor, SchemaType};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize, Debug)]
+/// struct Article {
+///     #[llm(description = "Topic tags", min_items = 3, max_items = 5, unique_items)]
+///     tags: Vec<String>,
+/// }
+///
+/// let schema = Article::schema().to_json();
+/// assert_eq!(schema["properties"]["tags"]["minItems"], 3);
+/// assert_eq!(schema["properties"]["tags"]["uniqueItems"], true);
+/// ```
+///
+/// ```compile_fail
+/// use rstructor::Instructor;
+/// use serde::{Serialize, Deserialize};
+/// use std::collections::HashSet;
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// struct Article {
+///     #[llm(unique_items = false)]
+///     tags: HashSet<String>, // error: sets always hold unique items
+/// }
+/// ```
+///
+/// ### String formats
+///
+/// `#[llm(format = "...")]` on a string field emits JSON Schema `format` (`email`,
+/// `uri`, `hostname`, `ipv4`, `time`, ...), alongside any `description` or `pattern`.
+/// OpenAI keeps every format; Gemini only accepts `date-time` and `enum` on strings, so
+/// other formats are stripped when the schema is prepared for it:
+///
+/// ```
+/// use rstructor::{Instructor, SchemaType};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize, Debug)]
+/// struct Contact {
+///     #[llm(description = "Work email", format = "email")]
+///     email: String,
+/// }
+///
+/// assert_eq!(Contact::schema().to_json()["properties"]["email"]["format"], "email");
+/// ```
+///
+/// ### Patterns
+///
+/// `#[llm(pattern = "...")]` on a string field emits JSON Schema `pattern`, and the
+/// generated `validate()` checks the value against it, so a response that doesn't match
+/// is retried. The regex is checked when the derive expands and must use syntax the
+/// `regex` crate accepts (no lookaround or backreferences). `pattern` isn't supported on
+/// enum variant fields:
+///
+/// ```
+/// use rstructor::{Instructor, SchemaType};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize, Debug)]
+/// struct Item {
+///     #[llm(pattern = r"^[A-Z]{3}-\d{4}$")]
+///     sku: String,
+/// }
+///
+/// assert_eq!(Item::schema().to_json()["properties"]["sku"]["pattern"], r"^[A-Z]{3}-\d{4}$");
+/// assert!(Item { sku: "abc".into() }.validate().is_err());
+/// ```
+///
+/// An invalid regex is a compile error:
+///
+/// ```compile_fail
+/// use rstructor::Instructor;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// struct Item {
+///     #[llm(pattern = "[A-Z")]
+///     sku: String, // error: invalid `pattern` regex
+/// }
+/// ```
+///
+/// ### Requiring descriptions
+///
+/// Descriptions are the main way to tell the model what a field means. Add
+/// `#[llm(require_descriptions)]` to a struct or enum to make every named field without
+/// a `description` a compile error. Tuple variants need a variant description instead,
+/// and fields skipped with `skip_if_none` are exempt:
+///
+/// ```compile_fail
+/// use rstructor::Instructor;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// #[llm(require_descriptions)]
+/// struct Shipment {
+///     #[llm(description = "Carrier tracking number")]
+///     tracking_number: String,
+///     weight_kg: f64, // error: field `weight_kg` has no description
+/// }
+/// ```
 ///
 /// ### Serde Integration
 ///
 /// - Respects `#[serde(rename_all = "...")]` for transforming property names
-///   - Supported values: "lowercase", "UPPERCASE", "camelCase", "PascalCase", "snake_case"
+///   - Supported values: "lowercase", "UPPERCASE", "camelCase", "PascalCase", "snake_case",
+///     "SCREAMING_SNAKE_CASE", "kebab-case", "SCREAMING-KEBAB-CASE"
 ///   - Example: With `#[serde(rename_all = "camelCase")]`, a field `user_id` becomes `userId` in the schema
+/// - `#[serde(alias = "...")]` names are listed in the field's description (e.g. "Full
+///   name (also accepts: full_name)") when the container has `#[llm(document_aliases)]`,
+///   so a model that drifts to an old key name still produces something that parses
+/// - `#[serde(skip)]` and `#[serde(skip_deserializing)]` fields are left out of the
+///   schema, and `#[serde(skip_serializing)]`/`#[serde(skip_serializing_if = "...")]`
+///   fields aren't required
+/// - `#[serde(default)]` and `#[serde(default = "...")]` fields are listed but not
+///   required, since serde fills them in when they're missing
+/// - `#[serde(flatten)]` struct fields have their type's properties (and required
+///   list) inlined into the parent, matching the JSON serde reads; a flattened map
+///   becomes `additionalProperties`. Flattened types that share a property name with
+///   each other or with the parent are a compile error:
+///
+/// ```compile_fail
+/// use rstructor::Instructor;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// struct Metadata { id: String }
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// struct Audit { id: String }
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// struct Request {
+///     #[serde(flatten)]
+///     metadata: Metadata,
+///     #[serde(flatten)]
+///     audit: Audit, // error: `metadata` and `audit` share a property name
+/// }
+/// ```
+///
+/// Names a flattened type inlines from its own flattened fields count too, and only
+/// structs and maps can be flattened; an enum, a primitive or a self-referential
+/// struct has no top-level properties to inline:
+///
+/// ```compile_fail
+/// use rstructor::Instructor;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// enum Payment { Card { last4: String }, Cash }
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// struct Order {
+///     id: String,
+///     #[serde(flatten)]
+///     payment: Payment, // error: `Payment` has no top-level properties
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use rstructor::Instructor;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// struct Trace { id: String }
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// struct Metadata {
+///     #[serde(flatten)]
+///     trace: Trace,
+/// }
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// struct Request {
+///     id: String,
+///     #[serde(flatten)]
+///     metadata: Metadata, // error: `id` comes from `Trace` through `Metadata`
+/// }
+/// ```
+///
+/// - Date fields using chrono's `ts_seconds`/`ts_milliseconds`/`ts_microseconds`/
+///   `ts_nanoseconds` (or the `time` crate's `timestamp`) through `#[serde(with = "...")]`,
+///   `serialize_with` or `deserialize_with` get an integer schema with the unit in the
+///   description. For other helpers that write Unix seconds, add `#[llm(as_timestamp)]`
+/// - Supports `#[serde(untagged)]` enums. Because untagged variants are told apart only
+///   by their shape, two variants with identical payloads are rejected at compile time:
+///
+/// ```compile_fail
+/// use rstructor::Instructor;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// #[serde(untagged)]
+/// enum Coordinate {
+///     Cartesian(f64, f64),
+///     Polar(f64, f64), // error: same shape as `Cartesian`
+/// }
+/// ```
+///
+/// Shapes are compared as JSON, so integer widths and paths don't make variants
+/// distinct:
+///
+/// ```compile_fail
+/// use rstructor::Instructor;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// #[serde(untagged)]
+/// enum Reading {
+///     Small(u8),
+///     Large(i64), // error: both are JSON integers
+/// }
+/// ```
+///
+/// Fields serde never reads (`#[serde(skip)]`) aren't part of the shape either:
+///
+/// ```compile_fail
+/// use rstructor::Instructor;
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize)]
+/// #[serde(untagged)]
+/// enum Tag {
+///     Plain { label: String },
+///     Cached {
+///         label: String,
+///         #[serde(skip)]
+///         hits: u32,
+///     }, // error: same shape as `Plain`
+/// }
+/// ```
 #[proc_macro_derive(Instructor, attributes(llm))]
 pub fn derive_instructor(input: TokenStream) -> TokenStream {
     // Parse the input tokens into a syntax tree
@@ -166,31 +574,70 @@ pub fn derive_instructor(input: TokenStream) -> TokenStream {
         _ => panic!("Instructor can only be derived for structs and enums"),
     };
 
+    // `example` picks one variant, so it only makes sense for enums without data
+    let is_simple_enum = matches!(
+        &input.data,
+        Data::Enum(data_enum) if data_enum.variants.iter().all(|v| v.fields.is_empty())
+    );
+    let example_error = if container_attrs.example.is_some() && !is_simple_enum {
+        syn::Error::new_spanned(
+            name,
+            "`example` is only supported on enums without data; use `examples` instead",
+        )
+        .to_compile_error()
+    } else {
+        proc_macro2::TokenStream::new()
+    };
+
     // Generate the Instructor trait implementation
-    let instructor_impl = if let Some(validate_fn) = &container_attrs.validate {
+    let validate_body = if let Some(validate_fn) = &container_attrs.validate {
         // Parse the validation function path
         let validate_path: syn::Path =
             syn::parse_str(validate_fn).expect("validate attribute must be a valid function path");
-        quote::quote! {
-            impl ::rstructor::model::Instructor for #name {
-                fn validate(&self) -> ::rstructor::error::Result<()> {
-                    #validate_path(self)
-                }
-            }
-        }
+        quote::quote! { #validate_path(self) }
     } else {
         // Default implementation - validation passes
+        quote::quote! { ::rstructor::error::Result::Ok(()) }
+    };
+    let skip_if_none_error = skip_if_none_errors(&input.data);
+    let string_constraint_error = string_constraint_errors(&input.data);
+    let array_constraint_error = array_constraint_errors(&input.data);
+    let missing_description_error = if container_attrs.require_descriptions {
+        missing_description_errors(&input.data)
+    } else {
+        proc_macro2::TokenStream::new()
+    };
+
+    // Per-field validators run first so their errors name the offending field
+    let field_checks = field_validations(&input.data, &container_attrs);
+    let normalize_method = container_attrs.normalize.as_ref().map(|normalize_fn| {
+        let normalize_path: syn::Path = syn::parse_str(normalize_fn)
+            .expect("normalize attribute must be a valid function path");
         quote::quote! {
-            impl ::rstructor::model::Instructor for #name {
-                fn validate(&self) -> ::rstructor::error::Result<()> {
-                    ::rstructor::error::Result::Ok(())
-                }
+            fn normalize(&mut self) -> ::rstructor::error::Result<()> {
+                #normalize_path(self)
             }
         }
+    });
+    let instructor_impl = quote::quote! {
+        impl ::rstructor::model::Instructor for #name {
+            fn validate(&self) -> ::rstructor::error::Result<()> {
+                #field_checks
+                #validate_body
+            }
+
+            #normalize_method
+        }
     };
 
     // Combine the two implementations
     let combined = quote::quote! {
+        #example_error
+        #skip_if_none_error
+        #string_constraint_error
+        #array_constraint_error
+        #missing_description_error
+
         #schema_impl
 
         #instructor_impl
@@ -199,14 +646,316 @@ pub fn derive_instructor(input: TokenStream) -> TokenStream {
     combined.into()
 }
 
-use quote::ToTokens;
+use parsers::array_parser::json_value_tokens;
+
+/// Compile errors for schema fields without a description, for
+/// `#[llm(require_descriptions)]`
+///
+/// Named fields need their own description; tuple variants are described by the
+/// variant's. Fields left out of the schema with `skip_if_none` don't need one.
+fn missing_description_errors(data: &Data) -> proc_macro2::TokenStream {
+    use parsers::field_parser::parse_field_attributes;
+    use parsers::variant_parser::parse_variant_attributes;
+
+    let error = |tokens: &dyn quote::ToTokens, name: String| {
+        syn::Error::new_spanned(
+            tokens,
+            format!(
+                "{} has no description; add `#[llm(description = \"...\")]` \
+                 (required by `require_descriptions`)",
+                name
+            ),
+        )
+        .to_compile_error()
+    };
+    let undescribed = |field: &&syn::Field| {
+        let attrs = parse_field_attributes(field);
+        field.ident.is_some()
+            && attrs.description.is_none()
+            && !attrs.skip_if_none
+            && !attrs.serde_skip
+            && !attrs.serde_flatten
+    };
+
+    match data {
+        Data::Struct(data_struct) => data_struct
+            .fields
+            .iter()
+            .filter(undescribed)
+            .map(|field| error(field, format!("field `{}`", field.ident.as_ref().unwrap())))
+            .collect(),
+        Data::Enum(data_enum) => data_enum
+            .variants
+            .iter()
+            .flat_map(|variant| match &variant.fields {
+                syn::Fields::Unnamed(_)
+                    if parse_variant_attributes(variant).description.is_none() =>
+                {
+                    vec![error(variant, format!("variant `{}`", variant.ident))]
+                }
+                fields => fields
+                    .iter()
+                    .filter(undescribed)
+                    .map(|field| {
+                        error(
+                            field,
+                            format!(
+                                "field `{}` of `{}`",
+                                field.ident.as_ref().unwrap(),
+                                variant.ident
+                            ),
+                        )
+                    })
+                    .collect(),
+            })
+            .collect(),
+        Data::Union(_) => proc_macro2::TokenStream::new(),
+    }
+}
+
+/// Compile errors for `#[llm(skip_if_none)]` on fields that aren't `Option`
+///
+/// Serde only tolerates a missing field when it's optional, so skipping a required
+/// field would make every response fail to deserialize.
+fn skip_if_none_errors(data: &Data) -> proc_macro2::TokenStream {
+    use parsers::field_parser::parse_field_attributes;
+    use type_utils::is_option_type;
+
+    let fields: Vec<&syn::Field> = match data {
+        Data::Struct(data_struct) => data_struct.fields.iter().collect(),
+        Data::Enum(data_enum) => data_enum
+            .variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter())
+            .collect(),
+        Data::Union(_) => Vec::new(),
+    };
+    fields
+        .into_iter()
+        .filter(|field| parse_field_attributes(field).skip_if_none && !is_option_type(&field.ty))
+        .map(|field| {
+            syn::Error::new_spanned(
+                &field.ty,
+                "`skip_if_none` is only supported on `Option` fields",
+            )
+            .to_compile_error()
+        })
+        .collect()
+}
+
+/// Fields of a struct, or of every variant of an enum
+fn all_fields(data: &Data) -> Vec<&syn::Field> {
+    match data {
+        Data::Struct(data_struct) => data_struct.fields.iter().collect(),
+        Data::Enum(data_enum) => data_enum
+            .variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter())
+            .collect(),
+        Data::Union(_) => Vec::new(),
+    }
+}
+
+/// Compile errors for `min_length`/`max_length`/`format`/`pattern` on non-string
+/// fields, a minimum above the maximum, a pattern that isn't a valid regex, or a
+/// `format` that isn't a string literal
+fn string_constraint_errors(data: &Data) -> proc_macro2::TokenStream {
+    use parsers::field_parser::parse_field_attributes;
+    use type_utils::{TypeCategory, get_option_inner_type, get_type_category};
+
+    all_fields(data)
+        .into_iter()
+        .filter_map(|field| {
+            if let Some(error) = non_literal_format_error(field) {
+                return Some(error.to_compile_error());
+            }
+            let attrs = parse_field_attributes(field);
+            if attrs.min_length.is_none()
+                && attrs.max_length.is_none()
+                && attrs.format.is_none()
+                && attrs.pattern.is_none()
+            {
+                return None;
+            }
+            let message = if !matches!(
+                get_type_category(get_option_inner_type(&field.ty)),
+                TypeCategory::String
+            ) {
+                "`min_length`, `max_length`, `format` and `pattern` are only supported on string fields"
+                    .to_string()
+            } else if let (Some(min), Some(max)) = (attrs.min_length, attrs.max_length)
+                && min > max
+            {
+                "`min_length` is greater than `max_length`".to_string()
+            } else if let Some(pattern) = &attrs.pattern
+                && let Err(e) = regex_syntax::Parser::new().parse(pattern)
+            {
+                format!("invalid `pattern` regex: {}", e)
+            } else {
+                return None;
+            };
+            Some(syn::Error::new_spanned(&field.ty, message).to_compile_error())
+        })
+        .collect()
+}
+
+/// The parse error for `#[llm(format = ...)]` with anything other than a string literal
+///
+/// `parse_field_attributes` drops malformed attributes, so this is checked separately.
+fn non_literal_format_error(field: &syn::Field) -> Option<syn::Error> {
+    let mut error = None;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("llm"))
+    {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("format") {
+                if let Err(e) = meta.value()?.parse::<syn::LitStr>() {
+                    error = Some(syn::Error::new(
+                        e.span(),
+                        "`format` must be a string literal, e.g. format = \"email\"",
+                    ));
+                }
+            } else if meta.input.peek(syn::Token![=]) {
+                meta.value()?.parse::<syn::Expr>()?;
+            }
+            Ok(())
+        });
+    }
+    error
+}
+
+/// Compile errors for `min_items`/`max_items`/`unique_items` on fields that aren't
+/// arrays or sets, a minimum above the maximum, or `unique_items = false` on a set
+fn array_constraint_errors(data: &Data) -> proc_macro2::TokenStream {
+    use parsers::field_parser::parse_field_attributes;
+    use type_utils::{get_option_inner_type, is_array_type, is_set_type};
+
+    all_fields(data)
+        .into_iter()
+        .filter_map(|field| {
+            let attrs = parse_field_attributes(field);
+            if attrs.min_items.is_none() && attrs.max_items.is_none() && attrs.unique_items.is_none()
+            {
+                return None;
+            }
+            let ty = get_option_inner_type(&field.ty);
+            let message = if !is_array_type(ty) {
+                "`min_items`, `max_items` and `unique_items` are only supported on `Vec` and set fields"
+            } else if let (Some(min), Some(max)) = (attrs.min_items, attrs.max_items)
+                && min > max
+            {
+                "`min_items` is greater than `max_items`"
+            } else if is_set_type(ty) && attrs.unique_items == Some(false) {
+                "sets always hold unique items; remove `unique_items = false`"
+            } else {
+                return None;
+            };
+            Some(syn::Error::new_spanned(&field.ty, message).to_compile_error())
+        })
+        .collect()
+}
+
+/// Calls to each `#[llm(validate = "...")]` field validator, in field order
+///
+/// Validation errors are prefixed with the field's name as it appears in the schema.
+fn field_validations(
+    data: &Data,
+    container_attrs: &ContainerAttributes,
+) -> proc_macro2::TokenStream {
+    use generators::struct_schema::apply_rename_all;
+    use parsers::field_parser::parse_field_attributes;
+    use type_utils::is_option_type;
+
+    let data_struct = match data {
+        Data::Struct(data_struct) => data_struct,
+        Data::Enum(data_enum) => {
+            // Variant fields aren't reachable from `&self` without a match on the variant
+            let field = data_enum
+                .variants
+                .iter()
+                .flat_map(|variant| variant.fields.iter())
+                .find(|field| {
+                    let attrs = parse_field_attributes(field);
+                    attrs.validate.is_some() || attrs.pattern.is_some()
+                });
+            return field.map_or_else(proc_macro2::TokenStream::new, |field| {
+                syn::Error::new_spanned(
+                    field,
+                    "field `validate` and `pattern` are only supported on struct fields; use a container `validate` instead",
+                )
+                .to_compile_error()
+            });
+        }
+        Data::Union(_) => return proc_macro2::TokenStream::new(),
+    };
+
+    let checks = data_struct.fields.iter().filter_map(|field| {
+        let attrs = parse_field_attributes(field);
+        if attrs.validate.is_none() && attrs.pattern.is_none() {
+            return None;
+        }
+        let ident = field.ident.as_ref()?;
+        let original = ident.to_string();
+        let field_name = match (&attrs.serde_rename, &container_attrs.serde_rename_all) {
+            (Some(rename), _) => rename.clone(),
+            (None, Some(rename_all)) => apply_rename_all(&original, rename_all),
+            (None, None) => original,
+        };
+        // Non-string fields are rejected in `string_constraint_errors`
+        let pattern_check = attrs.pattern.as_ref().map(|pattern| {
+            let check = if is_option_type(&field.ty) {
+                quote::quote! {
+                    if let ::core::option::Option::Some(value) = &self.#ident {
+                        PATTERN.check(#field_name, &::std::string::ToString::to_string(value))?;
+                    }
+                }
+            } else {
+                quote::quote! {
+                    PATTERN.check(#field_name, &::std::string::ToString::to_string(&self.#ident))?;
+                }
+            };
+            quote::quote! {
+                {
+                    static PATTERN: ::rstructor::model::FieldPattern =
+                        ::rstructor::model::FieldPattern::new(#pattern);
+                    #check
+                }
+            }
+        });
+        let Some(validate_fn) = attrs.validate else {
+            return pattern_check;
+        };
+        let validate_path: syn::Path =
+            syn::parse_str(&validate_fn).expect("validate attribute must be a valid function path");
+        Some(quote::quote! {
+            #pattern_check
+            if let ::core::result::Result::Err(err) = #validate_path(&self.#ident) {
+                return ::core::result::Result::Err(match err {
+                    ::rstructor::error::RStructorError::ValidationError(msg) => {
+                        ::rstructor::error::RStructorError::ValidationError(
+                            ::std::format!("{}: {}", #field_name, msg),
+                        )
+                    }
+                    other => other,
+                });
+            }
+        })
+    });
+    quote::quote! { #(#checks)* }
+}
 
 fn extract_container_attributes(attrs: &[syn::Attribute]) -> ContainerAttributes {
     let mut description = None;
     let mut title = None;
     let mut examples = Vec::new();
+    let mut example = None;
     let mut serde_rename_all = None;
     let mut validate = None;
+    let mut normalize = None;
+    let mut require_descriptions = false;
+    let mut document_aliases = false;
     let mut serde_tag = None;
     let mut serde_content = None;
     let mut serde_untagged = false;
@@ -227,31 +976,26 @@ fn extract_container_attributes(attrs: &[syn::Attribute]) -> ContainerAttributes
                     let value = meta.value()?;
                     let content: syn::LitStr = value.parse()?;
                     validate = Some(content.value());
+                } else if meta.path.is_ident("normalize") {
+                    let value = meta.value()?;
+                    let content: syn::LitStr = value.parse()?;
+                    normalize = Some(content.value());
+                } else if meta.path.is_ident("require_descriptions") {
+                    require_descriptions = true;
+                } else if meta.path.is_ident("document_aliases") {
+                    document_aliases = true;
+                } else if meta.path.is_ident("example") {
+                    let value = meta.value()?;
+                    let content: syn::LitStr = value.parse()?;
+                    example = Some(content.value());
                 } else if meta.path.is_ident("examples") {
                     // Handle array syntax like examples = ["one", "two"]
                     let value = meta.value()?;
 
                     // Try to parse as array expression
                     if let Ok(syn::Expr::Array(array)) = value.parse::<syn::Expr>() {
-                        // For each element in the array, convert to TokenStream
-                        for elem in array.elems.iter() {
-                            // For string literals, wrap them in serde_json::Value::String constructors
-                            if let syn::Expr::Lit(lit_expr) = elem {
-                                if let syn::Lit::Str(lit_str) = &lit_expr.lit {
-                                    let str_val = lit_str.value();
-                                    let json_str = quote::quote! {
-                                        ::serde_json::Value::String(#str_val.to_string())
-                                    };
-                                    examples.push(json_str);
-                                } else {
-                                    // For other literals, pass them through
-                                    examples.push(elem.to_token_stream());
-                                }
-                            } else {
-                                // For non-literals (like objects), pass them through
-                                examples.push(elem.to_token_stream());
-                            }
-                        }
+                        // Same element handling as field-level examples
+                        examples.extend(array.elems.iter().map(json_value_tokens));
                     }
                 }
                 Ok(())
@@ -287,8 +1031,12 @@ fn extract_container_attributes(attrs: &[syn::Attribute]) -> ContainerAttributes
         .description(description)
         .title(title)
         .examples(examples)
+        .example(example)
         .serde_rename_all(serde_rename_all)
         .validate(validate)
+        .normalize(normalize)
+        .require_descriptions(require_descriptions)
+        .document_aliases(document_aliases)
         .serde_tag(serde_tag)
         .serde_content(serde_content)
         .serde_untagged(serde_untagged)
@@ -2,6 +2,103 @@ use proc_macro2::TokenStream;
 use quote::{ToTokens, quote};
 use syn::{Expr, ExprArray, Lit, Token, bracketed, parse::Parse};
 
+/// Utility struct to parse array literal expressions
+/// Handles array literals like [1, 2, 3] or ["a", "b", "c"]
+pub struct ArrayAttr {
+    pub expr_array: ExprArray,
+}
+
+impl Parse for ArrayAttr {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let content;
+        bracketed!(content in input);
+        let mut elements = Vec::new();
+
+        // Parse comma-separated expressions inside brackets
+        while !content.is_empty() {
+            let elem: Expr = content.parse()?;
+            elements.push(elem);
+
+            if content.is_empty() {
+                break;
+            }
+
+            content.parse::<Token![,]>()?;
+        }
+
+        Ok(ArrayAttr {
+            expr_array: ExprArray {
+                attrs: Vec::new(),
+                bracket_token: syn::token::Bracket::default(),
+                elems: elements.into_iter().collect(),
+            },
+        })
+    }
+}
+
+/// Parse an array literal from an attribute value
+/// Returns a vector of TokenStreams representing JSON values for each array element
+pub fn parse_array_literal(value: &syn::parse::ParseBuffer) -> Option<Vec<TokenStream>> {
+    // Try to parse as a bracketed array
+    let array_attr = value.parse::<ArrayAttr>().ok()?;
+    Some(
+        array_attr
+            .expr_array
+            .elems
+            .iter()
+            .map(json_value_tokens)
+            .collect(),
+    )
+}
+
+/// Convert one example expression into tokens building a `serde_json::Value`
+///
+/// Literals map to the matching JSON value. Anything else (e.g. `serde_json::json!({...})`,
+/// a nested array or a constant) goes through `serde_json::json!`, so it keeps its
+/// structure instead of being stringified.
+pub fn json_value_tokens(elem: &Expr) -> TokenStream {
+    match elem {
+        Expr::Lit(lit) => match &lit.lit {
+            Lit::Str(lit_str) => {
+                let s = lit_str.value();
+                quote! {
+                    ::serde_json::Value::String(#s.to_string())
+                }
+            }
+            Lit::Int(lit_int) => {
+                quote! {
+                    ::serde_json::Value::Number(::serde_json::Number::from(#lit_int))
+                }
+            }
+            Lit::Float(lit_float) => {
+                let s = lit_float.to_string();
+                quote! {
+                    ::serde_json::json!(#s.parse::<f64>().unwrap())
+                }
+            }
+            Lit::Bool(lit_bool) => {
+                let b = lit_bool.value;
+                quote! {
+                    ::serde_json::Value::Bool(#b)
+                }
+            }
+            _ => {
+                // For other literals, convert to string
+                let elem_tokens = elem.to_token_stream();
+                quote! {
+                    ::serde_json::Value::String(#elem_tokens.to_string())
+                }
+            }
+        },
+        _ => {
+            let elem_tokens = elem.to_token_stream();
+            quote! {
+                ::serde_json::json!(#elem_tokens)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -60,97 +157,3 @@ mod tests {
         assert!(token_string.contains("apple"));
     }
 }
-
-/// Utility struct to parse array literal expressions
-/// Handles array literals like [1, 2, 3] or ["a", "b", "c"]
-pub struct ArrayAttr {
-    pub expr_array: ExprArray,
-}
-
-impl Parse for ArrayAttr {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let content;
-        bracketed!(content in input);
-        let mut elements = Vec::new();
-
-        // Parse comma-separated expressions inside brackets
-        while !content.is_empty() {
-            let elem: Expr = content.parse()?;
-            elements.push(elem);
-
-            if content.is_empty() {
-                break;
-            }
-
-            content.parse::<Token![,]>()?;
-        }
-
-        Ok(ArrayAttr {
-            expr_array: ExprArray {
-                attrs: Vec::new(),
-                bracket_token: syn::token::Bracket::default(),
-                elems: elements.into_iter().collect(),
-            },
-        })
-    }
-}
-
-/// Parse an array literal from an attribute value
-/// Returns a vector of TokenStreams representing JSON values for each array element
-pub fn parse_array_literal(value: &syn::parse::ParseBuffer) -> Option<Vec<TokenStream>> {
-    // Try to parse as a bracketed array
-    if let Ok(array_attr) = value.parse::<ArrayAttr>() {
-        // Process each element of the array
-        let mut tokens = Vec::new();
-
-        for elem in &array_attr.expr_array.elems {
-            match elem {
-                Expr::Lit(lit) => {
-                    match &lit.lit {
-                        Lit::Str(lit_str) => {
-                            let s = lit_str.value();
-                            tokens.push(quote! {
-                                ::serde_json::Value::String(#s.to_string())
-                            });
-                        }
-                        Lit::Int(lit_int) => {
-                            tokens.push(quote! {
-                                ::serde_json::Value::Number(::serde_json::Number::from(#lit_int))
-                            });
-                        }
-                        Lit::Float(lit_float) => {
-                            let s = lit_float.to_string();
-                            tokens.push(quote! {
-                                ::serde_json::json!(#s.parse::<f64>().unwrap())
-                            });
-                        }
-                        Lit::Bool(lit_bool) => {
-                            let b = lit_bool.value;
-                            tokens.push(quote! {
-                                ::serde_json::Value::Bool(#b)
-                            });
-                        }
-                        _ => {
-                            // For other literals, convert to string
-                            let elem_tokens = elem.to_token_stream();
-                            tokens.push(quote! {
-                                ::serde_json::Value::String(#elem_tokens.to_string())
-                            });
-                        }
-                    }
-                }
-                _ => {
-                    // For non-literals, convert to string
-                    let elem_tokens = elem.to_token_stream();
-                    tokens.push(quote! {
-                        ::serde_json::Value::String(format!("{}", #elem_tokens))
-                    });
-                }
-            }
-        }
-
-        Some(tokens)
-    } else {
-        None
-    }
-}
@@ -1,6 +1,7 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::Field;
+use syn::parse::ParseStream;
+use syn::{Field, Type};
 
 use crate::parsers::array_parser::parse_array_literal;
 use crate::type_utils::{TypeCategory, get_option_inner_type, get_type_category, is_option_type};
@@ -8,18 +9,79 @@ use crate::type_utils::{TypeCategory, get_option_inner_type, get_type_category,
 /// Represents parsed field attributes
 pub struct FieldAttributes {
     pub description: Option<String>,
+    /// Human-readable label from #[llm(title = "...")]
+    pub title: Option<String>,
     pub example_value: Option<TokenStream>,
     pub examples_array: Vec<TokenStream>,
+    /// Schema `default` from #[llm(default = ...)]
+    pub default_value: Option<TokenStream>,
+    /// Map cardinality from #[llm(min_properties = ..., max_properties = ...)]
+    pub min_properties: Option<u64>,
+    pub max_properties: Option<u64>,
+    /// String length bounds from #[llm(min_length = ..., max_length = ...)]
+    pub min_length: Option<u64>,
+    pub max_length: Option<u64>,
+    /// Array length bounds from #[llm(min_items = ..., max_items = ...)]
+    pub min_items: Option<u64>,
+    pub max_items: Option<u64>,
+    /// From #[llm(unique_items)] or #[llm(unique_items = true/false)]
+    pub unique_items: Option<bool>,
+    /// JSON Schema string format (`email`, `uri`, ...) from #[llm(format = "...")]
+    pub format: Option<String>,
+    /// Regex the string must match, from #[llm(pattern = "...")]
+    pub pattern: Option<String>,
+    /// Embedded payload format from #[llm(content_media_type = "...", content_encoding = "...")]
+    pub content_media_type: Option<String>,
+    pub content_encoding: Option<String>,
     /// Field rename from #[serde(rename = "...")]
     pub serde_rename: Option<String>,
+    /// Alternative names accepted on input, from #[serde(alias = "...")]
+    pub serde_aliases: Vec<String>,
+    /// Set when the field is serialized as a Unix timestamp, either through a known
+    /// `#[serde(with = "...")]` module or `#[llm(as_timestamp)]`; describes the unit
+    pub timestamp: Option<String>,
+    /// Per-field validation function path from #[llm(validate = "...")]
+    pub validate: Option<String>,
+    /// Leave an `Option` field out of the schema, from #[llm(skip_if_none)]
+    pub skip_if_none: bool,
+    /// Never read from JSON, from #[serde(skip)] or #[serde(skip_deserializing)]
+    pub serde_skip: bool,
+    /// May be absent from serialized JSON, from #[serde(skip_serializing)] or
+    /// #[serde(skip_serializing_if = "...")]
+    pub serde_skip_serializing: bool,
+    /// Properties are inlined into the parent, from #[serde(flatten)]
+    pub serde_flatten: bool,
+    /// Filled in when missing, from #[serde(default)] or #[serde(default = "...")]
+    pub serde_default: bool,
 }
 
 /// Parse a single field's llm and serde attributes
 pub fn parse_field_attributes(field: &Field) -> FieldAttributes {
     let mut description = None;
+    let mut title = None;
     let mut example_value = None;
     let mut examples_array = Vec::new();
+    let mut default_value = None;
+    let mut min_properties = None;
+    let mut max_properties = None;
+    let mut min_length = None;
+    let mut max_length = None;
+    let mut min_items = None;
+    let mut max_items = None;
+    let mut unique_items = None;
+    let mut format = None;
+    let mut pattern = None;
+    let mut content_media_type = None;
+    let mut content_encoding = None;
     let mut serde_rename = None;
+    let mut serde_aliases = Vec::new();
+    let mut timestamp = None;
+    let mut validate = None;
+    let mut skip_if_none = false;
+    let mut serde_skip = false;
+    let mut serde_skip_serializing = false;
+    let mut serde_flatten = false;
+    let mut serde_default = false;
 
     // Get the base type (unwrapping Option if present)
     let is_optional = is_option_type(&field.ty);
@@ -31,13 +93,43 @@ pub fn parse_field_attributes(field: &Field) -> FieldAttributes {
 
     // Extract attributes
     for attr in &field.attrs {
-        // Parse serde attributes for rename
+        // Parse serde attributes for rename and aliases
         if attr.path().is_ident("serde") {
             let _ = attr.parse_nested_meta(|meta| {
                 if meta.path.is_ident("rename") {
                     let value = meta.value()?;
                     let content: syn::LitStr = value.parse()?;
                     serde_rename = Some(content.value());
+                } else if meta.path.is_ident("alias") {
+                    let value = meta.value()?;
+                    let content: syn::LitStr = value.parse()?;
+                    serde_aliases.push(content.value());
+                } else if meta.path.is_ident("with")
+                    || meta.path.is_ident("serialize_with")
+                    || meta.path.is_ident("deserialize_with")
+                {
+                    let value = meta.value()?;
+                    let content: syn::LitStr = value.parse()?;
+                    if let Some(unit) = timestamp_unit(&content.value()) {
+                        timestamp = Some(format!("Unix timestamp in {}", unit));
+                    }
+                } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+                    serde_skip = true;
+                } else if meta.path.is_ident("default") {
+                    if meta.input.peek(syn::Token![=]) {
+                        meta.value()?.parse::<syn::LitStr>()?;
+                    }
+                    serde_default = true;
+                } else if meta.path.is_ident("flatten") {
+                    serde_flatten = true;
+                } else if meta.path.is_ident("skip_serializing") {
+                    serde_skip_serializing = true;
+                } else if meta.path.is_ident("skip_serializing_if") {
+                    meta.value()?.parse::<syn::LitStr>()?;
+                    serde_skip_serializing = true;
+                } else if meta.input.peek(syn::Token![=]) {
+                    // Skip values of other serde attributes so later ones still parse
+                    meta.value()?.parse::<syn::Expr>()?;
                 }
                 Ok(())
             });
@@ -50,118 +142,72 @@ pub fn parse_field_attributes(field: &Field) -> FieldAttributes {
                     let value = meta.value()?;
                     let content: syn::LitStr = value.parse()?;
                     description = Some(content.value());
+                } else if meta.path.is_ident("title") {
+                    let value = meta.value()?;
+                    let content: syn::LitStr = value.parse()?;
+                    title = Some(content.value());
                 } else if meta.path.is_ident("example") {
                     let value = meta.value()?;
-
-                    // First, try to parse as an array literal for array types
-                    if let TypeCategory::Array = get_type_category(base_type) {
-                        // Try to parse as an array literal
-                        if let Some(array_tokens) = parse_array_literal(value) {
-                            example_value = Some(quote! {
-                                ::serde_json::Value::Array(vec![#(#array_tokens),*])
-                            });
-                            return Ok(());
-                        }
-                    }
-
-                    // If not an array literal or not an array type, parse based on field type
-                    match get_type_category(base_type) {
-                        TypeCategory::String => {
-                            let content: syn::LitStr = value.parse()?;
-                            example_value = Some(quote! {
-                                ::serde_json::Value::String(#content.to_string())
-                            });
-                        },
-                        TypeCategory::Integer => {
-                            if let Ok(content) = value.parse::<syn::LitInt>() {
-                                example_value = Some(quote! {
-                                    ::serde_json::Value::Number(::serde_json::Number::from(#content))
-                                });
-                            } else if let Ok(content) = value.parse::<syn::LitStr>() {
-                                // Allow string for integer too
-                                let int_str = content.value();
-                                example_value = Some(quote! {
-                                    match #int_str.parse::<i64>() {
-                                        Ok(num) => ::serde_json::Value::Number(::serde_json::Number::from(num)),
-                                        Err(_) => ::serde_json::Value::String(#int_str.to_string())
-                                    }
-                                });
-                            }
-                        },
-                        TypeCategory::Float => {
-                            if let Ok(content) = value.parse::<syn::LitFloat>() {
-                                let float_str = content.to_string();
-                                example_value = Some(quote! {
-                                    match #float_str.parse::<f64>() {
-                                        Ok(num) => ::serde_json::json!(num),
-                                        Err(_) => ::serde_json::Value::String(#float_str.to_string())
-                                    }
-                                });
-                            } else if let Ok(content) = value.parse::<syn::LitStr>() {
-                                // Allow string for float too
-                                let float_str = content.value();
-                                example_value = Some(quote! {
-                                    match #float_str.parse::<f64>() {
-                                        Ok(num) => ::serde_json::json!(num),
-                                        Err(_) => ::serde_json::Value::String(#float_str.to_string())
-                                    }
-                                });
-                            }
-                        },
-                        TypeCategory::Boolean => {
-                            if let Ok(content) = value.parse::<syn::LitBool>() {
-                                let bool_val = content.value;
-                                example_value = Some(quote! {
-                                    ::serde_json::Value::Bool(#bool_val)
-                                });
-                            } else if let Ok(content) = value.parse::<syn::LitStr>() {
-                                // Allow string for boolean too
-                                let bool_str = content.value();
-                                example_value = Some(quote! {
-                                    match #bool_str.parse::<bool>() {
-                                        Ok(val) => ::serde_json::Value::Bool(val),
-                                        Err(_) => ::serde_json::Value::String(#bool_str.to_string())
-                                    }
-                                });
-                            }
-                        },
-                        TypeCategory::Array => {
-                            // For array types, we need to handle the special array syntax
-                            // Parse the attribute value as a string first
-                            if let Ok(content) = value.parse::<syn::LitStr>() {
-                                let array_str = content.value();
-
-                                // Check if it's a bracketed array like ["a", "b", "c"]
-                                if array_str.starts_with('[') && array_str.ends_with(']') {
-                                    // Parse as JSON array, but convert single quotes to double quotes
-                                    let json_array = array_str.replace('\'', "\"");
-                                    example_value = Some(quote! {
-                                        match ::serde_json::from_str(#json_array) {
-                                            Ok(val) => val,
-                                            Err(_) => ::serde_json::Value::String(#array_str.to_string())
-                                        }
-                                    });
-                                } else {
-                                    // Treat as a single string
-                                    example_value = Some(quote! {
-                                        ::serde_json::Value::Array(vec![::serde_json::Value::String(#array_str.to_string())])
-                                    });
-                                }
-                            }
-                        },
-                        TypeCategory::Object => {
-                            // For object types, parse as JSON string
-                            if let Ok(content) = value.parse::<syn::LitStr>() {
-                                let json_str = content.value();
-                                example_value = Some(quote! {
-                                    match ::serde_json::from_str(#json_str) {
-                                        Ok(val) => val,
-                                        Err(_) => ::serde_json::Value::String(#json_str.to_string())
-                                    }
-                                });
-                            }
-                        }
-                    }
+                    example_value = parse_typed_value(value, base_type)?;
+                } else if meta.path.is_ident("default") {
+                    let value = meta.value()?;
+                    default_value = parse_typed_value(value, base_type)?;
+                } else if meta.path.is_ident("validate") {
+                    let value = meta.value()?;
+                    let content: syn::LitStr = value.parse()?;
+                    validate = Some(content.value());
+                } else if meta.path.is_ident("skip_if_none") {
+                    skip_if_none = true;
+                } else if meta.path.is_ident("as_timestamp") {
+                    timestamp = Some("Unix timestamp in seconds".to_string());
+                } else if meta.path.is_ident("min_properties") {
+                    let value = meta.value()?;
+                    let lit: syn::LitInt = value.parse()?;
+                    min_properties = Some(lit.base10_parse()?);
+                } else if meta.path.is_ident("max_properties") {
+                    let value = meta.value()?;
+                    let lit: syn::LitInt = value.parse()?;
+                    max_properties = Some(lit.base10_parse()?);
+                } else if meta.path.is_ident("min_length") {
+                    let value = meta.value()?;
+                    let lit: syn::LitInt = value.parse()?;
+                    min_length = Some(lit.base10_parse()?);
+                } else if meta.path.is_ident("max_length") {
+                    let value = meta.value()?;
+                    let lit: syn::LitInt = value.parse()?;
+                    max_length = Some(lit.base10_parse()?);
+                } else if meta.path.is_ident("min_items") {
+                    let value = meta.value()?;
+                    let lit: syn::LitInt = value.parse()?;
+                    min_items = Some(lit.base10_parse()?);
+                } else if meta.path.is_ident("max_items") {
+                    let value = meta.value()?;
+                    let lit: syn::LitInt = value.parse()?;
+                    max_items = Some(lit.base10_parse()?);
+                } else if meta.path.is_ident("unique_items") {
+                    unique_items = Some(if meta.input.peek(syn::Token![=]) {
+                        let value = meta.value()?;
+                        let lit: syn::LitBool = value.parse()?;
+                        lit.value
+                    } else {
+                        true
+                    });
+                } else if meta.path.is_ident("format") {
+                    let value = meta.value()?;
+                    let content: syn::LitStr = value.parse()?;
+                    format = Some(content.value());
+                } else if meta.path.is_ident("pattern") {
+                    let value = meta.value()?;
+                    let content: syn::LitStr = value.parse()?;
+                    pattern = Some(content.value());
+                } else if meta.path.is_ident("content_media_type") {
+                    let value = meta.value()?;
+                    let content: syn::LitStr = value.parse()?;
+                    content_media_type = Some(content.value());
+                } else if meta.path.is_ident("content_encoding") {
+                    let value = meta.value()?;
+                    let content: syn::LitStr = value.parse()?;
+                    content_encoding = Some(content.value());
                 } else if meta.path.is_ident("examples") {
                     // First, try to parse as an array literal
                     let value = meta.value()?;
@@ -270,8 +316,190 @@ pub fn parse_field_attributes(field: &Field) -> FieldAttributes {
 
     FieldAttributes {
         description,
+        title,
         example_value,
         examples_array,
+        default_value,
+        min_properties,
+        max_properties,
+        min_length,
+        max_length,
+        min_items,
+        max_items,
+        unique_items,
+        format,
+        pattern,
+        content_media_type,
+        content_encoding,
         serde_rename,
+        serde_aliases,
+        timestamp,
+        validate,
+        skip_if_none,
+        serde_skip,
+        serde_skip_serializing,
+        serde_flatten,
+        serde_default,
+    }
+}
+
+/// Append a field's serde aliases to its description, for `#[llm(document_aliases)]`,
+/// so the model knows the alternative keys are also accepted
+pub fn describe_aliases(description: Option<String>, aliases: &[String]) -> Option<String> {
+    if aliases.is_empty() {
+        return description;
+    }
+    let aliases = aliases.join(", ");
+    Some(match description {
+        Some(desc) => format!("{} (also accepts: {})", desc, aliases),
+        None => format!("Also accepts: {}", aliases),
+    })
+}
+
+/// Unit of a serde helper module that encodes dates as Unix timestamps
+///
+/// Recognizes chrono's `ts_seconds`, `ts_milliseconds`, `ts_microseconds` and
+/// `ts_nanoseconds` (and their `_option` forms) and the `time` crate's `timestamp`
+/// modules, anywhere in the path (e.g. `chrono::serde::ts_seconds::serialize`).
+fn timestamp_unit(path: &str) -> Option<&'static str> {
+    let segments: Vec<&str> = path.split("::").map(str::trim).collect();
+    for (i, segment) in segments.iter().enumerate() {
+        let segment = segment.strip_suffix("_option").unwrap_or(segment);
+        match segment {
+            "ts_seconds" => return Some("seconds"),
+            "ts_milliseconds" => return Some("milliseconds"),
+            "ts_microseconds" => return Some("microseconds"),
+            "ts_nanoseconds" => return Some("nanoseconds"),
+            // time::serde::timestamp, optionally followed by a finer unit
+            "timestamp" => {
+                return match segments.get(i + 1).copied() {
+                    Some("milliseconds") => Some("milliseconds"),
+                    Some("microseconds") => Some("microseconds"),
+                    Some("nanoseconds") => Some("nanoseconds"),
+                    _ => Some("seconds"),
+                };
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Parse a single attribute value (e.g. `example = ...` or `default = ...`) into a
+/// `serde_json::Value` expression, interpreting the literal according to the field type
+fn parse_typed_value(value: ParseStream, base_type: &Type) -> syn::Result<Option<TokenStream>> {
+    // First, try to parse as an array literal for array types
+    if let TypeCategory::Array = get_type_category(base_type)
+        && let Some(array_tokens) = parse_array_literal(value)
+    {
+        return Ok(Some(quote! {
+            ::serde_json::Value::Array(vec![#(#array_tokens),*])
+        }));
     }
+
+    // If not an array literal or not an array type, parse based on field type
+    let tokens = match get_type_category(base_type) {
+        TypeCategory::String => {
+            let content: syn::LitStr = value.parse()?;
+            Some(quote! {
+                ::serde_json::Value::String(#content.to_string())
+            })
+        }
+        TypeCategory::Integer => {
+            if let Ok(content) = value.parse::<syn::LitInt>() {
+                Some(quote! {
+                    ::serde_json::Value::Number(::serde_json::Number::from(#content))
+                })
+            } else if let Ok(content) = value.parse::<syn::LitStr>() {
+                // Allow string for integer too
+                let int_str = content.value();
+                Some(quote! {
+                    match #int_str.parse::<i64>() {
+                        Ok(num) => ::serde_json::Value::Number(::serde_json::Number::from(num)),
+                        Err(_) => ::serde_json::Value::String(#int_str.to_string())
+                    }
+                })
+            } else {
+                None
+            }
+        }
+        TypeCategory::Float => {
+            let float_str = if let Ok(content) = value.parse::<syn::LitFloat>() {
+                Some(content.to_string())
+            } else if let Ok(content) = value.parse::<syn::LitStr>() {
+                // Allow string for float too
+                Some(content.value())
+            } else {
+                None
+            };
+            float_str.map(|float_str| {
+                quote! {
+                    match #float_str.parse::<f64>() {
+                        Ok(num) => ::serde_json::json!(num),
+                        Err(_) => ::serde_json::Value::String(#float_str.to_string())
+                    }
+                }
+            })
+        }
+        TypeCategory::Boolean => {
+            if let Ok(content) = value.parse::<syn::LitBool>() {
+                let bool_val = content.value;
+                Some(quote! {
+                    ::serde_json::Value::Bool(#bool_val)
+                })
+            } else if let Ok(content) = value.parse::<syn::LitStr>() {
+                // Allow string for boolean too
+                let bool_str = content.value();
+                Some(quote! {
+                    match #bool_str.parse::<bool>() {
+                        Ok(val) => ::serde_json::Value::Bool(val),
+                        Err(_) => ::serde_json::Value::String(#bool_str.to_string())
+                    }
+                })
+            } else {
+                None
+            }
+        }
+        TypeCategory::Array => {
+            // For array types given as a string, e.g. "['a', 'b']"
+            if let Ok(content) = value.parse::<syn::LitStr>() {
+                let array_str = content.value();
+
+                // Check if it's a bracketed array like ["a", "b", "c"]
+                if array_str.starts_with('[') && array_str.ends_with(']') {
+                    // Parse as JSON array, but convert single quotes to double quotes
+                    let json_array = array_str.replace('\'', "\"");
+                    Some(quote! {
+                        match ::serde_json::from_str(#json_array) {
+                            Ok(val) => val,
+                            Err(_) => ::serde_json::Value::String(#array_str.to_string())
+                        }
+                    })
+                } else {
+                    // Treat as a single string
+                    Some(quote! {
+                        ::serde_json::Value::Array(vec![::serde_json::Value::String(#array_str.to_string())])
+                    })
+                }
+            } else {
+                None
+            }
+        }
+        TypeCategory::Object => {
+            // For object types, parse as JSON string
+            if let Ok(content) = value.parse::<syn::LitStr>() {
+                let json_str = content.value();
+                Some(quote! {
+                    match ::serde_json::from_str(#json_str) {
+                        Ok(val) => val,
+                        Err(_) => ::serde_json::Value::String(#json_str.to_string())
+                    }
+                })
+            } else {
+                None
+            }
+        }
+    };
+
+    Ok(tokens)
 }
@@ -5,12 +5,18 @@ pub struct VariantAttributes {
     pub description: Option<String>,
     /// Variant rename from #[serde(rename = "...")]
     pub serde_rename: Option<String>,
+    /// Catch-all variant for unknown values, from #[llm(other)]
+    pub other: bool,
+    /// Variant-level #[serde(untagged)], which lets serde route unmatched values into it
+    pub serde_untagged: bool,
 }
 
 /// Parse a single enum variant's llm and serde attributes
 pub fn parse_variant_attributes(variant: &Variant) -> VariantAttributes {
     let mut description = None;
     let mut serde_rename = None;
+    let mut other = false;
+    let mut serde_untagged = false;
 
     // Extract attributes
     for attr in &variant.attrs {
@@ -21,6 +27,11 @@ pub fn parse_variant_attributes(variant: &Variant) -> VariantAttributes {
                     let value = meta.value()?;
                     let content: syn::LitStr = value.parse()?;
                     serde_rename = Some(content.value());
+                } else if meta.path.is_ident("untagged") {
+                    serde_untagged = true;
+                } else if meta.input.peek(syn::Token![=]) {
+                    // Skip values of other serde attributes so later ones still parse
+                    meta.value()?.parse::<syn::Expr>()?;
                 }
                 Ok(())
             });
@@ -33,6 +44,8 @@ pub fn parse_variant_attributes(variant: &Variant) -> VariantAttributes {
                     let value = meta.value()?;
                     let content: syn::LitStr = value.parse()?;
                     description = Some(content.value());
+                } else if meta.path.is_ident("other") {
+                    other = true;
                 }
                 Ok(())
             });
@@ -42,5 +55,7 @@ pub fn parse_variant_attributes(variant: &Variant) -> VariantAttributes {
     VariantAttributes {
         description,
         serde_rename,
+        other,
+        serde_untagged,
     }
 }
@@ -95,6 +95,57 @@ mod tests {
         assert_eq!(get_schema_type_from_rust_type(&map_type), "object");
         assert_eq!(get_schema_type_from_rust_type(&option_type), "string"); // Unwrapped
     }
+
+    #[test]
+    fn test_map_types_include_index_map() {
+        let index_map: Type = parse_quote!(IndexMap<String, u32>);
+        let qualified: Type = parse_quote!(indexmap::IndexMap<String, Vec<u8>>);
+        assert!(is_map_type(&index_map));
+        assert!(is_map_type(&qualified));
+
+        let (key, value) = get_map_types(&qualified).unwrap();
+        assert!(matches!(get_type_category(key), TypeCategory::String));
+        assert!(matches!(get_type_category(value), TypeCategory::Array));
+    }
+
+    #[test]
+    fn test_json_shape_ignores_numeric_width_and_paths() {
+        let shape = |ty: Type| json_shape(&ty);
+        assert_eq!(shape(parse_quote!(u8)), shape(parse_quote!(i64)));
+        assert_eq!(shape(parse_quote!(f32)), shape(parse_quote!(f64)));
+        assert_eq!(
+            shape(parse_quote!(String)),
+            shape(parse_quote!(std::string::String))
+        );
+        assert_eq!(
+            shape(parse_quote!(Vec<u16>)),
+            shape(parse_quote!(std::collections::BTreeSet<usize>))
+        );
+        assert_eq!(
+            shape(parse_quote!(HashMap<String, f32>)),
+            shape(parse_quote!(BTreeMap<u32, f64>))
+        );
+        assert_eq!(shape(parse_quote!(Box<&str>)), "string");
+    }
+
+    #[test]
+    fn test_json_shape_keeps_distinct_kinds_apart() {
+        let shape = |ty: Type| json_shape(&ty);
+        assert_ne!(shape(parse_quote!(i64)), shape(parse_quote!(f64)));
+        assert_ne!(
+            shape(parse_quote!(String)),
+            shape(parse_quote!(Option<String>))
+        );
+        assert_ne!(
+            shape(parse_quote!(Vec<u8>)),
+            shape(parse_quote!(Vec<String>))
+        );
+        assert_ne!(
+            shape(parse_quote!((f64, f64))),
+            shape(parse_quote!((f64, f64, f64)))
+        );
+        assert_ne!(shape(parse_quote!(Point)), shape(parse_quote!(Label)));
+    }
 }
 
 /// Enum to categorize Rust types for schema generation
@@ -143,7 +194,7 @@ pub fn get_type_category(ty: &Type) -> TypeCategory {
                 | "u128" | "usize" => return TypeCategory::Integer,
                 "f32" | "f64" => return TypeCategory::Float,
                 "Vec" | "Array" | "HashSet" | "BTreeSet" => return TypeCategory::Array,
-                "HashMap" | "BTreeMap" => return TypeCategory::Object,
+                "HashMap" | "BTreeMap" | "IndexMap" => return TypeCategory::Object,
                 _ => return TypeCategory::Object, // Default to object for custom types
             }
         }
@@ -163,13 +214,11 @@ pub fn get_schema_type_from_rust_type(ty: &Type) -> &'static str {
                 | "u128" | "usize" => return "integer",
                 "f32" | "f64" => return "number",
                 "Vec" | "Array" | "HashSet" | "BTreeSet" => return "array",
-                "HashMap" | "BTreeMap" => return "object",
+                "HashMap" | "BTreeMap" | "IndexMap" => return "object",
                 // Recognize common date types directly
-                "DateTime" | "NaiveDateTime" | "NaiveDate" | "Date" | "Utc" | "Local" => {
+                "DateTime" | "NaiveDateTime" | "NaiveDate" | "NaiveTime" | "Utc" | "Local" => {
                     return "string";
                 }
-                // Recognize UUID type
-                "Uuid" | "uuid::Uuid" => return "string",
                 "Option" => {
                     // For Option<T>, we need to look at the inner type
                     if let PathArguments::AngleBracketed(args) = &segment.arguments
@@ -214,24 +263,35 @@ pub fn is_array_type(ty: &Type) -> bool {
     false
 }
 
-/// Check if a type is a HashMap or BTreeMap
-pub fn is_map_type(ty: &Type) -> bool {
+/// Check if a type is a HashSet or BTreeSet
+pub fn is_set_type(ty: &Type) -> bool {
     if let Type::Path(type_path) = ty
         && let Some(segment) = type_path.path.segments.first()
     {
         let type_name = segment.ident.to_string();
-        return matches!(type_name.as_str(), "HashMap" | "BTreeMap");
+        return matches!(type_name.as_str(), "HashSet" | "BTreeSet");
+    }
+    false
+}
+
+/// Check if a type is a HashMap, BTreeMap or IndexMap
+pub fn is_map_type(ty: &Type) -> bool {
+    if let Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last()
+    {
+        let type_name = segment.ident.to_string();
+        return matches!(type_name.as_str(), "HashMap" | "BTreeMap" | "IndexMap");
     }
     false
 }
 
-/// Get the key and value types from a HashMap<K, V> or BTreeMap<K, V>
+/// Get the key and value types from a HashMap<K, V>, BTreeMap<K, V> or IndexMap<K, V>
 pub fn get_map_types(ty: &Type) -> Option<(&Type, &Type)> {
     if let Type::Path(type_path) = ty
-        && let Some(segment) = type_path.path.segments.first()
+        && let Some(segment) = type_path.path.segments.last()
     {
         let type_name = segment.ident.to_string();
-        if matches!(type_name.as_str(), "HashMap" | "BTreeMap") {
+        if matches!(type_name.as_str(), "HashMap" | "BTreeMap" | "IndexMap") {
             if let PathArguments::AngleBracketed(args) = &segment.arguments {
                 let mut args_iter = args.args.iter();
                 if let (Some(GenericArgument::Type(key_ty)), Some(GenericArgument::Type(val_ty))) =
@@ -245,6 +305,21 @@ pub fn get_map_types(ty: &Type) -> Option<(&Type, &Type)> {
     None
 }
 
+/// The `propertyNames` pattern for map keys of a primitive integer type, which JSON
+/// writes as strings (kept in sync with the `SchemaType` impls for integer-keyed maps)
+pub fn integer_key_pattern(ty: &Type) -> Option<&'static str> {
+    if let Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last()
+    {
+        return match segment.ident.to_string().as_str() {
+            "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => Some(r"^-?\d+$"),
+            "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => Some(r"^\d+$"),
+            _ => None,
+        };
+    }
+    None
+}
+
 /// Check if a type is a Box<T>
 pub fn is_box_type(ty: &Type) -> bool {
     if let Type::Path(type_path) = ty
@@ -284,6 +359,19 @@ pub fn is_json_value_type(ty: &Type) -> bool {
     false
 }
 
+/// Check if a type is a chrono date/time type with a `SchemaType` impl
+pub fn is_date_type(ty: &Type) -> bool {
+    if let Type::Path(type_path) = ty
+        && let Some(segment) = type_path.path.segments.last()
+    {
+        return matches!(
+            segment.ident.to_string().as_str(),
+            "DateTime" | "NaiveDateTime" | "NaiveDate" | "NaiveTime"
+        );
+    }
+    false
+}
+
 /// Check if a type is a tuple (e.g., (i32, String))
 pub fn is_tuple_type(ty: &Type) -> bool {
     matches!(ty, Type::Tuple(_))
@@ -332,3 +420,67 @@ pub fn get_core_type_name(ty: &Type) -> Option<String> {
 pub fn is_self_reference(ty: &Type, struct_name: &str) -> bool {
     get_core_type_name(ty).is_some_and(|name| name == struct_name)
 }
+
+/// The JSON shape a type serializes to, for telling untagged variants apart.
+///
+/// Types that produce the same kind of JSON value normalize to the same string, so
+/// `u8` and `i64` are both `integer`, `f32` and `f64` both `number`, and
+/// `std::string::String` is `string`. Wrappers that serialize transparently
+/// (`Box`, `Rc`, `Arc`, references) are looked through; other types keep their name,
+/// since their shape isn't visible from the macro.
+pub fn json_shape(ty: &Type) -> String {
+    match ty {
+        Type::Reference(reference) => json_shape(&reference.elem),
+        Type::Paren(paren) => json_shape(&paren.elem),
+        Type::Group(group) => json_shape(&group.elem),
+        Type::Array(array) => format!("array<{}>", json_shape(&array.elem)),
+        Type::Slice(slice) => format!("array<{}>", json_shape(&slice.elem)),
+        Type::Tuple(tuple) if tuple.elems.is_empty() => "null".to_string(),
+        Type::Tuple(tuple) => {
+            let elements: Vec<String> = tuple.elems.iter().map(json_shape).collect();
+            format!("[{}]", elements.join(", "))
+        }
+        Type::Path(type_path) => {
+            let Some(segment) = type_path.path.segments.last() else {
+                return "object".to_string();
+            };
+            let type_args: Vec<&Type> = match &segment.arguments {
+                PathArguments::AngleBracketed(args) => args
+                    .args
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            let name = segment.ident.to_string();
+            match (name.as_str(), type_args.as_slice()) {
+                ("String" | "str" | "char", _) => "string".to_string(),
+                ("bool", _) => "boolean".to_string(),
+                (
+                    "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64"
+                    | "u128" | "usize",
+                    _,
+                ) => "integer".to_string(),
+                ("f32" | "f64", _) => "number".to_string(),
+                ("Option", [inner]) => format!("{}?", json_shape(inner)),
+                ("Box" | "Rc" | "Arc" | "Cow", [.., inner]) => json_shape(inner),
+                (
+                    "Vec" | "VecDeque" | "Array" | "HashSet" | "BTreeSet" | "IndexSet",
+                    [inner, ..],
+                ) => format!("array<{}>", json_shape(inner)),
+                ("HashMap" | "BTreeMap" | "IndexMap", [_, value, ..]) => {
+                    format!("map<{}>", json_shape(value))
+                }
+                (_, []) => name,
+                (_, args) => {
+                    let args: Vec<String> = args.iter().map(|ty| json_shape(ty)).collect();
+                    format!("{}<{}>", name, args.join(", "))
+                }
+            }
+        }
+        other => quote::quote!(#other).to_string(),
+    }
+}
@@ -131,3 +131,46 @@ fn test_array_literal_multiple_examples() {
     assert_eq!(array[2], "Alice");
     assert_eq!(array[3], "Bob");
 }
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Address {
+    street: String,
+    city: String,
+}
+
+// Structured examples on object-typed and array-typed fields
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Customer {
+    #[llm(
+        description = "Shipping address",
+        examples = [
+            ::serde_json::json!({"street": "1 Infinite Loop", "city": "Cupertino"}),
+            ::serde_json::json!({"street": "221B Baker Street", "city": "London"})
+        ]
+    )]
+    address: Address,
+
+    #[llm(description = "Recent order totals", examples = [[12, 30], [-5]])]
+    order_totals: Vec<i64>,
+}
+
+#[test]
+fn test_array_literal_object_examples() {
+    let schema_json = Customer::schema().to_json();
+
+    // json! expressions keep their structure rather than becoming strings
+    let examples = schema_json["properties"]["address"]["examples"]
+        .as_array()
+        .unwrap();
+    assert_eq!(examples.len(), 2);
+    assert!(examples[0].is_object());
+    assert_eq!(examples[0]["street"], "1 Infinite Loop");
+    assert_eq!(examples[1]["city"], "London");
+
+    // Nested array literals become JSON arrays
+    let examples = schema_json["properties"]["order_totals"]["examples"]
+        .as_array()
+        .unwrap();
+    assert_eq!(examples[0], serde_json::json!([12, 30]));
+    assert_eq!(examples[1], serde_json::json!([-5]));
+}
@@ -34,3 +34,41 @@ fn test_enum_with_data_schema() {
         assert_eq!(variants.len(), 4, "Should have 4 variants");
     }
 }
+
+// Untagged variants are accepted as long as their payload shapes differ
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+enum Coordinate {
+    Point2D(f64, f64),
+    Point3D(f64, f64, f64),
+    Named { label: String, x: f64 },
+    Label(String),
+}
+
+#[test]
+fn test_untagged_enum_with_distinct_shapes() {
+    let schema = Coordinate::schema().to_json();
+    let variants = schema["oneOf"].as_array().unwrap();
+    assert_eq!(variants.len(), 4);
+    assert_eq!(variants[0]["maxItems"], 2);
+    assert_eq!(variants[1]["maxItems"], 3);
+}
+
+// Integers and floats are different JSON kinds
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+#[serde(untagged)]
+enum Measurement {
+    Count(u32),
+    Ratio(f64),
+    Labelled { label: String },
+    Scored { label: String, score: i64 },
+}
+
+#[test]
+fn test_untagged_enum_shapes_compare_json_kinds() {
+    let schema = Measurement::schema().to_json();
+    let variants = schema["oneOf"].as_array().unwrap();
+    assert_eq!(variants.len(), 4);
+    assert_eq!(variants[0]["type"], "integer");
+    assert_eq!(variants[1]["type"], "number");
+}
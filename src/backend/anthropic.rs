@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use reqwest::header::HeaderMap;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -7,13 +8,18 @@ use std::time::Duration;
 use tracing::{debug, error, info, instrument, trace, warn};
 
 use crate::backend::{
-    ChatMessage, GenerateResult, LLMClient, MaterializeInternalOutput, MaterializeResult,
-    ModelInfo, ThinkingLevel, TokenUsage, ValidationFailureContext, check_response_status,
-    generate_with_retry_with_history, handle_http_error, parse_validate_and_create_output,
-    prepare_strict_schema,
+    Budget, ChatMessage, ChatRole, GenerateResult, LLMClient, MaterializeInternalOutput,
+    MaterializeResult, ModelInfo, RequestOptions, RetryPolicy, ThinkingLevel, TokenUsage,
+    ValidationFailureContext, check_response_status, custom_headers, dry_run_output,
+    effective_top_p, estimated_usage, few_shot_messages, fit_context_window,
+    generate_with_retry_from_messages, generate_with_retry_with_history, handle_http_error,
+    log_schema_once, parse_validate_and_create_output, prepare_strict_schema, read_json_response,
+    reserved_tokens, resolve_thinking_level, response_request_id, unwrap_value_root,
+    with_system_prompts, wrap_non_object_root,
 };
 use crate::error::{ApiErrorKind, RStructorError, Result};
 use crate::model::Instructor;
+use crate::schema::Schema;
 
 /// Anthropic models available for completion
 ///
@@ -96,6 +102,31 @@ impl AnthropicModel {
             _ => AnthropicModel::Custom(name),
         }
     }
+
+    /// Parse a model name, rejecting names that don't match a known variant.
+    ///
+    /// Unlike [`from_string`](Self::from_string), an unknown name returns an
+    /// `InvalidModel` error instead of becoming `Custom`, so typos in configuration
+    /// are caught before the first request.
+    ///
+    /// ```rust
+    /// use rstructor::AnthropicModel;
+    ///
+    /// assert_eq!(AnthropicModel::try_known("claude-sonnet-4-5-20250929").unwrap(), AnthropicModel::ClaudeSonnet45);
+    /// assert!(AnthropicModel::try_known("claude-sonet-4-5").is_err());
+    /// ```
+    pub fn try_known(name: &str) -> Result<Self> {
+        match Self::from_string(name) {
+            AnthropicModel::Custom(_) => Err(RStructorError::api_error(
+                "Anthropic",
+                ApiErrorKind::InvalidModel {
+                    model: name.to_string(),
+                    suggestion: None,
+                },
+            )),
+            model => Ok(model),
+        }
+    }
 }
 
 impl FromStr for AnthropicModel {
@@ -126,13 +157,40 @@ pub struct AnthropicConfig {
     pub temperature: f32,
     pub max_tokens: Option<u32>,
     pub timeout: Option<Duration>,
+    /// Upper bound on the total time of a `materialize` call, including retries
+    pub deadline: Option<Duration>,
     pub max_retries: Option<usize>,
+    /// Waits between retry attempts
+    pub retry_policy: RetryPolicy,
+    /// Extra headers sent with every request
+    pub headers: HeaderMap,
+    /// Sequences that end generation when produced (empty = none)
+    pub stop_sequences: Vec<String>,
+    /// Nucleus sampling probability mass (None = provider default)
+    pub top_p: Option<f32>,
     /// Custom base URL for Anthropic-compatible APIs
     /// Defaults to "https://api.anthropic.com/v1" if not set
     pub base_url: Option<String>,
     /// Thinking level for Claude 4.x models (Sonnet 4, Opus 4, etc.)
     /// When enabled, temperature is automatically set to 1.0 as required by the API
     pub thinking_level: Option<ThinkingLevel>,
+    /// Cumulative spending limit shared across clones of this client
+    pub budget: Option<Budget>,
+    /// Strip stray control characters from responses before parsing
+    pub lenient_parsing: bool,
+    /// Drop the oldest messages when a conversation exceeds the model's context window
+    pub auto_truncate: bool,
+    /// Log each type's prepared schema once, at debug level
+    pub log_schemas: bool,
+    /// System instructions sent ahead of every request, in the order they were added
+    pub system_prompts: Vec<String>,
+    /// Return schema-shaped placeholder data instead of calling the API
+    pub dry_run: bool,
+    /// Largest response body to read, in bytes (None = no limit)
+    pub max_response_bytes: Option<usize>,
+    /// Request structured output by forcing a single tool call instead of using
+    /// native structured outputs
+    pub force_tool_use: bool,
 }
 
 /// Anthropic client for generating completions
@@ -156,16 +214,68 @@ struct OutputFormat {
     schema: Value,
 }
 
+/// Tool definition used to force structured output via `tool_choice`
+#[derive(Debug, Serialize)]
+struct AnthropicTool {
+    name: String,
+    description: String,
+    input_schema: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct ToolChoice {
+    #[serde(rename = "type")]
+    choice_type: String,
+    name: String,
+}
+
 #[derive(Debug, Serialize)]
 struct CompletionRequest {
     model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system: Option<String>,
     messages: Vec<AnthropicMessage>,
     temperature: f32,
     max_tokens: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    stop_sequences: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     thinking: Option<ClaudeThinkingConfig>,
     #[serde(skip_serializing_if = "Option::is_none")]
     output_format: Option<OutputFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<AnthropicTool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<ToolChoice>,
+}
+
+/// Name of the tool whose input carries the structured response
+const STRUCTURED_OUTPUT_TOOL: &str = "structured_output";
+
+/// Join system messages into Anthropic's top-level `system` prompt.
+///
+/// The Messages API has no `system` role, so system messages are sent separately.
+fn system_prompt(messages: &[ChatMessage]) -> Option<String> {
+    let texts: Vec<&str> = messages
+        .iter()
+        .filter(|msg| msg.role == ChatRole::System)
+        .map(|msg| msg.content.as_str())
+        .collect();
+    (!texts.is_empty()).then(|| texts.join("\n\n"))
+}
+
+/// Convert the user and assistant turns of a conversation to Anthropic's format
+fn api_messages(messages: &[ChatMessage]) -> Vec<AnthropicMessage> {
+    messages
+        .iter()
+        .filter(|msg| msg.role != ChatRole::System)
+        .map(|msg| AnthropicMessage {
+            role: msg.role.as_str().to_string(),
+            content: msg.content.clone(),
+        })
+        .collect()
 }
 
 #[derive(Debug, Serialize)]
@@ -179,7 +289,11 @@ struct ClaudeThinkingConfig {
 struct ContentBlock {
     #[serde(rename = "type")]
     block_type: String,
+    #[serde(default)]
     text: String,
+    /// Arguments of a `tool_use` block
+    #[serde(default)]
+    input: Option<Value>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -229,10 +343,23 @@ impl AnthropicClient {
             model: AnthropicModel::ClaudeSonnet45, // Default to Claude Sonnet 4.5 (latest flagship)
             temperature: 0.0,
             max_tokens: None,
-            timeout: None,        // Default: no timeout (uses reqwest's default)
-            max_retries: Some(3), // Default: 3 retries with error feedback
-            base_url: None,       // Default: use official Anthropic API
-            thinking_level: None, // Default: no extended thinking (faster responses)
+            timeout: None,              // Default: no timeout (uses reqwest's default)
+            deadline: None,             // Default: no overall deadline
+            max_retries: Some(3),       // Default: 3 retries with error feedback
+            base_url: None,             // Default: use official Anthropic API
+            budget: None,               // Default: no spending limit
+            lenient_parsing: false,     // Default: strict JSON parsing
+            auto_truncate: false,       // Default: send conversations as-is
+            log_schemas: false,         // Default: don't log schemas
+            system_prompts: Vec::new(), // Default: no system instructions
+            dry_run: false,             // Default: call the provider
+            max_response_bytes: None,   // Default: no limit
+            thinking_level: None,       // Default: no extended thinking (faster responses)
+            force_tool_use: false,      // Default: native structured outputs
+            retry_policy: RetryPolicy::default(), // Default: exponential backoff from 500ms
+            headers: HeaderMap::new(),  // Default: no extra headers
+            stop_sequences: Vec::new(), // Default: no stop sequences
+            top_p: None,                // Default: provider's default
         };
 
         debug!("Anthropic client created with default configuration");
@@ -271,10 +398,23 @@ impl AnthropicClient {
             model: AnthropicModel::ClaudeSonnet45, // Default to Claude Sonnet 4.5 (latest flagship)
             temperature: 0.0,
             max_tokens: None,
-            timeout: None,        // Default: no timeout (uses reqwest's default)
-            max_retries: Some(3), // Default: 3 retries with error feedback
-            base_url: None,       // Default: use official Anthropic API
-            thinking_level: None, // Default: no extended thinking (faster responses)
+            timeout: None,              // Default: no timeout (uses reqwest's default)
+            deadline: None,             // Default: no overall deadline
+            max_retries: Some(3),       // Default: 3 retries with error feedback
+            base_url: None,             // Default: use official Anthropic API
+            budget: None,               // Default: no spending limit
+            lenient_parsing: false,     // Default: strict JSON parsing
+            auto_truncate: false,       // Default: send conversations as-is
+            log_schemas: false,         // Default: don't log schemas
+            system_prompts: Vec::new(), // Default: no system instructions
+            dry_run: false,             // Default: call the provider
+            max_response_bytes: None,   // Default: no limit
+            thinking_level: None,       // Default: no extended thinking (faster responses)
+            force_tool_use: false,      // Default: native structured outputs
+            retry_policy: RetryPolicy::default(), // Default: exponential backoff from 500ms
+            headers: HeaderMap::new(),  // Default: no extra headers
+            stop_sequences: Vec::new(), // Default: no stop sequences
+            top_p: None,                // Default: provider's default
         };
 
         debug!("Anthropic client created with default configuration");
@@ -288,49 +428,38 @@ impl AnthropicClient {
 }
 
 impl AnthropicClient {
-    /// Internal implementation of materialize (without retry logic)
-    /// Accepts conversation history for multi-turn interactions.
-    /// Returns the data, raw response, and optional usage info.
-    ///
-    /// Uses Anthropic's native Structured Outputs with `output_format: json_schema`
-    /// for guaranteed schema compliance.
-    ///
-    /// The raw response is included to enable conversation history tracking for retries,
-    /// which improves prompt caching efficiency.
-    async fn materialize_internal<T>(
+    /// Build the structured-output request for a conversation, returning it together
+    /// with whether the schema root was wrapped in an object.
+    fn structured_request(
         &self,
         messages: &[ChatMessage],
-    ) -> std::result::Result<
-        MaterializeInternalOutput<T>,
-        (RStructorError, Option<ValidationFailureContext>),
-    >
-    where
-        T: Instructor + DeserializeOwned + Send + 'static,
-    {
-        info!("Generating structured response with Anthropic (native structured outputs)");
-
-        // Get the schema for type T
-        let schema = T::schema();
+        schema: &Schema,
+        options: &RequestOptions,
+    ) -> (CompletionRequest, bool) {
+        let force_tool_use = self.config.force_tool_use;
         trace!("Retrieved JSON schema for type");
 
         // Prepare schema with additionalProperties: false recursively for all nested objects
-        let schema_json = prepare_strict_schema(&schema);
+        let mut schema_json = prepare_strict_schema(schema);
+        // Strict mode needs an object root, so enums get wrapped in `{"value": ...}`
+        let wrapped_root = wrap_non_object_root(&mut schema_json);
 
         // Build API messages from conversation history
         // With native structured outputs, we don't need to include schema instructions in the prompt
-        let api_messages: Vec<AnthropicMessage> = messages
-            .iter()
-            .map(|msg| AnthropicMessage {
-                role: msg.role.as_str().to_string(),
-                content: msg.content.clone(),
-            })
-            .collect();
+        let api_messages = api_messages(messages);
 
         // Build thinking config for Claude 4.x models
-        let is_thinking_model = self.config.model.as_str().contains("sonnet-4")
-            || self.config.model.as_str().contains("opus-4");
-        let thinking_config = self.config.thinking_level.and_then(|level| {
-            if is_thinking_model && level.claude_thinking_enabled() {
+        let thinking_level = resolve_thinking_level(
+            self.config.model.as_str(),
+            options.thinking_level.or(self.config.thinking_level),
+        );
+        let thinking_config = thinking_level.and_then(|level| {
+            if level.claude_thinking_enabled() {
+                // The API rejects a forced tool_choice while extended thinking is enabled
+                if force_tool_use {
+                    warn!("Extended thinking is not supported with forced tool use, disabling it");
+                    return None;
+                }
                 Some(ClaudeThinkingConfig {
                     thinking_type: "enabled".to_string(),
                     budget_tokens: level.claude_budget_tokens(),
@@ -344,64 +473,171 @@ impl AnthropicClient {
         let effective_temp = if thinking_config.is_some() {
             1.0
         } else {
-            self.config.temperature
+            options.temperature.unwrap_or(self.config.temperature)
         };
 
-        // Create output format for native structured outputs
-        let output_format = OutputFormat {
-            format_type: "json_schema".to_string(),
-            schema: schema_json,
+        // Either force a single tool whose input is the schema, or use native structured outputs
+        let (output_format, tools, tool_choice) = if force_tool_use {
+            let tool = AnthropicTool {
+                name: STRUCTURED_OUTPUT_TOOL.to_string(),
+                description: "Respond with data matching this schema".to_string(),
+                input_schema: schema_json,
+            };
+            let choice = ToolChoice {
+                choice_type: "tool".to_string(),
+                name: STRUCTURED_OUTPUT_TOOL.to_string(),
+            };
+            (None, Some(vec![tool]), Some(choice))
+        } else {
+            let output_format = OutputFormat {
+                format_type: "json_schema".to_string(),
+                schema: schema_json,
+            };
+            (Some(output_format), None, None)
         };
 
-        // Build the request with native structured outputs
         debug!(
+            force_tool_use,
             "Building Anthropic API request with structured outputs (history_len={})",
             api_messages.len()
         );
         let request = CompletionRequest {
             model: self.config.model.as_str().to_string(),
+            system: system_prompt(messages),
             messages: api_messages,
             temperature: effective_temp,
-            max_tokens: self.config.max_tokens.unwrap_or(1024), // Default to 1024 if not specified
+            max_tokens: options
+                .max_tokens
+                .or(self.config.max_tokens)
+                .unwrap_or(1024), // Default to 1024 if not specified
+            top_p: effective_top_p(options, self.config.top_p),
+            stop_sequences: options
+                .stop
+                .clone()
+                .unwrap_or_else(|| self.config.stop_sequences.clone()),
             thinking: thinking_config,
-            output_format: Some(output_format),
+            output_format,
+            tools,
+            tool_choice,
         };
 
+        (request, wrapped_root)
+    }
+
+    /// Internal implementation of materialize (without retry logic)
+    /// Accepts conversation history for multi-turn interactions.
+    /// Returns the data, raw response, and optional usage info.
+    ///
+    /// Uses Anthropic's native Structured Outputs with `output_format: json_schema`
+    /// for guaranteed schema compliance.
+    ///
+    /// The raw response is included to enable conversation history tracking for retries,
+    /// which improves prompt caching efficiency.
+    async fn materialize_internal<T>(
+        &self,
+        messages: &[ChatMessage],
+        schema: &Schema,
+        options: &RequestOptions,
+    ) -> std::result::Result<
+        MaterializeInternalOutput<T>,
+        (RStructorError, Option<ValidationFailureContext>),
+    >
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        if self.config.dry_run {
+            return dry_run_output::<T>(schema);
+        }
+        let messages = with_system_prompts(&self.config.system_prompts, messages);
+        let fitted = fit_context_window(
+            &messages,
+            self.config.model.as_str(),
+            reserved_tokens(
+                self.config.model.as_str(),
+                Some(schema),
+                options.max_tokens.or(self.config.max_tokens),
+            ),
+            self.config.auto_truncate,
+        );
+        let messages: &[ChatMessage] = &fitted;
+        let reservation = self
+            .config
+            .budget
+            .as_ref()
+            .map(|budget| {
+                budget.check(&estimated_usage(
+                    self.config.model.as_str(),
+                    messages,
+                    Some(schema),
+                    options.max_tokens.or(self.config.max_tokens),
+                ))
+            })
+            .transpose()
+            .map_err(|e| (e, None))?;
+
+        let force_tool_use = self.config.force_tool_use;
+        if force_tool_use {
+            info!("Generating structured response with Anthropic (forced tool use)");
+        } else {
+            info!("Generating structured response with Anthropic (native structured outputs)");
+        }
+
+        let (request, wrapped_root) = self.structured_request(messages, schema, options);
+        if self.config.log_schemas {
+            let sent_schema = match (&request.output_format, &request.tools) {
+                (Some(format), _) => Some(&format.schema),
+                (None, Some(tools)) => tools.first().map(|tool| &tool.input_schema),
+                (None, None) => None,
+            };
+            if let Some(sent_schema) = sent_schema {
+                log_schema_once::<T>("Anthropic", sent_schema);
+            }
+        }
+
         // Send the request to Anthropic with structured outputs beta header
         debug!(
             model = %self.config.model.as_str(),
             max_tokens = request.max_tokens,
             "Sending request to Anthropic API with structured outputs"
         );
-        let base_url = self
-            .config
+        let base_url = options
             .base_url
             .as_deref()
+            .or(self.config.base_url.as_deref())
             .unwrap_or("https://api.anthropic.com/v1");
         let url = format!("{}/messages", base_url);
         debug!(url = %url, "Using Anthropic API endpoint");
-        let response = self
+        let mut request_builder = self
             .client
             .post(&url)
+            .headers(custom_headers(&self.config.headers))
             .header("x-api-key", &self.config.api_key)
             .header("anthropic-version", "2023-06-01")
-            .header("anthropic-beta", "structured-outputs-2025-11-13")
-            .header("Content-Type", "application/json")
+            .header("Content-Type", "application/json");
+        if !force_tool_use {
+            request_builder =
+                request_builder.header("anthropic-beta", "structured-outputs-2025-11-13");
+        }
+        let response = request_builder
             .json(&request)
             .send()
             .await
             .map_err(|e| (handle_http_error(e, "Anthropic"), None))?;
 
         // Parse the response
-        let response = check_response_status(response, "Anthropic")
+        let response = check_response_status(response, "Anthropic", self.config.max_response_bytes)
             .await
             .map_err(|e| (e, None))?;
+        let request_id = response_request_id(&response);
 
         debug!("Successfully received response from Anthropic");
-        let completion: CompletionResponse = response.json().await.map_err(|e| {
-            error!(error = %e, "Failed to parse JSON response from Anthropic");
-            (RStructorError::from(e), None)
-        })?;
+        let completion: CompletionResponse =
+            read_json_response(response, "Anthropic", self.config.max_response_bytes)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to parse JSON response from Anthropic");
+                    (e, None)
+                })?;
 
         // Extract usage info
         let model_name = completion
@@ -412,6 +648,41 @@ impl AnthropicClient {
             .usage
             .as_ref()
             .map(|u| TokenUsage::new(model_name.clone(), u.input_tokens, u.output_tokens));
+        if let Some(reservation) = reservation {
+            reservation.record(usage.as_ref());
+        }
+
+        if force_tool_use {
+            let input = completion
+                .content
+                .iter()
+                .find(|block| block.block_type == "tool_use")
+                .and_then(|block| block.input.as_ref());
+            let Some(input) = input else {
+                error!("No tool_use block in Anthropic response");
+                return Err((
+                    RStructorError::api_error(
+                        "Anthropic",
+                        ApiErrorKind::UnexpectedResponse {
+                            details: "No tool_use content in response".to_string(),
+                        },
+                    ),
+                    None,
+                ));
+            };
+            let raw_response = if wrapped_root {
+                unwrap_value_root(input.to_string(), false)
+            } else {
+                input.to_string()
+            };
+            trace!(json = %raw_response, "Parsing tool_use input");
+            return parse_validate_and_create_output(
+                raw_response,
+                usage,
+                self.config.lenient_parsing,
+            )
+            .map(|output| output.with_request_id(request_id));
+        }
 
         // Extract the content, assuming the first block is text containing JSON
         let raw_response = match completion
@@ -441,10 +712,17 @@ impl AnthropicClient {
             }
         };
 
+        let raw_response = if wrapped_root {
+            unwrap_value_root(raw_response, self.config.lenient_parsing)
+        } else {
+            raw_response
+        };
+
         // Parse the JSON content directly using shared utility
         // With native structured outputs, the response is guaranteed to be valid JSON
         trace!(json = %raw_response, "Parsing structured output response");
-        parse_validate_and_create_output(raw_response, usage)
+        parse_validate_and_create_output(raw_response, usage, self.config.lenient_parsing)
+            .map(|output| output.with_request_id(request_id))
     }
 }
 
@@ -508,93 +786,81 @@ impl AnthropicClient {
         self.config.thinking_level = Some(level);
         self
     }
-}
 
-#[async_trait]
-impl LLMClient for AnthropicClient {
-    fn from_env() -> Result<Self> {
-        Self::from_env()
-    }
-
-    #[instrument(
-        name = "anthropic_materialize",
-        skip(self, prompt),
-        fields(
-            type_name = std::any::type_name::<T>(),
-            model = %self.config.model.as_str(),
-            prompt_len = prompt.len()
-        )
-    )]
-    async fn materialize<T>(&self, prompt: &str) -> Result<T>
-    where
-        T: Instructor + DeserializeOwned + Send + 'static,
-    {
-        let output = generate_with_retry_with_history(
-            |messages: Vec<ChatMessage>| {
-                let this = self;
-                async move { this.materialize_internal::<T>(&messages).await }
-            },
-            prompt,
-            self.config.max_retries,
-        )
-        .await?;
-        Ok(output.data)
-    }
-
-    #[instrument(
-        name = "anthropic_materialize_with_metadata",
-        skip(self, prompt),
-        fields(
-            type_name = std::any::type_name::<T>(),
-            model = %self.config.model.as_str(),
-            prompt_len = prompt.len()
-        )
-    )]
-    async fn materialize_with_metadata<T>(&self, prompt: &str) -> Result<MaterializeResult<T>>
-    where
-        T: Instructor + DeserializeOwned + Send + 'static,
-    {
-        let output = generate_with_retry_with_history(
-            |messages: Vec<ChatMessage>| {
-                let this = self;
-                async move { this.materialize_internal::<T>(&messages).await }
-            },
-            prompt,
-            self.config.max_retries,
-        )
-        .await?;
-        Ok(MaterializeResult::new(output.data, output.usage))
+    /// Request structured output by forcing a tool call instead of native structured outputs.
+    ///
+    /// When enabled, the schema is sent as the `input_schema` of a single tool and
+    /// `tool_choice` forces Claude to call it; the tool's input is then parsed and
+    /// validated like any other response. This works on models and Anthropic-compatible
+    /// APIs without structured-outputs support. Extended thinking is disabled in this
+    /// mode because the API does not allow it alongside a forced tool choice.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rstructor::AnthropicClient;
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = AnthropicClient::from_env()?.force_tool_use(true);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub fn force_tool_use(mut self, enabled: bool) -> Self {
+        tracing::debug!(
+            previous = self.config.force_tool_use,
+            new = enabled,
+            "Setting forced tool use"
+        );
+        self.config.force_tool_use = enabled;
+        self
     }
 
-    #[instrument(
-        name = "anthropic_generate",
-        skip(self, prompt),
-        fields(
-            model = %self.config.model.as_str(),
-            prompt_len = prompt.len()
-        )
-    )]
-    async fn generate(&self, prompt: &str) -> Result<String> {
-        let result = self.generate_with_metadata(prompt).await?;
-        Ok(result.text)
-    }
+    /// Internal implementation of raw text generation for a conversation, with
+    /// per-call overrides applied over the client configuration.
+    async fn generate_internal(
+        &self,
+        messages: &[ChatMessage],
+        options: &RequestOptions,
+    ) -> Result<GenerateResult> {
+        if self.config.dry_run {
+            return Ok(GenerateResult::new(String::new(), None));
+        }
+        let messages = with_system_prompts(&self.config.system_prompts, messages);
+        let fitted = fit_context_window(
+            &messages,
+            self.config.model.as_str(),
+            reserved_tokens(
+                self.config.model.as_str(),
+                None,
+                options.max_tokens.or(self.config.max_tokens),
+            ),
+            self.config.auto_truncate,
+        );
+        let messages: &[ChatMessage] = &fitted;
+        let reservation = self
+            .config
+            .budget
+            .as_ref()
+            .map(|budget| {
+                budget.check(&estimated_usage(
+                    self.config.model.as_str(),
+                    messages,
+                    None,
+                    options.max_tokens.or(self.config.max_tokens),
+                ))
+            })
+            .transpose()?;
 
-    #[instrument(
-        name = "anthropic_generate_with_metadata",
-        skip(self, prompt),
-        fields(
-            model = %self.config.model.as_str(),
-            prompt_len = prompt.len()
-        )
-    )]
-    async fn generate_with_metadata(&self, prompt: &str) -> Result<GenerateResult> {
         info!("Generating raw text response with Anthropic");
 
         // Build thinking config for Claude 4.x models
-        let is_thinking_model = self.config.model.as_str().contains("sonnet-4")
-            || self.config.model.as_str().contains("opus-4");
-        let thinking_config = self.config.thinking_level.and_then(|level| {
-            if is_thinking_model && level.claude_thinking_enabled() {
+        let thinking_level = resolve_thinking_level(
+            self.config.model.as_str(),
+            options.thinking_level.or(self.config.thinking_level),
+        );
+        let thinking_config = thinking_level.and_then(|level| {
+            if level.claude_thinking_enabled() {
                 Some(ClaudeThinkingConfig {
                     thinking_type: "enabled".to_string(),
                     budget_tokens: level.claude_budget_tokens(),
@@ -608,21 +874,29 @@ impl LLMClient for AnthropicClient {
         let effective_temp = if thinking_config.is_some() {
             1.0
         } else {
-            self.config.temperature
+            options.temperature.unwrap_or(self.config.temperature)
         };
 
         // Build the request (no output_format for raw text generation)
         debug!("Building Anthropic API request for text generation");
         let request = CompletionRequest {
             model: self.config.model.as_str().to_string(),
-            messages: vec![AnthropicMessage {
-                role: "user".to_string(),
-                content: prompt.to_string(),
-            }],
+            system: system_prompt(messages),
+            messages: api_messages(messages),
             temperature: effective_temp,
-            max_tokens: self.config.max_tokens.unwrap_or(1024), // Default to 1024 if not specified
+            max_tokens: options
+                .max_tokens
+                .or(self.config.max_tokens)
+                .unwrap_or(1024), // Default to 1024 if not specified
+            top_p: effective_top_p(options, self.config.top_p),
+            stop_sequences: options
+                .stop
+                .clone()
+                .unwrap_or_else(|| self.config.stop_sequences.clone()),
             thinking: thinking_config,
             output_format: None, // Raw text generation doesn't use structured outputs
+            tools: None,
+            tool_choice: None,
         };
 
         // Send the request to Anthropic
@@ -631,16 +905,17 @@ impl LLMClient for AnthropicClient {
             max_tokens = request.max_tokens,
             "Sending request to Anthropic API"
         );
-        let base_url = self
-            .config
+        let base_url = options
             .base_url
             .as_deref()
+            .or(self.config.base_url.as_deref())
             .unwrap_or("https://api.anthropic.com/v1");
         let url = format!("{}/messages", base_url);
         debug!(url = %url, "Using Anthropic API endpoint");
         let response = self
             .client
             .post(&url)
+            .headers(custom_headers(&self.config.headers))
             .header("x-api-key", &self.config.api_key)
             .header("anthropic-version", "2023-06-01")
             .header("Content-Type", "application/json")
@@ -650,13 +925,18 @@ impl LLMClient for AnthropicClient {
             .map_err(|e| handle_http_error(e, "Anthropic"))?;
 
         // Parse the response
-        let response = check_response_status(response, "Anthropic").await?;
+        let response =
+            check_response_status(response, "Anthropic", self.config.max_response_bytes).await?;
+        let request_id = response_request_id(&response);
 
         debug!("Successfully received response from Anthropic");
-        let completion: CompletionResponse = response.json().await.map_err(|e| {
-            error!(error = %e, "Failed to parse JSON response from Anthropic");
-            e
-        })?;
+        let completion: CompletionResponse =
+            read_json_response(response, "Anthropic", self.config.max_response_bytes)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to parse JSON response from Anthropic");
+                    e
+                })?;
 
         // Extract usage info
         let model_name = completion
@@ -667,6 +947,9 @@ impl LLMClient for AnthropicClient {
             .usage
             .as_ref()
             .map(|u| TokenUsage::new(model_name, u.input_tokens, u.output_tokens));
+        if let Some(reservation) = reservation {
+            reservation.record(usage.as_ref());
+        }
 
         // Extract the content
         debug!("Extracting text content from response blocks");
@@ -692,7 +975,277 @@ impl LLMClient for AnthropicClient {
             content_len = content.len(),
             "Successfully extracted text content"
         );
-        Ok(GenerateResult::new(content, usage))
+        Ok(GenerateResult::new(content, usage).with_request_id(request_id))
+    }
+}
+
+#[async_trait]
+impl LLMClient for AnthropicClient {
+    fn from_env() -> Result<Self> {
+        Self::from_env()
+    }
+
+    #[instrument(
+        name = "anthropic_materialize",
+        skip(self, prompt),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn materialize<T>(&self, prompt: &str) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            prompt,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "anthropic_materialize_with_schema",
+        skip(self, prompt, schema),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn materialize_with_schema<T>(&self, prompt: &str, schema: Schema) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            prompt,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "anthropic_materialize_opts",
+        skip(self, prompt, options),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn materialize_opts<T>(&self, prompt: &str, options: &RequestOptions) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, options)
+                        .await
+                }
+            },
+            prompt,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    fn materialize_request_body<T>(&self, prompt: &str) -> Result<serde_json::Value>
+    where
+        T: Instructor,
+    {
+        let schema = T::schema();
+        let prompt = [ChatMessage::user(prompt)];
+        let messages = with_system_prompts(&self.config.system_prompts, &prompt);
+        let (request, _) = self.structured_request(&messages, &schema, &RequestOptions::default());
+        Ok(serde_json::to_value(&request)?)
+    }
+
+    #[instrument(
+        name = "anthropic_materialize_with_examples",
+        skip(self, prompt, examples),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len(),
+            examples_len = examples.len()
+        )
+    )]
+    async fn materialize_with_examples<T>(
+        &self,
+        prompt: &str,
+        examples: &[(String, T)],
+    ) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + Sync + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_from_messages(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            few_shot_messages(prompt, examples)?,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "anthropic_materialize_with_history",
+        skip(self, messages),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            messages_len = messages.len()
+        )
+    )]
+    async fn materialize_with_history<T>(&self, messages: &[ChatMessage]) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_from_messages(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            messages.to_vec(),
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "anthropic_materialize_with_metadata",
+        skip(self, prompt),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn materialize_with_metadata<T>(&self, prompt: &str) -> Result<MaterializeResult<T>>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            prompt,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(MaterializeResult::new(output.data, output.usage)
+            .with_attempts(output.attempts)
+            .with_request_id(output.request_id))
+    }
+
+    #[instrument(
+        name = "anthropic_generate",
+        skip(self, prompt),
+        fields(
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        let result = self.generate_with_metadata(prompt).await?;
+        Ok(result.text)
+    }
+
+    #[instrument(
+        name = "anthropic_generate_with_metadata",
+        skip(self, prompt),
+        fields(
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn generate_with_metadata(&self, prompt: &str) -> Result<GenerateResult> {
+        self.generate_with_messages(&[ChatMessage::user(prompt)])
+            .await
+    }
+
+    #[instrument(
+        name = "anthropic_generate_opts",
+        skip(self, prompt, options),
+        fields(
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn generate_opts(&self, prompt: &str, options: &RequestOptions) -> Result<String> {
+        let result = self
+            .generate_internal(&[ChatMessage::user(prompt)], options)
+            .await?;
+        Ok(result.text)
+    }
+
+    #[instrument(
+        name = "anthropic_generate_with_messages",
+        skip(self, messages),
+        fields(
+            model = %self.config.model.as_str(),
+            history_len = messages.len()
+        )
+    )]
+    async fn generate_with_messages(&self, messages: &[ChatMessage]) -> Result<GenerateResult> {
+        self.generate_internal(messages, &RequestOptions::default())
+            .await
     }
 
     /// Fetch available models from Anthropic's API.
@@ -711,6 +1264,7 @@ impl LLMClient for AnthropicClient {
         let response = self
             .client
             .get(&url)
+            .headers(custom_headers(&self.config.headers))
             .header("x-api-key", &self.config.api_key)
             .header("anthropic-version", "2023-06-01")
             .header("Content-Type", "application/json")
@@ -718,12 +1272,16 @@ impl LLMClient for AnthropicClient {
             .await
             .map_err(|e| handle_http_error(e, "Anthropic"))?;
 
-        let response = check_response_status(response, "Anthropic").await?;
+        let response =
+            check_response_status(response, "Anthropic", self.config.max_response_bytes).await?;
 
-        let json: serde_json::Value = response.json().await.map_err(|e| {
-            error!(error = %e, "Failed to parse models response from Anthropic");
-            e
-        })?;
+        let json: serde_json::Value =
+            read_json_response(response, "Anthropic", self.config.max_response_bytes)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to parse models response from Anthropic");
+                    e
+                })?;
 
         let models = json
             .get("data")
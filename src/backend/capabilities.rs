@@ -0,0 +1,468 @@
+use std::borrow::Cow;
+
+use tracing::{debug, warn};
+
+use crate::backend::{ChatMessage, ChatRole, ThinkingLevel, TokenUsage};
+use crate::schema::Schema;
+
+/// Tokens set aside for the response when no `max_tokens` is configured
+const DEFAULT_RESPONSE_TOKENS: usize = 1024;
+
+/// Context window sizes (in tokens) by model id prefix.
+///
+/// Lookups use the longest matching prefix, so more specific entries (e.g. `o1-mini`)
+/// take precedence over their family (`o1`).
+const CONTEXT_WINDOWS: &[(&str, usize)] = &[
+    // OpenAI
+    ("gpt-5", 400_000),
+    ("gpt-4.1", 1_047_576),
+    ("gpt-4o", 128_000),
+    ("gpt-4-turbo", 128_000),
+    ("gpt-4", 8_192),
+    ("gpt-3.5-turbo", 16_385),
+    ("o1-mini", 128_000),
+    ("o1", 200_000),
+    ("o3", 200_000),
+    ("o4", 200_000),
+    // Anthropic
+    ("claude-", 200_000),
+    // Gemini
+    ("gemini-", 1_048_576),
+    // Grok
+    ("grok-4-fast", 2_000_000),
+    ("grok-4-1-fast", 2_000_000),
+    ("grok-4", 256_000),
+    ("grok-code-fast", 256_000),
+    ("grok-3", 131_072),
+    ("grok-2", 131_072),
+    // Mistral
+    ("mistral-large", 128_000),
+    ("mistral-medium", 128_000),
+    ("mistral-small", 128_000),
+    ("codestral", 256_000),
+    ("ministral", 128_000),
+    ("pixtral", 128_000),
+    ("open-mistral-nemo", 128_000),
+];
+
+/// Context window of a model in tokens, or `None` for unknown models.
+pub(crate) fn context_window(model: &str) -> Option<usize> {
+    CONTEXT_WINDOWS
+        .iter()
+        .filter(|(prefix, _)| model.starts_with(prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+        .map(|(_, tokens)| *tokens)
+}
+
+/// Rough token count for text (about four bytes per token for English prose).
+pub(crate) fn estimate_tokens(text: &str) -> usize {
+    text.len().div_ceil(4)
+}
+
+/// Token count of text sent to `model`.
+///
+/// With the `tokenizer` feature, models with a known BPE encoding (OpenAI's) are counted
+/// exactly; everything else falls back to [`estimate_tokens`].
+pub(crate) fn count_tokens(model: &str, text: &str) -> usize {
+    #[cfg(feature = "tokenizer")]
+    if let Some(bpe) = bpe_for_model(model) {
+        return bpe.encode_with_special_tokens(text).len();
+    }
+    #[cfg(not(feature = "tokenizer"))]
+    let _ = model;
+    estimate_tokens(text)
+}
+
+/// The BPE encoding used by `model`, built once per encoding and shared by every model
+/// that uses it (building one parses a vocabulary of 100k+ entries).
+#[cfg(feature = "tokenizer")]
+fn bpe_for_model(model: &str) -> Option<&'static tiktoken_rs::CoreBPE> {
+    let tokenizer = tiktoken_rs::tokenizer::get_tokenizer(model)?;
+    // A process-wide singleton per encoding
+    tiktoken_rs::bpe_for_tokenizer(tokenizer).ok()
+}
+
+/// Tokens needed besides the conversation: the schema plus room for the response.
+pub(crate) fn reserved_tokens(
+    model: &str,
+    schema: Option<&Schema>,
+    max_tokens: Option<u32>,
+) -> usize {
+    let schema_tokens = schema.map_or(0, |schema| {
+        count_tokens(model, &schema.to_json().to_string())
+    });
+    schema_tokens + max_tokens.map_or(DEFAULT_RESPONSE_TOKENS, |max| max as usize)
+}
+
+/// The most a call could use, for reserving against a budget: the messages and schema
+/// as prompt tokens, and `max_tokens` (or the default response allowance) as output.
+pub(crate) fn estimated_usage(
+    model: &str,
+    messages: &[ChatMessage],
+    schema: Option<&Schema>,
+    max_tokens: Option<u32>,
+) -> TokenUsage {
+    let prompt_tokens: usize = messages
+        .iter()
+        .map(|msg| count_tokens(model, &msg.content))
+        .chain(schema.map(|schema| count_tokens(model, &schema.to_json().to_string())))
+        .sum();
+    let output_tokens = max_tokens.map_or(DEFAULT_RESPONSE_TOKENS, |max| max as usize);
+    TokenUsage::new(model, prompt_tokens as u64, output_tokens as u64)
+}
+
+/// Drop the oldest conversation messages until the rest fit in the model's context.
+///
+/// `reserved_tokens` covers everything sent besides the messages (the schema and the
+/// space left for the response). System messages and the latest message are always
+/// kept, and the remaining conversation still starts with a user message. Messages are
+/// returned unchanged when `enabled` is false, the model's context
+/// window is unknown, or they already fit.
+pub(crate) fn fit_context_window<'a>(
+    messages: &'a [ChatMessage],
+    model: &str,
+    reserved_tokens: usize,
+    enabled: bool,
+) -> Cow<'a, [ChatMessage]> {
+    if !enabled {
+        return Cow::Borrowed(messages);
+    }
+    let Some(window) = context_window(model) else {
+        debug!(model, "Unknown context window, skipping auto-truncation");
+        return Cow::Borrowed(messages);
+    };
+
+    let budget = window.saturating_sub(reserved_tokens);
+    let tokens: Vec<usize> = messages
+        .iter()
+        .map(|msg| count_tokens(model, &msg.content))
+        .collect();
+    let mut total: usize = tokens.iter().sum();
+    if total <= budget {
+        return Cow::Borrowed(messages);
+    }
+
+    let last = messages.len().saturating_sub(1);
+    let mut keep = vec![true; messages.len()];
+    let mut dropped = 0;
+    let mut dropped_tokens = 0;
+    for (i, msg) in messages.iter().enumerate() {
+        if total <= budget {
+            break;
+        }
+        if i == last || msg.role == ChatRole::System {
+            continue;
+        }
+        keep[i] = false;
+        total -= tokens[i];
+        dropped += 1;
+        dropped_tokens += tokens[i];
+    }
+
+    // The conversation must still open with a user turn
+    for (i, msg) in messages.iter().enumerate() {
+        if !keep[i] || msg.role == ChatRole::System {
+            continue;
+        }
+        if msg.role == ChatRole::User || i == last {
+            break;
+        }
+        keep[i] = false;
+        total -= tokens[i];
+        dropped += 1;
+        dropped_tokens += tokens[i];
+    }
+
+    if total > budget {
+        warn!(
+            model,
+            estimated_tokens = total + reserved_tokens,
+            context_window = window,
+            "Prompt still exceeds the context window after truncation"
+        );
+    }
+    warn!(
+        model,
+        dropped_messages = dropped,
+        dropped_tokens,
+        "Truncated oldest messages to fit the context window"
+    );
+    Cow::Owned(
+        messages
+            .iter()
+            .zip(keep)
+            .filter(|(_, keep)| *keep)
+            .map(|(msg, _)| msg.clone())
+            .collect(),
+    )
+}
+
+/// Thinking levels each model family accepts, by model id pattern.
+///
+/// Entries are checked in order, so more specific patterns (e.g. Gemini 3 Pro) come
+/// before their family. Models that match nothing don't support thinking controls.
+const THINKING_LEVELS: &[(&str, &[ThinkingLevel])] = &[
+    // Gemini 3 Pro only accepts low/high and can't disable thinking
+    ("gemini-3*pro", &[ThinkingLevel::Low, ThinkingLevel::High]),
+    (
+        "gemini-3",
+        &[
+            ThinkingLevel::Minimal,
+            ThinkingLevel::Low,
+            ThinkingLevel::Medium,
+            ThinkingLevel::High,
+        ],
+    ),
+    (
+        "gpt-5",
+        &[
+            ThinkingLevel::Off,
+            ThinkingLevel::Low,
+            ThinkingLevel::Medium,
+            ThinkingLevel::High,
+        ],
+    ),
+    // Claude 4.x maps every level onto a fixed thinking budget
+    (
+        "*sonnet-4",
+        &[
+            ThinkingLevel::Off,
+            ThinkingLevel::Minimal,
+            ThinkingLevel::Low,
+            ThinkingLevel::Medium,
+            ThinkingLevel::High,
+        ],
+    ),
+    (
+        "*opus-4",
+        &[
+            ThinkingLevel::Off,
+            ThinkingLevel::Minimal,
+            ThinkingLevel::Low,
+            ThinkingLevel::Medium,
+            ThinkingLevel::High,
+        ],
+    ),
+];
+
+/// Match a model id against a pattern: a prefix, optionally with a leading `*` (match
+/// anywhere) and a `*` between parts that must appear in order.
+fn model_matches(model: &str, pattern: &str) -> bool {
+    let (anywhere, pattern) = match pattern.strip_prefix('*') {
+        Some(rest) => (true, rest),
+        None => (false, pattern),
+    };
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let mut rest = if anywhere {
+        match model.find(first) {
+            Some(idx) => &model[idx + first.len()..],
+            None => return false,
+        }
+    } else {
+        match model.strip_prefix(first) {
+            Some(rest) => rest,
+            None => return false,
+        }
+    };
+    for part in parts {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Thinking levels a model accepts, or `None` if it has no thinking controls.
+pub(crate) fn supported_thinking_levels(model: &str) -> Option<&'static [ThinkingLevel]> {
+    THINKING_LEVELS
+        .iter()
+        .find(|(pattern, _)| model_matches(model, pattern))
+        .map(|(_, levels)| *levels)
+}
+
+/// Resolve the thinking level to send for a model, warning when it has to change.
+///
+/// Unsupported levels are clamped to the closest level the model accepts (ties go to
+/// the higher level), e.g. `Minimal` becomes `Low` on Gemini 3 Pro. Returns `None` when
+/// no thinking setting should be sent: no level was requested, the model has no
+/// thinking controls, or `Off` was requested for a model that can't disable thinking.
+pub(crate) fn resolve_thinking_level(
+    model: &str,
+    level: Option<ThinkingLevel>,
+) -> Option<ThinkingLevel> {
+    let level = level?;
+    let Some(supported) = supported_thinking_levels(model) else {
+        // Low is the client default, so ignoring it isn't worth a warning
+        if matches!(level, ThinkingLevel::Off | ThinkingLevel::Low) {
+            debug!(
+                model,
+                ?level,
+                "Model has no thinking controls, ignoring thinking level"
+            );
+        } else {
+            warn!(
+                model,
+                ?level,
+                "Model does not support thinking levels, ignoring it"
+            );
+        }
+        return None;
+    };
+    if supported.contains(&level) {
+        return Some(level);
+    }
+    if level == ThinkingLevel::Off {
+        warn!(
+            model,
+            "Model cannot disable thinking, using the provider default"
+        );
+        return None;
+    }
+    let rank = |l: ThinkingLevel| l as i32;
+    let clamped = supported
+        .iter()
+        .copied()
+        .filter(|l| *l != ThinkingLevel::Off)
+        .min_by_key(|l| ((rank(*l) - rank(level)).abs(), -rank(*l)))?;
+    warn!(
+        model,
+        requested = ?level,
+        using = ?clamped,
+        "Thinking level not supported by model, using closest supported level"
+    );
+    Some(clamped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "tokenizer")]
+    #[test]
+    fn test_models_share_one_bpe_per_encoding() {
+        let gpt_4o = bpe_for_model("gpt-4o").unwrap();
+        assert!(std::ptr::eq(gpt_4o, bpe_for_model("gpt-4o").unwrap()));
+        assert!(std::ptr::eq(gpt_4o, bpe_for_model("gpt-4o-mini").unwrap()));
+        assert!(!std::ptr::eq(gpt_4o, bpe_for_model("gpt-4").unwrap()));
+        assert!(bpe_for_model("claude-sonnet-4-5").is_none());
+    }
+
+    #[test]
+    fn test_context_window_prefers_longest_prefix() {
+        assert_eq!(context_window("o1-mini-2024-09-12"), Some(128_000));
+        assert_eq!(context_window("o1"), Some(200_000));
+        assert_eq!(context_window("gpt-4o-mini"), Some(128_000));
+        assert_eq!(context_window("gpt-4"), Some(8_192));
+        assert_eq!(context_window("codestral-2501"), Some(256_000));
+        assert_eq!(context_window("claude-sonnet-4-5-20250929"), Some(200_000));
+        assert_eq!(context_window("llama-3.1-70b"), None);
+    }
+
+    #[test]
+    fn test_fit_context_window_drops_oldest_turns() {
+        // gpt-4 has an 8,192 token window; each turn is ~3,000 tokens, however counted
+        let turn = "word ".repeat(3_000);
+        let messages = vec![
+            ChatMessage::system("Be brief."),
+            ChatMessage::user(turn.clone()),
+            ChatMessage::assistant(turn.clone()),
+            ChatMessage::user(turn.clone()),
+        ];
+
+        // Dropping the first user turn leaves an assistant turn first, so it goes too
+        let fitted = fit_context_window(&messages, "gpt-4", 1_000, true);
+        assert_eq!(fitted.len(), 2);
+        assert_eq!(fitted[0].role, ChatRole::System);
+        assert_eq!(fitted[1].role, ChatRole::User);
+
+        // Nothing changes when disabled, for unknown models, or when it already fits
+        assert_eq!(
+            fit_context_window(&messages, "gpt-4", 1_000, false).len(),
+            4
+        );
+        assert_eq!(
+            fit_context_window(&messages, "custom", 1_000, true).len(),
+            4
+        );
+        assert_eq!(
+            fit_context_window(&messages, "gpt-4o", 1_000, true).len(),
+            4
+        );
+    }
+
+    #[cfg(feature = "tokenizer")]
+    #[test]
+    fn test_fit_context_window_counts_with_the_tokenizer() {
+        // 10,000 tokens at four bytes each, but repeated letters encode to far fewer
+        let messages = vec![
+            ChatMessage::user("x".repeat(20_000)),
+            ChatMessage::user("x".repeat(20_000)),
+        ];
+        assert!(
+            count_tokens("gpt-4", &messages[0].content) < estimate_tokens(&messages[0].content)
+        );
+        assert_eq!(fit_context_window(&messages, "gpt-4", 0, true).len(), 2);
+    }
+
+    #[test]
+    fn test_fit_context_window_keeps_latest_message() {
+        let messages = vec![
+            ChatMessage::user("x".repeat(40_000)),
+            ChatMessage::user("x".repeat(40_000)),
+        ];
+        let fitted = fit_context_window(&messages, "gpt-4", 0, true);
+        assert_eq!(fitted.len(), 1);
+    }
+
+    #[test]
+    fn test_resolve_thinking_level_clamps_per_model() {
+        let pro = "gemini-3-pro-preview";
+        assert_eq!(
+            resolve_thinking_level(pro, Some(ThinkingLevel::Minimal)),
+            Some(ThinkingLevel::Low)
+        );
+        assert_eq!(
+            resolve_thinking_level(pro, Some(ThinkingLevel::Medium)),
+            Some(ThinkingLevel::High)
+        );
+        assert_eq!(resolve_thinking_level(pro, Some(ThinkingLevel::Off)), None);
+
+        let flash = "gemini-3-flash-preview";
+        assert_eq!(
+            resolve_thinking_level(flash, Some(ThinkingLevel::Minimal)),
+            Some(ThinkingLevel::Minimal)
+        );
+
+        assert_eq!(
+            resolve_thinking_level("gpt-5.2", Some(ThinkingLevel::Minimal)),
+            Some(ThinkingLevel::Low)
+        );
+        assert_eq!(
+            resolve_thinking_level("gpt-5.2", Some(ThinkingLevel::Off)),
+            Some(ThinkingLevel::Off)
+        );
+        assert_eq!(
+            resolve_thinking_level("claude-opus-4-5-20251101", Some(ThinkingLevel::Minimal)),
+            Some(ThinkingLevel::Minimal)
+        );
+    }
+
+    #[test]
+    fn test_resolve_thinking_level_ignores_non_thinking_models() {
+        assert_eq!(
+            resolve_thinking_level("gpt-4o", Some(ThinkingLevel::High)),
+            None
+        );
+        assert_eq!(
+            resolve_thinking_level("gemini-2.5-flash", Some(ThinkingLevel::Low)),
+            None
+        );
+        assert_eq!(
+            resolve_thinking_level("claude-3-5-haiku-latest", Some(ThinkingLevel::High)),
+            None
+        );
+        assert_eq!(resolve_thinking_level("gemini-3-pro-preview", None), None);
+    }
+}
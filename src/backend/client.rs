@@ -1,10 +1,136 @@
 use async_trait::async_trait;
-use serde::de::DeserializeOwned;
+use futures::stream::{BoxStream, StreamExt};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
 
-use crate::backend::ModelInfo;
-use crate::backend::usage::{GenerateResult, MaterializeResult};
-use crate::error::Result;
+use crate::backend::usage::{BatchItem, GenerateResult, MaterializeResult, UsageTracker};
+use crate::backend::{
+    ChatMessage, ChatRole, ModelInfo, RequestOptions, count_tokens, unwrap_value_root,
+    wrap_non_object_root,
+};
+use crate::error::{ApiErrorKind, RStructorError, Result};
 use crate::model::Instructor;
+use crate::schema::{Schema, SchemaType};
+
+/// Error returned by the default `*_opts` methods when overrides are requested
+fn unsupported_options_error() -> RStructorError {
+    RStructorError::api_error(
+        "LLMClient",
+        ApiErrorKind::BadRequest {
+            details: "Per-call request options are not supported by this client".to_string(),
+        },
+    )
+}
+
+/// Render structured input as a prompt: pretty-printed JSON, after `instruction` if given
+fn serialized_prompt<P: Serialize + ?Sized>(
+    instruction: Option<&str>,
+    input: &P,
+) -> Result<String> {
+    let json = serde_json::to_string_pretty(input)?;
+    Ok(match instruction {
+        Some(instruction) => format!("{}\n\n{}", instruction, json),
+        None => json,
+    })
+}
+
+/// Object wrapper used by `materialize_many_items`, since providers expect an object
+/// at the root of a structured output
+#[derive(Serialize, Deserialize)]
+struct ItemList<T> {
+    items: Vec<T>,
+}
+
+impl<T: SchemaType> SchemaType for ItemList<T> {
+    fn schema() -> Schema {
+        let mut items = Schema::for_array_of::<T>().into_value();
+        let mut schema = serde_json::Map::new();
+        schema.insert("type".to_string(), json!("object"));
+        // Keep recursive definitions at the root, where their `$ref`s point
+        if let Some(items_obj) = items.as_object_mut() {
+            for key in ["$defs", "definitions"] {
+                if let Some(defs) = items_obj.remove(key) {
+                    schema.insert(key.to_string(), defs);
+                }
+            }
+        }
+        schema.insert("properties".to_string(), json!({ "items": items }));
+        schema.insert("required".to_string(), json!(["items"]));
+        Schema::new(Value::Object(schema))
+    }
+
+    fn schema_name() -> Option<String> {
+        let item_name = T::schema_name().unwrap_or_else(|| "Item".to_string());
+        Some(format!("{}List", item_name))
+    }
+}
+
+impl<T: Instructor> Instructor for ItemList<T> {
+    fn validate(&self) -> Result<()> {
+        for (index, item) in self.items.iter().enumerate() {
+            item.validate().map_err(|err| match err {
+                RStructorError::ValidationError(msg) => {
+                    RStructorError::ValidationError(format!("items[{}]: {}", index, msg))
+                }
+                other => other,
+            })?;
+        }
+        Ok(())
+    }
+
+    fn normalize(&mut self) -> Result<()> {
+        self.items.iter_mut().try_for_each(Instructor::normalize)
+    }
+}
+
+/// Append `schema` to `prompt`, for extraction through plain text generation.
+///
+/// A non-object schema (such as an enum's) is wrapped the same way as for structured
+/// output modes; returns whether it was, so the answer can be unwrapped.
+fn schema_embedded_prompt(prompt: &str, schema: &Schema) -> (String, bool) {
+    let mut schema = schema.to_json();
+    let wrapped = wrap_non_object_root(&mut schema);
+    let prompt = format!(
+        "{}\n\nYou may explain your answer first. Then give the answer as a JSON value \
+         matching this JSON Schema:\n```json\n{}\n```",
+        prompt,
+        Schema::new(schema).to_pretty_json()
+    );
+    (prompt, wrapped)
+}
+
+/// The first top-level JSON object or array in `text` that deserializes into `T`.
+///
+/// Models often wrap the value in prose or a fenced code block, so each `{` and `[`
+/// outside an earlier complete value is tried as a starting point and parsing stops
+/// at the end of the value. Values nested inside one that doesn't fit `T` aren't
+/// tried, so a partly wrong answer can't match through one of its fields. With
+/// `wrapped` set, the answer is unwrapped from its `value` property first.
+fn find_embedded_json<T: DeserializeOwned>(text: &str, wrapped: bool) -> Option<T> {
+    let mut from = 0;
+    while let Some(offset) = text[from..].find(['{', '[']) {
+        let start = from + offset;
+        let mut values =
+            serde_json::Deserializer::from_str(&text[start..]).into_iter::<IgnoredAny>();
+        if let Some(Ok(_)) = values.next() {
+            let end = start + values.byte_offset();
+            let json = text[start..end].to_string();
+            let json = if wrapped {
+                unwrap_value_root(json, false)
+            } else {
+                json
+            };
+            if let Ok(value) = serde_json::from_str(&json) {
+                return Some(value);
+            }
+            from = end;
+        } else {
+            from = start + 1;
+        }
+    }
+    None
+}
 
 /// File reference for media-aware prompts (e.g., Gemini file URI + MIME type).
 #[derive(Debug, Clone)]
@@ -34,14 +160,19 @@ impl MediaFile {
 /// - `AnthropicClient` for Anthropic's Claude models
 /// - `GrokClient` for xAI's Grok models
 /// - `GeminiClient` for Google's Gemini models
+/// - `MistralClient` for Mistral AI's models
+/// - `OllamaClient` for local models served by Ollama
 ///
 /// All clients implement a consistent interface:
-/// - `new(api_key)` - Create client with explicit API key (rejects empty strings)
+/// - `new(api_key)` - Create client with explicit API key (rejects empty strings;
+///   `OllamaClient::new()` takes none)
 /// - `from_env()` - Create client from environment variable (required by this trait):
 ///   - OpenAI: `OPENAI_API_KEY`
 ///   - Anthropic: `ANTHROPIC_API_KEY`
 ///   - Grok: `XAI_API_KEY`
 ///   - Gemini: `GEMINI_API_KEY`
+///   - Mistral: `MISTRAL_API_KEY`
+///   - Ollama: `OLLAMA_HOST` (optional server address; no API key)
 /// - Builder methods: `model()`, `temperature()`, `max_tokens()`, `timeout()`
 /// - All clients validate `max_tokens >= 1` to avoid API errors
 /// - Timeout is applied immediately when `timeout()` is called - no need to call `build()`
@@ -153,6 +284,333 @@ pub trait LLMClient {
         self.materialize(prompt).await
     }
 
+    /// Materialize a structured object using a hand-written schema instead of `T::schema()`.
+    ///
+    /// This is an escape hatch for cases where the generated schema is wrong or too
+    /// rigid. The provided schema is sent to the provider as-is (after the usual
+    /// provider-specific preparation), and the response is still deserialized into `T`
+    /// and validated, with the normal retry logic.
+    ///
+    /// The default implementation returns a `SchemaError`; all built-in clients
+    /// support schema overrides.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rstructor::{LLMClient, OpenAIClient, Instructor, Schema};
+    /// # use serde::{Serialize, Deserialize};
+    /// # #[derive(Instructor, Serialize, Deserialize)]
+    /// # struct Movie { title: String }
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OpenAIClient::from_env()?;
+    /// let schema = Schema::new(serde_json::json!({
+    ///     "type": "object",
+    ///     "properties": {
+    ///         "title": { "type": "string", "description": "Original release title" }
+    ///     },
+    ///     "required": ["title"]
+    /// }));
+    /// let movie: Movie = client.materialize_with_schema("Describe Inception", schema).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn materialize_with_schema<T>(&self, _prompt: &str, _schema: Schema) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        Err(RStructorError::SchemaError(
+            "Schema overrides are not supported by this client".to_string(),
+        ))
+    }
+
+    /// Materialize a structured object with per-call parameter overrides.
+    ///
+    /// Like [`materialize`](Self::materialize), but any field set in `options`
+    /// (temperature, max tokens, top-p, thinking level, stop sequences) replaces the
+    /// client's configured value for this call only.
+    ///
+    /// The default implementation accepts only empty options; all built-in clients
+    /// support every override.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rstructor::{LLMClient, OpenAIClient, Instructor, RequestOptions};
+    /// # use serde::{Serialize, Deserialize};
+    /// # #[derive(Instructor, Serialize, Deserialize)]
+    /// # struct Movie { title: String }
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OpenAIClient::from_env()?;
+    /// let options = RequestOptions::new().with_temperature(0.7).with_max_tokens(200);
+    /// let movie: Movie = client.materialize_opts("Invent a movie", &options).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn materialize_opts<T>(&self, prompt: &str, options: &RequestOptions) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        if options.is_empty() {
+            return self.materialize(prompt).await;
+        }
+        Err(unsupported_options_error())
+    }
+
+    /// Materialize a structured object, guided by typed few-shot examples.
+    ///
+    /// Each `(prompt, expected)` pair is sent before the real prompt as a user turn
+    /// followed by an assistant turn containing `expected` serialized as JSON, so the
+    /// model sees worked examples in exactly the output format it must produce. The
+    /// examples stay in place across retries.
+    ///
+    /// The default implementation only supports an empty `examples` slice; all
+    /// built-in clients support few-shot examples.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rstructor::{LLMClient, OpenAIClient, Instructor};
+    /// # use serde::{Serialize, Deserialize};
+    /// #[derive(Instructor, Serialize, Deserialize)]
+    /// struct Sentiment {
+    ///     label: String,
+    ///     score: f32,
+    /// }
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OpenAIClient::from_env()?;
+    /// let examples = vec![
+    ///     (
+    ///         "I loved every minute of it".to_string(),
+    ///         Sentiment { label: "positive".into(), score: 0.95 },
+    ///     ),
+    ///     (
+    ///         "It was fine, I guess".to_string(),
+    ///         Sentiment { label: "neutral".into(), score: 0.5 },
+    ///     ),
+    /// ];
+    /// let sentiment: Sentiment = client
+    ///     .materialize_with_examples("The ending ruined it for me", &examples)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn materialize_with_examples<T>(
+        &self,
+        prompt: &str,
+        examples: &[(String, T)],
+    ) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + Sync + 'static,
+    {
+        if examples.is_empty() {
+            return self.materialize(prompt).await;
+        }
+        Err(RStructorError::api_error(
+            "LLMClient",
+            ApiErrorKind::BadRequest {
+                details: "Few-shot examples are not supported by this client".to_string(),
+            },
+        ))
+    }
+
+    /// Materialize a structured object from a conversation you maintain yourself.
+    ///
+    /// `messages` is sent as-is (after the client's system prompts), so earlier turns
+    /// give the model context for the current extraction. Validation failures are
+    /// retried exactly as in [`materialize`](Self::materialize): the invalid response
+    /// and the error are appended to a copy of the conversation, leaving `messages`
+    /// untouched. Consecutive turns from the same role are merged for providers that
+    /// require alternating roles.
+    ///
+    /// The default implementation only supports a single user message; all built-in
+    /// clients support full conversations.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rstructor::{ChatMessage, LLMClient, OpenAIClient, Instructor};
+    /// # use serde::{Serialize, Deserialize};
+    /// #[derive(Instructor, Serialize, Deserialize)]
+    /// struct Order {
+    ///     item: String,
+    ///     quantity: u32,
+    /// }
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OpenAIClient::from_env()?;
+    /// let mut conversation = vec![ChatMessage::user("I'd like two lattes")];
+    /// let order: Order = client.materialize_with_history(&conversation).await?;
+    ///
+    /// conversation.push(ChatMessage::assistant(serde_json::to_string(&order)?));
+    /// conversation.push(ChatMessage::user("Actually, make that three"));
+    /// let order: Order = client.materialize_with_history(&conversation).await?;
+    /// assert_eq!(order.quantity, 3);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn materialize_with_history<T>(&self, messages: &[ChatMessage]) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        match messages {
+            [msg] if msg.role == ChatRole::User && msg.media.is_empty() => {
+                self.materialize(&msg.content).await
+            }
+            _ => Err(RStructorError::api_error(
+                "LLMClient",
+                ApiErrorKind::BadRequest {
+                    details: "Conversation history is not supported by this client".to_string(),
+                },
+            )),
+        }
+    }
+
+    /// Materialize a structured object from structured input.
+    ///
+    /// `input` is serialized to pretty-printed JSON and sent as the user prompt, which
+    /// keeps structured-in/structured-out pipelines free of `serde_json::to_string`
+    /// boilerplate. Use [`materialize_from_with_instruction`](Self::materialize_from_with_instruction)
+    /// to say what should be done with the input.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rstructor::{LLMClient, OpenAIClient, Instructor};
+    /// # use serde::{Serialize, Deserialize};
+    /// #[derive(Serialize)]
+    /// struct Ticket {
+    ///     subject: String,
+    ///     body: String,
+    /// }
+    ///
+    /// #[derive(Instructor, Serialize, Deserialize)]
+    /// struct Triage {
+    ///     priority: u8,
+    ///     team: String,
+    /// }
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OpenAIClient::from_env()?;
+    /// let ticket = Ticket {
+    ///     subject: "Login broken".into(),
+    ///     body: "Nobody can sign in since the deploy".into(),
+    /// };
+    /// let triage: Triage = client
+    ///     .materialize_from_with_instruction("Triage this support ticket", &ticket)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn materialize_from<T, P>(&self, input: &P) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+        P: Serialize + Sync + ?Sized,
+    {
+        let prompt = serialized_prompt(None, input)?;
+        self.materialize(&prompt).await
+    }
+
+    /// Like [`materialize_from`](Self::materialize_from), with `instruction` placed
+    /// before the serialized input.
+    async fn materialize_from_with_instruction<T, P>(
+        &self,
+        instruction: &str,
+        input: &P,
+    ) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+        P: Serialize + Sync + ?Sized,
+    {
+        let prompt = serialized_prompt(Some(instruction), input)?;
+        self.materialize(&prompt).await
+    }
+
+    /// Build the JSON request body [`materialize`](Self::materialize) would send for
+    /// `prompt`, without sending it.
+    ///
+    /// The body contains the schema after the provider-specific transforms (strict mode,
+    /// stripped keywords, root wrapping), which makes this the quickest way to find out
+    /// why a provider rejects a schema.
+    ///
+    /// The default implementation returns an error; all built-in clients support it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rstructor::{LLMClient, OpenAIClient, Instructor};
+    /// # use serde::{Serialize, Deserialize};
+    /// #[derive(Instructor, Serialize, Deserialize)]
+    /// struct Movie {
+    ///     title: String,
+    /// }
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OpenAIClient::new("api-key")?;
+    /// let body = client.materialize_request_body::<Movie>("Name a movie")?;
+    /// let schema = &body["response_format"]["json_schema"]["schema"];
+    /// assert_eq!(schema["additionalProperties"], false);
+    /// # Ok(())
+    /// # }
+    /// # example().unwrap();
+    /// ```
+    fn materialize_request_body<T>(&self, _prompt: &str) -> Result<serde_json::Value>
+    where
+        T: Instructor,
+    {
+        Err(RStructorError::api_error(
+            "LLMClient",
+            ApiErrorKind::BadRequest {
+                details: "Request previews are not supported by this client".to_string(),
+            },
+        ))
+    }
+
+    /// Estimate how many input tokens [`materialize`](Self::materialize) would send for
+    /// `prompt`, without sending anything.
+    ///
+    /// The estimate covers the whole request from
+    /// [`materialize_request_body`](Self::materialize_request_body): the prompt, system
+    /// prompts and the schema wherever the provider embeds it (response format, tool
+    /// definition or system message). JSON punctuation is counted too, so it errs on the
+    /// high side. With the `tokenizer` feature, OpenAI models are counted with their BPE
+    /// encoding; other models, and builds without the feature, use about four bytes per
+    /// token. Clients without request previews estimate the prompt alone.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rstructor::{LLMClient, OpenAIClient, Instructor};
+    /// # use serde::{Serialize, Deserialize};
+    /// #[derive(Instructor, Serialize, Deserialize)]
+    /// struct Movie {
+    ///     title: String,
+    /// }
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OpenAIClient::new("api-key")?.model("gpt-4o");
+    /// let document = "A long transcript...";
+    /// if client.estimate_prompt_tokens::<Movie>(document) > 100_000 {
+    ///     println!("Split the document before extracting");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// # example().unwrap();
+    /// ```
+    fn estimate_prompt_tokens<T>(&self, prompt: &str) -> usize
+    where
+        T: Instructor,
+    {
+        match self.materialize_request_body::<T>(prompt) {
+            // Gemini names the model in the URL, and has no BPE encoding to use anyway
+            Ok(body) => count_tokens(
+                body["model"].as_str().unwrap_or_default(),
+                &body.to_string(),
+            ),
+            Err(_) => count_tokens("", prompt),
+        }
+    }
+
     /// Materialize a structured object with metadata (token usage).
     ///
     /// Like [`materialize`](Self::materialize), but returns a [`MaterializeResult<T>`]
@@ -181,6 +639,252 @@ pub trait LLMClient {
     where
         T: Instructor + DeserializeOwned + Send + 'static;
 
+    /// Request a structured `T`, streaming the raw text of the response as it's written.
+    ///
+    /// The request uses the same schema as [`materialize`](Self::materialize), but the
+    /// stream yields the provider's text deltas unparsed and unvalidated, so a UI can
+    /// show progress or watch fields with
+    /// [`JsonPathWatcher`](crate::streaming::JsonPathWatcher). Errors in the HTTP status
+    /// are classified before the stream is returned; errors while reading surface as
+    /// items. There are no validation retries: pass the stream to
+    /// [`collect_stream`](crate::streaming::collect_stream) to parse and validate the
+    /// whole response once it ends.
+    ///
+    /// The default implementation returns an error; [`OpenAIClient`](crate::OpenAIClient)
+    /// and [`GeminiClient`](crate::GeminiClient) support streaming.
+    ///
+    /// A configured budget is checked before the request and charged with the usage
+    /// reported at the end of the stream, so a stream dropped early is not counted.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rstructor::{LLMClient, OpenAIClient, Instructor};
+    /// # use serde::{Serialize, Deserialize};
+    /// # #[derive(Instructor, Serialize, Deserialize)]
+    /// # struct Movie { title: String }
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// use futures::StreamExt;
+    ///
+    /// let client = OpenAIClient::from_env()?;
+    /// let mut stream = client.materialize_stream::<Movie>("Describe Inception").await?;
+    /// while let Some(delta) = stream.next().await {
+    ///     print!("{}", delta?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn materialize_stream<T>(
+        &self,
+        _prompt: &str,
+    ) -> Result<BoxStream<'static, Result<String>>>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        Err(RStructorError::api_error(
+            "LLMClient",
+            ApiErrorKind::BadRequest {
+                details: "Streaming is not supported by this client".to_string(),
+            },
+        ))
+    }
+
+    /// Materialize many prompts concurrently, returning the results in input order.
+    ///
+    /// At most `concurrency` requests are in flight at once (a value of `0` is treated
+    /// as `1`). Each prompt goes through the normal retry logic, and a failure in one
+    /// prompt does not stop the others: the returned `Vec` has one entry per prompt,
+    /// holding either its value or its error. Use
+    /// [`materialize_many_stream`](Self::materialize_many_stream) to handle results as
+    /// they arrive or to track usage while the batch runs.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rstructor::{LLMClient, OpenAIClient, Instructor};
+    /// # use serde::{Serialize, Deserialize};
+    /// # #[derive(Instructor, Serialize, Deserialize)]
+    /// # struct Movie { title: String }
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OpenAIClient::from_env()?;
+    /// let prompts = ["Describe Inception", "Describe Alien", "Describe Heat"];
+    /// let results = client.materialize_many::<Movie>(&prompts, 2).await;
+    ///
+    /// for (prompt, result) in prompts.iter().zip(results) {
+    ///     match result {
+    ///         Ok(movie) => println!("{prompt}: {}", movie.title),
+    ///         Err(err) => eprintln!("{prompt} failed: {err}"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn materialize_many<T>(&self, prompts: &[&str], concurrency: usize) -> Vec<Result<T>>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+        Self: Sync,
+    {
+        let mut items: Vec<BatchItem<T>> = self
+            .materialize_many_stream(prompts, concurrency)
+            .collect()
+            .await;
+        items.sort_by_key(|item| item.index);
+        items.into_iter().map(|item| item.result).collect()
+    }
+
+    /// Materialize many prompts concurrently, yielding each item as it completes.
+    ///
+    /// At most `concurrency` requests are in flight at once (a value of `0` is treated
+    /// as `1`). Each prompt goes through the normal retry logic, and a failure in one
+    /// prompt does not stop the others. Every [`BatchItem`] carries its own token usage
+    /// plus the running totals for the batch (tokens and estimated cost), so long jobs
+    /// can report progress and stop early simply by dropping the stream.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rstructor::{LLMClient, OpenAIClient, Instructor};
+    /// # use serde::{Serialize, Deserialize};
+    /// # #[derive(Instructor, Serialize, Deserialize)]
+    /// # struct Movie { title: String }
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// use futures::StreamExt;
+    ///
+    /// let client = OpenAIClient::from_env()?;
+    /// let prompts = ["Describe Inception", "Describe Alien", "Describe Heat"];
+    /// let mut stream = client.materialize_many_stream::<Movie>(&prompts, 2);
+    ///
+    /// while let Some(item) = stream.next().await {
+    ///     println!(
+    ///         "#{} done ({} tokens so far): {:?}",
+    ///         item.index,
+    ///         item.totals.total_tokens(),
+    ///         item.result.map(|m| m.title)
+    ///     );
+    ///     if item.totals.cost_usd.unwrap_or(0.0) > 1.0 {
+    ///         break; // abort the rest of the batch
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn materialize_many_stream<'a, T>(
+        &'a self,
+        prompts: &'a [&'a str],
+        concurrency: usize,
+    ) -> BoxStream<'a, BatchItem<T>>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+        Self: Sync,
+    {
+        let tracker = UsageTracker::new();
+        futures::stream::iter(prompts.iter().enumerate())
+            .map(move |(index, prompt)| {
+                let tracker = tracker.clone();
+                async move {
+                    match self.materialize_with_metadata::<T>(prompt).await {
+                        Ok(result) => {
+                            if let Some(usage) = &result.usage {
+                                tracker.record(usage);
+                            }
+                            BatchItem {
+                                index,
+                                result: Ok(result.data),
+                                usage: result.usage,
+                                totals: tracker.totals(),
+                            }
+                        }
+                        Err(err) => BatchItem {
+                            index,
+                            result: Err(err),
+                            usage: None,
+                            totals: tracker.totals(),
+                        },
+                    }
+                }
+            })
+            .buffer_unordered(concurrency.max(1))
+            .boxed()
+    }
+
+    /// Extract a list of `T` without defining a wrapper type.
+    ///
+    /// The request asks for an object with a single `items` array of `T` (providers
+    /// expect an object at the root) and returns the unwrapped array. Each item is
+    /// validated and normalized; a validation failure names the offending index and is
+    /// retried like any other.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rstructor::{Instructor, LLMClient, OpenAIClient};
+    /// # use serde::{Serialize, Deserialize};
+    /// #[derive(Instructor, Serialize, Deserialize)]
+    /// struct Person {
+    ///     name: String,
+    ///     role: String,
+    /// }
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OpenAIClient::from_env()?;
+    /// let people: Vec<Person> = client
+    ///     .materialize_many_items("List everyone mentioned: Ada (engineer), Bo (designer)")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn materialize_many_items<T>(&self, prompt: &str) -> Result<Vec<T>>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let list: ItemList<T> = self.materialize(prompt).await?;
+        Ok(list.items)
+    }
+
+    /// Extract `T` from a plain text completion, returning it with the full text.
+    ///
+    /// Instead of the provider's structured output mode, the schema is embedded in
+    /// the prompt and the model may explain itself before giving the JSON. The first
+    /// top-level JSON value in the response that fits `T` is parsed, validated and
+    /// normalized, and returned alongside the raw text so caveats in the prose can be
+    /// shown to users. There are no validation retries in this mode.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rstructor::{Instructor, LLMClient, OpenAIClient};
+    /// # use serde::{Serialize, Deserialize};
+    /// #[derive(Instructor, Serialize, Deserialize)]
+    /// struct Estimate {
+    ///     population: u64,
+    /// }
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OpenAIClient::from_env()?;
+    /// let (estimate, text): (Estimate, String) = client
+    ///     .materialize_with_text("Estimate the population of Lisbon")
+    ///     .await?;
+    /// println!("{} (model said: {})", estimate.population, text);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn materialize_with_text<T>(&self, prompt: &str) -> Result<(T, String)>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let (prompt, wrapped) = schema_embedded_prompt(prompt, &T::schema());
+        let text = self.generate(&prompt).await?;
+        let mut value: T = find_embedded_json(&text, wrapped).ok_or_else(|| {
+            RStructorError::ValidationError(format!(
+                "Response contains no JSON matching the schema: {}",
+                text
+            ))
+        })?;
+        value.validate()?;
+        value.normalize()?;
+        Ok((value, text))
+    }
+
     /// Raw completion without structure (returns plain text).
     ///
     /// This method provides a simpler interface for getting raw text completions
@@ -223,6 +927,85 @@ pub trait LLMClient {
     /// ```
     async fn generate_with_metadata(&self, prompt: &str) -> Result<GenerateResult>;
 
+    /// Raw completion with per-call parameter overrides.
+    ///
+    /// Like [`generate`](Self::generate), but any field set in `options` replaces the
+    /// client's configured value for this call only.
+    ///
+    /// The default implementation accepts only empty options; all built-in clients
+    /// support every override.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rstructor::{LLMClient, OpenAIClient, RequestOptions};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OpenAIClient::from_env()?;
+    /// let options = RequestOptions::new().with_stop(["\n"]);
+    /// let line = client.generate_opts("Write one line of a poem", &options).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn generate_opts(&self, prompt: &str, options: &RequestOptions) -> Result<String> {
+        if options.is_empty() {
+            return self.generate(prompt).await;
+        }
+        Err(unsupported_options_error())
+    }
+
+    /// Raw completion for structured input, serialized to pretty-printed JSON and sent
+    /// as the prompt (see [`materialize_from`](Self::materialize_from)).
+    async fn generate_from<P>(&self, input: &P) -> Result<String>
+    where
+        P: Serialize + Sync + ?Sized,
+    {
+        let prompt = serialized_prompt(None, input)?;
+        self.generate(&prompt).await
+    }
+
+    /// Raw completion for a multi-turn conversation (returns plain text with metadata).
+    ///
+    /// Sends the full message history (system, user and assistant turns) without a
+    /// schema, so the model's reply can be appended to the history for the next turn.
+    ///
+    /// The default implementation only supports a single user message, which it
+    /// forwards to [`generate_with_metadata`](Self::generate_with_metadata); all
+    /// built-in clients support full conversations.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rstructor::{ChatMessage, LLMClient, OpenAIClient};
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OpenAIClient::from_env()?;
+    /// let mut messages = vec![
+    ///     ChatMessage::system("You are a concise travel guide."),
+    ///     ChatMessage::user("Name one museum in Paris."),
+    /// ];
+    /// let reply = client.generate_with_messages(&messages).await?;
+    ///
+    /// messages.push(ChatMessage::assistant(reply.text));
+    /// messages.push(ChatMessage::user("When was it founded?"));
+    /// let follow_up = client.generate_with_messages(&messages).await?;
+    /// println!("{}", follow_up.text);
+    /// # Ok(())
+    /// # }
+    /// ```
+    async fn generate_with_messages(&self, messages: &[ChatMessage]) -> Result<GenerateResult> {
+        match messages {
+            [message] if message.role == ChatRole::User && message.media.is_empty() => {
+                self.generate_with_metadata(&message.content).await
+            }
+            _ => Err(RStructorError::api_error(
+                "LLMClient",
+                ApiErrorKind::BadRequest {
+                    details: "Multi-turn conversations are not supported by this client"
+                        .to_string(),
+                },
+            )),
+        }
+    }
+
     /// Create a new client by reading the API key from an environment variable.
     ///
     /// This is a required associated function that all `LLMClient` implementations must provide.
@@ -231,6 +1014,9 @@ pub trait LLMClient {
     /// - Anthropic: `ANTHROPIC_API_KEY`
     /// - Grok: `XAI_API_KEY`
     /// - Gemini: `GEMINI_API_KEY`
+    /// - Mistral: `MISTRAL_API_KEY`
+    /// - Ollama: no key; the server address comes from `OLLAMA_HOST`, which is optional
+    ///   and defaults to `http://localhost:11434`
     ///
     /// # Errors
     ///
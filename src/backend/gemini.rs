@@ -1,4 +1,6 @@
 use async_trait::async_trait;
+use futures::stream::{BoxStream, StreamExt};
+use reqwest::header::HeaderMap;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -7,12 +9,17 @@ use std::time::Duration;
 use tracing::{debug, error, info, instrument, trace, warn};
 
 use crate::backend::{
-    ChatMessage, GenerateResult, LLMClient, MaterializeInternalOutput, MaterializeResult,
-    ModelInfo, ThinkingLevel, TokenUsage, ValidationFailureContext, check_response_status,
-    generate_with_retry_with_history, handle_http_error, parse_validate_and_create_output,
+    Budget, BudgetReservation, ChatMessage, ChatRole, GenerateResult, LLMClient,
+    MaterializeInternalOutput, MaterializeResult, ModelInfo, RequestOptions, RetryPolicy,
+    ThinkingLevel, TokenUsage, ValidationFailureContext, check_response_status, custom_headers,
+    dry_run_output, effective_top_p, estimated_usage, few_shot_messages, fit_context_window,
+    generate_with_retry_from_messages, generate_with_retry_with_history, handle_http_error,
+    log_schema_once, parse_validate_and_create_output, read_json_response, reserved_tokens,
+    resolve_thinking_level, sse_data_stream, with_system_prompts,
 };
 use crate::error::{ApiErrorKind, RStructorError, Result};
 use crate::model::Instructor;
+use crate::schema::Schema;
 
 /// Gemini models available for completion
 ///
@@ -109,6 +116,31 @@ impl Model {
             _ => Model::Custom(name),
         }
     }
+
+    /// Parse a model name, rejecting names that don't match a known variant.
+    ///
+    /// Unlike [`from_string`](Self::from_string), an unknown name returns an
+    /// `InvalidModel` error instead of becoming `Custom`, so typos in configuration
+    /// are caught before the first request.
+    ///
+    /// ```rust
+    /// use rstructor::GeminiModel;
+    ///
+    /// assert_eq!(GeminiModel::try_known("gemini-2.5-flash").unwrap(), GeminiModel::Gemini25Flash);
+    /// assert!(GeminiModel::try_known("gemini-2.5-flsh").is_err());
+    /// ```
+    pub fn try_known(name: &str) -> Result<Self> {
+        match Self::from_string(name) {
+            Model::Custom(_) => Err(RStructorError::api_error(
+                "Gemini",
+                ApiErrorKind::InvalidModel {
+                    model: name.to_string(),
+                    suggestion: None,
+                },
+            )),
+            model => Ok(model),
+        }
+    }
 }
 
 impl FromStr for Model {
@@ -139,13 +171,37 @@ pub struct GeminiConfig {
     pub temperature: f32,
     pub max_tokens: Option<u32>,
     pub timeout: Option<Duration>,
+    /// Upper bound on the total time of a `materialize` call, including retries
+    pub deadline: Option<Duration>,
     pub max_retries: Option<usize>,
+    /// Waits between retry attempts
+    pub retry_policy: RetryPolicy,
+    /// Extra headers sent with every request
+    pub headers: HeaderMap,
+    /// Sequences that end generation when produced (empty = none)
+    pub stop_sequences: Vec<String>,
+    /// Nucleus sampling probability mass (None = provider default)
+    pub top_p: Option<f32>,
     /// Custom base URL for Gemini-compatible APIs
     /// Defaults to "https://generativelanguage.googleapis.com/v1beta" if not set
     pub base_url: Option<String>,
     /// Thinking level for Gemini 3 models
     /// Controls the depth of reasoning applied to prompts
     pub thinking_level: Option<ThinkingLevel>,
+    /// Cumulative spending limit shared across clones of this client
+    pub budget: Option<Budget>,
+    /// Strip stray control characters from responses before parsing
+    pub lenient_parsing: bool,
+    /// Drop the oldest messages when a conversation exceeds the model's context window
+    pub auto_truncate: bool,
+    /// Log each type's prepared schema once, at debug level
+    pub log_schemas: bool,
+    /// System instructions sent ahead of every request, in the order they were added
+    pub system_prompts: Vec<String>,
+    /// Return schema-shaped placeholder data instead of calling the API
+    pub dry_run: bool,
+    /// Largest response body to read, in bytes (None = no limit)
+    pub max_response_bytes: Option<usize>,
 }
 
 /// Gemini client for generating completions
@@ -165,8 +221,13 @@ struct Content {
 #[derive(Debug, Serialize)]
 #[serde(untagged)]
 enum Part {
-    Text { text: String },
-    FileData { #[serde(rename = "fileData")] file_data: FileData },
+    Text {
+        text: String,
+    },
+    FileData {
+        #[serde(rename = "fileData")]
+        file_data: FileData,
+    },
 }
 
 #[derive(Debug, Serialize)]
@@ -178,15 +239,73 @@ struct FileData {
 
 #[derive(Debug, Serialize)]
 struct GenerateContentRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_instruction: Option<Content>,
     contents: Vec<Content>,
     generation_config: GenerationConfig,
 }
 
+/// Collect system messages into Gemini's dedicated `systemInstruction` content.
+///
+/// Gemini has no `system` role in `contents`, so system messages are joined and sent
+/// separately rather than as a user turn.
+fn system_instruction(messages: &[ChatMessage]) -> Option<Content> {
+    let texts: Vec<&str> = messages
+        .iter()
+        .filter(|msg| msg.role == ChatRole::System)
+        .map(|msg| msg.content.as_str())
+        .collect();
+    (!texts.is_empty()).then(|| Content {
+        role: None,
+        parts: vec![Part::Text {
+            text: texts.join("\n\n"),
+        }],
+    })
+}
+
+/// Convert the user and model turns of a conversation to Gemini `contents`
+fn contents(messages: &[ChatMessage]) -> Vec<Content> {
+    messages
+        .iter()
+        .filter(|msg| msg.role != ChatRole::System)
+        .map(|msg| {
+            // Gemini uses "user" and "model" (not "assistant")
+            let role = if msg.role.as_str() == "assistant" {
+                "model"
+            } else {
+                msg.role.as_str()
+            };
+            let mut parts = Vec::new();
+            if !msg.content.is_empty() {
+                parts.push(Part::Text {
+                    text: msg.content.clone(),
+                });
+            }
+            for media in &msg.media {
+                parts.push(Part::FileData {
+                    file_data: FileData {
+                        mime_type: media.mime_type.clone(),
+                        file_uri: media.uri.clone(),
+                    },
+                });
+            }
+            Content {
+                role: Some(role.to_string()),
+                parts,
+            }
+        })
+        .collect()
+}
+
 #[derive(Debug, Serialize)]
 struct GenerationConfig {
     temperature: f32,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_output_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "topP")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Vec::is_empty", rename = "stopSequences")]
+    stop_sequences: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     response_mime_type: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -207,32 +326,143 @@ struct UsageMetadata {
     prompt_token_count: u64,
     #[serde(rename = "candidatesTokenCount", default)]
     candidates_token_count: u64,
+    #[serde(rename = "totalTokenCount", default)]
+    total_token_count: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
 struct GenerateContentResponse {
+    #[serde(default)]
     candidates: Vec<Candidate>,
+    #[serde(rename = "promptFeedback", default)]
+    prompt_feedback: Option<PromptFeedback>,
     #[serde(rename = "usageMetadata", default)]
     usage_metadata: Option<UsageMetadata>,
     #[serde(rename = "modelVersion", default)]
     model_version: Option<String>,
+    #[serde(rename = "responseId", default)]
+    response_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PromptFeedback {
+    #[serde(rename = "blockReason", default)]
+    block_reason: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct Candidate {
-    content: CandidateContent,
     #[serde(default)]
+    content: CandidateContent,
+    #[serde(rename = "finishReason", default)]
     finish_reason: String,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 struct CandidateContent {
+    #[serde(default)]
     parts: Vec<CandidatePart>,
 }
 
+/// Finish reasons Gemini reports when it withholds a candidate for safety or policy reasons
+const BLOCKED_FINISH_REASONS: &[&str] = &[
+    "SAFETY",
+    "RECITATION",
+    "BLOCKLIST",
+    "PROHIBITED_CONTENT",
+    "SPII",
+    "IMAGE_SAFETY",
+];
+
+impl GenerateContentResponse {
+    /// Why the response was withheld, if Gemini blocked the prompt or the candidate
+    /// came back without text because of a safety finish reason.
+    fn blocked_reason(&self) -> Option<String> {
+        if let Some(reason) = self
+            .prompt_feedback
+            .as_ref()
+            .and_then(|f| f.block_reason.as_ref())
+        {
+            return Some(format!("prompt blocked ({})", reason));
+        }
+        self.candidates
+            .first()
+            .filter(|c| {
+                BLOCKED_FINISH_REASONS.contains(&c.finish_reason.as_str())
+                    && c.content.parts.iter().all(|p| p.text.is_none())
+            })
+            .map(|c| format!("finish reason {}", c.finish_reason))
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct CandidatePart {
     text: Option<String>,
+    /// Set on parts that carry the model's reasoning rather than the answer
+    #[serde(default)]
+    thought: bool,
+}
+
+/// Extract the answer text from one event of a streamed response, `None` for events
+/// without any (such as those carrying only thoughts or the final usage metadata).
+///
+/// The final event's usage, if any, settles the budget `reservation`.
+fn stream_delta(
+    event: &str,
+    reservation: &mut Option<BudgetReservation>,
+    model: &str,
+) -> Option<Result<String>> {
+    let chunk: GenerateContentResponse = match serde_json::from_str(event) {
+        Ok(chunk) => chunk,
+        Err(e) => return Some(Err(e.into())),
+    };
+    // Usage is cumulative across chunks, so only the last one is charged
+    let last_chunk = chunk.blocked_reason().is_some()
+        || chunk
+            .candidates
+            .first()
+            .is_some_and(|c| !c.finish_reason.is_empty());
+    if last_chunk
+        && let Some(u) = &chunk.usage_metadata
+        && let Some(reservation) = reservation.take()
+    {
+        let model = chunk.model_version.as_deref().unwrap_or(model);
+        let usage = TokenUsage::new(model, u.prompt_token_count, u.candidates_token_count)
+            .with_total_tokens(u.total_token_count);
+        reservation.record(Some(&usage));
+    }
+    if let Some(details) = chunk.blocked_reason() {
+        error!(details = %details, "Gemini response blocked by safety filters");
+        return Some(Err(RStructorError::api_error(
+            "Gemini",
+            ApiErrorKind::ContentFiltered { details },
+        )));
+    }
+    chunk.candidates.first()?.answer_text().map(Ok)
+}
+
+impl Candidate {
+    /// Concatenate the text of every part with the given `thought` flag
+    fn joined_text(&self, thought: bool) -> Option<String> {
+        let texts: Vec<&str> = self
+            .content
+            .parts
+            .iter()
+            .filter(|p| p.thought == thought)
+            .filter_map(|p| p.text.as_deref())
+            .collect();
+        (!texts.is_empty()).then(|| texts.concat())
+    }
+
+    /// The answer text, excluding any thought parts
+    fn answer_text(&self) -> Option<String> {
+        self.joined_text(false)
+    }
+
+    /// The model's reasoning, if thought parts were returned
+    fn thinking_text(&self) -> Option<String> {
+        self.joined_text(true)
+    }
 }
 
 impl GeminiClient {
@@ -266,10 +496,22 @@ impl GeminiClient {
             model: Model::Gemini3FlashPreview, // Default to Gemini 3 Flash Preview (latest)
             temperature: 0.0,
             max_tokens: None,
-            timeout: None,        // Default: no timeout (uses reqwest's default)
-            max_retries: Some(3), // Default: 3 retries with error feedback
-            base_url: None,       // Default: use official Gemini API
+            timeout: None,              // Default: no timeout (uses reqwest's default)
+            deadline: None,             // Default: no overall deadline
+            max_retries: Some(3),       // Default: 3 retries with error feedback
+            base_url: None,             // Default: use official Gemini API
+            budget: None,               // Default: no spending limit
+            lenient_parsing: false,     // Default: strict JSON parsing
+            auto_truncate: false,       // Default: send conversations as-is
+            log_schemas: false,         // Default: don't log schemas
+            system_prompts: Vec::new(), // Default: no system instructions
+            dry_run: false,             // Default: call the provider
+            max_response_bytes: None,   // Default: no limit
             thinking_level: Some(ThinkingLevel::Low), // Default to Low thinking for Gemini 3
+            retry_policy: RetryPolicy::default(), // Default: exponential backoff from 500ms
+            headers: HeaderMap::new(),  // Default: no extra headers
+            stop_sequences: Vec::new(), // Default: no stop sequences
+            top_p: None,                // Default: provider's default
         };
 
         let client = reqwest::Client::new();
@@ -308,10 +550,22 @@ impl GeminiClient {
             model: Model::Gemini3FlashPreview, // Default to Gemini 3 Flash Preview (latest)
             temperature: 0.0,
             max_tokens: None,
-            timeout: None,        // Default: no timeout (uses reqwest's default)
-            max_retries: Some(3), // Default: 3 retries with error feedback
-            base_url: None,       // Default: use official Gemini API
+            timeout: None,              // Default: no timeout (uses reqwest's default)
+            deadline: None,             // Default: no overall deadline
+            max_retries: Some(3),       // Default: 3 retries with error feedback
+            base_url: None,             // Default: use official Gemini API
+            budget: None,               // Default: no spending limit
+            lenient_parsing: false,     // Default: strict JSON parsing
+            auto_truncate: false,       // Default: send conversations as-is
+            log_schemas: false,         // Default: don't log schemas
+            system_prompts: Vec::new(), // Default: no system instructions
+            dry_run: false,             // Default: call the provider
+            max_response_bytes: None,   // Default: no limit
             thinking_level: Some(ThinkingLevel::Low), // Default to Low thinking for Gemini 3
+            retry_policy: RetryPolicy::default(), // Default: exponential backoff from 500ms
+            headers: HeaderMap::new(),  // Default: no extra headers
+            stop_sequences: Vec::new(), // Default: no stop sequences
+            top_p: None,                // Default: provider's default
         };
 
         let client = reqwest::Client::new();
@@ -328,6 +582,51 @@ impl GeminiClient {
 }
 
 impl GeminiClient {
+    /// Build the structured-output request for a conversation.
+    fn structured_request(
+        &self,
+        messages: &[ChatMessage],
+        schema: &Schema,
+        options: &RequestOptions,
+    ) -> GenerateContentRequest {
+        // Build API contents from conversation history
+        // With native response_schema, we don't need to include schema instructions in the prompt
+        let system_instruction = system_instruction(messages);
+        let contents = contents(messages);
+
+        // Build thinking config only for Gemini 3 models, clamped to the levels the model accepts
+        let thinking_config = resolve_thinking_level(
+            self.config.model.as_str(),
+            options.thinking_level.or(self.config.thinking_level),
+        )
+        .and_then(|level| {
+            level.gemini_level().map(|l| ThinkingConfig {
+                thinking_level: l.to_string(),
+            })
+        });
+
+        // Prepare schema for Gemini by stripping unsupported keywords (examples, additionalProperties, etc.)
+        let gemini_schema = crate::backend::prepare_gemini_schema(schema);
+        let generation_config = GenerationConfig {
+            temperature: options.temperature.unwrap_or(self.config.temperature),
+            max_output_tokens: options.max_tokens.or(self.config.max_tokens),
+            top_p: effective_top_p(options, self.config.top_p),
+            stop_sequences: options
+                .stop
+                .clone()
+                .unwrap_or_else(|| self.config.stop_sequences.clone()),
+            response_mime_type: Some("application/json".to_string()),
+            response_schema: Some(gemini_schema),
+            thinking_config,
+        };
+
+        GenerateContentRequest {
+            system_instruction,
+            contents,
+            generation_config,
+        }
+    }
+
     /// Internal implementation of materialize (without retry logic)
     /// Accepts conversation history for multi-turn interactions.
     /// Returns the data, raw response, and optional usage info.
@@ -340,6 +639,8 @@ impl GeminiClient {
     async fn materialize_internal<T>(
         &self,
         messages: &[ChatMessage],
+        schema: &Schema,
+        options: &RequestOptions,
     ) -> std::result::Result<
         MaterializeInternalOutput<T>,
         (RStructorError, Option<ValidationFailureContext>),
@@ -347,79 +648,56 @@ impl GeminiClient {
     where
         T: Instructor + DeserializeOwned + Send + 'static,
     {
+        if self.config.dry_run {
+            return dry_run_output::<T>(schema);
+        }
+        let messages = with_system_prompts(&self.config.system_prompts, messages);
+        let fitted = fit_context_window(
+            &messages,
+            self.config.model.as_str(),
+            reserved_tokens(
+                self.config.model.as_str(),
+                Some(schema),
+                options.max_tokens.or(self.config.max_tokens),
+            ),
+            self.config.auto_truncate,
+        );
+        let messages: &[ChatMessage] = &fitted;
+        let reservation = self
+            .config
+            .budget
+            .as_ref()
+            .map(|budget| {
+                budget.check(&estimated_usage(
+                    self.config.model.as_str(),
+                    messages,
+                    Some(schema),
+                    options.max_tokens.or(self.config.max_tokens),
+                ))
+            })
+            .transpose()
+            .map_err(|e| (e, None))?;
+
         info!("Generating structured response with Gemini");
 
-        let schema = T::schema();
         let schema_name = T::schema_name().unwrap_or_else(|| "output".to_string());
         trace!(schema_name = schema_name, "Retrieved JSON schema for type");
 
-        // Build API contents from conversation history
-        // With native response_schema, we don't need to include schema instructions in the prompt
-        let contents: Vec<Content> = messages
-            .iter()
-            .map(|msg| {
-                // Gemini uses "user" and "model" (not "assistant")
-                let role = if msg.role.as_str() == "assistant" {
-                    "model"
-                } else {
-                    msg.role.as_str()
-                };
-                let mut parts = Vec::new();
-                if !msg.content.is_empty() {
-                    parts.push(Part::Text {
-                        text: msg.content.clone(),
-                    });
-                }
-                for media in &msg.media {
-                    parts.push(Part::FileData {
-                        file_data: FileData {
-                            mime_type: media.mime_type.clone(),
-                            file_uri: media.uri.clone(),
-                        },
-                    });
-                }
-                Content {
-                    role: Some(role.to_string()),
-                    parts,
-                }
-            })
-            .collect();
-
-        // Build thinking config only for Gemini 3 models
-        let is_gemini3 = self.config.model.as_str().starts_with("gemini-3");
-        let thinking_config = if is_gemini3 {
-            self.config.thinking_level.and_then(|level| {
-                level.gemini_level().map(|l| ThinkingConfig {
-                    thinking_level: l.to_string(),
-                })
-            })
-        } else {
-            None
-        };
-
         // Extract adjacently tagged enum info before transformation (for response conversion)
         let adjacently_tagged_info =
             crate::backend::utils::extract_adjacently_tagged_info(&schema.to_json());
 
-        // Prepare schema for Gemini by stripping unsupported keywords (examples, additionalProperties, etc.)
-        let gemini_schema = crate::backend::utils::prepare_gemini_schema(&schema);
-        let generation_config = GenerationConfig {
-            temperature: self.config.temperature,
-            max_output_tokens: self.config.max_tokens,
-            response_mime_type: Some("application/json".to_string()),
-            response_schema: Some(gemini_schema),
-            thinking_config,
-        };
-
-        let request = GenerateContentRequest {
-            contents,
-            generation_config,
-        };
+        let request = self.structured_request(messages, schema, options);
+        if self.config.log_schemas
+            && let Some(sent_schema) = &request.generation_config.response_schema
+        {
+            log_schema_once::<T>("Gemini", sent_schema);
+        }
 
-        let base_url = self
-            .config
+        let base_url = options
             .base_url
             .as_deref()
+            .or(self.config.base_url.as_deref())
             .unwrap_or("https://generativelanguage.googleapis.com/v1beta");
         let url = format!(
             "{}/models/{}:generateContent",
@@ -435,6 +713,7 @@ impl GeminiClient {
         let response = self
             .client
             .post(&url)
+            .headers(custom_headers(&self.config.headers))
             .query(&[("key", &self.config.api_key)])
             .header("Content-Type", "application/json")
             .json(&request)
@@ -442,15 +721,26 @@ impl GeminiClient {
             .await
             .map_err(|e| (handle_http_error(e, "Gemini"), None))?;
 
-        let response = check_response_status(response, "Gemini")
+        let response = check_response_status(response, "Gemini", self.config.max_response_bytes)
             .await
             .map_err(|e| (e, None))?;
 
         debug!("Successfully received response from Gemini API");
-        let completion: GenerateContentResponse = response.json().await.map_err(|e| {
-            error!(error = %e, "Failed to parse JSON response from Gemini API");
-            (RStructorError::from(e), None)
-        })?;
+        let completion: GenerateContentResponse =
+            read_json_response(response, "Gemini", self.config.max_response_bytes)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to parse JSON response from Gemini API");
+                    (e, None)
+                })?;
+
+        if let Some(details) = completion.blocked_reason() {
+            error!(details = %details, "Gemini response blocked by safety filters");
+            return Err((
+                RStructorError::api_error("Gemini", ApiErrorKind::ContentFiltered { details }),
+                None,
+            ));
+        }
 
         if completion.candidates.is_empty() {
             error!("Gemini API returned empty candidates array");
@@ -476,35 +766,40 @@ impl GeminiClient {
                 u.prompt_token_count,
                 u.candidates_token_count,
             )
+            .with_total_tokens(u.total_token_count)
         });
+        if let Some(reservation) = reservation {
+            reservation.record(usage.as_ref());
+        }
 
         let candidate = &completion.candidates[0];
         trace!(finish_reason = ?candidate.finish_reason, "Completion finish reason");
 
-        let parts = &candidate.content.parts;
-        debug!(parts = parts.len(), "Processing candidate content parts");
-        for part in parts {
-            if let Some(text) = &part.text {
-                let mut raw_response = text.clone();
-                debug!(content_len = raw_response.len(), "Processing text part");
-                // With native response_schema, the response is guaranteed to be valid JSON
-                trace!(json = %raw_response, "Parsing structured output response");
-
-                // Transform internally tagged enums back to adjacently tagged format if needed
-                if let Some(ref enum_info) = adjacently_tagged_info
-                    && let Ok(mut json_value) =
-                        serde_json::from_str::<serde_json::Value>(&raw_response)
-                {
-                    crate::backend::utils::transform_internally_to_adjacently_tagged(
-                        &mut json_value,
-                        enum_info,
-                    );
-                    raw_response = serde_json::to_string(&json_value).unwrap_or(raw_response);
-                }
-
-                // Parse and validate the response using shared utility
-                return parse_validate_and_create_output(raw_response, usage);
+        debug!(
+            parts = candidate.content.parts.len(),
+            "Processing candidate content parts"
+        );
+        if let Some(mut raw_response) = candidate.answer_text() {
+            debug!(content_len = raw_response.len(), "Processing answer text");
+            // With native response_schema, the response is guaranteed to be valid JSON
+            trace!(json = %raw_response, "Parsing structured output response");
+
+            // Transform internally tagged enums back to adjacently tagged format if needed
+            if let Some(ref enum_info) = adjacently_tagged_info
+                && let Ok(mut json_value) = serde_json::from_str::<serde_json::Value>(&raw_response)
+            {
+                crate::backend::utils::transform_internally_to_adjacently_tagged(
+                    &mut json_value,
+                    enum_info,
+                );
+                raw_response = serde_json::to_string(&json_value).unwrap_or(raw_response);
             }
+
+            // Parse and validate the response using shared utility
+            let mut output =
+                parse_validate_and_create_output(raw_response, usage, self.config.lenient_parsing)?;
+            output.thinking = candidate.thinking_text();
+            return Ok(output.with_request_id(completion.response_id.clone()));
         }
 
         error!("No text content in Gemini response");
@@ -584,6 +879,171 @@ impl GeminiClient {
         self.config.thinking_level = Some(level);
         self
     }
+
+    /// Internal implementation of raw text generation for a conversation, with
+    /// per-call overrides applied over the client configuration.
+    async fn generate_internal(
+        &self,
+        messages: &[ChatMessage],
+        options: &RequestOptions,
+    ) -> Result<GenerateResult> {
+        if self.config.dry_run {
+            return Ok(GenerateResult::new(String::new(), None));
+        }
+        let messages = with_system_prompts(&self.config.system_prompts, messages);
+        let fitted = fit_context_window(
+            &messages,
+            self.config.model.as_str(),
+            reserved_tokens(
+                self.config.model.as_str(),
+                None,
+                options.max_tokens.or(self.config.max_tokens),
+            ),
+            self.config.auto_truncate,
+        );
+        let messages: &[ChatMessage] = &fitted;
+        let reservation = self
+            .config
+            .budget
+            .as_ref()
+            .map(|budget| {
+                budget.check(&estimated_usage(
+                    self.config.model.as_str(),
+                    messages,
+                    None,
+                    options.max_tokens.or(self.config.max_tokens),
+                ))
+            })
+            .transpose()?;
+
+        info!("Generating raw text response with Gemini");
+
+        // Build thinking config only for Gemini 3 models, clamped to the levels the model accepts
+        let thinking_config = resolve_thinking_level(
+            self.config.model.as_str(),
+            options.thinking_level.or(self.config.thinking_level),
+        )
+        .and_then(|level| {
+            level.gemini_level().map(|l| ThinkingConfig {
+                thinking_level: l.to_string(),
+            })
+        });
+
+        // Build the request
+        debug!("Building Gemini API request");
+        let request = GenerateContentRequest {
+            system_instruction: system_instruction(messages),
+            contents: contents(messages),
+            generation_config: GenerationConfig {
+                temperature: options.temperature.unwrap_or(self.config.temperature),
+                max_output_tokens: options.max_tokens.or(self.config.max_tokens),
+                top_p: effective_top_p(options, self.config.top_p),
+                stop_sequences: options
+                    .stop
+                    .clone()
+                    .unwrap_or_else(|| self.config.stop_sequences.clone()),
+                response_mime_type: None,
+                response_schema: None,
+                thinking_config,
+            },
+        };
+
+        // Send the request to Gemini API
+        let base_url = options
+            .base_url
+            .as_deref()
+            .or(self.config.base_url.as_deref())
+            .unwrap_or("https://generativelanguage.googleapis.com/v1beta");
+        let url = format!(
+            "{}/models/{}:generateContent",
+            base_url,
+            self.config.model.as_str()
+        );
+        debug!(
+            url = %url,
+            model = %self.config.model.as_str(),
+            "Sending request to Gemini API"
+        );
+        let response = self
+            .client
+            .post(&url)
+            .headers(custom_headers(&self.config.headers))
+            .query(&[("key", &self.config.api_key)])
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| handle_http_error(e, "Gemini"))?;
+
+        // Parse the response
+        let response =
+            check_response_status(response, "Gemini", self.config.max_response_bytes).await?;
+
+        debug!("Successfully received response from Gemini API");
+        let completion: GenerateContentResponse =
+            read_json_response(response, "Gemini", self.config.max_response_bytes)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to parse JSON response from Gemini API");
+                    e
+                })?;
+
+        if let Some(details) = completion.blocked_reason() {
+            error!(details = %details, "Gemini response blocked by safety filters");
+            return Err(RStructorError::api_error(
+                "Gemini",
+                ApiErrorKind::ContentFiltered { details },
+            ));
+        }
+
+        if completion.candidates.is_empty() {
+            error!("Gemini API returned empty candidates array");
+            return Err(RStructorError::api_error(
+                "Gemini",
+                ApiErrorKind::UnexpectedResponse {
+                    details: "No completion candidates returned".to_string(),
+                },
+            ));
+        }
+
+        // Extract usage info
+        let model_name = completion
+            .model_version
+            .clone()
+            .unwrap_or_else(|| self.config.model.as_str().to_string());
+        let usage = completion.usage_metadata.as_ref().map(|u| {
+            TokenUsage::new(model_name, u.prompt_token_count, u.candidates_token_count)
+                .with_total_tokens(u.total_token_count)
+        });
+        if let Some(reservation) = reservation {
+            reservation.record(usage.as_ref());
+        }
+
+        let candidate = &completion.candidates[0];
+        trace!(finish_reason = %candidate.finish_reason, "Completion finish reason");
+
+        // Extract the answer text, keeping any thought parts separate
+        match candidate.answer_text() {
+            Some(text) => {
+                debug!(
+                    content_len = text.len(),
+                    "Successfully extracted text content from response"
+                );
+                Ok(GenerateResult::new(text, usage)
+                    .with_thinking(candidate.thinking_text())
+                    .with_request_id(completion.response_id.clone()))
+            }
+            None => {
+                error!("No text content in Gemini response");
+                Err(RStructorError::api_error(
+                    "Gemini",
+                    ApiErrorKind::UnexpectedResponse {
+                        details: "No text content in response".to_string(),
+                    },
+                ))
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -605,13 +1065,20 @@ impl LLMClient for GeminiClient {
     where
         T: Instructor + DeserializeOwned + Send + 'static,
     {
+        let schema = T::schema();
         let output = generate_with_retry_with_history(
             |messages: Vec<ChatMessage>| {
                 let this = self;
-                async move { this.materialize_internal::<T>(&messages).await }
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
             },
             prompt,
             self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
         )
         .await?;
         Ok(output.data)
@@ -634,172 +1101,330 @@ impl LLMClient for GeminiClient {
         // For media support, we need to create a ChatMessage with media and pass it directly
         // We can't use generate_with_retry_with_history since it only takes a string prompt
         let initial_message = ChatMessage::user_with_media(prompt, media.to_vec());
-        let output = self.materialize_internal::<T>(&vec![initial_message]).await
+        let output = self
+            .materialize_internal::<T>(&[initial_message], &T::schema(), &RequestOptions::default())
+            .await
             .map_err(|(err, _)| err)?;
         Ok(output.data)
     }
 
     #[instrument(
-        name = "gemini_materialize_with_metadata",
-        skip(self, prompt),
+        name = "gemini_materialize_with_schema",
+        skip(self, prompt, schema),
         fields(
             type_name = std::any::type_name::<T>(),
             model = %self.config.model.as_str(),
             prompt_len = prompt.len()
         )
     )]
-    async fn materialize_with_metadata<T>(&self, prompt: &str) -> Result<MaterializeResult<T>>
+    async fn materialize_with_schema<T>(&self, prompt: &str, schema: Schema) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            prompt,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "gemini_materialize_opts",
+        skip(self, prompt, options),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn materialize_opts<T>(&self, prompt: &str, options: &RequestOptions) -> Result<T>
     where
         T: Instructor + DeserializeOwned + Send + 'static,
     {
+        let schema = T::schema();
         let output = generate_with_retry_with_history(
             |messages: Vec<ChatMessage>| {
                 let this = self;
-                async move { this.materialize_internal::<T>(&messages).await }
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, options)
+                        .await
+                }
             },
             prompt,
             self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
         )
         .await?;
-        Ok(MaterializeResult::new(output.data, output.usage))
+        Ok(output.data)
+    }
+
+    fn materialize_request_body<T>(&self, prompt: &str) -> Result<serde_json::Value>
+    where
+        T: Instructor,
+    {
+        let schema = T::schema();
+        let prompt = [ChatMessage::user(prompt)];
+        let messages = with_system_prompts(&self.config.system_prompts, &prompt);
+        let request = self.structured_request(&messages, &schema, &RequestOptions::default());
+        Ok(serde_json::to_value(&request)?)
     }
 
     #[instrument(
-        name = "gemini_generate",
+        name = "gemini_materialize_with_examples",
+        skip(self, prompt, examples),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len(),
+            examples_len = examples.len()
+        )
+    )]
+    async fn materialize_with_examples<T>(
+        &self,
+        prompt: &str,
+        examples: &[(String, T)],
+    ) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + Sync + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_from_messages(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            few_shot_messages(prompt, examples)?,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "gemini_materialize_with_history",
+        skip(self, messages),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            messages_len = messages.len()
+        )
+    )]
+    async fn materialize_with_history<T>(&self, messages: &[ChatMessage]) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_from_messages(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            messages.to_vec(),
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "gemini_materialize_with_metadata",
         skip(self, prompt),
         fields(
+            type_name = std::any::type_name::<T>(),
             model = %self.config.model.as_str(),
             prompt_len = prompt.len()
         )
     )]
-    async fn generate(&self, prompt: &str) -> Result<String> {
-        let result = self.generate_with_metadata(prompt).await?;
-        Ok(result.text)
+    async fn materialize_with_metadata<T>(&self, prompt: &str) -> Result<MaterializeResult<T>>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            prompt,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(MaterializeResult::new(output.data, output.usage)
+            .with_attempts(output.attempts)
+            .with_thinking(output.thinking)
+            .with_request_id(output.request_id))
     }
 
     #[instrument(
-        name = "gemini_generate_with_metadata",
+        name = "gemini_materialize_stream",
         skip(self, prompt),
         fields(
+            type_name = std::any::type_name::<T>(),
             model = %self.config.model.as_str(),
             prompt_len = prompt.len()
         )
     )]
-    async fn generate_with_metadata(&self, prompt: &str) -> Result<GenerateResult> {
-        info!("Generating raw text response with Gemini");
-
-        // Build thinking config only for Gemini 3 models
-        let is_gemini3 = self.config.model.as_str().starts_with("gemini-3");
-        let thinking_config = if is_gemini3 {
-            self.config.thinking_level.and_then(|level| {
-                level.gemini_level().map(|l| ThinkingConfig {
-                    thinking_level: l.to_string(),
-                })
+    async fn materialize_stream<T>(
+        &self,
+        prompt: &str,
+    ) -> Result<BoxStream<'static, Result<String>>>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        if self.config.dry_run {
+            let output = dry_run_output::<T>(&schema).map_err(|(e, _)| e)?;
+            return Ok(futures::stream::once(async move { Ok(output.raw_response) }).boxed());
+        }
+        let prompt = [ChatMessage::user(prompt)];
+        let messages = with_system_prompts(&self.config.system_prompts, &prompt);
+        let messages = fit_context_window(
+            &messages,
+            self.config.model.as_str(),
+            reserved_tokens(
+                self.config.model.as_str(),
+                Some(&schema),
+                self.config.max_tokens,
+            ),
+            self.config.auto_truncate,
+        );
+        let mut reservation = self
+            .config
+            .budget
+            .as_ref()
+            .map(|budget| {
+                budget.check(&estimated_usage(
+                    self.config.model.as_str(),
+                    &messages,
+                    Some(&schema),
+                    self.config.max_tokens,
+                ))
             })
-        } else {
-            None
-        };
+            .transpose()?;
 
-        // Build the request
-        debug!("Building Gemini API request");
-        let request = GenerateContentRequest {
-            contents: vec![Content {
-                role: Some("user".to_string()),
-                parts: vec![Part::Text {
-                    text: prompt.to_string(),
-                }],
-            }],
-            generation_config: GenerationConfig {
-                temperature: self.config.temperature,
-                max_output_tokens: self.config.max_tokens,
-                response_mime_type: None,
-                response_schema: None,
-                thinking_config,
-            },
-        };
+        info!("Streaming structured response from Gemini");
+        let request = self.structured_request(&messages, &schema, &RequestOptions::default());
 
-        // Send the request to Gemini API
         let base_url = self
             .config
             .base_url
             .as_deref()
             .unwrap_or("https://generativelanguage.googleapis.com/v1beta");
         let url = format!(
-            "{}/models/{}:generateContent",
+            "{}/models/{}:streamGenerateContent",
             base_url,
             self.config.model.as_str()
         );
-        debug!(
-            url = %url,
-            model = %self.config.model.as_str(),
-            "Sending request to Gemini API"
-        );
+        debug!(url = %url, "Sending streaming request to Gemini API");
         let response = self
             .client
             .post(&url)
-            .query(&[("key", &self.config.api_key)])
+            .headers(custom_headers(&self.config.headers))
+            .query(&[("alt", "sse"), ("key", self.config.api_key.as_str())])
             .header("Content-Type", "application/json")
             .json(&request)
             .send()
             .await
             .map_err(|e| handle_http_error(e, "Gemini"))?;
+        let response =
+            check_response_status(response, "Gemini", self.config.max_response_bytes).await?;
+
+        let model = self.config.model.as_str().to_string();
+        Ok(
+            sse_data_stream(response, "Gemini", self.config.max_response_bytes)
+                .filter_map(move |event| {
+                    futures::future::ready(match event {
+                        Ok(event) => stream_delta(&event, &mut reservation, &model),
+                        Err(e) => Some(Err(e)),
+                    })
+                })
+                .boxed(),
+        )
+    }
 
-        // Parse the response
-        let response = check_response_status(response, "Gemini").await?;
-
-        debug!("Successfully received response from Gemini API");
-        let completion: GenerateContentResponse = response.json().await.map_err(|e| {
-            error!(error = %e, "Failed to parse JSON response from Gemini API");
-            e
-        })?;
-
-        if completion.candidates.is_empty() {
-            error!("Gemini API returned empty candidates array");
-            return Err(RStructorError::api_error(
-                "Gemini",
-                ApiErrorKind::UnexpectedResponse {
-                    details: "No completion candidates returned".to_string(),
-                },
-            ));
-        }
+    #[instrument(
+        name = "gemini_generate",
+        skip(self, prompt),
+        fields(
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        let result = self.generate_with_metadata(prompt).await?;
+        Ok(result.text)
+    }
 
-        // Extract usage info
-        let model_name = completion
-            .model_version
-            .clone()
-            .unwrap_or_else(|| self.config.model.as_str().to_string());
-        let usage = completion
-            .usage_metadata
-            .as_ref()
-            .map(|u| TokenUsage::new(model_name, u.prompt_token_count, u.candidates_token_count));
+    #[instrument(
+        name = "gemini_generate_with_metadata",
+        skip(self, prompt),
+        fields(
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn generate_with_metadata(&self, prompt: &str) -> Result<GenerateResult> {
+        self.generate_with_messages(&[ChatMessage::user(prompt)])
+            .await
+    }
 
-        let candidate = &completion.candidates[0];
-        trace!(finish_reason = %candidate.finish_reason, "Completion finish reason");
+    #[instrument(
+        name = "gemini_generate_opts",
+        skip(self, prompt, options),
+        fields(
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn generate_opts(&self, prompt: &str, options: &RequestOptions) -> Result<String> {
+        let result = self
+            .generate_internal(&[ChatMessage::user(prompt)], options)
+            .await?;
+        Ok(result.text)
+    }
 
-        // Extract the text content
-        match candidate
-            .content
-            .parts
-            .first()
-            .and_then(|p| p.text.as_ref())
-        {
-            Some(text) => {
-                debug!(
-                    content_len = text.len(),
-                    "Successfully extracted text content from response"
-                );
-                Ok(GenerateResult::new(text.clone(), usage))
-            }
-            None => {
-                error!("No text content in Gemini response");
-                Err(RStructorError::api_error(
-                    "Gemini",
-                    ApiErrorKind::UnexpectedResponse {
-                        details: "No text content in response".to_string(),
-                    },
-                ))
-            }
-        }
+    #[instrument(
+        name = "gemini_generate_with_messages",
+        skip(self, messages),
+        fields(
+            model = %self.config.model.as_str(),
+            history_len = messages.len()
+        )
+    )]
+    async fn generate_with_messages(&self, messages: &[ChatMessage]) -> Result<GenerateResult> {
+        self.generate_internal(messages, &RequestOptions::default())
+            .await
     }
 
     /// Fetch available models from Gemini's API.
@@ -818,17 +1443,22 @@ impl LLMClient for GeminiClient {
         let response = self
             .client
             .get(&url)
+            .headers(custom_headers(&self.config.headers))
             .header("Content-Type", "application/json")
             .send()
             .await
             .map_err(|e| handle_http_error(e, "Gemini"))?;
 
-        let response = check_response_status(response, "Gemini").await?;
+        let response =
+            check_response_status(response, "Gemini", self.config.max_response_bytes).await?;
 
-        let json: serde_json::Value = response.json().await.map_err(|e| {
-            error!(error = %e, "Failed to parse models response from Gemini");
-            e
-        })?;
+        let json: serde_json::Value =
+            read_json_response(response, "Gemini", self.config.max_response_bytes)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to parse models response from Gemini");
+                    e
+                })?;
 
         let models = json
             .get("models")
@@ -881,6 +1511,82 @@ impl LLMClient for GeminiClient {
 
 #[cfg(test)]
 mod tests {
+    use super::{GenerateContentResponse, system_instruction};
+    use crate::backend::ChatMessage;
+
+    #[test]
+    fn system_messages_become_system_instruction() {
+        let messages = [
+            ChatMessage::system("You are terse."),
+            ChatMessage::system("Answer in JSON."),
+            ChatMessage::user("hello"),
+        ];
+        let instruction = serde_json::to_value(system_instruction(&messages)).unwrap();
+        assert_eq!(
+            instruction,
+            serde_json::json!({ "parts": [{ "text": "You are terse.\n\nAnswer in JSON." }] })
+        );
+
+        assert!(system_instruction(&[ChatMessage::user("hello")]).is_none());
+    }
+
+    #[test]
+    fn thought_parts_are_separated_from_answer() {
+        let response: GenerateContentResponse = serde_json::from_value(serde_json::json!({
+            "candidates": [{
+                "content": { "parts": [
+                    { "text": "Considering the fields...", "thought": true },
+                    { "text": "{\"name\": " },
+                    { "text": "\"Ada\"}" }
+                ]},
+                "finishReason": "STOP"
+            }]
+        }))
+        .unwrap();
+        let candidate = &response.candidates[0];
+        assert_eq!(
+            candidate.answer_text().as_deref(),
+            Some("{\"name\": \"Ada\"}")
+        );
+        assert_eq!(
+            candidate.thinking_text().as_deref(),
+            Some("Considering the fields...")
+        );
+    }
+
+    #[test]
+    fn blocked_prompt_is_reported() {
+        let response: GenerateContentResponse = serde_json::from_value(serde_json::json!({
+            "promptFeedback": { "blockReason": "SAFETY" }
+        }))
+        .unwrap();
+        assert_eq!(
+            response.blocked_reason().as_deref(),
+            Some("prompt blocked (SAFETY)")
+        );
+    }
+
+    #[test]
+    fn safety_finish_reason_without_text_is_reported() {
+        let response: GenerateContentResponse = serde_json::from_value(serde_json::json!({
+            "candidates": [{ "finishReason": "SAFETY" }]
+        }))
+        .unwrap();
+        assert_eq!(
+            response.blocked_reason().as_deref(),
+            Some("finish reason SAFETY")
+        );
+
+        let response: GenerateContentResponse = serde_json::from_value(serde_json::json!({
+            "candidates": [{
+                "content": { "parts": [{ "text": "{}" }] },
+                "finishReason": "STOP"
+            }]
+        }))
+        .unwrap();
+        assert!(response.blocked_reason().is_none());
+    }
+
     /// Helper to construct the URL that list_models would use
     fn build_list_models_url(base_url: &str, api_key: &str) -> String {
         format!("{}/models?key={}", base_url, api_key)
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use reqwest::header::HeaderMap;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -6,13 +7,18 @@ use std::time::Duration;
 use tracing::{debug, error, info, instrument, trace, warn};
 
 use crate::backend::{
-    ChatMessage, GenerateResult, LLMClient, MaterializeInternalOutput, MaterializeResult,
-    ModelInfo, ResponseFormat, TokenUsage, ValidationFailureContext, check_response_status,
-    generate_with_retry_with_history, handle_http_error, parse_validate_and_create_output,
-    prepare_strict_schema,
+    Budget, ChatMessage, GenerateResult, LLMClient, MaterializeInternalOutput, MaterializeResult,
+    ModelInfo, RequestOptions, ResponseFormat, RetryPolicy, TokenUsage, ValidationFailureContext,
+    check_response_status, clamp_sampling_param, custom_headers, dry_run_output, effective_penalty,
+    effective_top_p, estimated_usage, few_shot_messages, fit_context_window,
+    generate_with_retry_from_messages, generate_with_retry_with_history, handle_http_error,
+    log_schema_once, parse_validate_and_create_output, prepare_strict_schema, read_json_response,
+    reserved_tokens, response_request_id, unwrap_value_root, with_system_prompts,
+    wrap_non_object_root,
 };
 use crate::error::{ApiErrorKind, RStructorError, Result};
 use crate::model::Instructor;
+use crate::schema::Schema;
 
 /// Grok models available for completion
 ///
@@ -100,6 +106,31 @@ impl Model {
             _ => Model::Custom(name),
         }
     }
+
+    /// Parse a model name, rejecting names that don't match a known variant.
+    ///
+    /// Unlike [`from_string`](Self::from_string), an unknown name returns an
+    /// `InvalidModel` error instead of becoming `Custom`, so typos in configuration
+    /// are caught before the first request.
+    ///
+    /// ```rust
+    /// use rstructor::GrokModel;
+    ///
+    /// assert_eq!(GrokModel::try_known("grok-3").unwrap(), GrokModel::Grok3);
+    /// assert!(GrokModel::try_known("grok-33").is_err());
+    /// ```
+    pub fn try_known(name: &str) -> Result<Self> {
+        match Self::from_string(name) {
+            Model::Custom(_) => Err(RStructorError::api_error(
+                "Grok",
+                ApiErrorKind::InvalidModel {
+                    model: name.to_string(),
+                    suggestion: None,
+                },
+            )),
+            model => Ok(model),
+        }
+    }
 }
 
 impl FromStr for Model {
@@ -130,10 +161,40 @@ pub struct GrokConfig {
     pub temperature: f32,
     pub max_tokens: Option<u32>,
     pub timeout: Option<Duration>,
+    /// Upper bound on the total time of a `materialize` call, including retries
+    pub deadline: Option<Duration>,
     pub max_retries: Option<usize>,
+    /// Waits between retry attempts
+    pub retry_policy: RetryPolicy,
+    /// Extra headers sent with every request
+    pub headers: HeaderMap,
+    /// Sequences that end generation when produced (empty = none)
+    pub stop_sequences: Vec<String>,
+    /// Nucleus sampling probability mass (None = provider default)
+    pub top_p: Option<f32>,
+    /// Penalty on tokens by how often they have appeared so far (-2.0 to 2.0)
+    pub frequency_penalty: Option<f32>,
+    /// Penalty on tokens that have appeared at all so far (-2.0 to 2.0)
+    pub presence_penalty: Option<f32>,
     /// Custom base URL for Grok-compatible APIs (e.g., local LLMs, proxy endpoints)
     /// Defaults to "https://api.x.ai/v1" if not set
     pub base_url: Option<String>,
+    /// Cumulative spending limit shared across clones of this client
+    pub budget: Option<Budget>,
+    /// Strip stray control characters from responses before parsing
+    pub lenient_parsing: bool,
+    /// Drop the oldest messages when a conversation exceeds the model's context window
+    pub auto_truncate: bool,
+    /// Log each type's prepared schema once, at debug level
+    pub log_schemas: bool,
+    /// System instructions sent ahead of every request, in the order they were added
+    pub system_prompts: Vec<String>,
+    /// Return schema-shaped placeholder data instead of calling the API
+    pub dry_run: bool,
+    /// Largest response body to read, in bytes (None = no limit)
+    pub max_response_bytes: Option<usize>,
+    /// Processing tier sent as `service_tier` (e.g. "auto", "default", "flex")
+    pub service_tier: Option<String>,
 }
 
 /// Grok client for generating completions
@@ -160,6 +221,16 @@ struct ChatCompletionRequest {
     temperature: f32,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frequency_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    presence_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    stop: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    service_tier: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -177,12 +248,11 @@ struct ChatCompletionChoice {
 }
 
 #[derive(Debug, Deserialize)]
-#[allow(dead_code)]
 struct UsageInfo {
     prompt_tokens: u64,
     completion_tokens: u64,
     #[serde(default)]
-    total_tokens: u64,
+    total_tokens: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -227,9 +297,24 @@ impl GrokClient {
             model: Model::Grok41FastNonReasoning, // Default to Grok-4.1 Fast Non-Reasoning
             temperature: 0.0,
             max_tokens: None,
-            timeout: None,        // Default: no timeout (uses reqwest's default)
-            max_retries: Some(3), // Default: 3 retries with error feedback
-            base_url: None,       // Default: use official Grok API
+            timeout: None,              // Default: no timeout (uses reqwest's default)
+            deadline: None,             // Default: no overall deadline
+            max_retries: Some(3),       // Default: 3 retries with error feedback
+            base_url: None,             // Default: use official Grok API
+            budget: None,               // Default: no spending limit
+            lenient_parsing: false,     // Default: strict JSON parsing
+            auto_truncate: false,       // Default: send conversations as-is
+            log_schemas: false,         // Default: don't log schemas
+            system_prompts: Vec::new(), // Default: no system instructions
+            dry_run: false,             // Default: call the provider
+            max_response_bytes: None,   // Default: no limit
+            service_tier: None,         // Default: use the account's default tier
+            retry_policy: RetryPolicy::default(), // Default: exponential backoff from 500ms
+            headers: HeaderMap::new(),  // Default: no extra headers
+            stop_sequences: Vec::new(), // Default: no stop sequences
+            top_p: None,                // Default: provider's default
+            frequency_penalty: None,    // Default: no penalty
+            presence_penalty: None,     // Default: no penalty
         };
 
         debug!("Grok client created with default configuration");
@@ -267,9 +352,24 @@ impl GrokClient {
             model: Model::Grok41FastNonReasoning, // Default to Grok-4.1 Fast Non-Reasoning
             temperature: 0.0,
             max_tokens: None,
-            timeout: None,        // Default: no timeout (uses reqwest's default)
-            max_retries: Some(3), // Default: 3 retries with error feedback
-            base_url: None,       // Default: use official Grok API
+            timeout: None,              // Default: no timeout (uses reqwest's default)
+            deadline: None,             // Default: no overall deadline
+            max_retries: Some(3),       // Default: 3 retries with error feedback
+            base_url: None,             // Default: use official Grok API
+            budget: None,               // Default: no spending limit
+            lenient_parsing: false,     // Default: strict JSON parsing
+            auto_truncate: false,       // Default: send conversations as-is
+            log_schemas: false,         // Default: don't log schemas
+            system_prompts: Vec::new(), // Default: no system instructions
+            dry_run: false,             // Default: call the provider
+            max_response_bytes: None,   // Default: no limit
+            service_tier: None,         // Default: use the account's default tier
+            retry_policy: RetryPolicy::default(), // Default: exponential backoff from 500ms
+            headers: HeaderMap::new(),  // Default: no extra headers
+            stop_sequences: Vec::new(), // Default: no stop sequences
+            top_p: None,                // Default: provider's default
+            frequency_penalty: None,    // Default: no penalty
+            presence_penalty: None,     // Default: no penalty
         };
 
         debug!("Grok client created with default configuration");
@@ -282,45 +382,37 @@ impl GrokClient {
     // Builder methods are generated by the macro below
 }
 
+/// Convert conversation history to Grok's message format
+fn api_messages(messages: &[ChatMessage]) -> Vec<GrokChatMessage> {
+    messages
+        .iter()
+        .map(|msg| GrokChatMessage {
+            role: msg.role.as_str().to_string(),
+            content: msg.content.clone(),
+        })
+        .collect()
+}
+
 impl GrokClient {
-    /// Internal implementation of materialize (without retry logic)
-    /// Accepts conversation history for multi-turn interactions.
-    /// Returns the data, raw response, and optional usage info.
-    ///
-    /// Uses Grok's native Structured Outputs with `response_format: json_schema`
-    /// for guaranteed schema compliance via constrained decoding.
-    ///
-    /// The raw response is included to enable conversation history tracking for retries,
-    /// which improves prompt caching efficiency.
-    async fn materialize_internal<T>(
+    /// Build the structured-output request for a conversation, returning it together
+    /// with whether the schema root was wrapped in an object.
+    fn structured_request<T: Instructor>(
         &self,
         messages: &[ChatMessage],
-    ) -> std::result::Result<
-        MaterializeInternalOutput<T>,
-        (RStructorError, Option<ValidationFailureContext>),
-    >
-    where
-        T: Instructor + DeserializeOwned + Send + 'static,
-    {
-        info!("Generating structured response with Grok (native structured outputs)");
-
-        // Get the schema for type T
-        let schema = T::schema();
+        schema: &Schema,
+        options: &RequestOptions,
+    ) -> (ChatCompletionRequest, bool) {
         let schema_name = T::schema_name().unwrap_or_else(|| "output".to_string());
         trace!(schema_name = schema_name, "Retrieved JSON schema for type");
 
         // Prepare schema with additionalProperties: false recursively for all nested objects
-        let schema_json = prepare_strict_schema(&schema);
+        let mut schema_json = prepare_strict_schema(schema);
+        // Strict mode needs an object root, so enums get wrapped in `{"value": ...}`
+        let wrapped_root = wrap_non_object_root(&mut schema_json);
 
         // Build API messages from conversation history
         // With native structured outputs, we don't need to include schema instructions in the prompt
-        let api_messages: Vec<GrokChatMessage> = messages
-            .iter()
-            .map(|msg| GrokChatMessage {
-                role: msg.role.as_str().to_string(),
-                content: msg.content.clone(),
-            })
-            .collect();
+        let api_messages = api_messages(messages);
 
         // Create response format for native structured outputs
         let response_format = ResponseFormat::json_schema(schema_name.clone(), schema_json, None);
@@ -333,20 +425,95 @@ impl GrokClient {
             model: self.config.model.as_str().to_string(),
             messages: api_messages,
             response_format: Some(response_format),
-            temperature: self.config.temperature,
-            max_tokens: self.config.max_tokens,
+            temperature: options.temperature.unwrap_or(self.config.temperature),
+            max_tokens: options.max_tokens.or(self.config.max_tokens),
+            top_p: effective_top_p(options, self.config.top_p),
+            frequency_penalty: effective_penalty(
+                "frequency_penalty",
+                self.config.frequency_penalty,
+            ),
+            presence_penalty: effective_penalty("presence_penalty", self.config.presence_penalty),
+            stop: options
+                .stop
+                .clone()
+                .unwrap_or_else(|| self.config.stop_sequences.clone()),
+            service_tier: self.config.service_tier.clone(),
         };
 
-        let base_url = self
+        (request, wrapped_root)
+    }
+
+    /// Internal implementation of materialize (without retry logic)
+    /// Accepts conversation history for multi-turn interactions.
+    /// Returns the data, raw response, and optional usage info.
+    ///
+    /// Uses Grok's native Structured Outputs with `response_format: json_schema`
+    /// for guaranteed schema compliance via constrained decoding.
+    ///
+    /// The raw response is included to enable conversation history tracking for retries,
+    /// which improves prompt caching efficiency.
+    async fn materialize_internal<T>(
+        &self,
+        messages: &[ChatMessage],
+        schema: &Schema,
+        options: &RequestOptions,
+    ) -> std::result::Result<
+        MaterializeInternalOutput<T>,
+        (RStructorError, Option<ValidationFailureContext>),
+    >
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        if self.config.dry_run {
+            return dry_run_output::<T>(schema);
+        }
+        let messages = with_system_prompts(&self.config.system_prompts, messages);
+        let fitted = fit_context_window(
+            &messages,
+            self.config.model.as_str(),
+            reserved_tokens(
+                self.config.model.as_str(),
+                Some(schema),
+                options.max_tokens.or(self.config.max_tokens),
+            ),
+            self.config.auto_truncate,
+        );
+        let messages: &[ChatMessage] = &fitted;
+        let reservation = self
             .config
+            .budget
+            .as_ref()
+            .map(|budget| {
+                budget.check(&estimated_usage(
+                    self.config.model.as_str(),
+                    messages,
+                    Some(schema),
+                    options.max_tokens.or(self.config.max_tokens),
+                ))
+            })
+            .transpose()
+            .map_err(|e| (e, None))?;
+
+        info!("Generating structured response with Grok (native structured outputs)");
+
+        let (request, wrapped_root) = self.structured_request::<T>(messages, schema, options);
+        if self.config.log_schemas
+            && let Some(ResponseFormat::JsonSchema { json_schema }) = &request.response_format
+        {
+            log_schema_once::<T>("Grok", &json_schema.schema);
+        }
+
+        let base_url = options
             .base_url
             .as_deref()
+            .or(self.config.base_url.as_deref())
             .unwrap_or("https://api.x.ai/v1");
         let url = format!("{}/chat/completions", base_url);
         debug!(url = %url, "Sending request to Grok API with structured outputs");
         let response = self
             .client
             .post(&url)
+            .headers(custom_headers(&self.config.headers))
             .header("Authorization", format!("Bearer {}", self.config.api_key))
             .header("Content-Type", "application/json")
             .json(&request)
@@ -354,15 +521,19 @@ impl GrokClient {
             .await
             .map_err(|e| (handle_http_error(e, "Grok"), None))?;
 
-        let response = check_response_status(response, "Grok")
+        let response = check_response_status(response, "Grok", self.config.max_response_bytes)
             .await
             .map_err(|e| (e, None))?;
+        let request_id = response_request_id(&response);
 
         debug!("Successfully received response from Grok API");
-        let completion: ChatCompletionResponse = response.json().await.map_err(|e| {
-            error!(error = %e, "Failed to parse JSON response from Grok API");
-            (RStructorError::from(e), None)
-        })?;
+        let completion: ChatCompletionResponse =
+            read_json_response(response, "Grok", self.config.max_response_bytes)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to parse JSON response from Grok API");
+                    (e, None)
+                })?;
 
         if completion.choices.is_empty() {
             error!("Grok API returned empty choices array");
@@ -382,17 +553,37 @@ impl GrokClient {
             .model
             .clone()
             .unwrap_or_else(|| self.config.model.as_str().to_string());
-        let usage = completion
-            .usage
-            .as_ref()
-            .map(|u| TokenUsage::new(model_name.clone(), u.prompt_tokens, u.completion_tokens));
+        let usage = completion.usage.as_ref().map(|u| {
+            TokenUsage::new(model_name.clone(), u.prompt_tokens, u.completion_tokens)
+                .with_total_tokens(u.total_tokens)
+        });
+        if let Some(reservation) = reservation {
+            reservation.record(usage.as_ref());
+        }
 
         let message = &completion.choices[0].message;
         trace!(finish_reason = %completion.choices[0].finish_reason, "Completion finish reason");
 
+        if completion.choices[0].finish_reason == "content_filter" {
+            error!("Grok response blocked by content filter");
+            return Err((
+                RStructorError::api_error(
+                    "Grok",
+                    ApiErrorKind::ContentFiltered {
+                        details: "finish_reason: content_filter".to_string(),
+                    },
+                ),
+                None,
+            ));
+        }
+
         // With native structured outputs, the response is in message.content as guaranteed JSON
         if let Some(content) = &message.content {
-            let raw_response = content.clone();
+            let raw_response = if wrapped_root {
+                unwrap_value_root(content.clone(), self.config.lenient_parsing)
+            } else {
+                content.clone()
+            };
             debug!(
                 content_len = raw_response.len(),
                 "Received structured output response"
@@ -400,7 +591,8 @@ impl GrokClient {
 
             // Parse and validate the response using shared utility
             trace!(json = %raw_response, "Parsing structured output response");
-            parse_validate_and_create_output(raw_response, usage)
+            parse_validate_and_create_output(raw_response, usage, self.config.lenient_parsing)
+                .map(|output| output.with_request_id(request_id))
         } else {
             error!("No content in Grok API response");
             Err((
@@ -441,6 +633,225 @@ impl GrokClient {
         self.config.base_url = Some(base_url_str);
         self
     }
+
+    /// Set the processing tier for requests (`service_tier`).
+    ///
+    /// `"flex"` trades slower responses for a much lower price, which suits large
+    /// batch extraction jobs; `"auto"` and `"default"` follow the account settings.
+    /// Left out of requests unless set.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rstructor::GrokClient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = GrokClient::from_env()?.service_tier("flex");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self, tier))]
+    pub fn service_tier(mut self, tier: impl Into<String>) -> Self {
+        let tier = tier.into();
+        tracing::debug!(
+            previous = ?self.config.service_tier,
+            new = %tier,
+            "Setting service tier"
+        );
+        self.config.service_tier = Some(tier);
+        self
+    }
+
+    /// Penalize tokens by how often they have already appeared (`frequency_penalty`).
+    ///
+    /// Positive values make verbatim repetition less likely. Clamped to -2.0–2.0 and
+    /// left out of requests unless set.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rstructor::GrokClient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = GrokClient::from_env()?
+    ///     .top_p(0.9)
+    ///     .frequency_penalty(0.5)
+    ///     .presence_penalty(0.2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub fn frequency_penalty(mut self, penalty: f32) -> Self {
+        let penalty = clamp_sampling_param("frequency_penalty", penalty, -2.0, 2.0);
+        tracing::debug!(
+            previous = ?self.config.frequency_penalty,
+            new = penalty,
+            "Setting frequency penalty"
+        );
+        self.config.frequency_penalty = Some(penalty);
+        self
+    }
+
+    /// Penalize tokens that have appeared at all so far (`presence_penalty`).
+    ///
+    /// Positive values nudge the model towards new topics. Clamped to -2.0–2.0 and
+    /// left out of requests unless set.
+    #[tracing::instrument(skip(self))]
+    pub fn presence_penalty(mut self, penalty: f32) -> Self {
+        let penalty = clamp_sampling_param("presence_penalty", penalty, -2.0, 2.0);
+        tracing::debug!(
+            previous = ?self.config.presence_penalty,
+            new = penalty,
+            "Setting presence penalty"
+        );
+        self.config.presence_penalty = Some(penalty);
+        self
+    }
+
+    /// Internal implementation of raw text generation for a conversation, with
+    /// per-call overrides applied over the client configuration.
+    async fn generate_internal(
+        &self,
+        messages: &[ChatMessage],
+        options: &RequestOptions,
+    ) -> Result<GenerateResult> {
+        if self.config.dry_run {
+            return Ok(GenerateResult::new(String::new(), None));
+        }
+        let messages = with_system_prompts(&self.config.system_prompts, messages);
+        let fitted = fit_context_window(
+            &messages,
+            self.config.model.as_str(),
+            reserved_tokens(
+                self.config.model.as_str(),
+                None,
+                options.max_tokens.or(self.config.max_tokens),
+            ),
+            self.config.auto_truncate,
+        );
+        let messages: &[ChatMessage] = &fitted;
+        let reservation = self
+            .config
+            .budget
+            .as_ref()
+            .map(|budget| {
+                budget.check(&estimated_usage(
+                    self.config.model.as_str(),
+                    messages,
+                    None,
+                    options.max_tokens.or(self.config.max_tokens),
+                ))
+            })
+            .transpose()?;
+
+        info!("Generating raw text response with Grok");
+
+        // Build the request without structured outputs
+        debug!("Building Grok API request for text generation");
+        let request = ChatCompletionRequest {
+            model: self.config.model.as_str().to_string(),
+            messages: api_messages(messages),
+            response_format: None,
+            temperature: options.temperature.unwrap_or(self.config.temperature),
+            max_tokens: options.max_tokens.or(self.config.max_tokens),
+            top_p: effective_top_p(options, self.config.top_p),
+            frequency_penalty: effective_penalty(
+                "frequency_penalty",
+                self.config.frequency_penalty,
+            ),
+            presence_penalty: effective_penalty("presence_penalty", self.config.presence_penalty),
+            stop: options
+                .stop
+                .clone()
+                .unwrap_or_else(|| self.config.stop_sequences.clone()),
+            service_tier: self.config.service_tier.clone(),
+        };
+
+        // Send the request to Grok/xAI API
+        let base_url = options
+            .base_url
+            .as_deref()
+            .or(self.config.base_url.as_deref())
+            .unwrap_or("https://api.x.ai/v1");
+        let url = format!("{}/chat/completions", base_url);
+        debug!(url = %url, "Sending request to Grok API");
+        let response = self
+            .client
+            .post(&url)
+            .headers(custom_headers(&self.config.headers))
+            .header("Authorization", format!("Bearer {}", self.config.api_key))
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| handle_http_error(e, "Grok"))?;
+
+        // Parse the response
+        let response =
+            check_response_status(response, "Grok", self.config.max_response_bytes).await?;
+        let request_id = response_request_id(&response);
+
+        debug!("Successfully received response from Grok API");
+        let completion: ChatCompletionResponse =
+            read_json_response(response, "Grok", self.config.max_response_bytes)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to parse JSON response from Grok API");
+                    e
+                })?;
+
+        if completion.choices.is_empty() {
+            error!("Grok API returned empty choices array");
+            return Err(RStructorError::api_error(
+                "Grok",
+                ApiErrorKind::UnexpectedResponse {
+                    details: "No completion choices returned".to_string(),
+                },
+            ));
+        }
+
+        // Extract usage info
+        let model_name = completion
+            .model
+            .clone()
+            .unwrap_or_else(|| self.config.model.as_str().to_string());
+        let usage = completion.usage.as_ref().map(|u| {
+            TokenUsage::new(model_name, u.prompt_tokens, u.completion_tokens)
+                .with_total_tokens(u.total_tokens)
+        });
+        if let Some(reservation) = reservation {
+            reservation.record(usage.as_ref());
+        }
+
+        let message = &completion.choices[0].message;
+        trace!(finish_reason = %completion.choices[0].finish_reason, "Completion finish reason");
+
+        if completion.choices[0].finish_reason == "content_filter" {
+            error!("Grok response blocked by content filter");
+            return Err(RStructorError::api_error(
+                "Grok",
+                ApiErrorKind::ContentFiltered {
+                    details: "finish_reason: content_filter".to_string(),
+                },
+            ));
+        }
+
+        if let Some(content) = &message.content {
+            debug!(
+                content_len = content.len(),
+                "Successfully extracted content from response"
+            );
+            Ok(GenerateResult::new(content.clone(), usage).with_request_id(request_id))
+        } else {
+            error!("No content in Grok API response");
+            Err(RStructorError::api_error(
+                "Grok",
+                ApiErrorKind::UnexpectedResponse {
+                    details: "No content in response".to_string(),
+                },
+            ))
+        }
+    }
 }
 
 #[async_trait]
@@ -462,13 +873,164 @@ impl LLMClient for GrokClient {
     where
         T: Instructor + DeserializeOwned + Send + 'static,
     {
+        let schema = T::schema();
         let output = generate_with_retry_with_history(
             |messages: Vec<ChatMessage>| {
                 let this = self;
-                async move { this.materialize_internal::<T>(&messages).await }
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
             },
             prompt,
             self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "grok_materialize_with_schema",
+        skip(self, prompt, schema),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn materialize_with_schema<T>(&self, prompt: &str, schema: Schema) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            prompt,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "grok_materialize_opts",
+        skip(self, prompt, options),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn materialize_opts<T>(&self, prompt: &str, options: &RequestOptions) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, options)
+                        .await
+                }
+            },
+            prompt,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    fn materialize_request_body<T>(&self, prompt: &str) -> Result<serde_json::Value>
+    where
+        T: Instructor,
+    {
+        let schema = T::schema();
+        let prompt = [ChatMessage::user(prompt)];
+        let messages = with_system_prompts(&self.config.system_prompts, &prompt);
+        let (request, _) =
+            self.structured_request::<T>(&messages, &schema, &RequestOptions::default());
+        Ok(serde_json::to_value(&request)?)
+    }
+
+    #[instrument(
+        name = "grok_materialize_with_examples",
+        skip(self, prompt, examples),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len(),
+            examples_len = examples.len()
+        )
+    )]
+    async fn materialize_with_examples<T>(
+        &self,
+        prompt: &str,
+        examples: &[(String, T)],
+    ) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + Sync + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_from_messages(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            few_shot_messages(prompt, examples)?,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "grok_materialize_with_history",
+        skip(self, messages),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            messages_len = messages.len()
+        )
+    )]
+    async fn materialize_with_history<T>(&self, messages: &[ChatMessage]) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_from_messages(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            messages.to_vec(),
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
         )
         .await?;
         Ok(output.data)
@@ -487,16 +1049,25 @@ impl LLMClient for GrokClient {
     where
         T: Instructor + DeserializeOwned + Send + 'static,
     {
+        let schema = T::schema();
         let output = generate_with_retry_with_history(
             |messages: Vec<ChatMessage>| {
                 let this = self;
-                async move { this.materialize_internal::<T>(&messages).await }
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
             },
             prompt,
             self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
         )
         .await?;
-        Ok(MaterializeResult::new(output.data, output.usage))
+        Ok(MaterializeResult::new(output.data, output.usage)
+            .with_attempts(output.attempts)
+            .with_request_id(output.request_id))
     }
 
     #[instrument(
@@ -521,86 +1092,36 @@ impl LLMClient for GrokClient {
         )
     )]
     async fn generate_with_metadata(&self, prompt: &str) -> Result<GenerateResult> {
-        info!("Generating raw text response with Grok");
-
-        // Build the request without structured outputs
-        debug!("Building Grok API request for text generation");
-        let request = ChatCompletionRequest {
-            model: self.config.model.as_str().to_string(),
-            messages: vec![GrokChatMessage {
-                role: "user".to_string(),
-                content: prompt.to_string(),
-            }],
-            response_format: None,
-            temperature: self.config.temperature,
-            max_tokens: self.config.max_tokens,
-        };
-
-        // Send the request to Grok/xAI API
-        let base_url = self
-            .config
-            .base_url
-            .as_deref()
-            .unwrap_or("https://api.x.ai/v1");
-        let url = format!("{}/chat/completions", base_url);
-        debug!(url = %url, "Sending request to Grok API");
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.config.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
+        self.generate_with_messages(&[ChatMessage::user(prompt)])
             .await
-            .map_err(|e| handle_http_error(e, "Grok"))?;
-
-        // Parse the response
-        let response = check_response_status(response, "Grok").await?;
-
-        debug!("Successfully received response from Grok API");
-        let completion: ChatCompletionResponse = response.json().await.map_err(|e| {
-            error!(error = %e, "Failed to parse JSON response from Grok API");
-            e
-        })?;
-
-        if completion.choices.is_empty() {
-            error!("Grok API returned empty choices array");
-            return Err(RStructorError::api_error(
-                "Grok",
-                ApiErrorKind::UnexpectedResponse {
-                    details: "No completion choices returned".to_string(),
-                },
-            ));
-        }
-
-        // Extract usage info
-        let model_name = completion
-            .model
-            .clone()
-            .unwrap_or_else(|| self.config.model.as_str().to_string());
-        let usage = completion
-            .usage
-            .as_ref()
-            .map(|u| TokenUsage::new(model_name, u.prompt_tokens, u.completion_tokens));
+    }
 
-        let message = &completion.choices[0].message;
-        trace!(finish_reason = %completion.choices[0].finish_reason, "Completion finish reason");
+    #[instrument(
+        name = "grok_generate_opts",
+        skip(self, prompt, options),
+        fields(
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn generate_opts(&self, prompt: &str, options: &RequestOptions) -> Result<String> {
+        let result = self
+            .generate_internal(&[ChatMessage::user(prompt)], options)
+            .await?;
+        Ok(result.text)
+    }
 
-        if let Some(content) = &message.content {
-            debug!(
-                content_len = content.len(),
-                "Successfully extracted content from response"
-            );
-            Ok(GenerateResult::new(content.clone(), usage))
-        } else {
-            error!("No content in Grok API response");
-            Err(RStructorError::api_error(
-                "Grok",
-                ApiErrorKind::UnexpectedResponse {
-                    details: "No content in response".to_string(),
-                },
-            ))
-        }
+    #[instrument(
+        name = "grok_generate_with_messages",
+        skip(self, messages),
+        fields(
+            model = %self.config.model.as_str(),
+            history_len = messages.len()
+        )
+    )]
+    async fn generate_with_messages(&self, messages: &[ChatMessage]) -> Result<GenerateResult> {
+        self.generate_internal(messages, &RequestOptions::default())
+            .await
     }
 
     /// Fetch available models from Grok's API.
@@ -619,18 +1140,23 @@ impl LLMClient for GrokClient {
         let response = self
             .client
             .get(&url)
+            .headers(custom_headers(&self.config.headers))
             .header("Authorization", format!("Bearer {}", self.config.api_key))
             .header("Content-Type", "application/json")
             .send()
             .await
             .map_err(|e| handle_http_error(e, "Grok"))?;
 
-        let response = check_response_status(response, "Grok").await?;
+        let response =
+            check_response_status(response, "Grok", self.config.max_response_bytes).await?;
 
-        let json: serde_json::Value = response.json().await.map_err(|e| {
-            error!(error = %e, "Failed to parse models response from Grok");
-            e
-        })?;
+        let json: serde_json::Value =
+            read_json_response(response, "Grok", self.config.max_response_bytes)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to parse models response from Grok");
+                    e
+                })?;
 
         let models = json
             .get("data")
@@ -116,17 +116,32 @@ pub struct MaterializeInternalOutput<T> {
     pub raw_response: String,
     /// Token usage information if available
     pub usage: Option<crate::backend::TokenUsage>,
+    /// Number of attempts made, filled in by the retry loop (1 = no retries)
+    pub attempts: usize,
+    /// The model's reasoning text, for providers that return it separately
+    pub thinking: Option<String>,
+    /// Provider request id of this attempt, taken from the response headers
+    pub request_id: Option<String>,
 }
 
 impl<T> MaterializeInternalOutput<T> {
-    /// Create a new output with all fields.
+    /// Create a new output for a single attempt.
     pub fn new(data: T, raw_response: String, usage: Option<crate::backend::TokenUsage>) -> Self {
         Self {
             data,
             raw_response,
             usage,
+            attempts: 1,
+            thinking: None,
+            request_id: None,
         }
     }
+
+    /// Attach the provider's request id for this attempt.
+    pub fn with_request_id(mut self, request_id: Option<String>) -> Self {
+        self.request_id = request_id;
+        self
+    }
 }
 
 /// Error context for validation failures that preserves the raw response.
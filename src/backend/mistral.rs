@@ -0,0 +1,1126 @@
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::{debug, error, info, instrument, trace, warn};
+
+use crate::backend::{
+    Budget, ChatMessage, GenerateResult, LLMClient, MaterializeInternalOutput, MaterializeResult,
+    ModelInfo, RequestOptions, ResponseFormat, RetryPolicy, TokenUsage, ValidationFailureContext,
+    check_response_status, clamp_sampling_param, custom_headers, dry_run_output, effective_penalty,
+    effective_top_p, estimated_usage, few_shot_messages, fit_context_window,
+    generate_with_retry_from_messages, generate_with_retry_with_history, handle_http_error,
+    json_mode_instruction, log_schema_once, parse_validate_and_create_output, read_json_response,
+    reserved_tokens, response_request_id, unwrap_value_root, with_system_prompts,
+    wrap_non_object_root,
+};
+use crate::error::{ApiErrorKind, RStructorError, Result};
+use crate::model::Instructor;
+use crate::schema::Schema;
+
+/// Mistral models available for completion
+///
+/// These are convenience variants for common Mistral models; the `-latest` aliases
+/// follow the newest release of each model.
+/// For the latest available models and their identifiers, check the
+/// [Mistral Models Documentation](https://docs.mistral.ai/getting-started/models/).
+///
+/// # Using Custom Models
+///
+/// You can specify any model name as a string using `Custom` variant or `FromStr`:
+///
+/// ```rust
+/// use rstructor::MistralModel;
+/// use std::str::FromStr;
+///
+/// // Using Custom variant
+/// let model = MistralModel::Custom("mistral-large-2411".to_string());
+///
+/// // Using FromStr (useful for config files)
+/// let model = MistralModel::from_str("mistral-large-2411").unwrap();
+///
+/// // Or use the convenience method
+/// let model = MistralModel::from_string("mistral-large-2411");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Model {
+    /// Mistral Large (flagship model for complex tasks)
+    MistralLarge,
+    /// Mistral Medium (balance of capability and cost)
+    MistralMedium,
+    /// Mistral Small (fast, inexpensive model)
+    MistralSmall,
+    /// Codestral (optimized for code)
+    Codestral,
+    /// Ministral 8B (small edge model)
+    Ministral8B,
+    /// Ministral 3B (smallest edge model)
+    Ministral3B,
+    /// Pixtral Large (multimodal vision model)
+    PixtralLarge,
+    /// Mistral NeMo (12B open model)
+    OpenMistralNemo,
+    /// Custom model name (for new models or fine-tunes)
+    Custom(String),
+}
+
+impl Model {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Model::MistralLarge => "mistral-large-latest",
+            Model::MistralMedium => "mistral-medium-latest",
+            Model::MistralSmall => "mistral-small-latest",
+            Model::Codestral => "codestral-latest",
+            Model::Ministral8B => "ministral-8b-latest",
+            Model::Ministral3B => "ministral-3b-latest",
+            Model::PixtralLarge => "pixtral-large-latest",
+            Model::OpenMistralNemo => "open-mistral-nemo",
+            Model::Custom(name) => name,
+        }
+    }
+
+    /// Create a model from a string. This is a convenience method that always succeeds.
+    ///
+    /// If the string matches a known model variant, it returns that variant.
+    /// Otherwise, it returns `Custom(name)`.
+    pub fn from_string(name: impl Into<String>) -> Self {
+        let name = name.into();
+        match name.as_str() {
+            "mistral-large-latest" => Model::MistralLarge,
+            "mistral-medium-latest" => Model::MistralMedium,
+            "mistral-small-latest" => Model::MistralSmall,
+            "codestral-latest" => Model::Codestral,
+            "ministral-8b-latest" => Model::Ministral8B,
+            "ministral-3b-latest" => Model::Ministral3B,
+            "pixtral-large-latest" => Model::PixtralLarge,
+            "open-mistral-nemo" => Model::OpenMistralNemo,
+            _ => Model::Custom(name),
+        }
+    }
+
+    /// Parse a model name, rejecting names that don't match a known variant.
+    ///
+    /// Unlike [`from_string`](Self::from_string), an unknown name returns an
+    /// `InvalidModel` error instead of becoming `Custom`, so typos in configuration
+    /// are caught before the first request.
+    ///
+    /// ```rust
+    /// use rstructor::MistralModel;
+    ///
+    /// assert_eq!(
+    ///     MistralModel::try_known("mistral-small-latest").unwrap(),
+    ///     MistralModel::MistralSmall
+    /// );
+    /// assert!(MistralModel::try_known("mistral-tiny-latest").is_err());
+    /// ```
+    pub fn try_known(name: &str) -> Result<Self> {
+        match Self::from_string(name) {
+            Model::Custom(_) => Err(RStructorError::api_error(
+                "Mistral",
+                ApiErrorKind::InvalidModel {
+                    model: name.to_string(),
+                    suggestion: None,
+                },
+            )),
+            model => Ok(model),
+        }
+    }
+}
+
+impl FromStr for Model {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(Model::from_string(s))
+    }
+}
+
+impl From<&str> for Model {
+    fn from(s: &str) -> Self {
+        Model::from_string(s)
+    }
+}
+
+impl From<String> for Model {
+    fn from(s: String) -> Self {
+        Model::from_string(s)
+    }
+}
+
+/// Configuration for the Mistral client
+#[derive(Debug, Clone)]
+pub struct MistralConfig {
+    pub api_key: String,
+    pub model: Model,
+    pub temperature: f32,
+    pub max_tokens: Option<u32>,
+    pub timeout: Option<Duration>,
+    /// Upper bound on the total time of a `materialize` call, including retries
+    pub deadline: Option<Duration>,
+    pub max_retries: Option<usize>,
+    /// Waits between retry attempts
+    pub retry_policy: RetryPolicy,
+    /// Extra headers sent with every request
+    pub headers: HeaderMap,
+    /// Sequences that end generation when produced (empty = none)
+    pub stop_sequences: Vec<String>,
+    /// Nucleus sampling probability mass (None = provider default)
+    pub top_p: Option<f32>,
+    /// Penalty on tokens by how often they have appeared so far (-2.0 to 2.0)
+    pub frequency_penalty: Option<f32>,
+    /// Penalty on tokens that have appeared at all so far (-2.0 to 2.0)
+    pub presence_penalty: Option<f32>,
+    /// Custom base URL for Mistral-compatible APIs (e.g., proxy endpoints)
+    /// Defaults to "https://api.mistral.ai/v1" if not set
+    pub base_url: Option<String>,
+    /// Cumulative spending limit shared across clones of this client
+    pub budget: Option<Budget>,
+    /// Strip stray control characters from responses before parsing
+    pub lenient_parsing: bool,
+    /// Drop the oldest messages when a conversation exceeds the model's context window
+    pub auto_truncate: bool,
+    /// Log each type's prepared schema once, at debug level
+    pub log_schemas: bool,
+    /// System instructions sent ahead of every request, in the order they were added
+    pub system_prompts: Vec<String>,
+    /// Return schema-shaped placeholder data instead of calling the API
+    pub dry_run: bool,
+    /// Largest response body to read, in bytes (None = no limit)
+    pub max_response_bytes: Option<usize>,
+}
+
+/// Mistral client for generating completions
+pub struct MistralClient {
+    config: MistralConfig,
+    client: reqwest::Client,
+}
+
+// Mistral API request and response structures (OpenAI-compatible)
+#[derive(Debug, Serialize)]
+struct MistralChatMessage {
+    role: String,
+    content: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatCompletionRequest {
+    model: String,
+    messages: Vec<MistralChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    response_format: Option<ResponseFormat>,
+    temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frequency_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    presence_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    stop: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct ResponseMessage {
+    role: String,
+    content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct ChatCompletionChoice {
+    message: ResponseMessage,
+    finish_reason: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct UsageInfo {
+    prompt_tokens: u64,
+    completion_tokens: u64,
+    #[serde(default)]
+    total_tokens: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    choices: Vec<ChatCompletionChoice>,
+    #[serde(default)]
+    usage: Option<UsageInfo>,
+    model: Option<String>,
+}
+
+impl MistralClient {
+    /// Create a new Mistral client with the provided API key.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_key` - Your Mistral API key
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rstructor::MistralClient;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = MistralClient::new("your-mistral-api-key")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(name = "mistral_client_new", skip(api_key), fields(model = ?Model::MistralSmall))]
+    pub fn new(api_key: impl Into<String>) -> Result<Self> {
+        let api_key = api_key.into();
+        if api_key.is_empty() {
+            return Err(RStructorError::api_error(
+                "Mistral",
+                ApiErrorKind::AuthenticationFailed,
+            ));
+        }
+
+        info!("Creating new Mistral client");
+        trace!("API key length: {}", api_key.len());
+
+        let config = MistralConfig {
+            api_key,
+            model: Model::MistralSmall, // Default to Mistral Small
+            temperature: 0.0,
+            max_tokens: None,
+            timeout: None,              // Default: no timeout (uses reqwest's default)
+            deadline: None,             // Default: no overall deadline
+            max_retries: Some(3),       // Default: 3 retries with error feedback
+            base_url: None,             // Default: use official Mistral API
+            budget: None,               // Default: no spending limit
+            lenient_parsing: false,     // Default: strict JSON parsing
+            auto_truncate: false,       // Default: send conversations as-is
+            log_schemas: false,         // Default: don't log schemas
+            system_prompts: Vec::new(), // Default: no system instructions
+            dry_run: false,             // Default: call the provider
+            max_response_bytes: None,   // Default: no limit
+            retry_policy: RetryPolicy::default(), // Default: exponential backoff from 500ms
+            headers: HeaderMap::new(),  // Default: no extra headers
+            stop_sequences: Vec::new(), // Default: no stop sequences
+            top_p: None,                // Default: provider's default
+            frequency_penalty: None,    // Default: no penalty
+            presence_penalty: None,     // Default: no penalty
+        };
+
+        debug!("Mistral client created with default configuration");
+        Ok(Self {
+            config,
+            client: reqwest::Client::new(),
+        })
+    }
+
+    /// Create a new Mistral client by reading the API key from the `MISTRAL_API_KEY` environment variable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `MISTRAL_API_KEY` is not set.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rstructor::MistralClient;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = MistralClient::from_env()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(name = "mistral_client_from_env", fields(model = ?Model::MistralSmall))]
+    pub fn from_env() -> Result<Self> {
+        let api_key = std::env::var("MISTRAL_API_KEY").map_err(|_| {
+            RStructorError::api_error("Mistral", ApiErrorKind::AuthenticationFailed)
+        })?;
+
+        info!("Creating new Mistral client from environment variable");
+        trace!("API key length: {}", api_key.len());
+
+        let config = MistralConfig {
+            api_key,
+            model: Model::MistralSmall, // Default to Mistral Small
+            temperature: 0.0,
+            max_tokens: None,
+            timeout: None,              // Default: no timeout (uses reqwest's default)
+            deadline: None,             // Default: no overall deadline
+            max_retries: Some(3),       // Default: 3 retries with error feedback
+            base_url: None,             // Default: use official Mistral API
+            budget: None,               // Default: no spending limit
+            lenient_parsing: false,     // Default: strict JSON parsing
+            auto_truncate: false,       // Default: send conversations as-is
+            log_schemas: false,         // Default: don't log schemas
+            system_prompts: Vec::new(), // Default: no system instructions
+            dry_run: false,             // Default: call the provider
+            max_response_bytes: None,   // Default: no limit
+            retry_policy: RetryPolicy::default(), // Default: exponential backoff from 500ms
+            headers: HeaderMap::new(),  // Default: no extra headers
+            stop_sequences: Vec::new(), // Default: no stop sequences
+            top_p: None,                // Default: provider's default
+            frequency_penalty: None,    // Default: no penalty
+            presence_penalty: None,     // Default: no penalty
+        };
+
+        debug!("Mistral client created with default configuration");
+        Ok(Self {
+            config,
+            client: reqwest::Client::new(),
+        })
+    }
+
+    // Builder methods are generated by the macro below
+}
+
+/// Convert conversation history to Mistral's message format
+fn api_messages(messages: &[ChatMessage]) -> Vec<MistralChatMessage> {
+    messages
+        .iter()
+        .map(|msg| MistralChatMessage {
+            role: msg.role.as_str().to_string(),
+            content: msg.content.clone(),
+        })
+        .collect()
+}
+
+impl MistralClient {
+    /// Build the JSON-mode request for a conversation, returning it together with the
+    /// schema given to the model and whether its root was wrapped in an object.
+    ///
+    /// JSON mode only guarantees syntactically valid JSON, so the schema goes in a system
+    /// message ahead of the conversation; responses are checked against `T` on parsing.
+    fn structured_request<T: Instructor>(
+        &self,
+        messages: &[ChatMessage],
+        schema: &Schema,
+        options: &RequestOptions,
+    ) -> (ChatCompletionRequest, serde_json::Value, bool) {
+        let schema_name = T::schema_name().unwrap_or_else(|| "output".to_string());
+        trace!(schema_name = schema_name, "Retrieved JSON schema for type");
+
+        // JSON mode produces an object, so enums get wrapped in `{"value": ...}`
+        let mut schema_json = schema.to_json();
+        let wrapped_root = wrap_non_object_root(&mut schema_json);
+
+        let mut with_schema = vec![json_mode_instruction(&schema_json)];
+        with_schema.extend_from_slice(messages);
+        let api_messages = api_messages(&with_schema);
+
+        debug!(
+            "Building Mistral API request in JSON mode (history_len={})",
+            api_messages.len()
+        );
+        let request = ChatCompletionRequest {
+            model: self.config.model.as_str().to_string(),
+            messages: api_messages,
+            response_format: Some(ResponseFormat::JsonObject),
+            temperature: options.temperature.unwrap_or(self.config.temperature),
+            max_tokens: options.max_tokens.or(self.config.max_tokens),
+            top_p: effective_top_p(options, self.config.top_p),
+            frequency_penalty: effective_penalty(
+                "frequency_penalty",
+                self.config.frequency_penalty,
+            ),
+            presence_penalty: effective_penalty("presence_penalty", self.config.presence_penalty),
+            stop: options
+                .stop
+                .clone()
+                .unwrap_or_else(|| self.config.stop_sequences.clone()),
+        };
+
+        (request, schema_json, wrapped_root)
+    }
+
+    /// Internal implementation of materialize (without retry logic)
+    /// Accepts conversation history for multi-turn interactions.
+    /// Returns the data, raw response, and optional usage info.
+    ///
+    /// Uses Mistral's JSON mode (`response_format: json_object`) with the schema in the
+    /// prompt; mismatches are retried with feedback.
+    ///
+    /// The raw response is included to enable conversation history tracking for retries,
+    /// which improves prompt caching efficiency.
+    async fn materialize_internal<T>(
+        &self,
+        messages: &[ChatMessage],
+        schema: &Schema,
+        options: &RequestOptions,
+    ) -> std::result::Result<
+        MaterializeInternalOutput<T>,
+        (RStructorError, Option<ValidationFailureContext>),
+    >
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        if self.config.dry_run {
+            return dry_run_output::<T>(schema);
+        }
+        let messages = with_system_prompts(&self.config.system_prompts, messages);
+        let fitted = fit_context_window(
+            &messages,
+            self.config.model.as_str(),
+            reserved_tokens(
+                self.config.model.as_str(),
+                Some(schema),
+                options.max_tokens.or(self.config.max_tokens),
+            ),
+            self.config.auto_truncate,
+        );
+        let messages: &[ChatMessage] = &fitted;
+        let reservation = self
+            .config
+            .budget
+            .as_ref()
+            .map(|budget| {
+                budget.check(&estimated_usage(
+                    self.config.model.as_str(),
+                    messages,
+                    Some(schema),
+                    options.max_tokens.or(self.config.max_tokens),
+                ))
+            })
+            .transpose()
+            .map_err(|e| (e, None))?;
+
+        info!("Generating structured response with Mistral (JSON mode)");
+
+        let (request, schema_json, wrapped_root) =
+            self.structured_request::<T>(messages, schema, options);
+        if self.config.log_schemas {
+            log_schema_once::<T>("Mistral", &schema_json);
+        }
+
+        let base_url = options
+            .base_url
+            .as_deref()
+            .or(self.config.base_url.as_deref())
+            .unwrap_or("https://api.mistral.ai/v1");
+        let url = format!("{}/chat/completions", base_url);
+        debug!(url = %url, "Sending request to Mistral API");
+        let response = self
+            .client
+            .post(&url)
+            .headers(custom_headers(&self.config.headers))
+            .header("Authorization", format!("Bearer {}", self.config.api_key))
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| (handle_http_error(e, "Mistral"), None))?;
+
+        let response = check_response_status(response, "Mistral", self.config.max_response_bytes)
+            .await
+            .map_err(|e| (e, None))?;
+        let request_id = response_request_id(&response);
+
+        debug!("Successfully received response from Mistral API");
+        let completion: ChatCompletionResponse =
+            read_json_response(response, "Mistral", self.config.max_response_bytes)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to parse JSON response from Mistral API");
+                    (e, None)
+                })?;
+
+        if completion.choices.is_empty() {
+            error!("Mistral API returned empty choices array");
+            return Err((
+                RStructorError::api_error(
+                    "Mistral",
+                    ApiErrorKind::UnexpectedResponse {
+                        details: "No completion choices returned".to_string(),
+                    },
+                ),
+                None,
+            ));
+        }
+
+        // Extract usage info
+        let model_name = completion
+            .model
+            .clone()
+            .unwrap_or_else(|| self.config.model.as_str().to_string());
+        let usage = completion.usage.as_ref().map(|u| {
+            TokenUsage::new(model_name.clone(), u.prompt_tokens, u.completion_tokens)
+                .with_total_tokens(u.total_tokens)
+        });
+        if let Some(reservation) = reservation {
+            reservation.record(usage.as_ref());
+        }
+
+        let message = &completion.choices[0].message;
+        trace!(finish_reason = %completion.choices[0].finish_reason, "Completion finish reason");
+
+        if let Some(content) = &message.content {
+            let raw_response = if wrapped_root {
+                unwrap_value_root(content.clone(), self.config.lenient_parsing)
+            } else {
+                content.clone()
+            };
+            debug!(
+                content_len = raw_response.len(),
+                "Received JSON mode response"
+            );
+
+            // Parse and validate the response using shared utility
+            trace!(json = %raw_response, "Parsing JSON mode response");
+            parse_validate_and_create_output(raw_response, usage, self.config.lenient_parsing)
+                .map(|output| output.with_request_id(request_id))
+        } else {
+            error!("No content in Mistral API response");
+            Err((
+                RStructorError::api_error(
+                    "Mistral",
+                    ApiErrorKind::UnexpectedResponse {
+                        details: "No content in response".to_string(),
+                    },
+                ),
+                None,
+            ))
+        }
+    }
+}
+
+// Generate builder methods using macro
+crate::impl_client_builder_methods! {
+    client_type: MistralClient,
+    config_type: MistralConfig,
+    model_type: Model,
+    provider_name: "Mistral"
+}
+
+impl MistralClient {
+    /// Set a custom base URL for Mistral-compatible APIs (e.g., proxy endpoints).
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - Base URL without trailing slash (e.g., "https://proxy.example.com/v1")
+    #[tracing::instrument(skip(self, base_url))]
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        let base_url_str = base_url.into();
+        tracing::debug!(
+            previous_base_url = ?self.config.base_url,
+            new_base_url = %base_url_str,
+            "Setting custom base URL"
+        );
+        self.config.base_url = Some(base_url_str);
+        self
+    }
+
+    /// Penalize tokens by how often they have already appeared (`frequency_penalty`).
+    ///
+    /// Positive values make verbatim repetition less likely. Clamped to -2.0–2.0 and
+    /// left out of requests unless set.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rstructor::MistralClient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = MistralClient::from_env()?
+    ///     .top_p(0.9)
+    ///     .frequency_penalty(0.5)
+    ///     .presence_penalty(0.2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub fn frequency_penalty(mut self, penalty: f32) -> Self {
+        let penalty = clamp_sampling_param("frequency_penalty", penalty, -2.0, 2.0);
+        tracing::debug!(
+            previous = ?self.config.frequency_penalty,
+            new = penalty,
+            "Setting frequency penalty"
+        );
+        self.config.frequency_penalty = Some(penalty);
+        self
+    }
+
+    /// Penalize tokens that have appeared at all so far (`presence_penalty`).
+    ///
+    /// Positive values nudge the model towards new topics. Clamped to -2.0–2.0 and
+    /// left out of requests unless set.
+    #[tracing::instrument(skip(self))]
+    pub fn presence_penalty(mut self, penalty: f32) -> Self {
+        let penalty = clamp_sampling_param("presence_penalty", penalty, -2.0, 2.0);
+        tracing::debug!(
+            previous = ?self.config.presence_penalty,
+            new = penalty,
+            "Setting presence penalty"
+        );
+        self.config.presence_penalty = Some(penalty);
+        self
+    }
+
+    /// Internal implementation of raw text generation for a conversation, with
+    /// per-call overrides applied over the client configuration.
+    async fn generate_internal(
+        &self,
+        messages: &[ChatMessage],
+        options: &RequestOptions,
+    ) -> Result<GenerateResult> {
+        if self.config.dry_run {
+            return Ok(GenerateResult::new(String::new(), None));
+        }
+        let messages = with_system_prompts(&self.config.system_prompts, messages);
+        let fitted = fit_context_window(
+            &messages,
+            self.config.model.as_str(),
+            reserved_tokens(
+                self.config.model.as_str(),
+                None,
+                options.max_tokens.or(self.config.max_tokens),
+            ),
+            self.config.auto_truncate,
+        );
+        let messages: &[ChatMessage] = &fitted;
+        let reservation = self
+            .config
+            .budget
+            .as_ref()
+            .map(|budget| {
+                budget.check(&estimated_usage(
+                    self.config.model.as_str(),
+                    messages,
+                    None,
+                    options.max_tokens.or(self.config.max_tokens),
+                ))
+            })
+            .transpose()?;
+
+        info!("Generating raw text response with Mistral");
+
+        // Build the request without JSON mode
+        debug!("Building Mistral API request for text generation");
+        let request = ChatCompletionRequest {
+            model: self.config.model.as_str().to_string(),
+            messages: api_messages(messages),
+            response_format: None,
+            temperature: options.temperature.unwrap_or(self.config.temperature),
+            max_tokens: options.max_tokens.or(self.config.max_tokens),
+            top_p: effective_top_p(options, self.config.top_p),
+            frequency_penalty: effective_penalty(
+                "frequency_penalty",
+                self.config.frequency_penalty,
+            ),
+            presence_penalty: effective_penalty("presence_penalty", self.config.presence_penalty),
+            stop: options
+                .stop
+                .clone()
+                .unwrap_or_else(|| self.config.stop_sequences.clone()),
+        };
+
+        // Send the request to Mistral API
+        let base_url = options
+            .base_url
+            .as_deref()
+            .or(self.config.base_url.as_deref())
+            .unwrap_or("https://api.mistral.ai/v1");
+        let url = format!("{}/chat/completions", base_url);
+        debug!(url = %url, "Sending request to Mistral API");
+        let response = self
+            .client
+            .post(&url)
+            .headers(custom_headers(&self.config.headers))
+            .header("Authorization", format!("Bearer {}", self.config.api_key))
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| handle_http_error(e, "Mistral"))?;
+
+        // Parse the response
+        let response =
+            check_response_status(response, "Mistral", self.config.max_response_bytes).await?;
+        let request_id = response_request_id(&response);
+
+        debug!("Successfully received response from Mistral API");
+        let completion: ChatCompletionResponse =
+            read_json_response(response, "Mistral", self.config.max_response_bytes)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to parse JSON response from Mistral API");
+                    e
+                })?;
+
+        if completion.choices.is_empty() {
+            error!("Mistral API returned empty choices array");
+            return Err(RStructorError::api_error(
+                "Mistral",
+                ApiErrorKind::UnexpectedResponse {
+                    details: "No completion choices returned".to_string(),
+                },
+            ));
+        }
+
+        // Extract usage info
+        let model_name = completion
+            .model
+            .clone()
+            .unwrap_or_else(|| self.config.model.as_str().to_string());
+        let usage = completion.usage.as_ref().map(|u| {
+            TokenUsage::new(model_name, u.prompt_tokens, u.completion_tokens)
+                .with_total_tokens(u.total_tokens)
+        });
+        if let Some(reservation) = reservation {
+            reservation.record(usage.as_ref());
+        }
+
+        let message = &completion.choices[0].message;
+        trace!(finish_reason = %completion.choices[0].finish_reason, "Completion finish reason");
+
+        if let Some(content) = &message.content {
+            debug!(
+                content_len = content.len(),
+                "Successfully extracted content from response"
+            );
+            Ok(GenerateResult::new(content.clone(), usage).with_request_id(request_id))
+        } else {
+            error!("No content in Mistral API response");
+            Err(RStructorError::api_error(
+                "Mistral",
+                ApiErrorKind::UnexpectedResponse {
+                    details: "No content in response".to_string(),
+                },
+            ))
+        }
+    }
+}
+
+#[async_trait]
+impl LLMClient for MistralClient {
+    fn from_env() -> Result<Self> {
+        Self::from_env()
+    }
+
+    #[instrument(
+        name = "mistral_materialize",
+        skip(self, prompt),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn materialize<T>(&self, prompt: &str) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            prompt,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "mistral_materialize_with_schema",
+        skip(self, prompt, schema),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn materialize_with_schema<T>(&self, prompt: &str, schema: Schema) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            prompt,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "mistral_materialize_opts",
+        skip(self, prompt, options),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn materialize_opts<T>(&self, prompt: &str, options: &RequestOptions) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, options)
+                        .await
+                }
+            },
+            prompt,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    fn materialize_request_body<T>(&self, prompt: &str) -> Result<serde_json::Value>
+    where
+        T: Instructor,
+    {
+        let schema = T::schema();
+        let prompt = [ChatMessage::user(prompt)];
+        let messages = with_system_prompts(&self.config.system_prompts, &prompt);
+        let (request, _, _) =
+            self.structured_request::<T>(&messages, &schema, &RequestOptions::default());
+        Ok(serde_json::to_value(&request)?)
+    }
+
+    #[instrument(
+        name = "mistral_materialize_with_examples",
+        skip(self, prompt, examples),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len(),
+            examples_len = examples.len()
+        )
+    )]
+    async fn materialize_with_examples<T>(
+        &self,
+        prompt: &str,
+        examples: &[(String, T)],
+    ) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + Sync + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_from_messages(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            few_shot_messages(prompt, examples)?,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "mistral_materialize_with_history",
+        skip(self, messages),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            messages_len = messages.len()
+        )
+    )]
+    async fn materialize_with_history<T>(&self, messages: &[ChatMessage]) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_from_messages(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            messages.to_vec(),
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "mistral_materialize_with_metadata",
+        skip(self, prompt),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn materialize_with_metadata<T>(&self, prompt: &str) -> Result<MaterializeResult<T>>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            prompt,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(MaterializeResult::new(output.data, output.usage)
+            .with_attempts(output.attempts)
+            .with_request_id(output.request_id))
+    }
+
+    #[instrument(
+        name = "mistral_generate",
+        skip(self, prompt),
+        fields(
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        let result = self.generate_with_metadata(prompt).await?;
+        Ok(result.text)
+    }
+
+    #[instrument(
+        name = "mistral_generate_with_metadata",
+        skip(self, prompt),
+        fields(
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn generate_with_metadata(&self, prompt: &str) -> Result<GenerateResult> {
+        self.generate_with_messages(&[ChatMessage::user(prompt)])
+            .await
+    }
+
+    #[instrument(
+        name = "mistral_generate_opts",
+        skip(self, prompt, options),
+        fields(
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn generate_opts(&self, prompt: &str, options: &RequestOptions) -> Result<String> {
+        let result = self
+            .generate_internal(&[ChatMessage::user(prompt)], options)
+            .await?;
+        Ok(result.text)
+    }
+
+    #[instrument(
+        name = "mistral_generate_with_messages",
+        skip(self, messages),
+        fields(
+            model = %self.config.model.as_str(),
+            history_len = messages.len()
+        )
+    )]
+    async fn generate_with_messages(&self, messages: &[ChatMessage]) -> Result<GenerateResult> {
+        self.generate_internal(messages, &RequestOptions::default())
+            .await
+    }
+
+    /// Fetch available models from Mistral's API.
+    ///
+    /// Returns the models (including fine-tunes) available for chat completions.
+    async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        let base_url = self
+            .config
+            .base_url
+            .as_deref()
+            .unwrap_or("https://api.mistral.ai/v1");
+        let url = format!("{}/models", base_url);
+
+        debug!(url = %url, "Fetching available models from Mistral");
+
+        let response = self
+            .client
+            .get(&url)
+            .headers(custom_headers(&self.config.headers))
+            .header("Authorization", format!("Bearer {}", self.config.api_key))
+            .header("Content-Type", "application/json")
+            .send()
+            .await
+            .map_err(|e| handle_http_error(e, "Mistral"))?;
+
+        let response =
+            check_response_status(response, "Mistral", self.config.max_response_bytes).await?;
+
+        let json: serde_json::Value =
+            read_json_response(response, "Mistral", self.config.max_response_bytes)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to parse models response from Mistral");
+                    e
+                })?;
+
+        let models = json
+            .get("data")
+            .and_then(|data| data.as_array())
+            .map(|models_array| {
+                models_array
+                    .iter()
+                    .filter_map(|model| {
+                        let id = model.get("id").and_then(|id| id.as_str())?;
+                        // Filter out embedding and moderation models
+                        let chat = model
+                            .pointer("/capabilities/completion_chat")
+                            .and_then(|chat| chat.as_bool())
+                            .unwrap_or(true);
+                        chat.then(|| ModelInfo {
+                            id: id.to_string(),
+                            name: model
+                                .get("name")
+                                .and_then(|name| name.as_str())
+                                .map(str::to_string),
+                            description: model
+                                .get("description")
+                                .and_then(|d| d.as_str())
+                                .map(str::to_string),
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        debug!(count = models.len(), "Fetched Mistral models");
+        Ok(models)
+    }
+}
@@ -1,5 +1,9 @@
+mod capabilities;
 pub mod client;
 mod messages;
+mod options;
+pub mod pricing;
+mod retry;
 pub mod usage;
 mod utils;
 
@@ -9,12 +13,21 @@ pub mod anthropic;
 pub mod gemini;
 #[cfg(feature = "grok")]
 pub mod grok;
+#[cfg(feature = "mistral")]
+pub mod mistral;
+#[cfg(feature = "ollama")]
+pub mod ollama;
 #[cfg(feature = "openai")]
 pub mod openai;
 
 pub use client::{LLMClient, MediaFile};
 pub use messages::{ChatMessage, ChatRole, MaterializeInternalOutput, ValidationFailureContext};
-pub use usage::{GenerateResult, MaterializeResult, TokenUsage};
+pub use options::RequestOptions;
+pub use pricing::{Budget, BudgetReservation, ModelPricing, PricingTable};
+pub use retry::RetryPolicy;
+pub use usage::{
+    BatchItem, GenerateResult, MaterializeResult, TokenUsage, UsageTotals, UsageTracker,
+};
 
 /// Information about an available model from an LLM provider.
 ///
@@ -44,9 +57,18 @@ pub struct ModelInfo {
     /// Description of the model's capabilities
     pub description: Option<String>,
 }
+pub(crate) use capabilities::{
+    count_tokens, estimated_usage, fit_context_window, reserved_tokens, resolve_thinking_level,
+};
 pub(crate) use utils::{
-    ResponseFormat, check_response_status, generate_with_retry_with_history, handle_http_error,
-    parse_validate_and_create_output, prepare_strict_schema,
+    ResponseFormat, check_response_status, clamp_sampling_param, custom_headers, dry_run_output,
+    effective_penalty, effective_top_p, few_shot_messages, generate_with_retry_from_messages,
+    generate_with_retry_with_history, handle_http_error, is_client_managed_header,
+    json_mode_instruction, log_schema_once, parse_and_validate_response,
+    parse_validate_and_create_output, prepare_gemini_schema, prepare_gemini_schema_with_report,
+    prepare_strict_schema, prepare_strict_schema_with_report, read_json_response,
+    response_request_id, sse_data_stream, unwrap_value_root, with_system_prompts,
+    wrap_non_object_root,
 };
 
 /// Thinking level configuration for models that support extended reasoning.
@@ -60,6 +82,10 @@ pub(crate) use utils::{
 /// - **Gemini 3**: Supports `Minimal`, `Low`, `Medium`, `High` (Flash) or `Low`, `High` (Pro)
 /// - **Anthropic (Claude 4.x)**: Thinking is enabled via budget tokens when level is not `Off`
 ///
+/// Levels a model doesn't accept are adjusted when the request is built, with a
+/// `tracing` warning: e.g. `Minimal` becomes `Low` on Gemini 3 Pro, and levels set on
+/// models without thinking controls are ignored.
+///
 /// # Examples
 ///
 /// ```rust
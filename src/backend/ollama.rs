@@ -0,0 +1,968 @@
+use async_trait::async_trait;
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::time::Duration;
+use tracing::{debug, error, info, instrument, trace};
+
+use crate::backend::{
+    Budget, ChatMessage, GenerateResult, LLMClient, MaterializeInternalOutput, MaterializeResult,
+    ModelInfo, RequestOptions, RetryPolicy, TokenUsage, ValidationFailureContext,
+    check_response_status, custom_headers, dry_run_output, effective_top_p, estimated_usage,
+    few_shot_messages, fit_context_window, generate_with_retry_from_messages,
+    generate_with_retry_with_history, handle_http_error, json_mode_instruction, log_schema_once,
+    parse_validate_and_create_output, read_json_response, reserved_tokens, unwrap_value_root,
+    with_system_prompts, wrap_non_object_root,
+};
+use crate::error::{ApiErrorKind, RStructorError, Result};
+use crate::model::Instructor;
+use crate::schema::Schema;
+
+/// Address of a local Ollama server, used when no base URL is configured
+const DEFAULT_BASE_URL: &str = "http://localhost:11434";
+
+/// Ollama models available for completion
+///
+/// These are convenience variants for popular models in the
+/// [Ollama library](https://ollama.com/library); the model must already be pulled
+/// (`ollama pull llama3.2`). Any other model or tag works through `Custom`:
+///
+/// ```rust
+/// use rstructor::OllamaModel;
+/// use std::str::FromStr;
+///
+/// // Using Custom variant
+/// let model = OllamaModel::Custom("qwen2.5:14b".to_string());
+///
+/// // Using FromStr (useful for config files)
+/// let model = OllamaModel::from_str("qwen2.5:14b").unwrap();
+///
+/// // Or use the convenience method
+/// let model = OllamaModel::from_string("qwen2.5:14b");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Model {
+    /// Llama 3.2 (3B, Meta's small general-purpose model)
+    Llama32,
+    /// Llama 3.1 (8B)
+    Llama31,
+    /// Qwen 2.5 (7B, strong at following JSON formats)
+    Qwen25,
+    /// Gemma 3 (4B)
+    Gemma3,
+    /// Mistral (7B)
+    Mistral,
+    /// Phi-4 (14B)
+    Phi4,
+    /// Custom model name or tag (for any other pulled model)
+    Custom(String),
+}
+
+impl Model {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Model::Llama32 => "llama3.2",
+            Model::Llama31 => "llama3.1",
+            Model::Qwen25 => "qwen2.5",
+            Model::Gemma3 => "gemma3",
+            Model::Mistral => "mistral",
+            Model::Phi4 => "phi4",
+            Model::Custom(name) => name,
+        }
+    }
+
+    /// Create a model from a string. This is a convenience method that always succeeds.
+    ///
+    /// If the string matches a known model variant, it returns that variant.
+    /// Otherwise, it returns `Custom(name)`.
+    pub fn from_string(name: impl Into<String>) -> Self {
+        let name = name.into();
+        match name.as_str() {
+            "llama3.2" => Model::Llama32,
+            "llama3.1" => Model::Llama31,
+            "qwen2.5" => Model::Qwen25,
+            "gemma3" => Model::Gemma3,
+            "mistral" => Model::Mistral,
+            "phi4" => Model::Phi4,
+            _ => Model::Custom(name),
+        }
+    }
+
+    /// Parse a model name, rejecting names that don't match a known variant.
+    ///
+    /// Unlike [`from_string`](Self::from_string), an unknown name returns an
+    /// `InvalidModel` error instead of becoming `Custom`, so typos in configuration
+    /// are caught before the first request.
+    ///
+    /// ```rust
+    /// use rstructor::OllamaModel;
+    ///
+    /// assert_eq!(OllamaModel::try_known("llama3.2").unwrap(), OllamaModel::Llama32);
+    /// assert!(OllamaModel::try_known("llama3.22").is_err());
+    /// ```
+    pub fn try_known(name: &str) -> Result<Self> {
+        match Self::from_string(name) {
+            Model::Custom(_) => Err(RStructorError::api_error(
+                "Ollama",
+                ApiErrorKind::InvalidModel {
+                    model: name.to_string(),
+                    suggestion: None,
+                },
+            )),
+            model => Ok(model),
+        }
+    }
+}
+
+impl FromStr for Model {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(Model::from_string(s))
+    }
+}
+
+impl From<&str> for Model {
+    fn from(s: &str) -> Self {
+        Model::from_string(s)
+    }
+}
+
+impl From<String> for Model {
+    fn from(s: String) -> Self {
+        Model::from_string(s)
+    }
+}
+
+/// Configuration for the Ollama client
+#[derive(Debug, Clone)]
+pub struct OllamaConfig {
+    pub model: Model,
+    pub temperature: f32,
+    pub max_tokens: Option<u32>,
+    pub timeout: Option<Duration>,
+    /// Upper bound on the total time of a `materialize` call, including retries
+    pub deadline: Option<Duration>,
+    pub max_retries: Option<usize>,
+    /// Waits between retry attempts
+    pub retry_policy: RetryPolicy,
+    /// Extra headers sent with every request
+    pub headers: HeaderMap,
+    /// Sequences that end generation when produced (empty = none)
+    pub stop_sequences: Vec<String>,
+    /// Nucleus sampling probability mass (None = provider default)
+    pub top_p: Option<f32>,
+    /// Address of the Ollama server, without a trailing slash
+    /// Defaults to "http://localhost:11434" if not set
+    pub base_url: Option<String>,
+    /// Cumulative spending limit shared across clones of this client
+    pub budget: Option<Budget>,
+    /// Strip stray control characters from responses before parsing
+    pub lenient_parsing: bool,
+    /// Drop the oldest messages when a conversation exceeds the model's context window
+    pub auto_truncate: bool,
+    /// Log each type's schema once, at debug level
+    pub log_schemas: bool,
+    /// System instructions sent ahead of every request, in the order they were added
+    pub system_prompts: Vec<String>,
+    /// Return schema-shaped placeholder data instead of calling the API
+    pub dry_run: bool,
+    /// Largest response body to read, in bytes (None = no limit)
+    pub max_response_bytes: Option<usize>,
+}
+
+/// Ollama client for generating completions with locally served models
+pub struct OllamaClient {
+    config: OllamaConfig,
+    client: reqwest::Client,
+}
+
+// Ollama `/api/chat` request and response structures
+#[derive(Debug, Serialize)]
+struct OllamaChatMessage {
+    role: String,
+    content: String,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatRequest {
+    model: String,
+    messages: Vec<OllamaChatMessage>,
+    stream: bool,
+    /// `"json"` constrains the output to valid JSON (but not to a schema)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<&'static str>,
+    options: ModelOptions,
+}
+
+/// Sampling parameters, sent under `options`
+#[derive(Debug, Serialize)]
+struct ModelOptions {
+    temperature: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    num_predict: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    stop: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct ResponseMessage {
+    role: String,
+    #[serde(default)]
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatResponse {
+    model: Option<String>,
+    message: Option<ResponseMessage>,
+    #[serde(default)]
+    done_reason: Option<String>,
+    #[serde(default)]
+    prompt_eval_count: Option<u64>,
+    #[serde(default)]
+    eval_count: Option<u64>,
+}
+
+impl ChatResponse {
+    /// Token usage, if Ollama reported counts (it omits them for cached prompts)
+    fn usage(&self, fallback_model: &str) -> Option<TokenUsage> {
+        if self.prompt_eval_count.is_none() && self.eval_count.is_none() {
+            return None;
+        }
+        let model = self.model.as_deref().unwrap_or(fallback_model);
+        Some(TokenUsage::new(
+            model,
+            self.prompt_eval_count.unwrap_or(0),
+            self.eval_count.unwrap_or(0),
+        ))
+    }
+}
+
+/// Read the server address from `OLLAMA_HOST`, the variable the Ollama CLI itself uses.
+///
+/// The CLI accepts a bare `host:port`, so a missing scheme means `http`.
+fn base_url_from_host(host: &str) -> String {
+    let host = host.trim().trim_end_matches('/');
+    if host.contains("://") {
+        host.to_string()
+    } else {
+        format!("http://{}", host)
+    }
+}
+
+impl OllamaClient {
+    /// Create a new Ollama client for the server at `http://localhost:11434`.
+    ///
+    /// Ollama needs no API key; use [`base_url`](Self::base_url) for a server elsewhere.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rstructor::OllamaClient;
+    /// let client = OllamaClient::new().model("qwen2.5");
+    /// ```
+    #[instrument(name = "ollama_client_new", fields(model = ?Model::Llama32))]
+    pub fn new() -> Self {
+        info!("Creating new Ollama client");
+        let config = OllamaConfig {
+            model: Model::Llama32, // Default to Llama 3.2
+            temperature: 0.0,
+            max_tokens: None,
+            timeout: None,              // Default: no timeout (uses reqwest's default)
+            deadline: None,             // Default: no overall deadline
+            max_retries: Some(3),       // Default: 3 retries with error feedback
+            base_url: None,             // Default: local server on the standard port
+            budget: None,               // Default: no spending limit
+            lenient_parsing: false,     // Default: strict JSON parsing
+            auto_truncate: false,       // Default: send conversations as-is
+            log_schemas: false,         // Default: don't log schemas
+            system_prompts: Vec::new(), // Default: no system instructions
+            dry_run: false,             // Default: call the server
+            max_response_bytes: None,   // Default: no limit
+            retry_policy: RetryPolicy::default(), // Default: exponential backoff from 500ms
+            headers: HeaderMap::new(),  // Default: no extra headers
+            stop_sequences: Vec::new(), // Default: no stop sequences
+            top_p: None,                // Default: provider's default
+        };
+
+        debug!("Ollama client created with default configuration");
+        Self {
+            config,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Create a new Ollama client, reading the server address from `OLLAMA_HOST`.
+    ///
+    /// `OLLAMA_HOST` is optional (as it is for the Ollama CLI): without it the client
+    /// talks to `http://localhost:11434`. A value without a scheme, such as
+    /// `0.0.0.0:11434`, is treated as `http`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rstructor::OllamaClient;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OllamaClient::from_env()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(name = "ollama_client_from_env", fields(model = ?Model::Llama32))]
+    pub fn from_env() -> Result<Self> {
+        let mut client = Self::new();
+        if let Ok(host) = std::env::var("OLLAMA_HOST")
+            && !host.trim().is_empty()
+        {
+            client.config.base_url = Some(base_url_from_host(&host));
+        }
+        Ok(client)
+    }
+
+    // Builder methods are generated by the macro below
+}
+
+impl Default for OllamaClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convert conversation history to Ollama's message format
+fn api_messages(messages: &[ChatMessage]) -> Vec<OllamaChatMessage> {
+    messages
+        .iter()
+        .map(|msg| OllamaChatMessage {
+            role: msg.role.as_str().to_string(),
+            content: msg.content.clone(),
+        })
+        .collect()
+}
+
+impl OllamaClient {
+    /// Server address: the per-call override, then the configured one, then localhost
+    fn server_url<'a>(&'a self, options: &'a RequestOptions) -> &'a str {
+        options
+            .base_url
+            .as_deref()
+            .or(self.config.base_url.as_deref())
+            .unwrap_or(DEFAULT_BASE_URL)
+    }
+
+    fn model_options(&self, options: &RequestOptions) -> ModelOptions {
+        ModelOptions {
+            temperature: options.temperature.unwrap_or(self.config.temperature),
+            num_predict: options.max_tokens.or(self.config.max_tokens),
+            top_p: effective_top_p(options, self.config.top_p),
+            stop: options
+                .stop
+                .clone()
+                .unwrap_or_else(|| self.config.stop_sequences.clone()),
+        }
+    }
+
+    /// Build the JSON-mode request for a conversation, returning it together with the
+    /// schema given to the model and whether its root was wrapped in an object.
+    ///
+    /// Ollama has no strict structured outputs, so the schema goes in a system message
+    /// ahead of the conversation, where it stays in place across retries.
+    fn structured_request<T: Instructor>(
+        &self,
+        messages: &[ChatMessage],
+        schema: &Schema,
+        options: &RequestOptions,
+    ) -> (ChatRequest, serde_json::Value, bool) {
+        let schema_name = T::schema_name().unwrap_or_else(|| "output".to_string());
+        trace!(schema_name = schema_name, "Retrieved JSON schema for type");
+
+        // JSON mode produces an object, so enums get wrapped in `{"value": ...}`
+        let mut schema_json = schema.to_json();
+        let wrapped_root = wrap_non_object_root(&mut schema_json);
+
+        let mut with_schema = vec![json_mode_instruction(&schema_json)];
+        with_schema.extend_from_slice(messages);
+
+        debug!(
+            "Building Ollama API request in JSON mode (history_len={})",
+            messages.len()
+        );
+        let request = ChatRequest {
+            model: self.config.model.as_str().to_string(),
+            messages: api_messages(&with_schema),
+            stream: false,
+            format: Some("json"),
+            options: self.model_options(options),
+        };
+
+        (request, schema_json, wrapped_root)
+    }
+
+    /// Send a chat request and decode the response
+    async fn send(&self, request: &ChatRequest, options: &RequestOptions) -> Result<ChatResponse> {
+        let url = format!("{}/api/chat", self.server_url(options));
+        debug!(url = %url, "Sending request to Ollama API");
+        let response = self
+            .client
+            .post(&url)
+            .headers(custom_headers(&self.config.headers))
+            .header("Content-Type", "application/json")
+            .json(request)
+            .send()
+            .await
+            .map_err(|e| handle_http_error(e, "Ollama"))?;
+
+        let response =
+            check_response_status(response, "Ollama", self.config.max_response_bytes).await?;
+
+        debug!("Successfully received response from Ollama API");
+        read_json_response(response, "Ollama", self.config.max_response_bytes)
+            .await
+            .map_err(|e| {
+                error!(error = %e, "Failed to parse JSON response from Ollama API");
+                e
+            })
+    }
+
+    /// Internal implementation of materialize (without retry logic)
+    /// Accepts conversation history for multi-turn interactions.
+    /// Returns the data, raw response, and optional usage info.
+    ///
+    /// Uses Ollama's `format: "json"` with the schema in the prompt; the response is
+    /// checked against `T` when it's parsed, and mismatches are retried with feedback.
+    async fn materialize_internal<T>(
+        &self,
+        messages: &[ChatMessage],
+        schema: &Schema,
+        options: &RequestOptions,
+    ) -> std::result::Result<
+        MaterializeInternalOutput<T>,
+        (RStructorError, Option<ValidationFailureContext>),
+    >
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        if self.config.dry_run {
+            return dry_run_output::<T>(schema);
+        }
+        let messages = with_system_prompts(&self.config.system_prompts, messages);
+        let fitted = fit_context_window(
+            &messages,
+            self.config.model.as_str(),
+            reserved_tokens(
+                self.config.model.as_str(),
+                Some(schema),
+                options.max_tokens.or(self.config.max_tokens),
+            ),
+            self.config.auto_truncate,
+        );
+        let messages: &[ChatMessage] = &fitted;
+        let reservation = self
+            .config
+            .budget
+            .as_ref()
+            .map(|budget| {
+                budget.check(&estimated_usage(
+                    self.config.model.as_str(),
+                    messages,
+                    Some(schema),
+                    options.max_tokens.or(self.config.max_tokens),
+                ))
+            })
+            .transpose()
+            .map_err(|e| (e, None))?;
+
+        info!("Generating structured response with Ollama (JSON mode)");
+
+        let (request, schema_json, wrapped_root) =
+            self.structured_request::<T>(messages, schema, options);
+        if self.config.log_schemas {
+            log_schema_once::<T>("Ollama", &schema_json);
+        }
+
+        let completion = self.send(&request, options).await.map_err(|e| (e, None))?;
+
+        let usage = completion.usage(self.config.model.as_str());
+        if let Some(reservation) = reservation {
+            reservation.record(usage.as_ref());
+        }
+        trace!(done_reason = ?completion.done_reason, "Completion done reason");
+
+        match completion.message {
+            Some(message) if !message.content.is_empty() => {
+                let raw_response = if wrapped_root {
+                    unwrap_value_root(message.content, self.config.lenient_parsing)
+                } else {
+                    message.content
+                };
+                debug!(
+                    content_len = raw_response.len(),
+                    "Received JSON mode response"
+                );
+
+                // Parse and validate the response using shared utility
+                trace!(json = %raw_response, "Parsing JSON mode response");
+                parse_validate_and_create_output(raw_response, usage, self.config.lenient_parsing)
+            }
+            _ => {
+                error!("No content in Ollama API response");
+                Err((
+                    RStructorError::api_error(
+                        "Ollama",
+                        ApiErrorKind::UnexpectedResponse {
+                            details: "No content in response".to_string(),
+                        },
+                    ),
+                    None,
+                ))
+            }
+        }
+    }
+}
+
+// Generate builder methods using macro
+crate::impl_client_builder_methods! {
+    client_type: OllamaClient,
+    config_type: OllamaConfig,
+    model_type: Model,
+    provider_name: "Ollama"
+}
+
+impl OllamaClient {
+    /// Set the address of the Ollama server.
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - Server address without trailing slash (e.g., "http://gpu-box:11434")
+    #[tracing::instrument(skip(self, base_url))]
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        let base_url_str = base_url.into();
+        tracing::debug!(
+            previous_base_url = ?self.config.base_url,
+            new_base_url = %base_url_str,
+            "Setting custom base URL"
+        );
+        self.config.base_url = Some(base_url_str);
+        self
+    }
+
+    /// Internal implementation of raw text generation for a conversation, with
+    /// per-call overrides applied over the client configuration.
+    async fn generate_internal(
+        &self,
+        messages: &[ChatMessage],
+        options: &RequestOptions,
+    ) -> Result<GenerateResult> {
+        if self.config.dry_run {
+            return Ok(GenerateResult::new(String::new(), None));
+        }
+        let messages = with_system_prompts(&self.config.system_prompts, messages);
+        let fitted = fit_context_window(
+            &messages,
+            self.config.model.as_str(),
+            reserved_tokens(
+                self.config.model.as_str(),
+                None,
+                options.max_tokens.or(self.config.max_tokens),
+            ),
+            self.config.auto_truncate,
+        );
+        let messages: &[ChatMessage] = &fitted;
+        let reservation = self
+            .config
+            .budget
+            .as_ref()
+            .map(|budget| {
+                budget.check(&estimated_usage(
+                    self.config.model.as_str(),
+                    messages,
+                    None,
+                    options.max_tokens.or(self.config.max_tokens),
+                ))
+            })
+            .transpose()?;
+
+        info!("Generating raw text response with Ollama");
+
+        let request = ChatRequest {
+            model: self.config.model.as_str().to_string(),
+            messages: api_messages(messages),
+            stream: false,
+            format: None,
+            options: self.model_options(options),
+        };
+        let completion = self.send(&request, options).await?;
+
+        let usage = completion.usage(self.config.model.as_str());
+        if let Some(reservation) = reservation {
+            reservation.record(usage.as_ref());
+        }
+
+        match completion.message {
+            Some(message) => {
+                debug!(
+                    content_len = message.content.len(),
+                    "Successfully extracted content from response"
+                );
+                Ok(GenerateResult::new(message.content, usage))
+            }
+            None => {
+                error!("No message in Ollama API response");
+                Err(RStructorError::api_error(
+                    "Ollama",
+                    ApiErrorKind::UnexpectedResponse {
+                        details: "No message in response".to_string(),
+                    },
+                ))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl LLMClient for OllamaClient {
+    fn from_env() -> Result<Self> {
+        Self::from_env()
+    }
+
+    #[instrument(
+        name = "ollama_materialize",
+        skip(self, prompt),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn materialize<T>(&self, prompt: &str) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            prompt,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "ollama_materialize_with_schema",
+        skip(self, prompt, schema),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn materialize_with_schema<T>(&self, prompt: &str, schema: Schema) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            prompt,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "ollama_materialize_opts",
+        skip(self, prompt, options),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn materialize_opts<T>(&self, prompt: &str, options: &RequestOptions) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, options)
+                        .await
+                }
+            },
+            prompt,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    fn materialize_request_body<T>(&self, prompt: &str) -> Result<serde_json::Value>
+    where
+        T: Instructor,
+    {
+        let schema = T::schema();
+        let prompt = [ChatMessage::user(prompt)];
+        let messages = with_system_prompts(&self.config.system_prompts, &prompt);
+        let (request, _, _) =
+            self.structured_request::<T>(&messages, &schema, &RequestOptions::default());
+        Ok(serde_json::to_value(&request)?)
+    }
+
+    #[instrument(
+        name = "ollama_materialize_with_examples",
+        skip(self, prompt, examples),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len(),
+            examples_len = examples.len()
+        )
+    )]
+    async fn materialize_with_examples<T>(
+        &self,
+        prompt: &str,
+        examples: &[(String, T)],
+    ) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + Sync + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_from_messages(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            few_shot_messages(prompt, examples)?,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "ollama_materialize_with_history",
+        skip(self, messages),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            messages_len = messages.len()
+        )
+    )]
+    async fn materialize_with_history<T>(&self, messages: &[ChatMessage]) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_from_messages(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            messages.to_vec(),
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "ollama_materialize_with_metadata",
+        skip(self, prompt),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn materialize_with_metadata<T>(&self, prompt: &str) -> Result<MaterializeResult<T>>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            prompt,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(MaterializeResult::new(output.data, output.usage).with_attempts(output.attempts))
+    }
+
+    #[instrument(
+        name = "ollama_generate",
+        skip(self, prompt),
+        fields(
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        let result = self.generate_with_metadata(prompt).await?;
+        Ok(result.text)
+    }
+
+    #[instrument(
+        name = "ollama_generate_with_metadata",
+        skip(self, prompt),
+        fields(
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn generate_with_metadata(&self, prompt: &str) -> Result<GenerateResult> {
+        self.generate_with_messages(&[ChatMessage::user(prompt)])
+            .await
+    }
+
+    #[instrument(
+        name = "ollama_generate_opts",
+        skip(self, prompt, options),
+        fields(
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn generate_opts(&self, prompt: &str, options: &RequestOptions) -> Result<String> {
+        let result = self
+            .generate_internal(&[ChatMessage::user(prompt)], options)
+            .await?;
+        Ok(result.text)
+    }
+
+    #[instrument(
+        name = "ollama_generate_with_messages",
+        skip(self, messages),
+        fields(
+            model = %self.config.model.as_str(),
+            history_len = messages.len()
+        )
+    )]
+    async fn generate_with_messages(&self, messages: &[ChatMessage]) -> Result<GenerateResult> {
+        self.generate_internal(messages, &RequestOptions::default())
+            .await
+    }
+
+    /// Fetch the models pulled on the Ollama server (`/api/tags`).
+    async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        let url = format!("{}/api/tags", self.server_url(&RequestOptions::default()));
+
+        debug!(url = %url, "Fetching available models from Ollama");
+
+        let response = self
+            .client
+            .get(&url)
+            .headers(custom_headers(&self.config.headers))
+            .send()
+            .await
+            .map_err(|e| handle_http_error(e, "Ollama"))?;
+
+        let response =
+            check_response_status(response, "Ollama", self.config.max_response_bytes).await?;
+
+        let json: serde_json::Value =
+            read_json_response(response, "Ollama", self.config.max_response_bytes)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to parse models response from Ollama");
+                    e
+                })?;
+
+        let models = json
+            .get("models")
+            .and_then(|models| models.as_array())
+            .map(|models_array| {
+                models_array
+                    .iter()
+                    .filter_map(|model| {
+                        let id = model.get("name").and_then(|name| name.as_str())?;
+                        let description = model
+                            .pointer("/details/parameter_size")
+                            .and_then(|size| size.as_str())
+                            .map(|size| format!("{} parameters", size));
+                        Some(ModelInfo {
+                            id: id.to_string(),
+                            name: None,
+                            description,
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        debug!(count = models.len(), "Fetched Ollama models");
+        Ok(models)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ollama_host_without_scheme_is_http() {
+        assert_eq!(base_url_from_host("0.0.0.0:11434"), "http://0.0.0.0:11434");
+        assert_eq!(
+            base_url_from_host("https://ollama.example.com/"),
+            "https://ollama.example.com"
+        );
+    }
+
+    #[test]
+    fn usage_is_absent_without_counts() {
+        let response: ChatResponse = serde_json::from_value(serde_json::json!({
+            "model": "llama3.2",
+            "message": { "role": "assistant", "content": "{}" },
+            "done": true
+        }))
+        .unwrap();
+        assert!(response.usage("llama3.2").is_none());
+    }
+}
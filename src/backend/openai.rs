@@ -1,18 +1,27 @@
 use async_trait::async_trait;
+use futures::stream::{BoxStream, StreamExt};
+use reqwest::header::HeaderMap;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
-use std::time::Duration;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info, instrument, trace, warn};
 
 use crate::backend::{
-    ChatMessage, GenerateResult, LLMClient, MaterializeInternalOutput, MaterializeResult,
-    ModelInfo, ResponseFormat, ThinkingLevel, TokenUsage, ValidationFailureContext,
-    check_response_status, generate_with_retry_with_history, handle_http_error,
-    parse_validate_and_create_output, prepare_strict_schema,
+    Budget, BudgetReservation, ChatMessage, ChatRole, GenerateResult, LLMClient,
+    MaterializeInternalOutput, MaterializeResult, ModelInfo, RequestOptions, ResponseFormat,
+    RetryPolicy, ThinkingLevel, TokenUsage, ValidationFailureContext, check_response_status,
+    clamp_sampling_param, custom_headers, dry_run_output, effective_penalty, effective_top_p,
+    estimated_usage, few_shot_messages, fit_context_window, generate_with_retry_from_messages,
+    generate_with_retry_with_history, handle_http_error, log_schema_once,
+    parse_validate_and_create_output, prepare_strict_schema, read_json_response, reserved_tokens,
+    resolve_thinking_level, response_request_id, sse_data_stream, unwrap_value_root,
+    with_system_prompts, wrap_non_object_root,
 };
 use crate::error::{ApiErrorKind, RStructorError, Result};
 use crate::model::Instructor;
+use crate::schema::Schema;
 
 /// OpenAI models available for completion
 ///
@@ -111,6 +120,31 @@ impl Model {
             _ => Model::Custom(name),
         }
     }
+
+    /// Parse a model name, rejecting names that don't match a known variant.
+    ///
+    /// Unlike [`from_string`](Self::from_string), an unknown name returns an
+    /// `InvalidModel` error instead of becoming `Custom`, so typos in configuration
+    /// are caught before the first request.
+    ///
+    /// ```rust
+    /// use rstructor::OpenAIModel;
+    ///
+    /// assert_eq!(OpenAIModel::try_known("gpt-4o").unwrap(), OpenAIModel::Gpt4O);
+    /// assert!(OpenAIModel::try_known("gpt-4o-typo").is_err());
+    /// ```
+    pub fn try_known(name: &str) -> Result<Self> {
+        match Self::from_string(name) {
+            Model::Custom(_) => Err(RStructorError::api_error(
+                "OpenAI",
+                ApiErrorKind::InvalidModel {
+                    model: name.to_string(),
+                    suggestion: None,
+                },
+            )),
+            model => Ok(model),
+        }
+    }
 }
 
 impl FromStr for Model {
@@ -141,19 +175,119 @@ pub struct OpenAIConfig {
     pub temperature: f32,
     pub max_tokens: Option<u32>,
     pub timeout: Option<Duration>,
+    /// Upper bound on the total time of a `materialize` call, including retries
+    pub deadline: Option<Duration>,
     pub max_retries: Option<usize>,
+    /// Waits between retry attempts
+    pub retry_policy: RetryPolicy,
+    /// Extra headers sent with every request
+    pub headers: HeaderMap,
+    /// Sequences that end generation when produced (empty = none)
+    pub stop_sequences: Vec<String>,
+    /// Nucleus sampling probability mass (None = provider default)
+    pub top_p: Option<f32>,
+    /// Penalty on tokens by how often they have appeared so far (-2.0 to 2.0)
+    pub frequency_penalty: Option<f32>,
+    /// Penalty on tokens that have appeared at all so far (-2.0 to 2.0)
+    pub presence_penalty: Option<f32>,
     /// Custom base URL for OpenAI-compatible APIs (e.g., local LLMs, proxy endpoints)
     /// Defaults to "https://api.openai.com/v1" if not set
     pub base_url: Option<String>,
     /// Thinking level for GPT-5.x models (reasoning effort)
     /// Controls the depth of reasoning applied to prompts
     pub thinking_level: Option<ThinkingLevel>,
+    /// Cumulative spending limit shared across clones of this client
+    pub budget: Option<Budget>,
+    /// Strip stray control characters from responses before parsing
+    pub lenient_parsing: bool,
+    /// Drop the oldest messages when a conversation exceeds the model's context window
+    pub auto_truncate: bool,
+    /// Log each type's prepared schema once, at debug level
+    pub log_schemas: bool,
+    /// System instructions sent ahead of every request, in the order they were added
+    pub system_prompts: Vec<String>,
+    /// Return schema-shaped placeholder data instead of calling the API
+    pub dry_run: bool,
+    /// Largest response body to read, in bytes (None = no limit)
+    pub max_response_bytes: Option<usize>,
+    /// Processing tier sent as `service_tier` (e.g. "auto", "default", "flex")
+    pub service_tier: Option<String>,
+    /// Expected output sent as `prediction` with structured requests
+    pub predicted_output: Option<serde_json::Value>,
+    /// How long `list_models` results are cached (None = always fetch)
+    pub models_cache_ttl: Option<Duration>,
+    /// Send system messages with the `developer` role (None = detect from the model)
+    pub developer_role: Option<bool>,
+    /// Azure OpenAI deployment to call instead of an OpenAI-style endpoint
+    pub azure: Option<AzureDeployment>,
+}
+
+/// An Azure OpenAI deployment, addressed by resource endpoint, deployment name and API version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AzureDeployment {
+    /// Resource endpoint, e.g. "https://my-resource.openai.azure.com"
+    pub endpoint: String,
+    /// Deployment name chosen when the model was deployed
+    pub deployment: String,
+    /// Value sent as the `api-version` query parameter, e.g. "2024-10-21"
+    pub api_version: String,
 }
 
 /// OpenAI client for generating completions
 pub struct OpenAIClient {
     config: OpenAIConfig,
     client: reqwest::Client,
+    /// Cached `list_models` result and the time it was fetched
+    models_cache: Mutex<Option<(Instant, Vec<ModelInfo>)>>,
+}
+
+/// Model id prefixes for chat-completion models
+const CHAT_MODEL_PREFIXES: &[&str] = &["gpt-", "chatgpt-", "o1", "o3", "o4"];
+
+/// Id fragments of non-chat variants that share a chat prefix (e.g. `gpt-4o-mini-tts`)
+const NON_CHAT_MODEL_MARKERS: &[&str] = &[
+    "audio",
+    "realtime",
+    "transcribe",
+    "tts",
+    "image",
+    "instruct",
+    "embedding",
+    "moderation",
+];
+
+/// Whether a model id from `/models` refers to a chat-completion model
+fn is_chat_model(id: &str) -> bool {
+    CHAT_MODEL_PREFIXES
+        .iter()
+        .any(|prefix| id.starts_with(prefix))
+        && !NON_CHAT_MODEL_MARKERS
+            .iter()
+            .any(|marker| id.contains(marker))
+}
+
+/// Model id prefixes for models that expect `developer` instead of `system` messages
+const DEVELOPER_ROLE_MODEL_PREFIXES: &[&str] = &["gpt-5", "o1", "o3", "o4"];
+
+/// Whether a model expects system instructions under the `developer` role.
+///
+/// `o1-mini` and `o1-preview` predate developer messages and are excluded.
+fn uses_developer_role(model: &str) -> bool {
+    DEVELOPER_ROLE_MODEL_PREFIXES
+        .iter()
+        .any(|prefix| model.starts_with(prefix))
+        && !model.starts_with("o1-mini")
+        && !model.starts_with("o1-preview")
+}
+
+/// Model id prefixes for models that reject `max_tokens` in favor of `max_completion_tokens`
+const MAX_COMPLETION_TOKENS_MODEL_PREFIXES: &[&str] = &["gpt-5", "o1", "o3", "o4"];
+
+/// Whether a model requires `max_completion_tokens` instead of `max_tokens`
+fn uses_max_completion_tokens(model: &str) -> bool {
+    MAX_COMPLETION_TOKENS_MODEL_PREFIXES
+        .iter()
+        .any(|prefix| model.starts_with(prefix))
 }
 
 // OpenAI API request and response structures
@@ -174,9 +308,45 @@ struct ChatCompletionRequest {
     temperature: f32,
     #[serde(skip_serializing_if = "Option::is_none")]
     max_tokens: Option<u32>,
+    /// Replaces `max_tokens` for reasoning models
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_completion_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frequency_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    presence_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    stop: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    service_tier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prediction: Option<Prediction>,
     /// Reasoning effort for GPT-5.x models
     #[serde(skip_serializing_if = "Option::is_none")]
     reasoning_effort: Option<String>,
+    /// Send the response as server-sent events of deltas
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptions>,
+}
+
+/// Options for streamed responses
+#[derive(Debug, Serialize)]
+struct StreamOptions {
+    /// Send token usage in a final event with no choices
+    include_usage: bool,
+}
+
+/// Predicted output, which lets unchanged stretches of the response be accepted
+/// instead of generated
+#[derive(Debug, Serialize)]
+struct Prediction {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    content: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -194,12 +364,11 @@ struct ChatCompletionChoice {
 }
 
 #[derive(Debug, Deserialize)]
-#[allow(dead_code)]
 struct UsageInfo {
     prompt_tokens: u64,
     completion_tokens: u64,
     #[serde(default)]
-    total_tokens: u64,
+    total_tokens: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -210,6 +379,69 @@ struct ChatCompletionResponse {
     model: Option<String>,
 }
 
+/// One server-sent event of a streamed chat completion
+#[derive(Debug, Deserialize)]
+struct ChatCompletionChunk {
+    #[serde(default)]
+    choices: Vec<ChunkChoice>,
+    #[serde(default)]
+    usage: Option<UsageInfo>,
+    model: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChunkChoice {
+    #[serde(default)]
+    delta: ChunkDelta,
+    finish_reason: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ChunkDelta {
+    content: Option<String>,
+}
+
+/// Extract the text delta from a streamed event, `None` for events without any
+/// (the role preamble, the final usage event and the `[DONE]` marker).
+///
+/// The usage event, if any, settles the budget `reservation`.
+fn stream_delta(
+    event: &str,
+    reservation: &mut Option<BudgetReservation>,
+    model: &str,
+) -> Option<Result<String>> {
+    if event == "[DONE]" {
+        return None;
+    }
+    let chunk: ChatCompletionChunk = match serde_json::from_str(event) {
+        Ok(chunk) => chunk,
+        Err(e) => return Some(Err(e.into())),
+    };
+    if let Some(u) = &chunk.usage
+        && let Some(reservation) = reservation.take()
+    {
+        let model = chunk.model.as_deref().unwrap_or(model);
+        let usage = TokenUsage::new(model, u.prompt_tokens, u.completion_tokens)
+            .with_total_tokens(u.total_tokens);
+        reservation.record(Some(&usage));
+    }
+    let choice = chunk.choices.into_iter().next()?;
+    if choice.finish_reason.as_deref() == Some("content_filter") {
+        error!("OpenAI response blocked by content filter");
+        return Some(Err(RStructorError::api_error(
+            "OpenAI",
+            ApiErrorKind::ContentFiltered {
+                details: "finish_reason: content_filter".to_string(),
+            },
+        )));
+    }
+    choice
+        .delta
+        .content
+        .filter(|content| !content.is_empty())
+        .map(Ok)
+}
+
 impl OpenAIClient {
     /// Create a new OpenAI client with the provided API key.
     ///
@@ -243,16 +475,36 @@ impl OpenAIClient {
             model: Model::Gpt52, // Default to GPT-5.2 (latest GPT-5)
             temperature: 0.0,
             max_tokens: None,
-            timeout: None,        // Default: no timeout (uses reqwest's default)
-            max_retries: Some(3), // Default: 3 retries with error feedback
-            base_url: None,       // Default: use official OpenAI API
+            timeout: None,              // Default: no timeout (uses reqwest's default)
+            deadline: None,             // Default: no overall deadline
+            max_retries: Some(3),       // Default: 3 retries with error feedback
+            base_url: None,             // Default: use official OpenAI API
+            budget: None,               // Default: no spending limit
+            lenient_parsing: false,     // Default: strict JSON parsing
+            auto_truncate: false,       // Default: send conversations as-is
+            log_schemas: false,         // Default: don't log schemas
+            system_prompts: Vec::new(), // Default: no system instructions
+            dry_run: false,             // Default: call the provider
+            max_response_bytes: None,   // Default: no limit
+            service_tier: None,         // Default: use the account's default tier
+            predicted_output: None,     // Default: no prediction
+            models_cache_ttl: None,     // Default: no model list caching
+            developer_role: None,       // Default: detect from the model family
+            azure: None,                // Default: OpenAI-style URLs and bearer auth
             thinking_level: Some(ThinkingLevel::Low), // Default to Low thinking for GPT-5.x
+            retry_policy: RetryPolicy::default(), // Default: exponential backoff from 500ms
+            headers: HeaderMap::new(),  // Default: no extra headers
+            stop_sequences: Vec::new(), // Default: no stop sequences
+            top_p: None,                // Default: provider's default
+            frequency_penalty: None,    // Default: no penalty
+            presence_penalty: None,     // Default: no penalty
         };
 
         debug!("OpenAI client created with default configuration");
         Ok(Self {
             config,
             client: reqwest::Client::new(),
+            models_cache: Mutex::new(None),
         })
     }
 
@@ -284,19 +536,90 @@ impl OpenAIClient {
             model: Model::Gpt52, // Default to GPT-5.2 (latest GPT-5)
             temperature: 0.0,
             max_tokens: None,
-            timeout: None,        // Default: no timeout (uses reqwest's default)
-            max_retries: Some(3), // Default: 3 retries with error feedback
-            base_url: None,       // Default: use official OpenAI API
+            timeout: None,              // Default: no timeout (uses reqwest's default)
+            deadline: None,             // Default: no overall deadline
+            max_retries: Some(3),       // Default: 3 retries with error feedback
+            base_url: None,             // Default: use official OpenAI API
+            budget: None,               // Default: no spending limit
+            lenient_parsing: false,     // Default: strict JSON parsing
+            auto_truncate: false,       // Default: send conversations as-is
+            log_schemas: false,         // Default: don't log schemas
+            system_prompts: Vec::new(), // Default: no system instructions
+            dry_run: false,             // Default: call the provider
+            max_response_bytes: None,   // Default: no limit
+            service_tier: None,         // Default: use the account's default tier
+            predicted_output: None,     // Default: no prediction
+            models_cache_ttl: None,     // Default: no model list caching
+            developer_role: None,       // Default: detect from the model family
+            azure: None,                // Default: OpenAI-style URLs and bearer auth
             thinking_level: Some(ThinkingLevel::Low), // Default to Low thinking for GPT-5.x
+            retry_policy: RetryPolicy::default(), // Default: exponential backoff from 500ms
+            headers: HeaderMap::new(),  // Default: no extra headers
+            stop_sequences: Vec::new(), // Default: no stop sequences
+            top_p: None,                // Default: provider's default
+            frequency_penalty: None,    // Default: no penalty
+            presence_penalty: None,     // Default: no penalty
         };
 
         debug!("OpenAI client created with default configuration");
         Ok(Self {
             config,
             client: reqwest::Client::new(),
+            models_cache: Mutex::new(None),
         })
     }
 
+    /// Create a client for an Azure OpenAI deployment.
+    ///
+    /// Requests go to `{endpoint}/openai/deployments/{deployment}/chat/completions` with the
+    /// `api-version` query parameter and the key in the `api-key` header. Everything else,
+    /// including strict schema preparation, is shared with the OpenAI API.
+    ///
+    /// The model defaults to the deployment name; when the deployment is named differently,
+    /// set `.model(...)` to the deployed model so capabilities such as reasoning effort and
+    /// context window are detected correctly.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint` - Resource endpoint (e.g., "https://my-resource.openai.azure.com")
+    /// * `deployment` - Deployment name
+    /// * `api_version` - Azure OpenAI API version (e.g., "2024-10-21")
+    /// * `api_key` - Key for the Azure OpenAI resource
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use rstructor::OpenAIClient;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OpenAIClient::azure(
+    ///     "https://my-resource.openai.azure.com",
+    ///     "prod-chat",
+    ///     "2024-10-21",
+    ///     "your-azure-key",
+    /// )?
+    /// .model("gpt-4o");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(name = "openai_client_azure", skip_all)]
+    pub fn azure(
+        endpoint: impl Into<String>,
+        deployment: impl Into<String>,
+        api_version: impl Into<String>,
+        api_key: impl Into<String>,
+    ) -> Result<Self> {
+        let deployment = deployment.into();
+        let mut client = Self::new(api_key)?;
+        client.config.model = Model::from_string(deployment.clone());
+        client.config.azure = Some(AzureDeployment {
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            deployment,
+            api_version: api_version.into(),
+        });
+        debug!("OpenAI client configured for Azure OpenAI");
+        Ok(client)
+    }
+
     // Builder methods are generated by the macro below
 }
 
@@ -309,6 +632,29 @@ crate::impl_client_builder_methods! {
 }
 
 impl OpenAIClient {
+    /// Cache the result of `list_models` for the given duration.
+    ///
+    /// Repeated calls within the TTL return the cached list instead of hitting the API.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rstructor::OpenAIClient;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OpenAIClient::new("api-key")?
+    ///     .cache_models(Duration::from_secs(300));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub fn cache_models(mut self, ttl: Duration) -> Self {
+        tracing::debug!(ttl = ?ttl, "Setting model list cache TTL");
+        self.config.models_cache_ttl = Some(ttl);
+        self
+    }
+
     /// Set a custom base URL for OpenAI-compatible APIs (e.g., local LLMs, proxy endpoints).
     ///
     /// # Arguments
@@ -339,6 +685,131 @@ impl OpenAIClient {
         self
     }
 
+    /// Set the processing tier for requests (`service_tier`).
+    ///
+    /// `"flex"` trades slower responses for a much lower price, which suits large
+    /// batch extraction jobs; `"auto"` and `"default"` follow the account settings.
+    /// Left out of requests unless set.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rstructor::OpenAIClient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OpenAIClient::from_env()?.service_tier("flex");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self, tier))]
+    pub fn service_tier(mut self, tier: impl Into<String>) -> Self {
+        let tier = tier.into();
+        tracing::debug!(
+            previous = ?self.config.service_tier,
+            new = %tier,
+            "Setting service tier"
+        );
+        self.config.service_tier = Some(tier);
+        self
+    }
+
+    /// Penalize tokens by how often they have already appeared (`frequency_penalty`).
+    ///
+    /// Positive values make verbatim repetition less likely. Clamped to -2.0–2.0 and
+    /// left out of requests unless set.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rstructor::OpenAIClient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OpenAIClient::from_env()?
+    ///     .top_p(0.9)
+    ///     .frequency_penalty(0.5)
+    ///     .presence_penalty(0.2);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub fn frequency_penalty(mut self, penalty: f32) -> Self {
+        let penalty = clamp_sampling_param("frequency_penalty", penalty, -2.0, 2.0);
+        tracing::debug!(
+            previous = ?self.config.frequency_penalty,
+            new = penalty,
+            "Setting frequency penalty"
+        );
+        self.config.frequency_penalty = Some(penalty);
+        self
+    }
+
+    /// Penalize tokens that have appeared at all so far (`presence_penalty`).
+    ///
+    /// Positive values nudge the model towards new topics. Clamped to -2.0–2.0 and
+    /// left out of requests unless set.
+    #[tracing::instrument(skip(self))]
+    pub fn presence_penalty(mut self, penalty: f32) -> Self {
+        let penalty = clamp_sampling_param("presence_penalty", penalty, -2.0, 2.0);
+        tracing::debug!(
+            previous = ?self.config.presence_penalty,
+            new = penalty,
+            "Setting presence penalty"
+        );
+        self.config.presence_penalty = Some(penalty);
+        self
+    }
+
+    /// Predict the structured output to cut latency (`prediction`).
+    ///
+    /// When most of the response is known in advance, as when re-extracting or
+    /// editing a previous result, OpenAI can accept the matching stretches of the
+    /// prediction instead of generating them. Pass the value you expect back, usually
+    /// the prior result; it's serialized as JSON and sent with every `materialize`
+    /// request (but not with `generate`). Rejected prediction tokens are billed as
+    /// output, so only use a prediction that's likely to be mostly right. Supported by
+    /// the GPT-4o and GPT-4.1 families.
+    ///
+    /// A value that can't be serialized to JSON is logged and ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rstructor::{Instructor, LLMClient, OpenAIClient};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Instructor, Serialize, Deserialize)]
+    /// struct Invoice {
+    ///     number: String,
+    ///     total: f64,
+    /// }
+    ///
+    /// # async fn example(previous: Invoice) -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = OpenAIClient::from_env()?
+    ///     .model("gpt-4.1")
+    ///     .predicted_output(&previous);
+    /// let updated: Invoice = client
+    ///     .materialize("Apply the 10% discount from the attached email ...")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self, expected))]
+    pub fn predicted_output(mut self, expected: impl Serialize) -> Self {
+        match serde_json::to_value(expected) {
+            Ok(value) => {
+                tracing::debug!(
+                    replacing = self.config.predicted_output.is_some(),
+                    "Setting predicted output"
+                );
+                self.config.predicted_output = Some(value);
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Ignoring predicted output that isn't serializable");
+            }
+        }
+        self
+    }
+
     /// Set the thinking level for GPT-5.x models (reasoning effort).
     ///
     /// Controls the depth of reasoning the model applies to prompts.
@@ -377,35 +848,148 @@ impl OpenAIClient {
         self
     }
 
-    /// Internal implementation of materialize (without retry logic)
-    /// Accepts conversation history for multi-turn interactions.
-    /// Returns the data, raw response, and optional usage info.
+    /// Override the role used for system messages.
     ///
-    /// Uses OpenAI's native Structured Outputs with `response_format: json_schema`
-    /// for guaranteed schema compliance.
+    /// Newer OpenAI models (GPT-5.x and the o-series reasoning models) expect system
+    /// instructions under the `developer` role, while older models and most
+    /// OpenAI-compatible servers only understand `system`. By default the role is
+    /// chosen from the model name; use this to force one or the other.
     ///
-    /// The raw response is included to enable conversation history tracking for retries,
-    /// which improves prompt caching efficiency.
-    async fn materialize_internal<T>(
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use rstructor::OpenAIClient;
+    ///
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// // A local server hosting a model named "gpt-5" that only accepts `system`
+    /// let client = OpenAIClient::new("api-key")?
+    ///     .base_url("http://localhost:1234/v1")
+    ///     .model("gpt-5")
+    ///     .developer_role(false);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[tracing::instrument(skip(self))]
+    pub fn developer_role(mut self, enabled: bool) -> Self {
+        tracing::debug!(
+            previous = ?self.config.developer_role,
+            new = enabled,
+            "Setting developer role override"
+        );
+        self.config.developer_role = Some(enabled);
+        self
+    }
+
+    /// Role name sent to the API for a chat message role
+    fn api_role(&self, role: ChatRole) -> &'static str {
+        let developer = self
+            .config
+            .developer_role
+            .unwrap_or_else(|| uses_developer_role(self.config.model.as_str()));
+        match role {
+            ChatRole::System if developer => "developer",
+            role => role.as_str(),
+        }
+    }
+
+    /// URL of the chat completions endpoint, honoring a per-request base URL override
+    fn chat_completions_url(&self, options: &RequestOptions) -> String {
+        let base_url = options.base_url.as_deref();
+        match &self.config.azure {
+            Some(azure) => format!(
+                "{}/openai/deployments/{}/chat/completions?api-version={}",
+                base_url.unwrap_or(&azure.endpoint),
+                azure.deployment,
+                azure.api_version
+            ),
+            None => format!(
+                "{}/chat/completions",
+                base_url
+                    .or(self.config.base_url.as_deref())
+                    .unwrap_or("https://api.openai.com/v1")
+            ),
+        }
+    }
+
+    /// URL of the model listing endpoint
+    fn models_url(&self) -> String {
+        match &self.config.azure {
+            Some(azure) => format!(
+                "{}/openai/models?api-version={}",
+                azure.endpoint, azure.api_version
+            ),
+            None => format!(
+                "{}/models",
+                self.config
+                    .base_url
+                    .as_deref()
+                    .unwrap_or("https://api.openai.com/v1")
+            ),
+        }
+    }
+
+    /// Attach credentials: the `api-key` header for Azure, a bearer token otherwise
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        if self.config.azure.is_some() {
+            request.header("api-key", &self.config.api_key)
+        } else {
+            request.header("Authorization", format!("Bearer {}", self.config.api_key))
+        }
+    }
+
+    /// Convert conversation history to OpenAI's message format
+    fn api_messages(&self, messages: &[ChatMessage]) -> Vec<OpenAIChatMessage> {
+        messages
+            .iter()
+            .map(|msg| OpenAIChatMessage {
+                role: self.api_role(msg.role).to_string(),
+                content: msg.content.clone(),
+            })
+            .collect()
+    }
+
+    /// Whether to send `top_p`, the penalties and `stop`, which reasoning models
+    /// (o-series, GPT-5.x, or any request with `reasoning_effort`) reject with a 400.
+    ///
+    /// Warns when configured values are left out.
+    fn sends_sampling_params(&self, options: &RequestOptions, reasoning: bool) -> bool {
+        let model = self.config.model.as_str();
+        if !reasoning && !uses_max_completion_tokens(model) {
+            return true;
+        }
+        if options.top_p.or(self.config.top_p).is_some()
+            || self.config.frequency_penalty.is_some()
+            || self.config.presence_penalty.is_some()
+            || !options
+                .stop
+                .as_ref()
+                .unwrap_or(&self.config.stop_sequences)
+                .is_empty()
+        {
+            warn!(
+                model = %model,
+                "Reasoning models don't accept top_p, penalties or stop sequences, leaving them out"
+            );
+        }
+        false
+    }
+
+    /// Build the structured-output request for a conversation, returning it together
+    /// with whether the schema root was wrapped in an object.
+    fn structured_request<T: Instructor>(
         &self,
         messages: &[ChatMessage],
-    ) -> std::result::Result<
-        MaterializeInternalOutput<T>,
-        (RStructorError, Option<ValidationFailureContext>),
-    >
-    where
-        T: Instructor + DeserializeOwned + Send + 'static,
-    {
-        info!("Generating structured response with OpenAI (native structured outputs)");
-
-        // Get the schema for type T
-        let schema = T::schema();
+        schema: &Schema,
+        options: &RequestOptions,
+    ) -> (ChatCompletionRequest, bool) {
         let schema_name = T::schema_name().unwrap_or_else(|| "output".to_string());
         // Avoid calling to_string() in trace to prevent potential stack overflow with complex schemas
         trace!(schema_name = schema_name, "Retrieved JSON schema for type");
 
         // Prepare schema with additionalProperties: false recursively for all nested objects
-        let schema_json = prepare_strict_schema(&schema);
+        let mut schema_json = prepare_strict_schema(schema);
+        // Strict mode needs an object root, so enums get wrapped in `{"value": ...}`
+        let wrapped_root = wrap_non_object_root(&mut schema_json);
 
         // Create response format with JSON schema (strict mode)
         let response_format = ResponseFormat::json_schema(
@@ -415,57 +999,139 @@ impl OpenAIClient {
         );
 
         // Build reasoning_effort for GPT-5.x models
-        let is_gpt5 = self.config.model.as_str().starts_with("gpt-5");
-        let reasoning_effort = if is_gpt5 {
-            self.config
-                .thinking_level
-                .and_then(|level| level.openai_reasoning_effort().map(|s| s.to_string()))
-        } else {
-            None
-        };
+        let reasoning_effort = resolve_thinking_level(
+            self.config.model.as_str(),
+            options.thinking_level.or(self.config.thinking_level),
+        )
+        .and_then(|level| level.openai_reasoning_effort().map(|s| s.to_string()));
 
         // GPT-5.x with reasoning requires temperature=1.0
         let effective_temp = if reasoning_effort.is_some() {
             1.0
         } else {
-            self.config.temperature
+            options.temperature.unwrap_or(self.config.temperature)
         };
 
-        // Convert ChatMessage to OpenAI's format
-        let api_messages: Vec<OpenAIChatMessage> = messages
-            .iter()
-            .map(|msg| OpenAIChatMessage {
-                role: msg.role.as_str().to_string(),
-                content: msg.content.clone(),
-            })
-            .collect();
+        let api_messages = self.api_messages(messages);
 
         // Build the request with native structured outputs
         debug!(
             "Building OpenAI API request with structured outputs (history_len={})",
             api_messages.len()
         );
+        let max_tokens = options.max_tokens.or(self.config.max_tokens);
+        let completion_tokens = uses_max_completion_tokens(self.config.model.as_str());
+        let sampling = self.sends_sampling_params(options, reasoning_effort.is_some());
         let request = ChatCompletionRequest {
             model: self.config.model.as_str().to_string(),
             messages: api_messages,
             response_format: Some(response_format),
             temperature: effective_temp,
-            max_tokens: self.config.max_tokens,
+            max_tokens: max_tokens.filter(|_| !completion_tokens),
+            max_completion_tokens: max_tokens.filter(|_| completion_tokens),
+            top_p: effective_top_p(options, self.config.top_p).filter(|_| sampling),
+            frequency_penalty: effective_penalty(
+                "frequency_penalty",
+                self.config.frequency_penalty,
+            )
+            .filter(|_| sampling),
+            presence_penalty: effective_penalty("presence_penalty", self.config.presence_penalty)
+                .filter(|_| sampling),
+            stop: if sampling {
+                options
+                    .stop
+                    .clone()
+                    .unwrap_or_else(|| self.config.stop_sequences.clone())
+            } else {
+                Vec::new()
+            },
             reasoning_effort,
+            service_tier: self.config.service_tier.clone(),
+            prediction: self.config.predicted_output.as_ref().map(|value| {
+                // Predict the output as the model will write it, inside the wrapper
+                let content = if wrapped_root {
+                    serde_json::json!({ "value": value }).to_string()
+                } else {
+                    value.to_string()
+                };
+                Prediction {
+                    kind: "content",
+                    content,
+                }
+            }),
+            stream: None,
+            stream_options: None,
         };
 
-        // Send the request to OpenAI
-        let base_url = self
+        (request, wrapped_root)
+    }
+
+    /// Internal implementation of materialize (without retry logic)
+    /// Accepts conversation history for multi-turn interactions.
+    /// Returns the data, raw response, and optional usage info.
+    ///
+    /// Uses OpenAI's native Structured Outputs with `response_format: json_schema`
+    /// for guaranteed schema compliance.
+    ///
+    /// The raw response is included to enable conversation history tracking for retries,
+    /// which improves prompt caching efficiency.
+    async fn materialize_internal<T>(
+        &self,
+        messages: &[ChatMessage],
+        schema: &Schema,
+        options: &RequestOptions,
+    ) -> std::result::Result<
+        MaterializeInternalOutput<T>,
+        (RStructorError, Option<ValidationFailureContext>),
+    >
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        if self.config.dry_run {
+            return dry_run_output::<T>(schema);
+        }
+        let messages = with_system_prompts(&self.config.system_prompts, messages);
+        let fitted = fit_context_window(
+            &messages,
+            self.config.model.as_str(),
+            reserved_tokens(
+                self.config.model.as_str(),
+                Some(schema),
+                options.max_tokens.or(self.config.max_tokens),
+            ),
+            self.config.auto_truncate,
+        );
+        let messages: &[ChatMessage] = &fitted;
+        let reservation = self
             .config
-            .base_url
-            .as_deref()
-            .unwrap_or("https://api.openai.com/v1");
-        let url = format!("{}/chat/completions", base_url);
+            .budget
+            .as_ref()
+            .map(|budget| {
+                budget.check(&estimated_usage(
+                    self.config.model.as_str(),
+                    messages,
+                    Some(schema),
+                    options.max_tokens.or(self.config.max_tokens),
+                ))
+            })
+            .transpose()
+            .map_err(|e| (e, None))?;
+
+        info!("Generating structured response with OpenAI (native structured outputs)");
+
+        let (request, wrapped_root) = self.structured_request::<T>(messages, schema, options);
+        if self.config.log_schemas
+            && let Some(ResponseFormat::JsonSchema { json_schema }) = &request.response_format
+        {
+            log_schema_once::<T>("OpenAI", &json_schema.schema);
+        }
+
+        // Send the request to OpenAI
+        let url = self.chat_completions_url(options);
         debug!(url = %url, "Sending request to OpenAI API");
         let response = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.config.api_key))
+            .authorize(self.client.post(&url))
+            .headers(custom_headers(&self.config.headers))
             .header("Content-Type", "application/json")
             .json(&request)
             .send()
@@ -473,15 +1139,19 @@ impl OpenAIClient {
             .map_err(|e| (handle_http_error(e, "OpenAI"), None))?;
 
         // Parse the response
-        let response = check_response_status(response, "OpenAI")
+        let response = check_response_status(response, "OpenAI", self.config.max_response_bytes)
             .await
             .map_err(|e| (e, None))?;
+        let request_id = response_request_id(&response);
 
         debug!("Successfully received response from OpenAI");
-        let completion: ChatCompletionResponse = response.json().await.map_err(|e| {
-            error!(error = %e, "Failed to parse JSON response from OpenAI");
-            (RStructorError::from(e), None)
-        })?;
+        let completion: ChatCompletionResponse =
+            read_json_response(response, "OpenAI", self.config.max_response_bytes)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to parse JSON response from OpenAI");
+                    (e, None)
+                })?;
 
         if completion.choices.is_empty() {
             error!("OpenAI returned empty choices array");
@@ -501,24 +1171,45 @@ impl OpenAIClient {
             .model
             .clone()
             .unwrap_or_else(|| self.config.model.as_str().to_string());
-        let usage = completion
-            .usage
-            .as_ref()
-            .map(|u| TokenUsage::new(model_name.clone(), u.prompt_tokens, u.completion_tokens));
+        let usage = completion.usage.as_ref().map(|u| {
+            TokenUsage::new(model_name.clone(), u.prompt_tokens, u.completion_tokens)
+                .with_total_tokens(u.total_tokens)
+        });
+        if let Some(reservation) = reservation {
+            reservation.record(usage.as_ref());
+        }
 
         let message = &completion.choices[0].message;
         trace!(finish_reason = %completion.choices[0].finish_reason, "Completion finish reason");
 
-        // With structured outputs, the response is in message.content as guaranteed-valid JSON
-        if let Some(content) = &message.content {
-            let raw_response = content.clone();
-            debug!(
+        if completion.choices[0].finish_reason == "content_filter" {
+            error!("OpenAI response blocked by content filter");
+            return Err((
+                RStructorError::api_error(
+                    "OpenAI",
+                    ApiErrorKind::ContentFiltered {
+                        details: "finish_reason: content_filter".to_string(),
+                    },
+                ),
+                None,
+            ));
+        }
+
+        // With structured outputs, the response is in message.content as guaranteed-valid JSON
+        if let Some(content) = &message.content {
+            let raw_response = if wrapped_root {
+                unwrap_value_root(content.clone(), self.config.lenient_parsing)
+            } else {
+                content.clone()
+            };
+            debug!(
                 content_len = raw_response.len(),
                 "Structured output received from OpenAI"
             );
 
             // Parse and validate the response using shared utility
-            parse_validate_and_create_output(raw_response, usage)
+            parse_validate_and_create_output(raw_response, usage, self.config.lenient_parsing)
+                .map(|output| output.with_request_id(request_id))
         } else {
             error!("No content in OpenAI response");
             Err((
@@ -532,6 +1223,172 @@ impl OpenAIClient {
             ))
         }
     }
+
+    /// Internal implementation of raw text generation for a conversation, with
+    /// per-call overrides applied over the client configuration.
+    async fn generate_internal(
+        &self,
+        messages: &[ChatMessage],
+        options: &RequestOptions,
+    ) -> Result<GenerateResult> {
+        if self.config.dry_run {
+            return Ok(GenerateResult::new(String::new(), None));
+        }
+        let messages = with_system_prompts(&self.config.system_prompts, messages);
+        let fitted = fit_context_window(
+            &messages,
+            self.config.model.as_str(),
+            reserved_tokens(
+                self.config.model.as_str(),
+                None,
+                options.max_tokens.or(self.config.max_tokens),
+            ),
+            self.config.auto_truncate,
+        );
+        let messages: &[ChatMessage] = &fitted;
+        let reservation = self
+            .config
+            .budget
+            .as_ref()
+            .map(|budget| {
+                budget.check(&estimated_usage(
+                    self.config.model.as_str(),
+                    messages,
+                    None,
+                    options.max_tokens.or(self.config.max_tokens),
+                ))
+            })
+            .transpose()?;
+
+        info!("Generating raw text response with OpenAI");
+
+        // Build reasoning_effort for GPT-5.x models
+        let reasoning_effort = resolve_thinking_level(
+            self.config.model.as_str(),
+            options.thinking_level.or(self.config.thinking_level),
+        )
+        .and_then(|level| level.openai_reasoning_effort().map(|s| s.to_string()));
+
+        // GPT-5.x with reasoning requires temperature=1.0
+        let effective_temp = if reasoning_effort.is_some() {
+            1.0
+        } else {
+            options.temperature.unwrap_or(self.config.temperature)
+        };
+
+        // Build the request for text generation (no structured output)
+        debug!("Building OpenAI API request for text generation");
+        let max_tokens = options.max_tokens.or(self.config.max_tokens);
+        let completion_tokens = uses_max_completion_tokens(self.config.model.as_str());
+        let sampling = self.sends_sampling_params(options, reasoning_effort.is_some());
+        let request = ChatCompletionRequest {
+            model: self.config.model.as_str().to_string(),
+            messages: self.api_messages(messages),
+            response_format: None,
+            temperature: effective_temp,
+            max_tokens: max_tokens.filter(|_| !completion_tokens),
+            max_completion_tokens: max_tokens.filter(|_| completion_tokens),
+            top_p: effective_top_p(options, self.config.top_p).filter(|_| sampling),
+            frequency_penalty: effective_penalty(
+                "frequency_penalty",
+                self.config.frequency_penalty,
+            )
+            .filter(|_| sampling),
+            presence_penalty: effective_penalty("presence_penalty", self.config.presence_penalty)
+                .filter(|_| sampling),
+            stop: if sampling {
+                options
+                    .stop
+                    .clone()
+                    .unwrap_or_else(|| self.config.stop_sequences.clone())
+            } else {
+                Vec::new()
+            },
+            reasoning_effort,
+            service_tier: self.config.service_tier.clone(),
+            prediction: None,
+            stream: None,
+            stream_options: None,
+        };
+
+        // Send the request to OpenAI
+        let url = self.chat_completions_url(options);
+        debug!(url = %url, "Sending request to OpenAI API");
+        let response = self
+            .authorize(self.client.post(&url))
+            .headers(custom_headers(&self.config.headers))
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| handle_http_error(e, "OpenAI"))?;
+
+        // Parse the response
+        let response =
+            check_response_status(response, "OpenAI", self.config.max_response_bytes).await?;
+        let request_id = response_request_id(&response);
+
+        debug!("Successfully received response from OpenAI");
+        let completion: ChatCompletionResponse =
+            read_json_response(response, "OpenAI", self.config.max_response_bytes)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to parse JSON response from OpenAI");
+                    e
+                })?;
+
+        if completion.choices.is_empty() {
+            error!("OpenAI returned empty choices array");
+            return Err(RStructorError::api_error(
+                "OpenAI",
+                ApiErrorKind::UnexpectedResponse {
+                    details: "No completion choices returned".to_string(),
+                },
+            ));
+        }
+
+        // Extract usage info
+        let model_name = completion
+            .model
+            .clone()
+            .unwrap_or_else(|| self.config.model.as_str().to_string());
+        let usage = completion.usage.as_ref().map(|u| {
+            TokenUsage::new(model_name, u.prompt_tokens, u.completion_tokens)
+                .with_total_tokens(u.total_tokens)
+        });
+        if let Some(reservation) = reservation {
+            reservation.record(usage.as_ref());
+        }
+
+        let message = &completion.choices[0].message;
+        trace!(finish_reason = %completion.choices[0].finish_reason, "Completion finish reason");
+
+        if completion.choices[0].finish_reason == "content_filter" {
+            error!("OpenAI response blocked by content filter");
+            return Err(RStructorError::api_error(
+                "OpenAI",
+                ApiErrorKind::ContentFiltered {
+                    details: "finish_reason: content_filter".to_string(),
+                },
+            ));
+        }
+
+        if let Some(content) = &message.content {
+            debug!(
+                content_len = content.len(),
+                "Successfully extracted content from response"
+            );
+            Ok(GenerateResult::new(content.clone(), usage).with_request_id(request_id))
+        } else {
+            error!("No content in OpenAI response");
+            Err(RStructorError::api_error(
+                "OpenAI",
+                ApiErrorKind::UnexpectedResponse {
+                    details: "No content in response".to_string(),
+                },
+            ))
+        }
+    }
 }
 
 #[async_trait]
@@ -550,16 +1407,167 @@ impl LLMClient for OpenAIClient {
         )
     )]
     async fn materialize<T>(&self, prompt: &str) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            prompt,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "openai_materialize_with_schema",
+        skip(self, prompt, schema),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn materialize_with_schema<T>(&self, prompt: &str, schema: Schema) -> Result<T>
     where
         T: Instructor + DeserializeOwned + Send + 'static,
     {
         let output = generate_with_retry_with_history(
             |messages: Vec<ChatMessage>| {
                 let this = self;
-                async move { this.materialize_internal::<T>(&messages).await }
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
             },
             prompt,
             self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "openai_materialize_opts",
+        skip(self, prompt, options),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn materialize_opts<T>(&self, prompt: &str, options: &RequestOptions) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, options)
+                        .await
+                }
+            },
+            prompt,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    fn materialize_request_body<T>(&self, prompt: &str) -> Result<serde_json::Value>
+    where
+        T: Instructor,
+    {
+        let schema = T::schema();
+        let prompt = [ChatMessage::user(prompt)];
+        let messages = with_system_prompts(&self.config.system_prompts, &prompt);
+        let (request, _) =
+            self.structured_request::<T>(&messages, &schema, &RequestOptions::default());
+        Ok(serde_json::to_value(&request)?)
+    }
+
+    #[instrument(
+        name = "openai_materialize_with_examples",
+        skip(self, prompt, examples),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len(),
+            examples_len = examples.len()
+        )
+    )]
+    async fn materialize_with_examples<T>(
+        &self,
+        prompt: &str,
+        examples: &[(String, T)],
+    ) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + Sync + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_from_messages(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            few_shot_messages(prompt, examples)?,
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
+        )
+        .await?;
+        Ok(output.data)
+    }
+
+    #[instrument(
+        name = "openai_materialize_with_history",
+        skip(self, messages),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            messages_len = messages.len()
+        )
+    )]
+    async fn materialize_with_history<T>(&self, messages: &[ChatMessage]) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        let output = generate_with_retry_from_messages(
+            |messages: Vec<ChatMessage>| {
+                let this = self;
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
+            },
+            messages.to_vec(),
+            self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
         )
         .await?;
         Ok(output.data)
@@ -578,16 +1586,109 @@ impl LLMClient for OpenAIClient {
     where
         T: Instructor + DeserializeOwned + Send + 'static,
     {
+        let schema = T::schema();
         let output = generate_with_retry_with_history(
             |messages: Vec<ChatMessage>| {
                 let this = self;
-                async move { this.materialize_internal::<T>(&messages).await }
+                let schema = &schema;
+                async move {
+                    this.materialize_internal::<T>(&messages, schema, &RequestOptions::default())
+                        .await
+                }
             },
             prompt,
             self.config.max_retries,
+            self.config.deadline,
+            &self.config.retry_policy,
         )
         .await?;
-        Ok(MaterializeResult::new(output.data, output.usage))
+        Ok(MaterializeResult::new(output.data, output.usage)
+            .with_attempts(output.attempts)
+            .with_request_id(output.request_id))
+    }
+
+    #[instrument(
+        name = "openai_materialize_stream",
+        skip(self, prompt),
+        fields(
+            type_name = std::any::type_name::<T>(),
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn materialize_stream<T>(
+        &self,
+        prompt: &str,
+    ) -> Result<BoxStream<'static, Result<String>>>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let schema = T::schema();
+        if self.config.dry_run {
+            let output = dry_run_output::<T>(&schema).map_err(|(e, _)| e)?;
+            return Ok(futures::stream::once(async move { Ok(output.raw_response) }).boxed());
+        }
+        let prompt = [ChatMessage::user(prompt)];
+        let messages = with_system_prompts(&self.config.system_prompts, &prompt);
+        let messages = fit_context_window(
+            &messages,
+            self.config.model.as_str(),
+            reserved_tokens(
+                self.config.model.as_str(),
+                Some(&schema),
+                self.config.max_tokens,
+            ),
+            self.config.auto_truncate,
+        );
+        let mut reservation = self
+            .config
+            .budget
+            .as_ref()
+            .map(|budget| {
+                budget.check(&estimated_usage(
+                    self.config.model.as_str(),
+                    &messages,
+                    Some(&schema),
+                    self.config.max_tokens,
+                ))
+            })
+            .transpose()?;
+
+        info!("Streaming structured response from OpenAI");
+        let (mut request, _) =
+            self.structured_request::<T>(&messages, &schema, &RequestOptions::default());
+        request.stream = Some(true);
+        if self.config.budget.is_some() {
+            // Usage is only reported on streams when asked for, and the budget needs it
+            request.stream_options = Some(StreamOptions {
+                include_usage: true,
+            });
+        }
+
+        let url = self.chat_completions_url(&RequestOptions::default());
+        debug!(url = %url, "Sending streaming request to OpenAI API");
+        let response = self
+            .authorize(self.client.post(&url))
+            .headers(custom_headers(&self.config.headers))
+            .header("Content-Type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| handle_http_error(e, "OpenAI"))?;
+        let response =
+            check_response_status(response, "OpenAI", self.config.max_response_bytes).await?;
+
+        let model = self.config.model.as_str().to_string();
+        Ok(
+            sse_data_stream(response, "OpenAI", self.config.max_response_bytes)
+                .filter_map(move |event| {
+                    futures::future::ready(match event {
+                        Ok(event) => stream_delta(&event, &mut reservation, &model),
+                        Err(e) => Some(Err(e)),
+                    })
+                })
+                .boxed(),
+        )
     }
 
     #[instrument(
@@ -612,104 +1713,36 @@ impl LLMClient for OpenAIClient {
         )
     )]
     async fn generate_with_metadata(&self, prompt: &str) -> Result<GenerateResult> {
-        info!("Generating raw text response with OpenAI");
-
-        // Build reasoning_effort for GPT-5.x models
-        let is_gpt5 = self.config.model.as_str().starts_with("gpt-5");
-        let reasoning_effort = if is_gpt5 {
-            self.config
-                .thinking_level
-                .and_then(|level| level.openai_reasoning_effort().map(|s| s.to_string()))
-        } else {
-            None
-        };
-
-        // GPT-5.x with reasoning requires temperature=1.0
-        let effective_temp = if reasoning_effort.is_some() {
-            1.0
-        } else {
-            self.config.temperature
-        };
-
-        // Build the request for text generation (no structured output)
-        debug!("Building OpenAI API request for text generation");
-        let request = ChatCompletionRequest {
-            model: self.config.model.as_str().to_string(),
-            messages: vec![OpenAIChatMessage {
-                role: "user".to_string(),
-                content: prompt.to_string(),
-            }],
-            response_format: None,
-            temperature: effective_temp,
-            max_tokens: self.config.max_tokens,
-            reasoning_effort,
-        };
-
-        // Send the request to OpenAI
-        let base_url = self
-            .config
-            .base_url
-            .as_deref()
-            .unwrap_or("https://api.openai.com/v1");
-        let url = format!("{}/chat/completions", base_url);
-        debug!(url = %url, "Sending request to OpenAI API");
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.config.api_key))
-            .header("Content-Type", "application/json")
-            .json(&request)
-            .send()
+        self.generate_with_messages(&[ChatMessage::user(prompt)])
             .await
-            .map_err(|e| handle_http_error(e, "OpenAI"))?;
-
-        // Parse the response
-        let response = check_response_status(response, "OpenAI").await?;
-
-        debug!("Successfully received response from OpenAI");
-        let completion: ChatCompletionResponse = response.json().await.map_err(|e| {
-            error!(error = %e, "Failed to parse JSON response from OpenAI");
-            e
-        })?;
-
-        if completion.choices.is_empty() {
-            error!("OpenAI returned empty choices array");
-            return Err(RStructorError::api_error(
-                "OpenAI",
-                ApiErrorKind::UnexpectedResponse {
-                    details: "No completion choices returned".to_string(),
-                },
-            ));
-        }
-
-        // Extract usage info
-        let model_name = completion
-            .model
-            .clone()
-            .unwrap_or_else(|| self.config.model.as_str().to_string());
-        let usage = completion
-            .usage
-            .as_ref()
-            .map(|u| TokenUsage::new(model_name, u.prompt_tokens, u.completion_tokens));
+    }
 
-        let message = &completion.choices[0].message;
-        trace!(finish_reason = %completion.choices[0].finish_reason, "Completion finish reason");
+    #[instrument(
+        name = "openai_generate_opts",
+        skip(self, prompt, options),
+        fields(
+            model = %self.config.model.as_str(),
+            prompt_len = prompt.len()
+        )
+    )]
+    async fn generate_opts(&self, prompt: &str, options: &RequestOptions) -> Result<String> {
+        let result = self
+            .generate_internal(&[ChatMessage::user(prompt)], options)
+            .await?;
+        Ok(result.text)
+    }
 
-        if let Some(content) = &message.content {
-            debug!(
-                content_len = content.len(),
-                "Successfully extracted content from response"
-            );
-            Ok(GenerateResult::new(content.clone(), usage))
-        } else {
-            error!("No content in OpenAI response");
-            Err(RStructorError::api_error(
-                "OpenAI",
-                ApiErrorKind::UnexpectedResponse {
-                    details: "No content in response".to_string(),
-                },
-            ))
-        }
+    #[instrument(
+        name = "openai_generate_with_messages",
+        skip(self, messages),
+        fields(
+            model = %self.config.model.as_str(),
+            history_len = messages.len()
+        )
+    )]
+    async fn generate_with_messages(&self, messages: &[ChatMessage]) -> Result<GenerateResult> {
+        self.generate_internal(messages, &RequestOptions::default())
+            .await
     }
 
     /// Fetch available models from OpenAI's API.
@@ -717,30 +1750,37 @@ impl LLMClient for OpenAIClient {
     /// Returns a list of GPT models available for chat completions.
     /// Filters out embedding, whisper, and other non-chat models.
     async fn list_models(&self) -> Result<Vec<ModelInfo>> {
-        let base_url = self
-            .config
-            .base_url
-            .as_deref()
-            .unwrap_or("https://api.openai.com/v1");
-        let url = format!("{}/models", base_url);
+        if let Some(ttl) = self.config.models_cache_ttl
+            && let Some((fetched_at, models)) =
+                &*self.models_cache.lock().unwrap_or_else(|e| e.into_inner())
+            && fetched_at.elapsed() < ttl
+        {
+            debug!(count = models.len(), "Returning cached OpenAI models");
+            return Ok(models.clone());
+        }
+
+        let url = self.models_url();
 
         debug!(url = %url, "Fetching available models from OpenAI");
 
         let response = self
-            .client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.config.api_key))
+            .authorize(self.client.get(&url))
+            .headers(custom_headers(&self.config.headers))
             .header("Content-Type", "application/json")
             .send()
             .await
             .map_err(|e| handle_http_error(e, "OpenAI"))?;
 
-        let response = check_response_status(response, "OpenAI").await?;
+        let response =
+            check_response_status(response, "OpenAI", self.config.max_response_bytes).await?;
 
-        let json: serde_json::Value = response.json().await.map_err(|e| {
-            error!(error = %e, "Failed to parse models response from OpenAI");
-            e
-        })?;
+        let json: serde_json::Value =
+            read_json_response(response, "OpenAI", self.config.max_response_bytes)
+                .await
+                .map_err(|e| {
+                    error!(error = %e, "Failed to parse models response from OpenAI");
+                    e
+                })?;
 
         let models = json
             .get("data")
@@ -750,8 +1790,8 @@ impl LLMClient for OpenAIClient {
                     .iter()
                     .filter_map(|model| {
                         let id = model.get("id").and_then(|id| id.as_str())?;
-                        // Filter to only GPT models (chat completion models)
-                        if id.starts_with("gpt-") || id.starts_with("o1") || id.starts_with("o3") {
+                        // Filter to chat completion models (no embeddings, TTS, moderation, etc.)
+                        if is_chat_model(id) {
                             Some(ModelInfo {
                                 id: id.to_string(),
                                 name: None,
@@ -766,6 +1806,57 @@ impl LLMClient for OpenAIClient {
             .unwrap_or_default();
 
         debug!(count = models.len(), "Fetched OpenAI models");
+        if self.config.models_cache_ttl.is_some() {
+            *self.models_cache.lock().unwrap_or_else(|e| e.into_inner()) =
+                Some((Instant::now(), models.clone()));
+        }
         Ok(models)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_developer_role_detection() {
+        for model in [
+            "gpt-5.2",
+            "gpt-5-mini",
+            "o1",
+            "o3-mini",
+            "o4-mini-2025-04-16",
+        ] {
+            assert!(uses_developer_role(model), "{model}");
+        }
+        for model in ["gpt-4o", "gpt-3.5-turbo", "o1-mini", "llama-3.1-70b"] {
+            assert!(!uses_developer_role(model), "{model}");
+        }
+    }
+
+    #[test]
+    fn test_max_completion_tokens_detection() {
+        for model in ["gpt-5.2", "gpt-5-mini", "o1-mini", "o3", "o4-mini"] {
+            assert!(uses_max_completion_tokens(model), "{model}");
+        }
+        for model in ["gpt-4o", "gpt-4-turbo", "gpt-3.5-turbo", "llama-3.1-70b"] {
+            assert!(!uses_max_completion_tokens(model), "{model}");
+        }
+    }
+
+    #[test]
+    fn test_api_role_respects_override() {
+        let client = OpenAIClient::new("test-key").unwrap().model(Model::Gpt52);
+        assert_eq!(client.api_role(ChatRole::System), "developer");
+        assert_eq!(client.api_role(ChatRole::User), "user");
+
+        let client = client.developer_role(false);
+        assert_eq!(client.api_role(ChatRole::System), "system");
+
+        let client = OpenAIClient::new("test-key")
+            .unwrap()
+            .model(Model::Gpt4O)
+            .developer_role(true);
+        assert_eq!(client.api_role(ChatRole::System), "developer");
+    }
+}
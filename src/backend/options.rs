@@ -0,0 +1,93 @@
+use crate::backend::ThinkingLevel;
+
+/// Per-call overrides for generation parameters.
+///
+/// Passed to [`LLMClient::materialize_opts`](crate::LLMClient::materialize_opts) and
+/// [`LLMClient::generate_opts`](crate::LLMClient::generate_opts) to change a few
+/// parameters for a single request without building a new client. Every field is
+/// optional; `None` means "use the client's configured value".
+///
+/// # Example
+///
+/// ```
+/// use rstructor::{RequestOptions, ThinkingLevel};
+///
+/// let options = RequestOptions::new()
+///     .with_temperature(0.2)
+///     .with_max_tokens(256)
+///     .with_thinking_level(ThinkingLevel::Off)
+///     .with_stop(["\n\n"]);
+///
+/// assert_eq!(options.temperature, Some(0.2));
+/// assert_eq!(options.stop, Some(vec!["\n\n".to_string()]));
+/// assert!(options.top_p.is_none());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RequestOptions {
+    /// Sampling temperature
+    pub temperature: Option<f32>,
+    /// Maximum number of output tokens
+    pub max_tokens: Option<u32>,
+    /// Nucleus sampling probability mass
+    pub top_p: Option<f32>,
+    /// Reasoning depth for models that support extended thinking
+    pub thinking_level: Option<ThinkingLevel>,
+    /// Sequences that end generation when produced
+    pub stop: Option<Vec<String>>,
+    /// Endpoint to send this request to instead of the client's `base_url`
+    pub base_url: Option<String>,
+}
+
+impl RequestOptions {
+    /// Create options that override nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the sampling temperature
+    pub fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    /// Override the maximum number of output tokens
+    pub fn with_max_tokens(mut self, max_tokens: u32) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Set nucleus sampling (`top_p`), clamped to 0.0–1.0 when the request is built
+    pub fn with_top_p(mut self, top_p: f32) -> Self {
+        self.top_p = Some(top_p);
+        self
+    }
+
+    /// Override the thinking level
+    pub fn with_thinking_level(mut self, level: ThinkingLevel) -> Self {
+        self.thinking_level = Some(level);
+        self
+    }
+
+    /// Override the stop sequences (an empty list sends none)
+    pub fn with_stop<I, S>(mut self, stop: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.stop = Some(stop.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Send this request to a different endpoint, e.g. to route a canary call
+    ///
+    /// Takes the same form as the client's `base_url` builder (no trailing slash).
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = Some(base_url.into());
+        self
+    }
+
+    /// Whether no parameter is overridden
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
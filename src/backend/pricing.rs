@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex};
+
+use tracing::{debug, warn};
+
+use crate::backend::usage::TokenUsage;
+use crate::error::{RStructorError, Result};
+
+/// Per-model token pricing in USD per million tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPricing {
+    /// Cost per million input/prompt tokens
+    pub input_per_million: f64,
+    /// Cost per million output/completion tokens
+    pub output_per_million: f64,
+}
+
+impl ModelPricing {
+    /// Create a new pricing entry (USD per million tokens)
+    pub fn new(input_per_million: f64, output_per_million: f64) -> Self {
+        Self {
+            input_per_million,
+            output_per_million,
+        }
+    }
+
+    /// Cost in USD for the given token counts
+    pub fn cost(&self, input_tokens: u64, output_tokens: u64) -> f64 {
+        (input_tokens as f64 * self.input_per_million
+            + output_tokens as f64 * self.output_per_million)
+            / 1_000_000.0
+    }
+}
+
+/// List prices (USD per million input/output tokens) of the providers' hosted models.
+///
+/// Standard-tier prices from each provider's pricing page as of December 2025:
+/// <https://openai.com/api/pricing/>, <https://www.anthropic.com/pricing>,
+/// <https://ai.google.dev/gemini-api/docs/pricing>, <https://docs.x.ai/docs/models> and
+/// <https://mistral.ai/pricing>. Entries are matched exactly (or with a dated snapshot
+/// suffix), so every priced model needs its own entry.
+const BUILTIN_PRICES: &[(&str, f64, f64)] = &[
+    // OpenAI
+    ("gpt-5.2", 1.75, 14.00),
+    ("gpt-5.2-pro", 21.00, 168.00),
+    ("gpt-5.1", 1.25, 10.00),
+    ("gpt-5", 1.25, 10.00),
+    ("gpt-5-mini", 0.25, 2.00),
+    ("gpt-5-nano", 0.05, 0.40),
+    ("gpt-5-pro", 15.00, 120.00),
+    ("gpt-4.1", 2.00, 8.00),
+    ("gpt-4.1-mini", 0.40, 1.60),
+    ("gpt-4.1-nano", 0.10, 0.40),
+    ("gpt-4o", 2.50, 10.00),
+    ("gpt-4o-mini", 0.15, 0.60),
+    ("o1", 15.00, 60.00),
+    ("o1-mini", 1.10, 4.40),
+    ("o3", 2.00, 8.00),
+    ("o3-mini", 1.10, 4.40),
+    ("o3-pro", 20.00, 80.00),
+    ("o4-mini", 1.10, 4.40),
+    // Anthropic
+    ("claude-opus-4-5", 5.00, 25.00),
+    ("claude-opus-4-1", 15.00, 75.00),
+    ("claude-opus-4", 15.00, 75.00),
+    ("claude-sonnet-4-5", 3.00, 15.00),
+    ("claude-sonnet-4", 3.00, 15.00),
+    ("claude-haiku-4-5", 1.00, 5.00),
+    ("claude-3-7-sonnet", 3.00, 15.00),
+    ("claude-3-5-haiku", 0.80, 4.00),
+    // Gemini (standard context tier)
+    ("gemini-3-pro-preview", 2.00, 12.00),
+    ("gemini-3-flash-preview", 0.50, 3.00),
+    ("gemini-2.5-pro", 1.25, 10.00),
+    ("gemini-2.5-flash", 0.30, 2.50),
+    ("gemini-2.5-flash-lite", 0.10, 0.40),
+    ("gemini-2.0-flash", 0.10, 0.40),
+    // xAI
+    ("grok-4", 3.00, 15.00),
+    ("grok-4-fast", 0.20, 0.50),
+    ("grok-4-1-fast", 0.20, 0.50),
+    ("grok-code-fast-1", 0.20, 1.50),
+    ("grok-3", 3.00, 15.00),
+    ("grok-3-mini", 0.30, 0.50),
+    // Mistral
+    ("mistral-large-latest", 0.50, 1.50),
+    ("mistral-medium-latest", 0.40, 2.00),
+    ("mistral-small-latest", 0.10, 0.30),
+    ("codestral-latest", 0.30, 0.90),
+    ("ministral-8b-latest", 0.10, 0.10),
+    ("ministral-3b-latest", 0.04, 0.04),
+    ("pixtral-large-latest", 2.00, 6.00),
+    ("open-mistral-nemo", 0.15, 0.15),
+];
+
+static BUILTIN_PRICING: LazyLock<PricingTable> = LazyLock::new(PricingTable::builtin);
+
+/// The built-in pricing table, shared by every [`TokenUsage::cost_usd`] call
+pub(crate) fn builtin_pricing() -> &'static PricingTable {
+    &BUILTIN_PRICING
+}
+
+/// Table of model prices used to turn [`TokenUsage`] into dollar costs.
+///
+/// Lookups match the model name exactly, or with a dated snapshot suffix
+/// (`-YYYY-MM-DD` or `-YYYYMMDD`) removed, so `"gpt-4o"` also prices
+/// `"gpt-4o-2024-08-06"`. Any other unlisted model, such as a `-pro` variant of a
+/// listed one, has no price rather than borrowing a similar model's.
+///
+/// # Example
+///
+/// ```
+/// use rstructor::{PricingTable, TokenUsage};
+///
+/// let pricing = PricingTable::new().with_model("gpt-4o", 2.50, 10.00);
+///
+/// let usage = TokenUsage::new("gpt-4o-2024-08-06", 1_000_000, 100_000);
+/// assert_eq!(pricing.cost(&usage), Some(3.50));
+/// assert_eq!(pricing.cost(&TokenUsage::new("gpt-4o-audio", 10, 10)), None);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PricingTable {
+    models: HashMap<String, ModelPricing>,
+}
+
+impl PricingTable {
+    /// Create an empty pricing table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A table of list prices for the built-in providers' hosted models.
+    ///
+    /// Prices change over time, so treat costs from this table as estimates; use
+    /// [`with_model`](Self::with_model) to override an entry or add one. Local models
+    /// (Ollama) aren't included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rstructor::{PricingTable, TokenUsage};
+    ///
+    /// let pricing = PricingTable::builtin().with_model("my-finetune", 3.00, 12.00);
+    /// assert!(pricing.cost(&TokenUsage::new("gpt-4o-mini-2024-07-18", 1000, 100)).is_some());
+    /// assert_eq!(pricing.cost(&TokenUsage::new("llama3.2", 1000, 100)), None);
+    /// ```
+    pub fn builtin() -> Self {
+        BUILTIN_PRICES
+            .iter()
+            .fold(Self::new(), |table, &(model, input, output)| {
+                table.with_model(model, input, output)
+            })
+    }
+
+    /// Add or replace the pricing for a model (USD per million tokens)
+    pub fn with_model(
+        mut self,
+        model: impl Into<String>,
+        input_per_million: f64,
+        output_per_million: f64,
+    ) -> Self {
+        self.models.insert(
+            model.into(),
+            ModelPricing::new(input_per_million, output_per_million),
+        );
+        self
+    }
+
+    /// Look up the pricing for a model name, or for its undated name if it's a dated
+    /// snapshot
+    pub fn get(&self, model: &str) -> Option<ModelPricing> {
+        self.models
+            .get(model)
+            .or_else(|| strip_snapshot_date(model).and_then(|undated| self.models.get(undated)))
+            .copied()
+    }
+
+    /// Cost in USD of a call, or `None` if the model isn't in the table
+    pub fn cost(&self, usage: &TokenUsage) -> Option<f64> {
+        self.get(&usage.model)
+            .map(|pricing| pricing.cost(usage.input_tokens, usage.output_tokens))
+    }
+}
+
+/// `model` without a trailing `-YYYY-MM-DD` or `-YYYYMMDD` snapshot date, if it has one
+fn strip_snapshot_date(model: &str) -> Option<&str> {
+    let digits =
+        |part: &str, len: usize| part.len() == len && part.bytes().all(|b| b.is_ascii_digit());
+    let (undated, date) = model.rsplit_once('-')?;
+    if digits(date, 8) {
+        return Some(undated);
+    }
+    let mut parts = model.rsplitn(4, '-');
+    let (day, month, year, undated) = (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+    (digits(year, 4) && digits(month, 2) && digits(day, 2)).then_some(undated)
+}
+
+/// A cumulative spend limit shared by every clone of a client.
+///
+/// Created by the `budget(max_usd, &pricing)` builder method on each client. Before
+/// each request the client [`check`](Self::check)s the call's estimated cost (its
+/// prompt plus `max_tokens` of output) against the ceiling and reserves it, returning
+/// [`RStructorError::BudgetExceeded`] if the call could exceed the limit; after the
+/// response the reservation is replaced by the call's actual cost. Reserving under the
+/// same lock as the check keeps concurrent calls (such as `materialize_many`) from all
+/// passing it before any of them is charged.
+///
+/// # Example
+///
+/// ```
+/// use rstructor::{Budget, PricingTable, TokenUsage};
+///
+/// let pricing = PricingTable::new().with_model("gpt-4o", 2.50, 10.00);
+/// let budget = Budget::new(1.0, &pricing);
+/// let shared = budget.clone();
+///
+/// shared.record(Some(&TokenUsage::new("gpt-4o", 200_000, 0)));
+/// assert_eq!(budget.spent_usd(), 0.5);
+///
+/// // A call estimated at $0.60 would cross the ceiling
+/// assert!(budget.check(&TokenUsage::new("gpt-4o", 200_000, 10_000)).is_err());
+///
+/// let reservation = budget.check(&TokenUsage::new("gpt-4o", 100_000, 10_000)).unwrap();
+/// assert_eq!(budget.reserved_usd(), 0.35);
+/// reservation.record(Some(&TokenUsage::new("gpt-4o", 100_000, 1_000)));
+/// assert_eq!(budget.reserved_usd(), 0.0);
+/// assert!((budget.spent_usd() - 0.76).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Budget {
+    max_usd: f64,
+    pricing: Arc<PricingTable>,
+    spend: Arc<Mutex<Spend>>,
+}
+
+/// Money spent by completed calls and held for calls in flight
+#[derive(Debug, Default)]
+struct Spend {
+    spent_usd: f64,
+    reserved_usd: f64,
+}
+
+impl Budget {
+    /// Create a new budget with the given ceiling in USD
+    pub fn new(max_usd: f64, pricing: &PricingTable) -> Self {
+        Self {
+            max_usd,
+            pricing: Arc::new(pricing.clone()),
+            spend: Arc::new(Mutex::new(Spend::default())),
+        }
+    }
+
+    fn spend(&self) -> std::sync::MutexGuard<'_, Spend> {
+        self.spend.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The spending ceiling in USD
+    pub fn max_usd(&self) -> f64 {
+        self.max_usd
+    }
+
+    /// Total spent so far in USD
+    pub fn spent_usd(&self) -> f64 {
+        self.spend().spent_usd
+    }
+
+    /// Estimated cost in USD of the calls currently in flight
+    pub fn reserved_usd(&self) -> f64 {
+        self.spend().reserved_usd
+    }
+
+    /// Amount left before the ceiling is reached (never negative); calls in flight
+    /// aren't subtracted
+    pub fn remaining_usd(&self) -> f64 {
+        (self.max_usd - self.spent_usd()).max(0.0)
+    }
+
+    /// Reserve the estimated cost of a call, or return `BudgetExceeded` if the amount
+    /// spent, plus the calls in flight, plus this one could exceed the ceiling.
+    ///
+    /// `estimate` gives the call's model, its prompt tokens and the most output tokens
+    /// it may produce. A model missing from the pricing table reserves nothing and is
+    /// only refused once the ceiling has been reached. The reservation is released
+    /// when dropped, or replaced by the actual cost through
+    /// [`BudgetReservation::record`].
+    pub fn check(&self, estimate: &TokenUsage) -> Result<BudgetReservation> {
+        let cost = self.pricing.cost(estimate).unwrap_or(0.0);
+        let mut spend = self.spend();
+        let committed = spend.spent_usd + spend.reserved_usd;
+        if spend.spent_usd >= self.max_usd || committed + cost > self.max_usd {
+            warn!(
+                spent_usd = spend.spent_usd,
+                reserved_usd = spend.reserved_usd,
+                estimated_cost_usd = cost,
+                limit_usd = self.max_usd,
+                "Budget exceeded"
+            );
+            return Err(RStructorError::BudgetExceeded {
+                spent_usd: spend.spent_usd,
+                limit_usd: self.max_usd,
+            });
+        }
+        spend.reserved_usd += cost;
+        Ok(BudgetReservation {
+            budget: self.clone(),
+            reserved_usd: cost,
+        })
+    }
+
+    /// Add the cost of a completed call to the running total.
+    ///
+    /// Calls without usage information, or for models missing from the pricing
+    /// table, are not counted.
+    pub fn record(&self, usage: Option<&TokenUsage>) {
+        self.settle(0.0, usage);
+    }
+
+    /// Release `reserved_usd` and add the cost of `usage` under one lock
+    fn settle(&self, reserved_usd: f64, usage: Option<&TokenUsage>) {
+        let cost = usage.and_then(|usage| {
+            let cost = self.pricing.cost(usage);
+            if cost.is_none() {
+                warn!(
+                    model = %usage.model,
+                    "No pricing for model, call not counted against budget"
+                );
+            }
+            cost
+        });
+        let mut spend = self.spend();
+        spend.reserved_usd = (spend.reserved_usd - reserved_usd).max(0.0);
+        if let Some(cost) = cost {
+            spend.spent_usd += cost;
+            debug!(
+                cost_usd = cost,
+                spent_usd = spend.spent_usd,
+                limit_usd = self.max_usd,
+                "Recorded call cost"
+            );
+        }
+    }
+}
+
+/// The estimated cost of a call in flight, held against a [`Budget`] by
+/// [`Budget::check`].
+///
+/// Dropping it (when the call fails) releases the amount.
+#[derive(Debug)]
+pub struct BudgetReservation {
+    budget: Budget,
+    reserved_usd: f64,
+}
+
+impl BudgetReservation {
+    /// Replace the reservation with the call's actual cost
+    pub fn record(mut self, usage: Option<&TokenUsage>) {
+        let reserved_usd = std::mem::take(&mut self.reserved_usd);
+        self.budget.settle(reserved_usd, usage);
+    }
+}
+
+impl Drop for BudgetReservation {
+    fn drop(&mut self) {
+        if self.reserved_usd > 0.0 {
+            self.budget.settle(self.reserved_usd, None);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use std::time::Duration;
+
+/// How long to wait between retry attempts.
+///
+/// The wait before retry `n` (counting from 0) is `base_delay * multiplier^n`, capped at
+/// `max_delay`. With jitter on, each wait is shortened by a random amount of up to a
+/// quarter, so clients that failed together don't all retry at the same instant.
+///
+/// Validation retries wait exactly this long. Rate limits and other transient API
+/// errors wait at least as long as the provider's `Retry-After` (or the error's
+/// suggested delay, see [`RStructorError::retry_delay`](crate::RStructorError::retry_delay)),
+/// and jitter only ever adds to that floor.
+///
+/// # Example
+///
+/// ```
+/// use rstructor::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new()
+///     .with_base_delay(Duration::from_millis(200))
+///     .with_multiplier(3.0)
+///     .with_max_delay(Duration::from_secs(2))
+///     .with_jitter(false);
+///
+/// assert_eq!(policy.backoff(0), Duration::from_millis(200));
+/// assert_eq!(policy.backoff(1), Duration::from_millis(600));
+/// assert_eq!(policy.backoff(2), Duration::from_millis(1800));
+/// assert_eq!(policy.backoff(3), Duration::from_secs(2));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Wait before the first retry
+    pub base_delay: Duration,
+    /// Upper bound on any single computed wait
+    pub max_delay: Duration,
+    /// Factor applied to the wait after each retry (1.0 = constant)
+    pub multiplier: f64,
+    /// Randomize each wait to spread out retries from concurrent clients
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Exponential backoff from 500ms, doubling up to 30s, with jitter
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The same wait before every retry, without jitter
+    pub fn fixed(delay: Duration) -> Self {
+        Self {
+            base_delay: delay,
+            max_delay: delay,
+            multiplier: 1.0,
+            jitter: false,
+        }
+    }
+
+    /// Set the wait before the first retry
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Set the upper bound on any single computed wait
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Set the growth factor between consecutive waits
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Turn jitter on or off
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Wait before retry `retry` (0 for the first retry), including jitter if enabled
+    pub fn backoff(&self, retry: usize) -> Duration {
+        let exponent = i32::try_from(retry).unwrap_or(i32::MAX);
+        let secs = self.base_delay.as_secs_f64() * self.multiplier.max(0.0).powi(exponent);
+        let delay = if secs.is_finite() && secs < self.max_delay.as_secs_f64() {
+            Duration::from_secs_f64(secs)
+        } else {
+            self.max_delay
+        };
+        if !self.jitter {
+            return delay;
+        }
+        let max_jitter_ms = (delay.as_millis() / 4) as u64;
+        delay - Duration::from_millis(random_u64() % (max_jitter_ms + 1))
+    }
+}
+
+/// A random number, for jitter
+pub(crate) fn random_u64() -> u64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    // RandomState is seeded randomly per instance, which is enough for jitter
+    std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish()
+}
@@ -1,8 +1,20 @@
+use std::sync::{Arc, Mutex};
+
+use crate::backend::pricing::{ModelPricing, builtin_pricing};
+use crate::error::Result;
+
 /// Token usage information from an LLM API call.
 ///
 /// This struct contains the token counts returned by LLM providers,
 /// which can be used for monitoring usage and debugging.
 ///
+/// [`total_tokens`](Self::total_tokens) is the provider-reported total when the
+/// response includes one, and `input_tokens + output_tokens` otherwise. The two can
+/// differ: cached prompt tokens are part of `input_tokens` and reasoning tokens are
+/// part of `output_tokens` for OpenAI, but Gemini counts thinking tokens only in its
+/// total. Pricing works from `input_tokens` and `output_tokens`, so use those for cost
+/// math and `total_tokens` for context-window and quota accounting.
+///
 /// # Example
 ///
 /// ```no_run
@@ -25,7 +37,7 @@
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TokenUsage {
     /// The model used for this request
     pub model: String,
@@ -33,6 +45,10 @@ pub struct TokenUsage {
     pub input_tokens: u64,
     /// Number of tokens in the output/completion
     pub output_tokens: u64,
+    /// Total token count reported by the provider, if the response included one
+    pub reported_total_tokens: Option<u64>,
+    /// Rates used by [`cost_usd`](Self::cost_usd) instead of the built-in pricing table
+    pub pricing: Option<ModelPricing>,
 }
 
 impl TokenUsage {
@@ -42,12 +58,71 @@ impl TokenUsage {
             model: model.into(),
             input_tokens,
             output_tokens,
+            reported_total_tokens: None,
+            pricing: None,
         }
     }
 
-    /// Total tokens used (input + output)
+    /// Attach the provider-reported total
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rstructor::TokenUsage;
+    ///
+    /// let usage = TokenUsage::new("gemini-2.5-flash", 100, 20);
+    /// assert_eq!(usage.total_tokens(), 120);
+    ///
+    /// // Thinking tokens show up only in Gemini's total
+    /// let usage = usage.with_total_tokens(Some(150));
+    /// assert_eq!(usage.total_tokens(), 150);
+    /// ```
+    pub fn with_total_tokens(mut self, total: Option<u64>) -> Self {
+        self.reported_total_tokens = total;
+        self
+    }
+
+    /// Total tokens used: the provider-reported total if available, otherwise
+    /// input + output
     pub fn total_tokens(&self) -> u64 {
-        self.input_tokens + self.output_tokens
+        self.reported_total_tokens
+            .unwrap_or(self.input_tokens + self.output_tokens)
+    }
+
+    /// Price this usage at the given rates (USD per million input/output tokens),
+    /// for custom or local models the built-in pricing table doesn't know
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rstructor::TokenUsage;
+    ///
+    /// let usage = TokenUsage::new("my-finetune", 2_000_000, 500_000).with_pricing(0.50, 2.00);
+    /// assert_eq!(usage.cost_usd(), Some(2.0));
+    /// ```
+    pub fn with_pricing(mut self, input_per_million: f64, output_per_million: f64) -> Self {
+        self.pricing = Some(ModelPricing::new(input_per_million, output_per_million));
+        self
+    }
+
+    /// Estimated cost in USD, from the rates set with [`with_pricing`](Self::with_pricing)
+    /// or else the built-in pricing table ([`PricingTable::builtin`](crate::PricingTable::builtin)).
+    ///
+    /// Returns `None` for models without known pricing rather than guessing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rstructor::TokenUsage;
+    ///
+    /// let usage = TokenUsage::new("gpt-4o-2024-08-06", 1_000_000, 100_000);
+    /// assert_eq!(usage.cost_usd(), Some(3.5));
+    /// assert_eq!(TokenUsage::new("llama3.2", 1000, 100).cost_usd(), None);
+    /// ```
+    pub fn cost_usd(&self) -> Option<f64> {
+        self.pricing
+            .or_else(|| builtin_pricing().get(&self.model))
+            .map(|pricing| pricing.cost(self.input_tokens, self.output_tokens))
     }
 }
 
@@ -76,6 +151,9 @@ impl TokenUsage {
 /// if let Some(usage) = result.usage {
 ///     println!("Used {} total tokens", usage.total_tokens());
 /// }
+///
+/// // A high attempt count suggests the schema or prompt needs work
+/// println!("Took {} attempt(s)", result.attempts);
 /// # Ok(())
 /// # }
 /// ```
@@ -85,17 +163,54 @@ pub struct MaterializeResult<T> {
     pub data: T,
     /// Token usage information (if available from the provider)
     pub usage: Option<TokenUsage>,
+    /// Number of attempts it took to get a valid response (1 = no retries)
+    pub attempts: usize,
+    /// The model's reasoning text, when the provider returns it separately
+    pub thinking: Option<String>,
+    /// Provider request id of the successful attempt (e.g. OpenAI's `x-request-id`),
+    /// for correlating a response with the provider's logs in support tickets
+    pub request_id: Option<String>,
 }
 
 impl<T> MaterializeResult<T> {
     /// Create a new MaterializeResult with data and usage
     pub fn new(data: T, usage: Option<TokenUsage>) -> Self {
-        Self { data, usage }
+        Self {
+            data,
+            usage,
+            attempts: 1,
+            thinking: None,
+            request_id: None,
+        }
     }
 
     /// Create a MaterializeResult with just data (no usage info)
     pub fn from_data(data: T) -> Self {
-        Self { data, usage: None }
+        Self::new(data, None)
+    }
+
+    /// Set the number of attempts the call took
+    pub fn with_attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Attach the model's reasoning text
+    pub fn with_thinking(mut self, thinking: Option<String>) -> Self {
+        self.thinking = thinking;
+        self
+    }
+
+    /// Attach the provider's request id
+    pub fn with_request_id(mut self, request_id: Option<String>) -> Self {
+        self.request_id = request_id;
+        self
+    }
+
+    /// Estimated cost of the call in USD (see [`TokenUsage::cost_usd`]), or `None`
+    /// without usage or pricing for the model
+    pub fn cost_usd(&self) -> Option<f64> {
+        self.usage.as_ref().and_then(TokenUsage::cost_usd)
     }
 
     /// Map the data to a new type
@@ -103,6 +218,9 @@ impl<T> MaterializeResult<T> {
         MaterializeResult {
             data: f(self.data),
             usage: self.usage,
+            attempts: self.attempts,
+            thinking: self.thinking,
+            request_id: self.request_id,
         }
     }
 }
@@ -114,11 +232,133 @@ pub struct GenerateResult {
     pub text: String,
     /// Token usage information (if available from the provider)
     pub usage: Option<TokenUsage>,
+    /// The model's reasoning text, when the provider returns it separately
+    pub thinking: Option<String>,
+    /// Provider request id (e.g. OpenAI's `x-request-id`), for correlating a response
+    /// with the provider's logs in support tickets
+    pub request_id: Option<String>,
 }
 
 impl GenerateResult {
     /// Create a new GenerateResult with text and usage
     pub fn new(text: String, usage: Option<TokenUsage>) -> Self {
-        Self { text, usage }
+        Self {
+            text,
+            usage,
+            thinking: None,
+            request_id: None,
+        }
     }
+
+    /// Attach the model's reasoning text
+    pub fn with_thinking(mut self, thinking: Option<String>) -> Self {
+        self.thinking = thinking;
+        self
+    }
+
+    /// Attach the provider's request id
+    pub fn with_request_id(mut self, request_id: Option<String>) -> Self {
+        self.request_id = request_id;
+        self
+    }
+}
+
+/// Aggregated token usage across multiple LLM calls.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct UsageTotals {
+    /// Number of calls that reported usage
+    pub requests: u64,
+    /// Total input/prompt tokens
+    pub input_tokens: u64,
+    /// Total output/completion tokens
+    pub output_tokens: u64,
+    /// Tokens in provider-reported totals beyond input and output (e.g. Gemini
+    /// thinking tokens)
+    pub other_tokens: u64,
+    /// Estimated cost in USD, summed from each call's [`TokenUsage::cost_usd`].
+    ///
+    /// Calls to models without known pricing add nothing, so this is `None` until
+    /// at least one priced call has been recorded.
+    pub cost_usd: Option<f64>,
+}
+
+impl UsageTotals {
+    /// Total tokens used (input + output + other), matching the sum of each call's
+    /// [`TokenUsage::total_tokens`]
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens + self.other_tokens
+    }
+}
+
+/// Thread-safe running total of token usage.
+///
+/// Cloning a `UsageTracker` shares the underlying totals, so a single tracker can
+/// be handed to concurrent tasks and read from anywhere.
+///
+/// # Example
+///
+/// ```
+/// use rstructor::{TokenUsage, UsageTracker};
+///
+/// let tracker = UsageTracker::new();
+/// let shared = tracker.clone();
+///
+/// shared.record(&TokenUsage::new("gpt-5-mini", 120, 30));
+/// tracker.record(&TokenUsage::new("gpt-5-mini", 80, 20));
+///
+/// let totals = tracker.totals();
+/// assert_eq!(totals.requests, 2);
+/// assert_eq!(totals.total_tokens(), 250);
+/// assert!(totals.cost_usd.is_some());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct UsageTracker {
+    totals: Arc<Mutex<UsageTotals>>,
+}
+
+impl UsageTracker {
+    /// Create a new tracker with zeroed totals
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a call's usage to the running total
+    pub fn record(&self, usage: &TokenUsage) {
+        let mut totals = self.totals.lock().unwrap_or_else(|e| e.into_inner());
+        totals.requests += 1;
+        totals.input_tokens += usage.input_tokens;
+        totals.output_tokens += usage.output_tokens;
+        totals.other_tokens += usage
+            .total_tokens()
+            .saturating_sub(usage.input_tokens + usage.output_tokens);
+        if let Some(cost) = usage.cost_usd() {
+            totals.cost_usd = Some(totals.cost_usd.unwrap_or(0.0) + cost);
+        }
+    }
+
+    /// Snapshot of the current totals
+    pub fn totals(&self) -> UsageTotals {
+        *self.totals.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Reset the totals to zero
+    pub fn reset(&self) {
+        *self.totals.lock().unwrap_or_else(|e| e.into_inner()) = UsageTotals::default();
+    }
+}
+
+/// A single completed item from [`LLMClient::materialize_many_stream`](crate::LLMClient::materialize_many_stream).
+///
+/// Items are yielded as they complete, so `index` refers to the position of the
+/// prompt in the input slice rather than the order of arrival.
+#[derive(Debug)]
+pub struct BatchItem<T> {
+    /// Index of the prompt in the input slice
+    pub index: usize,
+    /// The materialized value or the error for this prompt
+    pub result: Result<T>,
+    /// Token usage for this prompt (if available from the provider)
+    pub usage: Option<TokenUsage>,
+    /// Running usage totals across the batch, including this item
+    pub totals: UsageTotals,
 }
@@ -1,14 +1,24 @@
+use crate::backend::retry::random_u64;
 use crate::backend::{
-    ChatMessage, MaterializeInternalOutput, TokenUsage, ValidationFailureContext,
+    ChatMessage, ChatRole, MaterializeInternalOutput, RequestOptions, RetryPolicy, TokenUsage,
+    ValidationFailureContext,
 };
 use crate::error::{ApiErrorKind, RStructorError, Result};
 use crate::model::Instructor;
+use crate::schema::{
+    SIGNED_INTEGER_KEY_PATTERN, SchemaTransform, SchemaTransformKind, UNSIGNED_INTEGER_KEY_PATTERN,
+};
+use futures::stream::{BoxStream, StreamExt};
 use reqwest::Response;
+use reqwest::header::{HeaderMap, HeaderName};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::sync::{LazyLock, Mutex};
 use std::time::Duration;
-use tokio::time::sleep;
+use tokio::time::{Instant, sleep, timeout_at};
 use tracing::{debug, error, info, trace, warn};
 
 /// Prepare a JSON schema for strict mode by recursively adding required fields
@@ -27,15 +37,127 @@ use tracing::{debug, error, info, trace, warn};
 ///
 /// A new schema Value with strict mode requirements added to all objects
 pub fn prepare_strict_schema(schema: &crate::schema::Schema) -> Value {
+    prepare_strict_schema_with_report(schema).0
+}
+
+/// [`prepare_strict_schema`], also returning the changes it made.
+pub fn prepare_strict_schema_with_report(
+    schema: &crate::schema::Schema,
+) -> (Value, Vec<SchemaTransform>) {
     let mut schema_json = schema.to_json();
-    add_additional_properties_false(&mut schema_json);
-    schema_json
+    let mut log = Vec::new();
+    add_additional_properties_false_at(&mut schema_json, "#", &mut log);
+    (schema_json, log)
+}
+
+/// Property holding the actual value when a schema root is wrapped in an object
+const WRAPPED_VALUE_KEY: &str = "value";
+
+/// Wrap a schema whose root isn't an object in an object with a single `value` property.
+///
+/// Strict structured output modes (OpenAI, Anthropic, Grok) only accept an object at the
+/// root, but an enum's schema is a bare `oneOf` (or a string `enum`). Definitions stay at
+/// the root so `$ref`s keep resolving. Returns whether the schema was wrapped; if so,
+/// responses must go through [`unwrap_value_root`] before parsing.
+pub fn wrap_non_object_root(schema: &mut Value) -> bool {
+    let Some(obj) = schema.as_object_mut() else {
+        return false;
+    };
+    let is_object = obj.get("type").and_then(|t| t.as_str()) == Some("object")
+        || obj.contains_key("properties");
+    if is_object {
+        return false;
+    }
+
+    let defs: Vec<(String, Value)> = ["$defs", "definitions"]
+        .into_iter()
+        .filter_map(|key| obj.remove(key).map(|defs| (key.to_string(), defs)))
+        .collect();
+    let inner = std::mem::take(schema);
+    let mut wrapper = serde_json::json!({
+        "type": "object",
+        "properties": { WRAPPED_VALUE_KEY: inner },
+        "required": [WRAPPED_VALUE_KEY],
+        "additionalProperties": false
+    });
+    if let Some(wrapper_obj) = wrapper.as_object_mut() {
+        wrapper_obj.extend(defs);
+    }
+    *schema = wrapper;
+    debug!("Wrapped non-object schema root in an object with a `value` property");
+    true
+}
+
+/// Extract the `value` property from a response to a schema wrapped by
+/// [`wrap_non_object_root`].
+///
+/// Responses that aren't a wrapper object are returned unchanged, so parsing reports the
+/// problem against the original text.
+pub fn unwrap_value_root(raw_response: String, lenient: bool) -> String {
+    let sanitized = if lenient {
+        sanitize_control_characters(&raw_response)
+    } else {
+        None
+    };
+    let json = sanitized.as_deref().unwrap_or(&raw_response);
+    match serde_json::from_str::<Value>(json) {
+        Ok(Value::Object(mut obj)) if obj.len() == 1 && obj.contains_key(WRAPPED_VALUE_KEY) => obj
+            .remove(WRAPPED_VALUE_KEY)
+            .map_or(raw_response, |value| value.to_string()),
+        _ => raw_response,
+    }
+}
+
+/// Allow `null` for a property that strict mode forces into `required`.
+///
+/// A single `type` gains `"null"` (and an `enum` gains a `null` value); schemas without
+/// one, such as `$ref`s or `oneOf` enums, are offered as `anyOf` alongside `null`.
+fn make_nullable(schema: &mut Value) {
+    let Some(obj) = schema.as_object_mut() else {
+        return;
+    };
+    match obj.get("type").cloned() {
+        Some(Value::String(ty)) => {
+            obj.insert("type".to_string(), serde_json::json!([ty, "null"]));
+        }
+        Some(Value::Array(mut types)) => {
+            if !types.iter().any(|t| t == "null") {
+                types.push(Value::String("null".to_string()));
+            }
+            obj.insert("type".to_string(), Value::Array(types));
+        }
+        _ => {
+            let inner = std::mem::take(schema);
+            *schema = serde_json::json!({ "anyOf": [inner, { "type": "null" }] });
+            return;
+        }
+    }
+    if let Some(Value::Array(values)) = obj.get_mut("enum")
+        && !values.contains(&Value::Null)
+    {
+        values.push(Value::Null);
+    }
 }
 
 /// Recursively prepares a JSON schema for strict mode by adding:
 /// 1. `additionalProperties: false` to all object types
 /// 2. `required` array with all property keys (if not already present)
-fn add_additional_properties_false(schema: &mut Value) {
+///
+/// Properties the original `required` array left out (`Option` fields, including those
+/// inside enum variants, or every property if there was no `required` array) become
+/// nullable, so they stay optional in practice: the model must send the key but may
+/// send `null`, which [`parse_and_validate_response`] drops again so a
+/// `#[serde(default)]` field gets its default.
+///
+/// Map-style objects (a schema-valued `additionalProperties` and no `properties`) keep
+/// their value schema, which is processed recursively instead.
+///
+/// `path` locates `schema` within the root for the changes recorded in `log`.
+fn add_additional_properties_false_at(
+    schema: &mut Value,
+    path: &str,
+    log: &mut Vec<SchemaTransform>,
+) {
     if let Some(obj) = schema.as_object_mut() {
         // Check if this is an object type schema
         let is_object_type = obj
@@ -46,8 +168,22 @@ fn add_additional_properties_false(schema: &mut Value) {
         // Also check if it has properties (even without explicit type: object)
         let has_properties = obj.contains_key("properties");
 
-        if is_object_type || has_properties {
-            obj.insert("additionalProperties".to_string(), serde_json::json!(false));
+        // Maps (e.g. HashMap<String, V>) describe their values through additionalProperties
+        let is_map = !has_properties
+            && obj
+                .get("additionalProperties")
+                .is_some_and(|additional| additional.is_object());
+
+        if is_map {
+            if let Some(additional) = obj.get_mut("additionalProperties") {
+                let child = pointer_child(path, &["additionalProperties"]);
+                add_additional_properties_false_at(additional, &child, log);
+            }
+        } else if is_object_type || has_properties {
+            let previous = obj.insert("additionalProperties".to_string(), serde_json::json!(false));
+            if previous != Some(Value::Bool(false)) {
+                record_transform(log, path, SchemaTransformKind::AdditionalPropertiesClosed);
+            }
 
             // OpenAI strict mode requires ALL properties to be listed in `required`
             // This overrides any existing `required` array since the derive macro
@@ -55,113 +191,92 @@ fn add_additional_properties_false(schema: &mut Value) {
             if let Some(properties) = obj.get("properties")
                 && let Some(props_obj) = properties.as_object()
             {
+                // Without a `required` array every property was optional
+                let optional_keys: Vec<String> = match obj.get("required") {
+                    Some(Value::Array(required)) => props_obj
+                        .keys()
+                        .filter(|key| !required.iter().any(|r| r.as_str() == Some(key.as_str())))
+                        .cloned()
+                        .collect(),
+                    _ => props_obj.keys().cloned().collect(),
+                };
                 let required_keys: Vec<Value> =
                     props_obj.keys().map(|k| serde_json::json!(k)).collect();
                 if !required_keys.is_empty() {
                     obj.insert("required".to_string(), Value::Array(required_keys));
                 }
+                if let Some(props_obj) = obj.get_mut("properties").and_then(Value::as_object_mut) {
+                    for key in &optional_keys {
+                        if let Some(prop_schema) = props_obj.get_mut(key) {
+                            make_nullable(prop_schema);
+                            record_transform(
+                                log,
+                                &pointer_child(path, &["properties", key]),
+                                SchemaTransformKind::MadeNullable,
+                            );
+                        }
+                    }
+                }
             }
         }
 
-        // Recursively process nested schemas
-        // Process 'properties' object
-        if let Some(properties) = obj.get_mut("properties")
-            && let Some(props_obj) = properties.as_object_mut()
-        {
-            for (_key, prop_schema) in props_obj.iter_mut() {
-                add_additional_properties_false(prop_schema);
-            }
-        }
-
-        // Process 'items' for arrays
-        if let Some(items) = obj.get_mut("items") {
-            add_additional_properties_false(items);
-        }
-
-        // Process 'additionalItems' for arrays
-        if let Some(additional_items) = obj.get_mut("additionalItems") {
-            add_additional_properties_false(additional_items);
-        }
-
-        // Process 'allOf' array
-        if let Some(all_of) = obj.get_mut("allOf")
-            && let Some(arr) = all_of.as_array_mut()
-        {
-            for item in arr.iter_mut() {
-                add_additional_properties_false(item);
-            }
-        }
-
-        // Process 'anyOf' array
-        if let Some(any_of) = obj.get_mut("anyOf")
-            && let Some(arr) = any_of.as_array_mut()
-        {
-            for item in arr.iter_mut() {
-                add_additional_properties_false(item);
-            }
-        }
-
-        // Process 'oneOf' array
-        if let Some(one_of) = obj.get_mut("oneOf")
-            && let Some(arr) = one_of.as_array_mut()
-        {
-            for item in arr.iter_mut() {
-                add_additional_properties_false(item);
-            }
-        }
-
-        // Process 'definitions' / '$defs' for reusable schemas
-        if let Some(definitions) = obj.get_mut("definitions")
-            && let Some(defs_obj) = definitions.as_object_mut()
-        {
-            for (_key, def_schema) in defs_obj.iter_mut() {
-                add_additional_properties_false(def_schema);
+        // Recursively process nested schemas: single subschemas, keyed subschemas and
+        // lists of subschemas
+        for keyword in ["properties", "definitions", "$defs", "patternProperties"] {
+            if let Some(subschemas) = obj.get_mut(keyword).and_then(Value::as_object_mut) {
+                for (key, subschema) in subschemas.iter_mut() {
+                    let child = pointer_child(path, &[keyword, key]);
+                    add_additional_properties_false_at(subschema, &child, log);
+                }
             }
         }
-
-        if let Some(defs) = obj.get_mut("$defs")
-            && let Some(defs_obj) = defs.as_object_mut()
-        {
-            for (_key, def_schema) in defs_obj.iter_mut() {
-                add_additional_properties_false(def_schema);
+        for keyword in [
+            "items",
+            "additionalItems",
+            "not",
+            "if",
+            "then",
+            "else",
+            "contains",
+            "propertyNames",
+        ] {
+            if let Some(subschema) = obj.get_mut(keyword) {
+                let child = pointer_child(path, &[keyword]);
+                add_additional_properties_false_at(subschema, &child, log);
             }
         }
-
-        // Process 'not' schema
-        if let Some(not_schema) = obj.get_mut("not") {
-            add_additional_properties_false(not_schema);
-        }
-
-        // Process 'if', 'then', 'else' schemas
-        if let Some(if_schema) = obj.get_mut("if") {
-            add_additional_properties_false(if_schema);
-        }
-        if let Some(then_schema) = obj.get_mut("then") {
-            add_additional_properties_false(then_schema);
-        }
-        if let Some(else_schema) = obj.get_mut("else") {
-            add_additional_properties_false(else_schema);
-        }
-
-        // Process 'patternProperties' object
-        if let Some(pattern_props) = obj.get_mut("patternProperties")
-            && let Some(pattern_obj) = pattern_props.as_object_mut()
-        {
-            for (_pattern, pattern_schema) in pattern_obj.iter_mut() {
-                add_additional_properties_false(pattern_schema);
+        for keyword in ["allOf", "anyOf", "oneOf"] {
+            if let Some(subschemas) = obj.get_mut(keyword).and_then(Value::as_array_mut) {
+                for (i, subschema) in subschemas.iter_mut().enumerate() {
+                    let child = pointer_child(path, &[keyword, &i.to_string()]);
+                    add_additional_properties_false_at(subschema, &child, log);
+                }
             }
         }
+    }
+}
 
-        // Process 'contains' for arrays
-        if let Some(contains) = obj.get_mut("contains") {
-            add_additional_properties_false(contains);
-        }
+/// [`add_additional_properties_false_at`] for a root schema, discarding the report
+#[cfg(test)]
+fn add_additional_properties_false(schema: &mut Value) {
+    add_additional_properties_false_at(schema, "#", &mut Vec::new());
+}
 
-        // Process 'propertyNames' schema
-        if let Some(property_names) = obj.get_mut("propertyNames") {
-            add_additional_properties_false(property_names);
-        }
+/// Extend a JSON Pointer fragment with `segments`, escaping `~` and `/` in each.
+fn pointer_child(path: &str, segments: &[&str]) -> String {
+    let mut child = path.to_string();
+    for segment in segments {
+        child.push('/');
+        child.push_str(&segment.replace('~', "~0").replace('/', "~1"));
     }
+    child
+}
+
+fn record_transform(log: &mut Vec<SchemaTransform>, path: &str, kind: SchemaTransformKind) {
+    log.push(SchemaTransform {
+        path: path.to_string(),
+        kind,
+    });
 }
 
 /// Information about adjacently tagged enum transformations for response conversion
@@ -284,7 +399,8 @@ pub fn transform_internally_to_adjacently_tagged(
 ///
 /// Gemini's structured outputs API doesn't support certain JSON Schema keywords like
 /// `examples`, `additionalProperties`, `title`, etc. This function recursively removes
-/// them from the schema.
+/// them from the schema. Optional properties (those missing from `required`) get
+/// Gemini's `nullable: true`, so the model can return null for them.
 ///
 /// # Arguments
 ///
@@ -294,22 +410,37 @@ pub fn transform_internally_to_adjacently_tagged(
 ///
 /// A new schema Value with unsupported keywords removed
 pub fn prepare_gemini_schema(schema: &crate::schema::Schema) -> Value {
+    prepare_gemini_schema_with_report(schema).0
+}
+
+/// [`prepare_gemini_schema`], also returning the changes it made.
+pub fn prepare_gemini_schema_with_report(
+    schema: &crate::schema::Schema,
+) -> (Value, Vec<SchemaTransform>) {
     let mut schema_json = schema.to_json();
-    strip_gemini_unsupported_keywords(&mut schema_json);
-    schema_json
+    let mut log = Vec::new();
+    strip_gemini_unsupported_keywords_logged(&mut schema_json, &mut log);
+    (schema_json, log)
 }
 
-/// Recursively removes keywords unsupported by Gemini's structured outputs.
+/// [`strip_gemini_unsupported_keywords_logged`], discarding the report
+#[cfg(test)]
 fn strip_gemini_unsupported_keywords(schema: &mut Value) {
+    strip_gemini_unsupported_keywords_logged(schema, &mut Vec::new());
+}
+
+/// Recursively removes keywords unsupported by Gemini's structured outputs, recording
+/// each change in `log`.
+fn strip_gemini_unsupported_keywords_logged(schema: &mut Value, log: &mut Vec<SchemaTransform>) {
     // First, resolve any $ref references by inlining definitions
-    resolve_refs_for_gemini(schema);
+    resolve_refs_for_gemini(schema, log);
 
-    strip_gemini_unsupported_keywords_recursive(schema);
+    strip_gemini_unsupported_keywords_recursive(schema, "#", log);
 }
 
 /// Resolves $ref references by inlining definitions for Gemini compatibility.
 /// This handles recursive schemas by inlining to a limited depth.
-fn resolve_refs_for_gemini(schema: &mut Value) {
+fn resolve_refs_for_gemini(schema: &mut Value, log: &mut Vec<SchemaTransform>) {
     // Extract $defs if present
     let defs = if let Some(obj) = schema.as_object_mut() {
         obj.remove("$defs").or_else(|| obj.remove("definitions"))
@@ -328,23 +459,42 @@ fn resolve_refs_for_gemini(schema: &mut Value) {
             && let Some(defs_obj) = defs.as_object()
             && let Some(definition) = defs_obj.get(def_name)
         {
+            record_transform(
+                log,
+                "#",
+                SchemaTransformKind::RefInlined {
+                    reference: ref_str.to_string(),
+                },
+            );
             // Replace schema with the definition
             *schema = definition.clone();
 
             // Recursively inline refs in the new schema (with depth limit)
-            inline_refs_recursive(schema, &defs, 3);
+            inline_refs_recursive(schema, &defs, 3, "#", log);
         }
     }
 }
 
 /// Recursively inlines $ref references with a depth limit to prevent infinite recursion.
-fn inline_refs_recursive(schema: &mut Value, defs: &Value, depth: usize) {
+fn inline_refs_recursive(
+    schema: &mut Value,
+    defs: &Value,
+    depth: usize,
+    path: &str,
+    log: &mut Vec<SchemaTransform>,
+) {
     if depth == 0 {
         // At max depth, replace self-references with a simple object schema
         if let Some(obj) = schema.as_object_mut()
-            && obj.contains_key("$ref")
+            && let Some(ref_value) = obj.remove("$ref")
         {
-            obj.remove("$ref");
+            record_transform(
+                log,
+                path,
+                SchemaTransformKind::RecursionTruncated {
+                    reference: ref_value.as_str().unwrap_or_default().to_string(),
+                },
+            );
             obj.insert("type".to_string(), Value::String("object".to_string()));
             obj.insert(
                 "description".to_string(),
@@ -364,10 +514,17 @@ fn inline_refs_recursive(schema: &mut Value, defs: &Value, depth: usize) {
             && let Some(defs_obj) = defs.as_object()
             && let Some(definition) = defs_obj.get(def_name)
         {
+            record_transform(
+                log,
+                path,
+                SchemaTransformKind::RefInlined {
+                    reference: ref_str.to_string(),
+                },
+            );
             // Replace with inline definition
             *schema = definition.clone();
             // Continue recursively with reduced depth
-            inline_refs_recursive(schema, defs, depth - 1);
+            inline_refs_recursive(schema, defs, depth - 1, path, log);
             return;
         }
 
@@ -375,44 +532,23 @@ fn inline_refs_recursive(schema: &mut Value, defs: &Value, depth: usize) {
         if let Some(properties) = obj.get_mut("properties")
             && let Some(props_obj) = properties.as_object_mut()
         {
-            for prop_schema in props_obj.values_mut() {
-                inline_refs_recursive(prop_schema, defs, depth);
+            for (key, prop_schema) in props_obj.iter_mut() {
+                let child = pointer_child(path, &["properties", key]);
+                inline_refs_recursive(prop_schema, defs, depth, &child, log);
             }
         }
 
         if let Some(items) = obj.get_mut("items") {
-            inline_refs_recursive(items, defs, depth);
-        }
-
-        if let Some(prefix_items) = obj.get_mut("prefixItems")
-            && let Some(arr) = prefix_items.as_array_mut()
-        {
-            for item in arr.iter_mut() {
-                inline_refs_recursive(item, defs, depth);
-            }
-        }
-
-        if let Some(one_of) = obj.get_mut("oneOf")
-            && let Some(arr) = one_of.as_array_mut()
-        {
-            for item in arr.iter_mut() {
-                inline_refs_recursive(item, defs, depth);
-            }
-        }
-
-        if let Some(any_of) = obj.get_mut("anyOf")
-            && let Some(arr) = any_of.as_array_mut()
-        {
-            for item in arr.iter_mut() {
-                inline_refs_recursive(item, defs, depth);
-            }
+            let child = pointer_child(path, &["items"]);
+            inline_refs_recursive(items, defs, depth, &child, log);
         }
 
-        if let Some(all_of) = obj.get_mut("allOf")
-            && let Some(arr) = all_of.as_array_mut()
-        {
-            for item in arr.iter_mut() {
-                inline_refs_recursive(item, defs, depth);
+        for keyword in ["prefixItems", "oneOf", "anyOf", "allOf"] {
+            if let Some(arr) = obj.get_mut(keyword).and_then(Value::as_array_mut) {
+                for (i, item) in arr.iter_mut().enumerate() {
+                    let child = pointer_child(path, &[keyword, &i.to_string()]);
+                    inline_refs_recursive(item, defs, depth, &child, log);
+                }
             }
         }
 
@@ -420,7 +556,8 @@ fn inline_refs_recursive(schema: &mut Value, defs: &Value, depth: usize) {
         if let Some(additional) = obj.get_mut("additionalProperties")
             && additional.is_object()
         {
-            inline_refs_recursive(additional, defs, depth);
+            let child = pointer_child(path, &["additionalProperties"]);
+            inline_refs_recursive(additional, defs, depth, &child, log);
         }
     }
 }
@@ -526,7 +663,7 @@ fn transform_adjacently_tagged_to_internally_tagged(
         }
 
         // Remove the content field itself
-        properties.remove(content_key);
+        properties.shift_remove(content_key);
     }
 
     // Update required array
@@ -552,23 +689,85 @@ fn transform_adjacently_tagged_to_internally_tagged(
 }
 
 /// Internal function that strips unsupported keywords after refs are resolved.
-fn strip_gemini_unsupported_keywords_recursive(schema: &mut Value) {
+fn strip_gemini_unsupported_keywords_recursive(
+    schema: &mut Value,
+    path: &str,
+    log: &mut Vec<SchemaTransform>,
+) {
     if let Some(obj) = schema.as_object_mut() {
-        // Remove unsupported keywords
-        obj.remove("examples");
-        obj.remove("title");
-        obj.remove("$schema");
-        obj.remove("$id");
-        obj.remove("default");
-        obj.remove("$defs");
-        obj.remove("definitions");
-        obj.remove("$ref"); // Should be resolved by now, but remove if any remain
+        // Integer-keyed maps get numeric placeholder keys below, once their
+        // `propertyNames` has been stripped
+        let integer_keys = obj
+            .get("propertyNames")
+            .and_then(|names| names.get("pattern"))
+            .and_then(Value::as_str)
+            .is_some_and(|pattern| {
+                pattern == SIGNED_INTEGER_KEY_PATTERN || pattern == UNSIGNED_INTEGER_KEY_PATTERN
+            });
+
+        // Remove unsupported keywords. `$ref`s should be resolved by now, but any that
+        // remain are removed too
+        for keyword in [
+            "examples",
+            "example",
+            "title",
+            "$schema",
+            "$id",
+            "default",
+            "contentMediaType",
+            "contentEncoding",
+            "$defs",
+            "definitions",
+            "$ref",
+            "propertyNames",
+        ] {
+            if obj.remove(keyword).is_some() {
+                record_transform(
+                    log,
+                    path,
+                    SchemaTransformKind::KeywordStripped {
+                        keyword: keyword.to_string(),
+                    },
+                );
+            }
+        }
+
+        // Gemini only accepts a few formats per type and rejects requests with others
+        if let Some(format) = obj.get("format").and_then(Value::as_str)
+            && !gemini_supports_format(obj.get("type"), format)
+        {
+            obj.remove("format");
+            record_transform(
+                log,
+                path,
+                SchemaTransformKind::KeywordStripped {
+                    keyword: "format".to_string(),
+                },
+            );
+        }
 
         // Handle additionalProperties: remove if boolean, keep if it's a schema for maps
         if let Some(additional) = obj.get("additionalProperties")
             && additional.is_boolean()
         {
             obj.remove("additionalProperties");
+            record_transform(
+                log,
+                path,
+                SchemaTransformKind::KeywordStripped {
+                    keyword: "additionalProperties".to_string(),
+                },
+            );
+        }
+
+        // Optional fields (Option<T>) are the properties missing from `required`; mark
+        // them nullable so Gemini may return null for them
+        for name in mark_optional_properties_nullable(obj) {
+            record_transform(
+                log,
+                &pointer_child(path, &["properties", &name]),
+                SchemaTransformKind::MadeNullable,
+            );
         }
 
         // For object types with additionalProperties but no properties, this is a Map type
@@ -599,7 +798,11 @@ fn strip_gemini_unsupported_keywords_recursive(schema: &mut Value) {
 
             // Try to extract specific keys from description (e.g., "Keys: [info, warn, error]")
             // This allows us to provide concrete enum variants as keys instead of generic placeholders
-            let mut keys = vec!["key1".to_string(), "key2".to_string(), "key3".to_string()];
+            let mut keys = if integer_keys {
+                vec!["1".to_string(), "2".to_string(), "3".to_string()]
+            } else {
+                vec!["key1".to_string(), "key2".to_string(), "key3".to_string()]
+            };
             if let Some(start) = existing_desc.find("Keys: [")
                 && let Some(end) = existing_desc[start..].find(']')
             {
@@ -622,11 +825,26 @@ fn strip_gemini_unsupported_keywords_recursive(schema: &mut Value) {
             }
 
             obj.insert("properties".to_string(), Value::Object(placeholder_props));
+            record_transform(
+                log,
+                path,
+                SchemaTransformKind::MapRewritten { keys: keys.clone() },
+            );
 
             // Update description to explain this is a map with specific or example keys
             let map_desc = if existing_desc.contains("Keys: [") {
                 // If keys were specified, keep the original description as-is
                 existing_desc
+            } else if integer_keys {
+                let hint = format!(
+                    "Object whose keys are integers written as strings ({} are examples)",
+                    keys.join(", ")
+                );
+                if existing_desc.is_empty() {
+                    hint
+                } else {
+                    format!("{}. {}", existing_desc, hint)
+                }
             } else if existing_desc.is_empty() {
                 format!(
                     "Object with any string keys ({} are examples - use actual meaningful key names)",
@@ -646,14 +864,16 @@ fn strip_gemini_unsupported_keywords_recursive(schema: &mut Value) {
         if let Some(properties) = obj.get_mut("properties")
             && let Some(props_obj) = properties.as_object_mut()
         {
-            for prop_schema in props_obj.values_mut() {
-                strip_gemini_unsupported_keywords_recursive(prop_schema);
+            for (key, prop_schema) in props_obj.iter_mut() {
+                let child = pointer_child(path, &["properties", key]);
+                strip_gemini_unsupported_keywords_recursive(prop_schema, &child, log);
             }
         }
 
         // Process 'items' for arrays
         if let Some(items) = obj.get_mut("items") {
-            strip_gemini_unsupported_keywords_recursive(items);
+            let child = pointer_child(path, &["items"]);
+            strip_gemini_unsupported_keywords_recursive(items, &child, log);
         }
 
         // Handle tuples (prefixItems) - Gemini doesn't support prefixItems
@@ -662,11 +882,18 @@ fn strip_gemini_unsupported_keywords_recursive(schema: &mut Value) {
             && let Some(arr) = prefix_items.as_array()
         {
             // Recursively process each item schema
+            record_transform(
+                log,
+                path,
+                SchemaTransformKind::TupleFlattened { len: arr.len() },
+            );
             let mut processed_items: Vec<Value> = arr
                 .iter()
-                .map(|item| {
+                .enumerate()
+                .map(|(i, item)| {
                     let mut item_clone = item.clone();
-                    strip_gemini_unsupported_keywords_recursive(&mut item_clone);
+                    let child = pointer_child(path, &["prefixItems", &i.to_string()]);
+                    strip_gemini_unsupported_keywords_recursive(&mut item_clone, &child, log);
                     item_clone
                 })
                 .collect();
@@ -710,21 +937,13 @@ fn strip_gemini_unsupported_keywords_recursive(schema: &mut Value) {
             );
         }
 
-        // Process 'allOf' array
-        if let Some(all_of) = obj.get_mut("allOf")
-            && let Some(arr) = all_of.as_array_mut()
-        {
-            for item in arr.iter_mut() {
-                strip_gemini_unsupported_keywords_recursive(item);
-            }
-        }
-
-        // Process 'anyOf' array
-        if let Some(any_of) = obj.get_mut("anyOf")
-            && let Some(arr) = any_of.as_array_mut()
-        {
-            for item in arr.iter_mut() {
-                strip_gemini_unsupported_keywords_recursive(item);
+        // Process 'allOf' and 'anyOf' arrays
+        for keyword in ["allOf", "anyOf"] {
+            if let Some(arr) = obj.get_mut(keyword).and_then(Value::as_array_mut) {
+                for (i, item) in arr.iter_mut().enumerate() {
+                    let child = pointer_child(path, &[keyword, &i.to_string()]);
+                    strip_gemini_unsupported_keywords_recursive(item, &child, log);
+                }
             }
         }
 
@@ -776,8 +995,16 @@ fn strip_gemini_unsupported_keywords_recursive(schema: &mut Value) {
                 // Transform each variant
                 *arr = arr
                     .iter()
-                    .map(|item| {
+                    .enumerate()
+                    .map(|(i, item)| {
                         if let Some((t, c, v)) = detect_adjacently_tagged_variant(item) {
+                            record_transform(
+                                log,
+                                &pointer_child(path, &["oneOf", &i.to_string()]),
+                                SchemaTransformKind::AdjacentlyTaggedFlattened {
+                                    content_key: c.clone(),
+                                },
+                            );
                             transform_adjacently_tagged_to_internally_tagged(item, &t, &c, &v)
                         } else {
                             // Unit variant - leave as is
@@ -788,8 +1015,9 @@ fn strip_gemini_unsupported_keywords_recursive(schema: &mut Value) {
             }
 
             // Now recursively process all variants
-            for item in arr.iter_mut() {
-                strip_gemini_unsupported_keywords_recursive(item);
+            for (i, item) in arr.iter_mut().enumerate() {
+                let child = pointer_child(path, &["oneOf", &i.to_string()]);
+                strip_gemini_unsupported_keywords_recursive(item, &child, log);
             }
         }
 
@@ -797,9 +1025,59 @@ fn strip_gemini_unsupported_keywords_recursive(schema: &mut Value) {
         if let Some(additional) = obj.get_mut("additionalProperties")
             && additional.is_object()
         {
-            strip_gemini_unsupported_keywords_recursive(additional);
+            let child = pointer_child(path, &["additionalProperties"]);
+            strip_gemini_unsupported_keywords_recursive(additional, &child, log);
+        }
+    }
+}
+
+/// Whether Gemini's response schema accepts `format` on a schema of type `ty`: `date-time`
+/// and `enum` for strings, `float` and `double` for numbers, `int32` and `int64` for
+/// integers.
+fn gemini_supports_format(ty: Option<&Value>, format: &str) -> bool {
+    let ty = match ty {
+        Some(Value::String(ty)) => ty.as_str(),
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|ty| *ty != "null")
+            .unwrap_or_default(),
+        _ => "",
+    };
+    matches!(
+        (ty, format),
+        ("string", "date-time" | "enum")
+            | ("number", "float" | "double")
+            | ("integer", "int32" | "int64")
+    )
+}
+
+/// Set Gemini's `nullable: true` on every property not listed in `required`, returning
+/// the names of the properties marked.
+fn mark_optional_properties_nullable(obj: &mut serde_json::Map<String, Value>) -> Vec<String> {
+    let required: Vec<String> = obj
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| {
+            required
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut marked = Vec::new();
+    if let Some(properties) = obj.get_mut("properties").and_then(Value::as_object_mut) {
+        for (name, prop_schema) in properties.iter_mut() {
+            if !required.contains(name)
+                && let Some(prop_obj) = prop_schema.as_object_mut()
+            {
+                prop_obj.insert("nullable".to_string(), Value::Bool(true));
+                marked.push(name.clone());
+            }
         }
     }
+    marked
 }
 
 /// JSON Schema format specification for structured outputs.
@@ -829,6 +1107,10 @@ pub enum ResponseFormat {
         /// The JSON schema specification
         json_schema: JsonSchemaFormat,
     },
+    /// JSON mode: any syntactically valid JSON object, with the schema given in the
+    /// prompt (see [`json_mode_instruction`])
+    #[serde(rename = "json_object")]
+    JsonObject,
 }
 
 impl ResponseFormat {
@@ -851,27 +1133,51 @@ impl ResponseFormat {
     }
 }
 
+/// System message asking for JSON matching `schema`, for providers whose JSON mode only
+/// guarantees syntactically valid JSON.
+///
+/// Sent ahead of the conversation, so it stays in place across retries.
+pub fn json_mode_instruction(schema: &Value) -> ChatMessage {
+    ChatMessage::system(format!(
+        "Respond only with a JSON value that matches this JSON Schema, with no other text:\n{}",
+        schema
+    ))
+}
+
 /// Parse a raw JSON response and validate it against the Instructor trait.
 ///
 /// This function handles:
-/// 1. JSON parsing with detailed error messages
-/// 2. Custom validation via the Instructor trait
+/// 1. Control character sanitization (only when `lenient` is set)
+/// 2. JSON parsing with detailed error messages, retried without `null`s for
+///    properties the schema doesn't require
+/// 3. Custom validation via the Instructor trait
 ///
 /// # Arguments
 ///
 /// * `raw_response` - The raw JSON string from the LLM
+/// * `lenient` - Whether to strip stray control characters before parsing
 ///
 /// # Returns
 ///
 /// The parsed and validated data, or an error with validation context
 pub fn parse_and_validate_response<T>(
     raw_response: &str,
+    lenient: bool,
 ) -> std::result::Result<T, (RStructorError, Option<ValidationFailureContext>)>
 where
     T: Instructor + DeserializeOwned,
 {
+    let sanitized = if lenient {
+        sanitize_control_characters(raw_response)
+    } else {
+        None
+    };
+    let json = sanitized.as_deref().unwrap_or(raw_response);
+
     // Parse the JSON content into our target type
-    let result: T = match serde_json::from_str(raw_response) {
+    let parsed =
+        serde_json::from_str(json).or_else(|e| parse_without_optional_nulls::<T>(json).ok_or(e));
+    let mut result: T = match parsed {
         Ok(parsed) => parsed,
         Err(e) => {
             let error_msg = format!(
@@ -906,9 +1212,209 @@ where
         ));
     }
 
+    // Let the type clean up the validated value
+    if let Err(e) = result.normalize() {
+        error!(error = ?e, "Normalization failed");
+        let error_msg = e.to_string();
+        return Err((
+            e,
+            Some(ValidationFailureContext::new(
+                error_msg,
+                raw_response.to_string(),
+            )),
+        ));
+    }
+
     Ok(result)
 }
 
+/// Parse `json` again without the `null`s [`strip_optional_nulls`] removes, if any.
+///
+/// Strict mode makes properties the schema left optional nullable, but a
+/// `#[serde(default)]` field only accepts its own type, so a `null` there would fail
+/// every attempt even though the model followed the schema.
+fn parse_without_optional_nulls<T>(json: &str) -> Option<T>
+where
+    T: Instructor + DeserializeOwned,
+{
+    let mut value: Value = serde_json::from_str(json).ok()?;
+    let schema = T::schema().into_value();
+    if !strip_optional_nulls(&mut value, &schema, &schema, 0) {
+        return None;
+    }
+    serde_json::from_value(value).ok()
+}
+
+/// How many schemas [`strip_optional_nulls`] follows into `value`, bounding `$ref` cycles
+const MAX_NULL_STRIP_DEPTH: usize = 64;
+
+/// Remove `null` values for properties `schema` doesn't require, so serde falls back to
+/// the field's default (or `None`).
+///
+/// Recurses through properties, map values, array items, local `$ref`s resolved against
+/// `root`, and the `anyOf`/`oneOf` branch whose properties fit an object value. Returns
+/// whether anything was removed.
+fn strip_optional_nulls(value: &mut Value, schema: &Value, root: &Value, depth: usize) -> bool {
+    let Some(obj) = schema.as_object() else {
+        return false;
+    };
+    if depth > MAX_NULL_STRIP_DEPTH {
+        return false;
+    }
+    if let Some(reference) = obj.get("$ref").and_then(Value::as_str) {
+        return reference
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer))
+            .is_some_and(|target| strip_optional_nulls(value, target, root, depth + 1));
+    }
+
+    let mut changed = false;
+    if let Some(branches) = obj.get("allOf").and_then(Value::as_array) {
+        for branch in branches {
+            changed |= strip_optional_nulls(value, branch, root, depth + 1);
+        }
+    }
+    for keyword in ["anyOf", "oneOf"] {
+        if let Some(branch) = obj
+            .get(keyword)
+            .and_then(Value::as_array)
+            .and_then(|branches| branches.iter().find(|b| branch_fits(value, b, root)))
+        {
+            changed |= strip_optional_nulls(value, branch, root, depth + 1);
+        }
+    }
+
+    match value {
+        Value::Object(fields) => {
+            if let Some(props) = obj.get("properties").and_then(Value::as_object) {
+                let required: Vec<&str> = obj
+                    .get("required")
+                    .and_then(Value::as_array)
+                    .map(|required| required.iter().filter_map(Value::as_str).collect())
+                    .unwrap_or_default();
+                let before = fields.len();
+                fields.retain(|key, field| {
+                    !(field.is_null()
+                        && props.contains_key(key)
+                        && !required.contains(&key.as_str()))
+                });
+                changed |= fields.len() != before;
+                for (key, field) in fields.iter_mut() {
+                    if let Some(prop_schema) = props.get(key) {
+                        changed |= strip_optional_nulls(field, prop_schema, root, depth + 1);
+                    }
+                }
+            } else if let Some(value_schema) =
+                obj.get("additionalProperties").filter(|v| v.is_object())
+            {
+                for field in fields.values_mut() {
+                    changed |= strip_optional_nulls(field, value_schema, root, depth + 1);
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = obj.get("items") {
+                for item in items {
+                    changed |= strip_optional_nulls(item, item_schema, root, depth + 1);
+                }
+            }
+        }
+        _ => {}
+    }
+    changed
+}
+
+/// Whether `value` could be an instance of the `anyOf`/`oneOf` branch `schema`: objects
+/// must only use its properties and match any `const` or single-value `enum` (such as an
+/// internal tag); arrays need an `items` schema. Other values fit no branch.
+fn branch_fits(value: &Value, schema: &Value, root: &Value) -> bool {
+    let schema = match schema.get("$ref").and_then(Value::as_str) {
+        Some(reference) => match reference.strip_prefix('#').and_then(|p| root.pointer(p)) {
+            Some(target) => target,
+            None => return false,
+        },
+        None => schema,
+    };
+    match value {
+        Value::Object(fields) => {
+            let Some(props) = schema.get("properties").and_then(Value::as_object) else {
+                return false;
+            };
+            fields.iter().all(|(key, field)| {
+                props.get(key).is_some_and(|prop| {
+                    let fixed = prop.get("const").or_else(|| {
+                        prop.get("enum")
+                            .and_then(Value::as_array)
+                            .filter(|values| values.len() == 1)
+                            .and_then(|values| values.first())
+                    });
+                    fixed.is_none_or(|fixed| fixed == field)
+                })
+            })
+        }
+        Value::Array(_) => schema.get("items").is_some(),
+        _ => false,
+    }
+}
+
+/// Remove control characters that would make a response invalid JSON.
+///
+/// Outside of strings, tab, newline and carriage return are kept as whitespace; inside
+/// strings they are escaped. Every other C0 control character, and a leading byte
+/// order mark, is dropped. Returns `None` if the input needed no changes.
+fn sanitize_control_characters(raw: &str) -> Option<String> {
+    let needs_sanitizing =
+        raw.starts_with('\u{feff}') || raw.chars().any(|c| c.is_ascii_control() && c != '\x7f');
+    if !needs_sanitizing {
+        return None;
+    }
+
+    let mut sanitized = String::with_capacity(raw.len());
+    let mut removed = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in raw.strip_prefix('\u{feff}').unwrap_or(raw).chars() {
+        if in_string {
+            match c {
+                '\n' => sanitized.push_str("\\n"),
+                '\r' => sanitized.push_str("\\r"),
+                '\t' => sanitized.push_str("\\t"),
+                c if c.is_ascii_control() && c != '\x7f' => removed += 1,
+                '\\' if !escaped => {
+                    sanitized.push(c);
+                    escaped = true;
+                    continue;
+                }
+                '"' if !escaped => {
+                    sanitized.push(c);
+                    in_string = false;
+                }
+                c => sanitized.push(c),
+            }
+            escaped = false;
+        } else {
+            match c {
+                '\n' | '\r' | '\t' => sanitized.push(c),
+                c if c.is_ascii_control() && c != '\x7f' => removed += 1,
+                '"' => {
+                    sanitized.push(c);
+                    in_string = true;
+                }
+                c => sanitized.push(c),
+            }
+        }
+    }
+
+    if sanitized == raw {
+        return None;
+    }
+    warn!(
+        removed,
+        "Sanitized control characters in response before parsing"
+    );
+    Some(sanitized)
+}
+
 /// Helper to create a successful MaterializeInternalOutput from parsed data.
 ///
 /// This is a convenience function that combines parsing, validation, and
@@ -918,6 +1424,7 @@ where
 ///
 /// * `raw_response` - The raw JSON string from the LLM
 /// * `usage` - Optional token usage information
+/// * `lenient` - Whether to strip stray control characters before parsing
 ///
 /// # Returns
 ///
@@ -925,6 +1432,7 @@ where
 pub fn parse_validate_and_create_output<T>(
     raw_response: String,
     usage: Option<TokenUsage>,
+    lenient: bool,
 ) -> std::result::Result<
     MaterializeInternalOutput<T>,
     (RStructorError, Option<ValidationFailureContext>),
@@ -932,29 +1440,253 @@ pub fn parse_validate_and_create_output<T>(
 where
     T: Instructor + DeserializeOwned,
 {
-    let result = parse_and_validate_response::<T>(&raw_response)?;
+    let result = parse_and_validate_response::<T>(&raw_response, lenient)?;
     info!("Successfully generated and validated structured data");
     Ok(MaterializeInternalOutput::new(result, raw_response, usage))
 }
 
-/// Convert a reqwest error to a RStructorError, handling timeout errors specially.
-pub fn handle_http_error(e: reqwest::Error, provider_name: &str) -> RStructorError {
-    error!(error = %e, "HTTP request to {} failed", provider_name);
-    if e.is_timeout() {
-        RStructorError::Timeout
+/// Synthesize a `T` from its schema without calling the provider, for `dry_run` mode.
+///
+/// The placeholder skips `validate`, since empty strings and zeros rarely satisfy
+/// domain rules.
+pub fn dry_run_output<T>(
+    schema: &crate::schema::Schema,
+) -> std::result::Result<
+    MaterializeInternalOutput<T>,
+    (RStructorError, Option<ValidationFailureContext>),
+>
+where
+    T: DeserializeOwned,
+{
+    let schema = schema.to_json();
+    let placeholder = placeholder_value(&schema, &schema, 0);
+    info!("Dry run: returning placeholder instead of calling the provider");
+    let data = serde_json::from_value(placeholder.clone()).map_err(|e| {
+        (
+            RStructorError::SerializationError(format!(
+                "Dry run placeholder doesn't deserialize into the target type: {}",
+                e
+            )),
+            None,
+        )
+    })?;
+    Ok(MaterializeInternalOutput::new(
+        data,
+        placeholder.to_string(),
+        None,
+    ))
+}
+
+/// Fill `schema` with type-appropriate placeholder data.
+///
+/// Strings are empty (or a fixed valid value for date and UUID formats), numbers
+/// are zero or their minimum, enums and unions take their first option, arrays hold
+/// `minItems` elements and objects hold their required properties.
+fn placeholder_value(schema: &Value, root: &Value, depth: usize) -> Value {
+    // Only reachable through a required self-reference, which serde couldn't build either
+    if depth > 32 {
+        return Value::Null;
+    }
+    if let Some(value) = schema.get("const") {
+        return value.clone();
+    }
+    if let Some(first) = schema
+        .get("enum")
+        .and_then(Value::as_array)
+        .and_then(|values| values.first())
+    {
+        return first.clone();
+    }
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let target = if reference == "#" {
+            Some(root)
+        } else {
+            reference
+                .strip_prefix("#")
+                .and_then(|pointer| root.pointer(pointer))
+        };
+        return target.map_or(Value::Null, |target| {
+            placeholder_value(target, root, depth + 1)
+        });
+    }
+    for key in ["oneOf", "anyOf", "allOf"] {
+        if let Some(first) = schema
+            .get(key)
+            .and_then(Value::as_array)
+            .and_then(|options| options.first())
+        {
+            return placeholder_value(first, root, depth + 1);
+        }
+    }
+
+    let ty = match schema.get("type") {
+        Some(Value::String(ty)) => ty.as_str(),
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|ty| *ty != "null")
+            .unwrap_or("null"),
+        _ if schema.get("properties").is_some() => "object",
+        _ => "null",
+    };
+    let minimum = schema
+        .get("minimum")
+        .or_else(|| schema.get("exclusiveMinimum"))
+        .and_then(Value::as_f64)
+        .unwrap_or(0.0);
+    match ty {
+        "string" => Value::String(
+            match schema.get("format").and_then(Value::as_str) {
+                Some("date-time") => "1970-01-01T00:00:00Z",
+                Some("date") => "1970-01-01",
+                Some("uuid") => "00000000-0000-0000-0000-000000000000",
+                _ => "",
+            }
+            .to_string(),
+        ),
+        "integer" => Value::from(minimum.ceil().max(0.0) as u64),
+        "number" => serde_json::json!(minimum.max(0.0)),
+        "boolean" => Value::Bool(false),
+        "array" => {
+            if let Some(prefix) = schema.get("prefixItems").and_then(Value::as_array) {
+                return Value::Array(
+                    prefix
+                        .iter()
+                        .map(|item| placeholder_value(item, root, depth + 1))
+                        .collect(),
+                );
+            }
+            let min_items = schema.get("minItems").and_then(Value::as_u64).unwrap_or(0);
+            let item = schema.get("items").map_or(Value::Null, |items| {
+                placeholder_value(items, root, depth + 1)
+            });
+            Value::Array(vec![item; min_items as usize])
+        }
+        "object" => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            let required: Option<HashSet<&str>> = schema
+                .get("required")
+                .and_then(Value::as_array)
+                .map(|required| required.iter().filter_map(Value::as_str).collect());
+            let object = properties
+                .into_iter()
+                .flatten()
+                .filter(|(name, _)| {
+                    required
+                        .as_ref()
+                        .is_none_or(|required| required.contains(name.as_str()))
+                })
+                .map(|(name, property)| {
+                    (name.clone(), placeholder_value(property, root, depth + 1))
+                })
+                .collect();
+            Value::Object(object)
+        }
+        _ => Value::Null,
+    }
+}
+
+/// Convert a reqwest error to a RStructorError, handling timeout errors specially.
+pub fn handle_http_error(e: reqwest::Error, provider_name: &str) -> RStructorError {
+    error!(error = %e, "HTTP request to {} failed", provider_name);
+    if e.is_timeout() {
+        RStructorError::Timeout
     } else {
         RStructorError::HttpError(e)
     }
 }
 
+/// Headers the clients set themselves (lowercase), which custom headers must not override.
+const CLIENT_MANAGED_HEADERS: [&str; 5] = [
+    "authorization",
+    "content-type",
+    "x-api-key",
+    "api-key",
+    "anthropic-version",
+];
+
+/// Whether `name` is a header the clients set themselves.
+pub fn is_client_managed_header(name: &HeaderName) -> bool {
+    CLIENT_MANAGED_HEADERS.contains(&name.as_str())
+}
+
+/// The caller's custom headers to send with a request, without any the client sets itself.
+///
+/// Applied before the client adds its own auth and `Content-Type` headers, so those
+/// always win.
+pub fn custom_headers(headers: &HeaderMap) -> HeaderMap {
+    let mut custom = headers.clone();
+    for name in CLIENT_MANAGED_HEADERS {
+        if custom.remove(name).is_some() {
+            debug!(
+                header = name,
+                "Ignoring custom header the client sets itself"
+            );
+        }
+    }
+    custom
+}
+
+/// Clamp a sampling parameter to the provider's documented range, warning when the
+/// requested value falls outside it.
+pub fn clamp_sampling_param(name: &str, value: f32, min: f32, max: f32) -> f32 {
+    let clamped = value.clamp(min, max);
+    if clamped != value {
+        warn!(
+            parameter = name,
+            requested = value,
+            clamped,
+            "Sampling parameter out of range, clamping"
+        );
+    }
+    clamped
+}
+
+/// The `top_p` to send: the per-call override, else the client's, clamped to 0.0–1.0.
+pub fn effective_top_p(options: &RequestOptions, configured: Option<f32>) -> Option<f32> {
+    options
+        .top_p
+        .or(configured)
+        .map(|top_p| clamp_sampling_param("top_p", top_p, 0.0, 1.0))
+}
+
+/// A configured frequency or presence penalty, clamped to -2.0–2.0.
+pub fn effective_penalty(name: &str, configured: Option<f32>) -> Option<f32> {
+    configured.map(|penalty| clamp_sampling_param(name, penalty, -2.0, 2.0))
+}
+
 /// Parse retry-after header value to Duration.
+///
+/// Accepts both forms allowed by RFC 9110: delay-seconds and an HTTP-date.
 fn parse_retry_after(value: &str) -> Option<Duration> {
+    parse_retry_after_at(value, chrono::Utc::now())
+}
+
+/// Parse retry-after relative to `now` (dates in the past mean "retry immediately").
+fn parse_retry_after_at(value: &str, now: chrono::DateTime<chrono::Utc>) -> Option<Duration> {
+    let value = value.trim();
     // Try parsing as seconds (most common)
     if let Ok(secs) = value.parse::<u64>() {
         return Some(Duration::from_secs(secs));
     }
-    // Could also parse HTTP-date format, but seconds is most common
-    None
+    // HTTP-date, e.g. "Wed, 21 Oct 2015 07:28:00 GMT"
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&chrono::Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// Add random jitter of up to a quarter of `delay`, so clients that were throttled
+/// together don't all retry at the same instant.
+fn with_jitter(delay: Duration) -> Duration {
+    let random = random_u64();
+    let max_jitter_ms = (delay.as_millis() / 4) as u64;
+    if max_jitter_ms == 0 {
+        return delay;
+    }
+    delay + Duration::from_millis(random % (max_jitter_ms + 1))
 }
 
 /// Classify an API error based on HTTP status code and response body.
@@ -994,7 +1726,11 @@ fn classify_api_error(
             }
         }
 
-        // Bad request
+        // Bad request - context overflows get their own kind so callers can react
+        400 if is_context_length_error(&error_lower) => {
+            let (limit, requested) = extract_context_token_counts(error_text);
+            ApiErrorKind::ContextLengthExceeded { limit, requested }
+        }
         400 => ApiErrorKind::BadRequest {
             details: truncate_message(error_text, 200),
         },
@@ -1005,6 +1741,12 @@ fn classify_api_error(
         // Rate limited
         429 => ApiErrorKind::RateLimited { retry_after },
 
+        // Overloaded (Anthropic uses 529 and an `overloaded_error` type)
+        529 => ApiErrorKind::Overloaded { retry_after },
+        500..=599 if error_lower.contains("overloaded_error") => {
+            ApiErrorKind::Overloaded { retry_after }
+        }
+
         // Server errors
         500 | 502 => ApiErrorKind::ServerError { code },
 
@@ -1022,6 +1764,41 @@ fn classify_api_error(
     }
 }
 
+/// Whether a (lowercased) 400 body reports that the prompt overflowed the context window.
+fn is_context_length_error(error_lower: &str) -> bool {
+    [
+        "context length",
+        "context_length_exceeded",
+        "maximum context",
+        "context window",
+        "prompt is too long",
+    ]
+    .iter()
+    .any(|phrase| error_lower.contains(phrase))
+}
+
+/// Pull `(limit, requested)` token counts out of a context length error message.
+///
+/// Providers phrase these differently ("maximum context length is 8192 tokens. However,
+/// you requested 8500 tokens" vs "prompt is too long: 208000 tokens > 200000 maximum"),
+/// but the first two counts are always the limit and the request in some order, and
+/// the request is the larger one. A lone count is taken to be the limit.
+fn extract_context_token_counts(error_text: &str) -> (Option<u64>, Option<u64>) {
+    let counts: Vec<u64> = error_text
+        .split(|c: char| !c.is_ascii_digit() && c != ',')
+        .map(|part| part.replace(',', ""))
+        // Skip short numbers such as model versions; token counts are never that small
+        .filter(|digits| digits.len() >= 3)
+        .filter_map(|digits| digits.parse().ok())
+        .take(2)
+        .collect();
+    match counts[..] {
+        [a, b] => (Some(a.min(b)), Some(a.max(b))),
+        [limit] => (Some(limit), None),
+        _ => (None, None),
+    }
+}
+
 /// Extract model name from error message if present.
 fn extract_model_from_error(error_text: &str) -> Option<String> {
     // Look for quoted model names like 'gpt-4' or "gpt-4"
@@ -1072,11 +1849,55 @@ fn truncate_message(msg: &str, max_len: usize) -> String {
     }
 }
 
+/// Provider request id from the response headers.
+///
+/// OpenAI and xAI send `x-request-id`; Anthropic sends `request-id`. Gemini has no
+/// such header, so its client reads `responseId` from the body instead.
+pub fn response_request_id(response: &Response) -> Option<String> {
+    ["x-request-id", "request-id"].iter().find_map(|name| {
+        response
+            .headers()
+            .get(*name)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string)
+    })
+}
+
+/// Log the schema sent to `provider` for `T` at debug level, once per provider and type.
+///
+/// Returns whether this call logged it.
+pub fn log_schema_once<T: ?Sized>(provider: &'static str, schema: &Value) -> bool {
+    static LOGGED: LazyLock<Mutex<HashSet<(&'static str, &'static str)>>> =
+        LazyLock::new(Default::default);
+
+    let type_name = std::any::type_name::<T>();
+    let first = LOGGED
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert((provider, type_name));
+    if first {
+        let pretty = serde_json::to_string_pretty(schema).unwrap_or_default();
+        debug!(
+            provider,
+            type_name,
+            schema = %pretty,
+            "Prepared {} schema for {}", provider, type_name
+        );
+    }
+    first
+}
+
 /// Check HTTP response status and extract error message if unsuccessful.
 ///
 /// This function classifies errors into actionable types (rate limit, auth failure, etc.)
-/// and provides user-friendly error messages with suggested actions.
-pub async fn check_response_status(response: Response, provider_name: &str) -> Result<Response> {
+/// and provides user-friendly error messages with suggested actions. At most
+/// `max_bytes` of an error body is read; the rest is dropped, since the status alone
+/// decides whether the error is retryable.
+pub async fn check_response_status(
+    response: Response,
+    provider_name: &str,
+    max_bytes: Option<usize>,
+) -> Result<Response> {
     if !response.status().is_success() {
         let status = response.status();
 
@@ -1087,7 +1908,8 @@ pub async fn check_response_status(response: Response, provider_name: &str) -> R
             .and_then(|v| v.to_str().ok())
             .and_then(parse_retry_after);
 
-        let error_text = response.text().await?;
+        let body = read_error_body(response, max_bytes).await;
+        let error_text = String::from_utf8_lossy(&body).into_owned();
 
         let kind = classify_api_error(status, &error_text, retry_after, None);
 
@@ -1103,6 +1925,135 @@ pub async fn check_response_status(response: Response, provider_name: &str) -> R
     Ok(response)
 }
 
+/// Read an error response body, truncated to `max_bytes`.
+///
+/// Unlike [`read_response_body`], an oversized body is cut short rather than failing
+/// with `ResponseTooLarge`, which would hide a retryable 429 or 503. A body that can't
+/// be read in full is likewise kept as far as it got.
+async fn read_error_body(mut response: Response, max_bytes: Option<usize>) -> Vec<u8> {
+    let limit = max_bytes.unwrap_or(usize::MAX);
+    let mut body = Vec::new();
+    while body.len() < limit
+        && let Ok(Some(chunk)) = response.chunk().await
+    {
+        let take = chunk.len().min(limit - body.len());
+        body.extend_from_slice(&chunk[..take]);
+    }
+    body
+}
+
+/// Read a response body, giving up once it passes `max_bytes`.
+///
+/// A `Content-Length` over the limit fails before anything is read; otherwise the body
+/// is read chunk by chunk, so an endpoint that streams an enormous (or endless) body
+/// can't exhaust memory. `None` reads the whole body.
+async fn read_response_body(
+    mut response: Response,
+    provider_name: &str,
+    max_bytes: Option<usize>,
+) -> Result<Vec<u8>> {
+    let Some(limit) = max_bytes else {
+        return Ok(response.bytes().await?.to_vec());
+    };
+    let too_large = || {
+        error!(
+            limit,
+            "{} response exceeded max_response_bytes", provider_name
+        );
+        RStructorError::api_error(provider_name, ApiErrorKind::ResponseTooLarge { limit })
+    };
+
+    if response
+        .content_length()
+        .is_some_and(|length| length > limit as u64)
+    {
+        return Err(too_large());
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > limit {
+            return Err(too_large());
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Deserialize a successful response body as JSON, reading at most `max_bytes`.
+pub async fn read_json_response<T: DeserializeOwned>(
+    response: Response,
+    provider_name: &str,
+    max_bytes: Option<usize>,
+) -> Result<T> {
+    if max_bytes.is_none() {
+        return Ok(response.json().await?);
+    }
+    let body = read_response_body(response, provider_name, max_bytes).await?;
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// Read a `text/event-stream` body, yielding the `data` payload of each event.
+///
+/// Multi-line payloads are joined with newlines, and events without data (such as
+/// `:` keep-alive comments) are skipped. A final event that isn't followed by a blank
+/// line is still delivered when the body ends. Like [`read_json_response`], the body is
+/// abandoned with `ApiErrorKind::ResponseTooLarge` once it passes `max_bytes`.
+pub fn sse_data_stream(
+    response: Response,
+    provider_name: &'static str,
+    max_bytes: Option<usize>,
+) -> BoxStream<'static, Result<String>> {
+    futures::stream::unfold(
+        (Some(response), Vec::new(), 0usize),
+        move |(mut response, mut buffer, mut read)| async move {
+            loop {
+                if let Some(end) = buffer.windows(2).position(|w| w == b"\n\n") {
+                    let event: Vec<u8> = buffer.drain(..end + 2).collect();
+                    if let Some(data) = sse_event_data(&event) {
+                        return Some((Ok(data), (response, buffer, read)));
+                    }
+                    continue;
+                }
+                let Some(body) = response.as_mut() else {
+                    let data = sse_event_data(&std::mem::take(&mut buffer))?;
+                    return Some((Ok(data), (None, buffer, read)));
+                };
+                match body.chunk().await {
+                    Ok(Some(chunk)) => {
+                        read += chunk.len();
+                        if let Some(limit) = max_bytes.filter(|limit| read > *limit) {
+                            error!(
+                                limit,
+                                "{} response exceeded max_response_bytes", provider_name
+                            );
+                            let err = RStructorError::api_error(
+                                provider_name,
+                                ApiErrorKind::ResponseTooLarge { limit },
+                            );
+                            return Some((Err(err), (None, Vec::new(), read)));
+                        }
+                        buffer.extend(chunk.iter().filter(|b| **b != b'\r'));
+                    }
+                    Ok(None) => response = None,
+                    Err(e) => return Some((Err(e.into()), (None, Vec::new(), read))),
+                }
+            }
+        },
+    )
+    .boxed()
+}
+
+/// Join the `data:` lines of one server-sent event, if it has any
+fn sse_event_data(event: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(event);
+    let lines: Vec<&str> = text
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| data.strip_prefix(' ').unwrap_or(data))
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
 /// Helper function to execute generation with retry logic using conversation history.
 ///
 /// This function maintains a conversation history across retry attempts, which enables:
@@ -1124,10 +2075,44 @@ pub async fn check_response_status(response: Response, provider_name: &str) -> R
 /// * `generate_fn` - Function that takes a conversation history and returns the result plus raw response
 /// * `prompt` - The initial user prompt
 /// * `max_retries` - Maximum number of retry attempts (None or 0 means no retries)
+/// * `deadline` - Upper bound on the total time across all attempts and retry delays.
+///   Once exceeded, the in-flight attempt is dropped and `RStructorError::Timeout` is returned.
+/// * `retry_policy` - Waits between attempts. Validation retries back off geometrically;
+///   transient API errors wait at least the provider's suggested delay.
 pub async fn generate_with_retry_with_history<F, Fut, T>(
-    mut generate_fn: F,
+    generate_fn: F,
     prompt: &str,
     max_retries: Option<usize>,
+    deadline: Option<Duration>,
+    retry_policy: &RetryPolicy,
+) -> Result<MaterializeInternalOutput<T>>
+where
+    F: FnMut(Vec<ChatMessage>) -> Fut,
+    Fut: std::future::Future<
+            Output = std::result::Result<
+                MaterializeInternalOutput<T>,
+                (RStructorError, Option<ValidationFailureContext>),
+            >,
+        >,
+{
+    generate_with_retry_from_messages(
+        generate_fn,
+        vec![ChatMessage::user(prompt)],
+        max_retries,
+        deadline,
+        retry_policy,
+    )
+    .await
+}
+
+/// Like [`generate_with_retry_with_history`], but starting from an existing conversation
+/// (e.g. few-shot examples followed by the prompt) instead of a single user message.
+pub async fn generate_with_retry_from_messages<F, Fut, T>(
+    mut generate_fn: F,
+    initial_messages: Vec<ChatMessage>,
+    max_retries: Option<usize>,
+    deadline: Option<Duration>,
+    retry_policy: &RetryPolicy,
 ) -> Result<MaterializeInternalOutput<T>>
 where
     F: FnMut(Vec<ChatMessage>) -> Fut,
@@ -1138,16 +2123,24 @@ where
             >,
         >,
 {
+    let deadline_at = deadline.map(|d| Instant::now() + d);
+
     let Some(max_retries) = max_retries.filter(|&n| n > 0) else {
-        // No retries configured - just run once with a single user message
-        let messages = vec![ChatMessage::user(prompt)];
-        return generate_fn(messages).await.map_err(|(err, _)| err);
+        // No retries configured - just run once with the initial conversation
+        let messages = normalize_alternation(&initial_messages);
+        return match deadline_at {
+            Some(at) => match timeout_at(at, generate_fn(messages)).await {
+                Ok(result) => result.map_err(|(err, _)| err),
+                Err(_) => Err(deadline_exceeded(deadline)),
+            },
+            None => generate_fn(messages).await.map_err(|(err, _)| err),
+        };
     };
 
     let max_attempts = max_retries + 1; // +1 for initial attempt
 
-    // Initialize conversation history with the original user prompt
-    let mut messages = vec![ChatMessage::user(prompt)];
+    // Initialize conversation history with the original conversation
+    let mut messages = initial_messages;
 
     trace!(
         "Starting structured generation with conversation history: max_attempts={}",
@@ -1163,9 +2156,19 @@ where
             "Generation attempt with conversation history"
         );
 
-        // Attempt to generate structured data
-        match generate_fn(messages.clone()).await {
-            Ok(result) => {
+        // Attempt to generate structured data, bounded by the overall deadline
+        let request_messages = normalize_alternation(&messages);
+        let attempt_result = match deadline_at {
+            Some(at) => match timeout_at(at, generate_fn(request_messages)).await {
+                Ok(result) => result,
+                Err(_) => return Err(deadline_exceeded(deadline)),
+            },
+            None => generate_fn(request_messages).await,
+        };
+
+        match attempt_result {
+            Ok(mut result) => {
+                result.attempts = attempt + 1;
                 if attempt > 0 {
                     info!(
                         attempts_used = attempt + 1,
@@ -1218,8 +2221,16 @@ where
                             );
                         }
 
-                        // Wait briefly before retrying
-                        sleep(Duration::from_millis(500)).await;
+                        // Back off before retrying
+                        let delay = retry_policy.backoff(attempt);
+                        debug!(
+                            delay_ms = delay.as_millis(),
+                            "Waiting before validation retry"
+                        );
+                        if deadline_at.is_some_and(|at| Instant::now() + delay >= at) {
+                            return Err(deadline_exceeded(deadline));
+                        }
+                        sleep(delay).await;
                         continue;
                     } else {
                         error!(
@@ -1231,7 +2242,13 @@ where
                 }
                 // Handle retryable API errors (rate limits, transient failures)
                 else if err.is_retryable() && !is_last_attempt {
-                    let delay = err.retry_delay().unwrap_or(Duration::from_secs(1));
+                    // The provider's Retry-After (or the error's suggested delay) is a floor
+                    let backoff = retry_policy.backoff(attempt);
+                    let delay = match err.retry_delay() {
+                        Some(floor) if floor > backoff && retry_policy.jitter => with_jitter(floor),
+                        Some(floor) if floor > backoff => floor,
+                        _ => backoff,
+                    };
                     warn!(
                         attempt = attempt + 1,
                         error = ?err,
@@ -1240,6 +2257,9 @@ where
                     );
                     // For API errors, we don't modify the conversation history
                     // Just retry with the same messages
+                    if deadline_at.is_some_and(|at| Instant::now() + delay >= at) {
+                        return Err(deadline_exceeded(deadline));
+                    }
                     sleep(delay).await;
                     continue;
                 }
@@ -1266,10 +2286,103 @@ where
     unreachable!()
 }
 
+/// Build a few-shot conversation: each example becomes a user turn with its prompt and
+/// an assistant turn with the expected value as JSON, followed by the real prompt.
+pub fn few_shot_messages<T: Serialize>(
+    prompt: &str,
+    examples: &[(String, T)],
+) -> Result<Vec<ChatMessage>> {
+    let mut messages = Vec::with_capacity(examples.len() * 2 + 1);
+    for (example_prompt, expected) in examples {
+        messages.push(ChatMessage::user(example_prompt.as_str()));
+        messages.push(ChatMessage::assistant(serde_json::to_string(expected)?));
+    }
+    messages.push(ChatMessage::user(prompt));
+    Ok(messages)
+}
+
+/// Prepend the client's configured system prompts to a conversation.
+///
+/// Each prompt becomes its own system message ahead of the conversation, so layered
+/// instructions go from the most general (added first) to the most specific. Backends
+/// without a `system` role join them into their dedicated system field.
+pub fn with_system_prompts<'a>(
+    system_prompts: &[String],
+    messages: &'a [ChatMessage],
+) -> Cow<'a, [ChatMessage]> {
+    if system_prompts.is_empty() {
+        return Cow::Borrowed(messages);
+    }
+    let mut layered: Vec<ChatMessage> = system_prompts
+        .iter()
+        .map(|prompt| ChatMessage::system(prompt.as_str()))
+        .collect();
+    layered.extend_from_slice(messages);
+    Cow::Owned(layered)
+}
+
+/// Placeholder sent in place of an empty assistant response
+const EMPTY_RESPONSE_PLACEHOLDER: &str = "(empty response)";
+
+/// Make a conversation safe for providers that require strictly alternating roles.
+///
+/// Anthropic and Gemini reject empty assistant turns and consecutive turns from the
+/// same role with a 400. Empty assistant messages are replaced with a placeholder and
+/// consecutive user (or assistant) messages are merged into one, keeping their media.
+/// System messages are passed through untouched and don't count as turns, since those
+/// providers send them outside the conversation.
+fn normalize_alternation(messages: &[ChatMessage]) -> Vec<ChatMessage> {
+    let mut normalized: Vec<ChatMessage> = Vec::with_capacity(messages.len());
+    for msg in messages {
+        let empty = msg.role == ChatRole::Assistant && msg.content.trim().is_empty();
+        let prev_turn = if msg.role == ChatRole::System {
+            None
+        } else {
+            normalized
+                .iter_mut()
+                .rev()
+                .find(|prev| prev.role != ChatRole::System)
+        };
+        match prev_turn {
+            Some(prev) if prev.role == msg.role => {
+                debug!(role = msg.role.as_str(), "Merging consecutive messages");
+                if empty {
+                    // Nothing to add
+                } else if prev.content == EMPTY_RESPONSE_PLACEHOLDER {
+                    prev.content = msg.content.clone();
+                } else {
+                    prev.content.push_str("\n\n");
+                    prev.content.push_str(&msg.content);
+                }
+                prev.media.extend(msg.media.iter().cloned());
+            }
+            _ => {
+                let mut msg = msg.clone();
+                if empty {
+                    debug!("Replacing empty assistant message with a placeholder");
+                    msg.content = EMPTY_RESPONSE_PLACEHOLDER.to_string();
+                }
+                normalized.push(msg);
+            }
+        }
+    }
+    normalized
+}
+
+/// Log and build the error returned when a call runs past its overall deadline.
+fn deadline_exceeded(deadline: Option<Duration>) -> RStructorError {
+    warn!(
+        deadline_ms = deadline.map(|d| d.as_millis()),
+        "Deadline exceeded, aborting generation"
+    );
+    RStructorError::Timeout
+}
+
 /// Macro to generate standard builder methods for LLM clients.
 ///
-/// This macro generates `model()`, `temperature()`, `max_tokens()`, and `timeout()` methods
-/// that are identical across all LLM client implementations.
+/// This macro generates `model()`, `temperature()`, `top_p()`, `max_tokens()`, `timeout()`,
+/// `with_http_client()`, `deadline()` and the other shared builder methods that are
+/// identical across all LLM client implementations.
 #[macro_export]
 macro_rules! impl_client_builder_methods {
     (
@@ -1309,6 +2422,24 @@ macro_rules! impl_client_builder_methods {
                 self
             }
 
+            /// Set nucleus sampling (`top_p`), clamped to 0.0–1.0.
+            ///
+            /// Only the most likely tokens whose probabilities add up to `top_p` are
+            /// considered. Providers usually recommend tuning this or `temperature`, not
+            /// both. [`RequestOptions::with_top_p`](crate::RequestOptions::with_top_p)
+            /// replaces it for a single call.
+            #[tracing::instrument(skip(self))]
+            pub fn top_p(mut self, top_p: f32) -> Self {
+                let top_p = $crate::backend::clamp_sampling_param("top_p", top_p, 0.0, 1.0);
+                tracing::debug!(
+                    previous_top_p = ?self.config.top_p,
+                    new_top_p = top_p,
+                    "Setting top_p"
+                );
+                self.config.top_p = Some(top_p);
+                self
+            }
+
             /// Set the maximum tokens to generate
             #[tracing::instrument(skip(self))]
             pub fn max_tokens(mut self, max: u32) -> Self {
@@ -1322,6 +2453,39 @@ macro_rules! impl_client_builder_methods {
                 self
             }
 
+            /// Set sequences that end generation when the model produces them.
+            ///
+            /// Sent as `stop` (OpenAI, Grok, Mistral, Ollama), `stop_sequences` (Anthropic)
+            /// or `stopSequences` (Gemini), and left out of the request when empty or when
+            /// the model doesn't accept them (OpenAI's reasoning models).
+            /// [`RequestOptions::with_stop`](crate::RequestOptions::with_stop) replaces them
+            /// for a single call.
+            ///
+            /// # Examples
+            ///
+            /// ```no_run
+            /// # use rstructor::OpenAIClient;
+            /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+            /// let client = OpenAIClient::new("api-key")?.stop_sequences(["\n\n", "END"]);
+            /// # Ok(())
+            /// # }
+            /// ```
+            #[tracing::instrument(skip(self, stop))]
+            pub fn stop_sequences<I, S>(mut self, stop: I) -> Self
+            where
+                I: IntoIterator<Item = S>,
+                S: Into<String>,
+            {
+                let stop: Vec<String> = stop.into_iter().map(Into::into).collect();
+                tracing::debug!(
+                    previous_stop = ?self.config.stop_sequences,
+                    new_stop = ?stop,
+                    "Setting stop sequences"
+                );
+                self.config.stop_sequences = stop;
+                self
+            }
+
             /// Set the timeout for HTTP requests.
             ///
             /// This sets the timeout for both the connection and the entire request.
@@ -1330,6 +2494,10 @@ macro_rules! impl_client_builder_methods {
             /// # Arguments
             ///
             /// * `timeout` - Timeout duration (e.g., `Duration::from_secs(30)` for 30 seconds)
+            ///
+            /// This builds a fresh HTTP client, so it replaces any client set with
+            /// [`with_http_client`](Self::with_http_client). To combine the two, set the
+            /// timeout on your own `reqwest::ClientBuilder` instead.
             #[tracing::instrument(skip(self))]
             pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
                 tracing::debug!(
@@ -1354,42 +2522,386 @@ macro_rules! impl_client_builder_methods {
                 self
             }
 
-            /// Set the maximum number of retry attempts for validation errors.
-            ///
-            /// When `materialize` encounters a validation error, it will automatically
-            /// retry up to this many times, including the validation error message in subsequent attempts.
-            ///
-            /// The default is 3 retries. Use `.no_retries()` to disable retries entirely.
+            /// Use your own HTTP client for every request.
+            ///
+            /// Useful for proxies, custom TLS roots, default headers, or sharing one
+            /// connection pool between clients. The injected client is used as-is, so
+            /// configure its timeout on the `reqwest::ClientBuilder`: calling
+            /// [`timeout`](Self::timeout) afterwards builds a new client and discards this one.
+            ///
+            /// # Examples
+            ///
+            /// ```no_run
+            /// # use rstructor::OpenAIClient;
+            /// # use std::time::Duration;
+            /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+            /// let http = reqwest::Client::builder()
+            ///     .proxy(reqwest::Proxy::all("http://proxy.internal:3128")?)
+            ///     .timeout(Duration::from_secs(60))
+            ///     .build()?;
+            ///
+            /// let client = OpenAIClient::new("api-key")?.with_http_client(http);
+            /// # Ok(())
+            /// # }
+            /// ```
+            #[tracing::instrument(skip_all)]
+            pub fn with_http_client(mut self, client: reqwest::Client) -> Self {
+                tracing::debug!("Using caller-provided HTTP client");
+                self.client = client;
+                self
+            }
+
+            /// Send an extra header with every request, e.g. an organization or routing
+            /// header required by a gateway in front of the API.
+            ///
+            /// Setting the same name again replaces the earlier value. Headers the client
+            /// manages itself (`Authorization`, `Content-Type`, API-key and version headers)
+            /// are never overridden, and a name or value that isn't valid in HTTP is
+            /// ignored with a warning.
+            ///
+            /// # Examples
+            ///
+            /// ```no_run
+            /// # use rstructor::OpenAIClient;
+            /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+            /// let client = OpenAIClient::new("api-key")?
+            ///     .base_url("https://gateway.internal/v1")
+            ///     .header("X-Org-Id", "research")
+            ///     .header("X-Route", "eu-west");
+            /// # Ok(())
+            /// # }
+            /// ```
+            #[tracing::instrument(skip(self, name, value))]
+            pub fn header(mut self, name: impl AsRef<str>, value: impl AsRef<str>) -> Self {
+                let name = name.as_ref();
+                let parsed = (
+                    reqwest::header::HeaderName::from_bytes(name.as_bytes()),
+                    reqwest::header::HeaderValue::from_str(value.as_ref()),
+                );
+                match parsed {
+                    (Ok(name), Ok(value)) => {
+                        if $crate::backend::is_client_managed_header(&name) {
+                            tracing::warn!(
+                                header = %name,
+                                "Header is set by the client and will not be overridden"
+                            );
+                        }
+                        tracing::debug!(header = %name, "Adding custom header");
+                        self.config.headers.insert(name, value);
+                    }
+                    _ => tracing::warn!(header = name, "Ignoring invalid custom header"),
+                }
+                self
+            }
+
+            /// Send these extra headers with every request, in addition to any added
+            /// earlier (names already set are replaced). See [`header`](Self::header).
+            #[tracing::instrument(skip(self, headers))]
+            pub fn headers(mut self, headers: reqwest::header::HeaderMap) -> Self {
+                tracing::debug!(count = headers.len(), "Adding custom headers");
+                self.config.headers.extend(headers);
+                self
+            }
+
+            /// Set an overall deadline for each `materialize` call.
+            ///
+            /// Unlike [`timeout`](Self::timeout), which applies to each HTTP request
+            /// individually, the deadline bounds the whole call including every retry
+            /// and the delays between them. Once it passes, the call is aborted with
+            /// `RStructorError::Timeout`.
+            ///
+            /// # Examples
+            ///
+            /// ```no_run
+            /// # use rstructor::OpenAIClient;
+            /// # use std::time::Duration;
+            /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+            /// let client = OpenAIClient::new("api-key")?
+            ///     .timeout(Duration::from_secs(30))   // Each request
+            ///     .deadline(Duration::from_secs(60)); // Whole call, including retries
+            /// # Ok(())
+            /// # }
+            /// ```
+            #[tracing::instrument(skip(self))]
+            pub fn deadline(mut self, deadline: std::time::Duration) -> Self {
+                tracing::debug!(
+                    previous_deadline = ?self.config.deadline,
+                    new_deadline = ?deadline,
+                    "Setting deadline"
+                );
+                self.config.deadline = Some(deadline);
+                self
+            }
+
+            /// Set the maximum number of retry attempts for validation errors.
+            ///
+            /// When `materialize` encounters a validation error, it will automatically
+            /// retry up to this many times, including the validation error message in subsequent attempts.
+            ///
+            /// The default is 3 retries. Use `.no_retries()` to disable retries entirely.
+            ///
+            /// # Arguments
+            ///
+            /// * `max_retries` - Maximum number of retry attempts (0 = no retries, only single attempt)
+            ///
+            /// # Examples
+            ///
+            /// ```no_run
+            /// # use rstructor::OpenAIClient;
+            /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+            /// let client = OpenAIClient::new("api-key")?
+            ///     .max_retries(5);  // Increase to 5 retries (default is 3)
+            /// # Ok(())
+            /// # }
+            /// ```
+            #[tracing::instrument(skip(self))]
+            pub fn max_retries(mut self, max_retries: usize) -> Self {
+                tracing::debug!(
+                    previous_max_retries = ?self.config.max_retries,
+                    new_max_retries = max_retries,
+                    "Setting max_retries"
+                );
+                self.config.max_retries = Some(max_retries);
+                self
+            }
+
+            /// Disable automatic retries on validation errors.
+            ///
+            /// By default, the client retries up to 3 times when validation errors occur.
+            /// Use this method to disable retries and fail immediately on the first error.
+            ///
+            /// # Examples
+            ///
+            /// ```no_run
+            /// # use rstructor::OpenAIClient;
+            /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+            /// let client = OpenAIClient::new("api-key")?
+            ///     .no_retries();  // Fail immediately on validation errors
+            /// # Ok(())
+            /// # }
+            /// ```
+            #[tracing::instrument(skip(self))]
+            pub fn no_retries(mut self) -> Self {
+                tracing::debug!(
+                    previous_max_retries = ?self.config.max_retries,
+                    "Disabling retries"
+                );
+                self.config.max_retries = Some(0);
+                self
+            }
+
+            /// Set how long to wait between retries.
+            ///
+            /// The default backs off exponentially from 500ms, doubling up to 30s, with
+            /// jitter. Rate-limit retries always wait at least the provider's `Retry-After`.
+            ///
+            /// # Examples
+            ///
+            /// ```no_run
+            /// # use rstructor::{OpenAIClient, RetryPolicy};
+            /// # use std::time::Duration;
+            /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+            /// let client = OpenAIClient::new("api-key")?
+            ///     .retry_policy(RetryPolicy::new().with_base_delay(Duration::from_secs(1)));
+            /// # Ok(())
+            /// # }
+            /// ```
+            #[tracing::instrument(skip(self))]
+            pub fn retry_policy(mut self, retry_policy: $crate::RetryPolicy) -> Self {
+                tracing::debug!(
+                    previous_retry_policy = ?self.config.retry_policy,
+                    new_retry_policy = ?retry_policy,
+                    "Setting retry policy"
+                );
+                self.config.retry_policy = retry_policy;
+                self
+            }
+
+            /// Set a cumulative spending limit in USD.
+            ///
+            /// Each call's cost is computed from `pricing` and added to a running total
+            /// that is shared by every clone of this client. A call whose estimated cost
+            /// (its prompt plus `max_tokens` of output) could take the total past
+            /// `max_usd` fails with `RStructorError::BudgetExceeded` before any request is
+            /// sent. Calls for models missing from the pricing table are not counted.
+            ///
+            /// # Examples
+            ///
+            /// ```no_run
+            /// # use rstructor::{OpenAIClient, PricingTable};
+            /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+            /// let pricing = PricingTable::new().with_model("gpt-5.2", 1.75, 14.00);
+            /// let client = OpenAIClient::new("api-key")?
+            ///     .budget(5.0, &pricing);  // Stop after $5 of spend
+            /// # Ok(())
+            /// # }
+            /// ```
+            #[tracing::instrument(skip(self, pricing))]
+            pub fn budget(mut self, max_usd: f64, pricing: &$crate::PricingTable) -> Self {
+                tracing::debug!(max_usd, "Setting budget");
+                self.config.budget = Some($crate::Budget::new(max_usd, pricing));
+                self
+            }
+
+            /// Tolerate stray control characters in responses.
+            ///
+            /// Providers occasionally return JSON containing raw control characters,
+            /// which makes parsing fail with an opaque error. When enabled, such
+            /// characters are escaped (inside strings) or removed before parsing, and a
+            /// warning is logged. Disabled by default so well-formed output is parsed
+            /// exactly as received.
+            ///
+            /// # Examples
+            ///
+            /// ```no_run
+            /// # use rstructor::OpenAIClient;
+            /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+            /// let client = OpenAIClient::new("api-key")?
+            ///     .lenient_parsing(true);
+            /// # Ok(())
+            /// # }
+            /// ```
+            #[tracing::instrument(skip(self))]
+            pub fn lenient_parsing(mut self, enabled: bool) -> Self {
+                tracing::debug!(
+                    previous = self.config.lenient_parsing,
+                    new = enabled,
+                    "Setting lenient parsing"
+                );
+                self.config.lenient_parsing = enabled;
+                self
+            }
+
+            /// Drop the oldest messages when a conversation would overflow the model's
+            /// context window.
+            ///
+            /// Before each request the prompt size is estimated (roughly four characters
+            /// per token) and compared with the model's known context window, leaving
+            /// room for the schema and the response. The oldest user and assistant
+            /// messages are removed until the conversation fits; system messages and
+            /// the latest message are always kept, and a warning records how much was
+            /// dropped. Models with an unknown context window are sent unchanged.
+            ///
+            /// # Examples
+            ///
+            /// ```no_run
+            /// # use rstructor::OpenAIClient;
+            /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+            /// let client = OpenAIClient::new("api-key")?
+            ///     .auto_truncate(true);
+            /// # Ok(())
+            /// # }
+            /// ```
+            #[tracing::instrument(skip(self))]
+            pub fn auto_truncate(mut self, enabled: bool) -> Self {
+                tracing::debug!(
+                    previous = self.config.auto_truncate,
+                    new = enabled,
+                    "Setting auto-truncation"
+                );
+                self.config.auto_truncate = enabled;
+                self
+            }
+
+            /// Add a system instruction sent ahead of every request.
+            ///
+            /// Calls accumulate, so a base instruction can be layered with task-specific
+            /// ones. OpenAI, Grok, Mistral and Ollama receive one system message per call;
+            /// Anthropic and Gemini, which take a single system field, get them joined by
+            /// blank lines in the order they were added. System prompts are never part of
+            /// the retry conversation, so validation retries keep alternating user and
+            /// assistant turns.
+            ///
+            /// # Examples
+            ///
+            /// ```no_run
+            /// # use rstructor::OpenAIClient;
+            /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+            /// let client = OpenAIClient::new("api-key")?
+            ///     .system_prompt("You are a meticulous data extraction assistant.")
+            ///     .system_prompt("Dates are written day-first (DD/MM/YYYY).");
+            /// # Ok(())
+            /// # }
+            /// ```
+            #[tracing::instrument(skip(self, prompt))]
+            pub fn system_prompt(mut self, prompt: impl Into<String>) -> Self {
+                let prompt = prompt.into();
+                tracing::debug!(
+                    previous_count = self.config.system_prompts.len(),
+                    prompt_len = prompt.len(),
+                    "Adding system prompt"
+                );
+                self.config.system_prompts.push(prompt);
+                self
+            }
+
+            /// Log the schema actually sent to the provider, once per type.
+            ///
+            /// On the first `materialize` call for each type, the schema after the
+            /// provider's transformations (strict-mode tweaks, Gemini stripping, root
+            /// wrapping) is logged at `debug` level. Useful when working out why an
+            /// extraction fails. Disabled by default.
+            ///
+            /// # Examples
+            ///
+            /// ```no_run
+            /// # use rstructor::OpenAIClient;
+            /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+            /// let client = OpenAIClient::new("api-key")?
+            ///     .log_schemas(true);
+            /// # Ok(())
+            /// # }
+            /// ```
+            #[tracing::instrument(skip(self))]
+            pub fn log_schemas(mut self, enabled: bool) -> Self {
+                tracing::debug!(
+                    previous = self.config.log_schemas,
+                    new = enabled,
+                    "Setting schema logging"
+                );
+                self.config.log_schemas = enabled;
+                self
+            }
+
+            /// Return placeholder data instead of calling the provider.
             ///
-            /// # Arguments
-            ///
-            /// * `max_retries` - Maximum number of retry attempts (0 = no retries, only single attempt)
+            /// In dry-run mode, `materialize` fills `T`'s schema with type-appropriate
+            /// placeholders (empty strings, zeros, the first enum variant, only required
+            /// fields) and deserializes that, skipping validation; `generate` returns an
+            /// empty string. No request is sent, so the API key is never used, which
+            /// makes it handy for wiring up pipelines and running them in CI. Because
+            /// the placeholder comes from the schema, it stays in sync with the type.
             ///
             /// # Examples
             ///
             /// ```no_run
             /// # use rstructor::OpenAIClient;
             /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
-            /// let client = OpenAIClient::new("api-key")?
-            ///     .max_retries(5);  // Increase to 5 retries (default is 3)
+            /// let client = OpenAIClient::new("unused")?
+            ///     .dry_run(true);
             /// # Ok(())
             /// # }
             /// ```
             #[tracing::instrument(skip(self))]
-            pub fn max_retries(mut self, max_retries: usize) -> Self {
+            pub fn dry_run(mut self, enabled: bool) -> Self {
                 tracing::debug!(
-                    previous_max_retries = ?self.config.max_retries,
-                    new_max_retries = max_retries,
-                    "Setting max_retries"
+                    previous = self.config.dry_run,
+                    new = enabled,
+                    "Setting dry-run mode"
                 );
-                self.config.max_retries = Some(max_retries);
+                self.config.dry_run = enabled;
                 self
             }
 
-            /// Disable automatic retries on validation errors.
+            /// Cap how many bytes of a response body are read.
             ///
-            /// By default, the client retries up to 3 times when validation errors occur.
-            /// Use this method to disable retries and fail immediately on the first error.
+            /// Responses are normally buffered in full, so a misbehaving endpoint that
+            /// returns an enormous body can exhaust memory. With a limit set, a
+            /// `Content-Length` over it is rejected up front and other bodies are read
+            /// in chunks until they pass it. Either way the call fails with
+            /// `ApiErrorKind::ResponseTooLarge`, which isn't retried. Error responses
+            /// are truncated to the limit instead, so they keep their usual
+            /// classification (a 429 is still rate limited and retried).
             ///
             /// # Examples
             ///
@@ -1397,19 +2909,25 @@ macro_rules! impl_client_builder_methods {
             /// # use rstructor::OpenAIClient;
             /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
             /// let client = OpenAIClient::new("api-key")?
-            ///     .no_retries();  // Fail immediately on validation errors
+            ///     .max_response_bytes(4 * 1024 * 1024); // 4 MiB
             /// # Ok(())
             /// # }
             /// ```
             #[tracing::instrument(skip(self))]
-            pub fn no_retries(mut self) -> Self {
+            pub fn max_response_bytes(mut self, max_bytes: usize) -> Self {
                 tracing::debug!(
-                    previous_max_retries = ?self.config.max_retries,
-                    "Disabling retries"
+                    previous_max_bytes = ?self.config.max_response_bytes,
+                    new_max_bytes = max_bytes,
+                    "Setting maximum response size"
                 );
-                self.config.max_retries = Some(0);
+                self.config.max_response_bytes = Some(max_bytes);
                 self
             }
+
+            /// The spending limit configured with [`budget`](Self::budget), if any.
+            pub fn current_budget(&self) -> Option<&$crate::Budget> {
+                self.config.budget.as_ref()
+            }
         }
     };
 }
@@ -1418,6 +2936,66 @@ macro_rules! impl_client_builder_methods {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_strip_optional_nulls_drops_only_unrequired_nulls() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "number": { "type": "string" },
+                "currency": { "type": "string" },
+                "lines": { "type": "array", "items": { "$ref": "#/$defs/Line" } }
+            },
+            "required": ["number", "lines"],
+            "$defs": {
+                "Line": {
+                    "type": "object",
+                    "properties": {
+                        "sku": { "type": ["string", "null"] },
+                        "note": { "type": "string" }
+                    },
+                    "required": ["sku"]
+                }
+            }
+        });
+        let mut value = serde_json::json!({
+            "number": null,
+            "currency": null,
+            "lines": [{ "sku": null, "note": null }]
+        });
+        assert!(strip_optional_nulls(&mut value, &schema, &schema, 0));
+        assert_eq!(
+            value,
+            serde_json::json!({ "number": null, "lines": [{ "sku": null }] })
+        );
+
+        // Nothing left to remove
+        assert!(!strip_optional_nulls(&mut value, &schema, &schema, 0));
+    }
+
+    #[test]
+    fn test_strip_optional_nulls_follows_the_matching_variant() {
+        let schema = serde_json::json!({
+            "oneOf": [
+                {
+                    "type": "object",
+                    "properties": { "type": { "const": "card" }, "saved": { "type": "boolean" } },
+                    "required": ["type"]
+                },
+                {
+                    "type": "object",
+                    "properties": { "type": { "const": "cash" }, "saved": { "type": "boolean" } },
+                    "required": ["type", "saved"]
+                }
+            ]
+        });
+        let mut card = serde_json::json!({ "type": "card", "saved": null });
+        assert!(strip_optional_nulls(&mut card, &schema, &schema, 0));
+        assert_eq!(card, serde_json::json!({ "type": "card" }));
+
+        let mut cash = serde_json::json!({ "type": "cash", "saved": null });
+        assert!(!strip_optional_nulls(&mut cash, &schema, &schema, 0));
+    }
+
     #[test]
     fn test_add_additional_properties_simple_object() {
         let mut schema = serde_json::json!({
@@ -1592,6 +3170,26 @@ mod tests {
         assert_eq!(schema["additionalProperties"], serde_json::json!(false));
     }
 
+    #[test]
+    fn test_add_additional_properties_keeps_map_value_schema() {
+        let mut schema = serde_json::json!({
+            "type": "object",
+            "additionalProperties": {
+                "type": "object",
+                "properties": { "score": { "type": "number" } }
+            }
+        });
+        add_additional_properties_false(&mut schema);
+        let value_schema = &schema["additionalProperties"];
+        assert_eq!(value_schema["type"], "object");
+        // The map's value type is still processed for strict mode
+        assert_eq!(
+            value_schema["additionalProperties"],
+            serde_json::json!(false)
+        );
+        assert_eq!(value_schema["required"], serde_json::json!(["score"]));
+    }
+
     #[test]
     fn test_add_additional_properties_no_type() {
         // Object with properties but no explicit type should still get additionalProperties: false
@@ -1647,6 +3245,35 @@ mod tests {
         assert!(required.contains(&serde_json::json!("age")));
     }
 
+    #[test]
+    fn test_previously_optional_properties_become_nullable() {
+        let mut schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "age": { "type": "integer" },
+                "mood": { "type": "string", "enum": ["happy", "sad"] },
+                "address": { "$ref": "#/$defs/Address" }
+            },
+            "required": ["name"]
+        });
+        add_additional_properties_false(&mut schema);
+
+        let props = &schema["properties"];
+        assert_eq!(props["name"]["type"], "string");
+        assert_eq!(props["age"]["type"], serde_json::json!(["integer", "null"]));
+        assert_eq!(props["mood"]["type"], serde_json::json!(["string", "null"]));
+        assert_eq!(
+            props["mood"]["enum"],
+            serde_json::json!(["happy", "sad", null])
+        );
+        assert_eq!(
+            props["address"]["anyOf"],
+            serde_json::json!([{ "$ref": "#/$defs/Address" }, { "type": "null" }])
+        );
+        assert_eq!(schema["required"].as_array().unwrap().len(), 4);
+    }
+
     #[test]
     fn test_adds_required_array_to_nested_objects() {
         // Nested objects should also get required arrays
@@ -1771,7 +3398,11 @@ mod tests {
             "examples": [{
                 "title": "The Matrix",
                 "year": 1999
-            }]
+            }],
+            "example": {
+                "title": "Alien",
+                "year": 1979
+            }
         }));
 
         let gemini_schema = prepare_gemini_schema(&schema);
@@ -1781,6 +3412,10 @@ mod tests {
             gemini_schema.get("examples").is_none(),
             "examples should be stripped from Gemini schema"
         );
+        assert!(
+            gemini_schema.get("example").is_none(),
+            "example should be stripped from Gemini schema"
+        );
 
         // Verify title is stripped (Gemini doesn't support it)
         assert!(
@@ -1841,6 +3476,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_gemini_schema_marks_optional_properties_nullable() {
+        let schema = crate::schema::Schema::new(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "nickname": { "type": "string" },
+                "address": {
+                    "type": "object",
+                    "properties": {
+                        "city": { "type": "string" },
+                        "zip": { "type": "string" }
+                    },
+                    "required": ["city"]
+                },
+                "scores": {
+                    "type": "object",
+                    "additionalProperties": { "type": "integer" }
+                }
+            },
+            "required": ["name", "address", "scores"]
+        }));
+
+        let gemini_schema = prepare_gemini_schema(&schema);
+        let props = &gemini_schema["properties"];
+        assert!(props["name"].get("nullable").is_none());
+        assert_eq!(props["nickname"]["nullable"], serde_json::json!(true));
+        assert!(props["address"].get("nullable").is_none());
+        assert_eq!(
+            props["address"]["properties"]["zip"]["nullable"],
+            serde_json::json!(true)
+        );
+        assert!(
+            props["address"]["properties"]["city"]
+                .get("nullable")
+                .is_none()
+        );
+
+        // Placeholder keys added for maps are not optional fields
+        assert!(
+            props["scores"]["properties"]["key1"]
+                .get("nullable")
+                .is_none()
+        );
+    }
+
     #[test]
     fn test_gemini_schema_strips_additional_properties() {
         let mut schema_json = serde_json::json!({
@@ -1888,4 +3569,483 @@ mod tests {
             "nested title should be stripped"
         );
     }
+
+    #[test]
+    fn test_classify_overloaded_errors() {
+        let body = r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#;
+        let status = |code| reqwest::StatusCode::from_u16(code).unwrap();
+
+        assert_eq!(
+            classify_api_error(status(529), body, None, None),
+            ApiErrorKind::Overloaded { retry_after: None }
+        );
+        assert_eq!(
+            classify_api_error(status(529), "", Some(Duration::from_secs(3)), None),
+            ApiErrorKind::Overloaded {
+                retry_after: Some(Duration::from_secs(3))
+            }
+        );
+        // The error type wins over a generic server status
+        assert_eq!(
+            classify_api_error(status(500), body, None, None),
+            ApiErrorKind::Overloaded { retry_after: None }
+        );
+        assert_eq!(
+            classify_api_error(status(500), "internal error", None, None),
+            ApiErrorKind::ServerError { code: 500 }
+        );
+    }
+
+    #[test]
+    fn test_classify_context_length_errors() {
+        let status = reqwest::StatusCode::BAD_REQUEST;
+
+        let openai = r#"{"error":{"message":"This model's maximum context length is 128000 tokens. However, you requested 130,512 tokens (129512 in the messages, 1000 in the completion).","type":"invalid_request_error","code":"context_length_exceeded"}}"#;
+        assert_eq!(
+            classify_api_error(status, openai, None, None),
+            ApiErrorKind::ContextLengthExceeded {
+                limit: Some(128000),
+                requested: Some(130512)
+            }
+        );
+
+        let anthropic = r#"{"type":"error","error":{"type":"invalid_request_error","message":"prompt is too long: 208000 tokens > 200000 maximum"}}"#;
+        assert_eq!(
+            classify_api_error(status, anthropic, None, None),
+            ApiErrorKind::ContextLengthExceeded {
+                limit: Some(200000),
+                requested: Some(208000)
+            }
+        );
+
+        assert_eq!(
+            classify_api_error(status, "Input exceeds the context window", None, None),
+            ApiErrorKind::ContextLengthExceeded {
+                limit: None,
+                requested: None
+            }
+        );
+
+        // Other bad requests are unaffected
+        assert!(matches!(
+            classify_api_error(status, "temperature must be at most 2", None, None),
+            ApiErrorKind::BadRequest { .. }
+        ));
+    }
+
+    #[test]
+    fn test_placeholder_value_respects_constraints_and_refs() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "count": { "type": "integer", "minimum": 3 },
+                "ratio": { "type": ["number", "null"], "minimum": 0.5 },
+                "slots": { "type": "array", "items": { "type": "boolean" }, "minItems": 2 },
+                "pair": { "type": "array", "prefixItems": [{ "type": "string" }, { "type": "integer" }] },
+                "kind": { "type": "string", "const": "node" },
+                "child": { "$ref": "#/$defs/Child" },
+                "note": { "type": "string" }
+            },
+            "required": ["count", "ratio", "slots", "pair", "kind", "child"],
+            "$defs": {
+                "Child": {
+                    "type": "object",
+                    "properties": { "id": { "type": "string", "format": "uuid" } },
+                    "required": ["id"]
+                }
+            }
+        });
+        assert_eq!(
+            placeholder_value(&schema, &schema, 0),
+            serde_json::json!({
+                "count": 3,
+                "ratio": 0.5,
+                "slots": [false, false],
+                "pair": ["", 0],
+                "kind": "node",
+                "child": { "id": "00000000-0000-0000-0000-000000000000" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_log_schema_once_dedupes_per_provider_and_type() {
+        struct Probe;
+        let schema = serde_json::json!({ "type": "object" });
+        assert!(log_schema_once::<Probe>("OpenAI", &schema));
+        assert!(!log_schema_once::<Probe>("OpenAI", &schema));
+        // Another provider prepares its own schema, so it is logged separately
+        assert!(log_schema_once::<Probe>("Gemini", &schema));
+        assert!(log_schema_once::<Vec<Probe>>("OpenAI", &schema));
+    }
+
+    #[test]
+    fn test_parse_retry_after_formats() {
+        let now = chrono::DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+
+        assert_eq!(
+            parse_retry_after_at("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after_at("Wed, 21 Oct 2015 07:28:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        // A date in the past means the client may retry right away
+        assert_eq!(
+            parse_retry_after_at("Wed, 21 Oct 2015 07:27:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after_at("soon", now), None);
+    }
+
+    #[test]
+    fn test_with_jitter_stays_within_bounds() {
+        let delay = Duration::from_secs(2);
+        for _ in 0..100 {
+            let jittered = with_jitter(delay);
+            assert!(jittered >= delay);
+            assert!(jittered <= delay + Duration::from_millis(500));
+        }
+        assert_eq!(with_jitter(Duration::ZERO), Duration::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_retry_loop_reports_attempts() {
+        let mut calls = 0;
+        let output = generate_with_retry_with_history(
+            |_messages: Vec<ChatMessage>| {
+                calls += 1;
+                let call = calls;
+                async move {
+                    if call < 3 {
+                        Err((
+                            RStructorError::ValidationError("missing field".to_string()),
+                            Some(ValidationFailureContext::new("missing field", "{}")),
+                        ))
+                    } else {
+                        Ok(MaterializeInternalOutput::new(
+                            "done".to_string(),
+                            "\"done\"".to_string(),
+                            None,
+                        ))
+                    }
+                }
+            },
+            "prompt",
+            Some(3),
+            None,
+            &RetryPolicy::fixed(Duration::ZERO),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(output.data, "done");
+        assert_eq!(output.attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn test_validation_retries_back_off_geometrically() {
+        let policy = RetryPolicy::new()
+            .with_base_delay(Duration::from_millis(40))
+            .with_multiplier(3.0)
+            .with_jitter(false);
+        let mut sent_at = Vec::new();
+        let start = Instant::now();
+        let result = generate_with_retry_with_history(
+            |_messages: Vec<ChatMessage>| {
+                sent_at.push(start.elapsed());
+                async move {
+                    Err::<MaterializeInternalOutput<String>, _>((
+                        RStructorError::ValidationError("missing field".to_string()),
+                        Some(ValidationFailureContext::new("missing field", "{}")),
+                    ))
+                }
+            },
+            "prompt",
+            Some(2),
+            None,
+            &policy,
+        )
+        .await;
+
+        assert!(result.is_err());
+        // Waits of 40ms and then 120ms between the three attempts
+        assert_eq!(sent_at.len(), 3);
+        assert!(sent_at[1] - sent_at[0] >= Duration::from_millis(40));
+        assert!(sent_at[2] - sent_at[1] >= Duration::from_millis(120));
+    }
+
+    #[tokio::test]
+    async fn test_provider_retry_after_is_a_floor() {
+        let mut sent_at = Vec::new();
+        let start = Instant::now();
+        let output = generate_with_retry_with_history(
+            |_messages: Vec<ChatMessage>| {
+                sent_at.push(start.elapsed());
+                let call = sent_at.len();
+                async move {
+                    if call == 1 {
+                        Err((
+                            RStructorError::api_error(
+                                "OpenAI",
+                                ApiErrorKind::RateLimited {
+                                    retry_after: Some(Duration::from_millis(150)),
+                                },
+                            ),
+                            None,
+                        ))
+                    } else {
+                        Ok(MaterializeInternalOutput::new(
+                            "done".to_string(),
+                            "\"done\"".to_string(),
+                            None,
+                        ))
+                    }
+                }
+            },
+            "prompt",
+            Some(1),
+            None,
+            &RetryPolicy::fixed(Duration::from_millis(10)),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(output.attempts, 2);
+        assert!(sent_at[1] - sent_at[0] >= Duration::from_millis(150));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_empty_response_keeps_roles_alternating() {
+        let mut sent = Vec::new();
+        let output = generate_with_retry_with_history(
+            |messages: Vec<ChatMessage>| {
+                sent.push(messages);
+                let call = sent.len();
+                async move {
+                    if call == 1 {
+                        // The provider returned nothing at all
+                        Err((
+                            RStructorError::ValidationError("EOF while parsing".to_string()),
+                            Some(ValidationFailureContext::new("EOF while parsing", "")),
+                        ))
+                    } else {
+                        Ok(MaterializeInternalOutput::new(
+                            "done".to_string(),
+                            "\"done\"".to_string(),
+                            None,
+                        ))
+                    }
+                }
+            },
+            "prompt",
+            Some(2),
+            None,
+            &RetryPolicy::fixed(Duration::ZERO),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(output.attempts, 2);
+        let retry = &sent[1];
+        let roles: Vec<ChatRole> = retry.iter().map(|m| m.role).collect();
+        assert_eq!(
+            roles,
+            vec![ChatRole::User, ChatRole::Assistant, ChatRole::User]
+        );
+        assert_eq!(retry[1].content, EMPTY_RESPONSE_PLACEHOLDER);
+    }
+
+    #[test]
+    fn test_normalize_alternation_merges_consecutive_roles() {
+        let messages = vec![
+            ChatMessage::system("Be brief."),
+            ChatMessage::user("first"),
+            ChatMessage::user("second"),
+            ChatMessage::assistant("   "),
+            ChatMessage::assistant("answer"),
+            ChatMessage::user("third"),
+        ];
+        let normalized = normalize_alternation(&messages);
+
+        let roles: Vec<ChatRole> = normalized.iter().map(|m| m.role).collect();
+        assert_eq!(
+            roles,
+            vec![
+                ChatRole::System,
+                ChatRole::User,
+                ChatRole::Assistant,
+                ChatRole::User
+            ]
+        );
+        assert_eq!(normalized[1].content, "first\n\nsecond");
+        assert_eq!(normalized[2].content, "answer");
+    }
+
+    #[test]
+    fn test_normalize_alternation_skips_system_messages_between_turns() {
+        let messages = vec![
+            ChatMessage::user("first"),
+            ChatMessage::system("Answer in French."),
+            ChatMessage::user("second"),
+        ];
+        let normalized = normalize_alternation(&messages);
+
+        let roles: Vec<ChatRole> = normalized.iter().map(|m| m.role).collect();
+        assert_eq!(roles, vec![ChatRole::User, ChatRole::System]);
+        assert_eq!(normalized[0].content, "first\n\nsecond");
+        assert_eq!(normalized[1].content, "Answer in French.");
+    }
+
+    #[test]
+    fn test_sanitize_control_characters() {
+        // Well-formed JSON, including pretty-printing whitespace, is left alone
+        assert_eq!(sanitize_control_characters("{\n\t\"a\": \"b\"\r\n}"), None);
+
+        // Stray control characters are dropped and raw newlines in strings escaped
+        let raw = "\u{feff}{\"text\": \"line one\nline\u{0}two\u{7}\"}\u{1b}";
+        let sanitized = sanitize_control_characters(raw).unwrap();
+        assert_eq!(sanitized, "{\"text\": \"line one\\nlinetwo\"}");
+        let value: Value = serde_json::from_str(&sanitized).unwrap();
+        assert_eq!(value["text"], "line one\nlinetwo");
+
+        // Escaped quotes don't end the string early
+        let sanitized = sanitize_control_characters("{\"a\": \"say \\\"hi\\\"\n\"}").unwrap();
+        assert_eq!(sanitized, "{\"a\": \"say \\\"hi\\\"\\n\"}");
+    }
+
+    /// Schema shaped like the derive output for `enum Event { Draw(Shape), Clear }`
+    /// where `Shape` is itself a data-carrying enum.
+    fn enum_wrapping_enum_schema() -> crate::schema::Schema {
+        crate::schema::Schema::new(serde_json::json!({
+            "title": "Event",
+            "oneOf": [
+                {
+                    "type": "object",
+                    "description": "Variant Draw",
+                    "properties": {
+                        "Draw": {
+                            "title": "Shape",
+                            "oneOf": [
+                                {
+                                    "type": "object",
+                                    "properties": {
+                                        "Circle": {
+                                            "type": "object",
+                                            "properties": {
+                                                "radius": { "type": "number" },
+                                                "label": { "type": "string" }
+                                            },
+                                            "required": ["radius"]
+                                        }
+                                    },
+                                    "required": ["Circle"]
+                                },
+                                { "type": "string", "enum": ["Empty"] }
+                            ]
+                        }
+                    },
+                    "required": ["Draw"]
+                },
+                { "type": "string", "enum": ["Clear"] }
+            ]
+        }))
+    }
+
+    #[test]
+    fn test_wrap_non_object_root() {
+        let mut schema = prepare_strict_schema(&enum_wrapping_enum_schema());
+        let original = schema.clone();
+        assert!(wrap_non_object_root(&mut schema));
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["properties"]["value"], original);
+        assert_eq!(schema["required"], serde_json::json!(["value"]));
+        assert_eq!(schema["additionalProperties"], serde_json::json!(false));
+
+        // Object roots are left alone
+        let mut object = serde_json::json!({"type": "object", "properties": {}});
+        assert!(!wrap_non_object_root(&mut object));
+        assert_eq!(
+            object,
+            serde_json::json!({"type": "object", "properties": {}})
+        );
+    }
+
+    #[test]
+    fn test_wrap_non_object_root_keeps_definitions_at_root() {
+        let mut schema = serde_json::json!({
+            "oneOf": [{ "$ref": "#/$defs/Leaf" }, { "type": "string", "enum": ["Empty"] }],
+            "$defs": { "Leaf": { "type": "object", "properties": {} } }
+        });
+        assert!(wrap_non_object_root(&mut schema));
+        assert!(schema["$defs"]["Leaf"].is_object());
+        assert!(schema["properties"]["value"].get("$defs").is_none());
+    }
+
+    #[test]
+    fn test_unwrap_value_root() {
+        let unwrapped = unwrap_value_root(r#"{"value": {"Draw": {"radius": 1}}}"#.into(), false);
+        assert_eq!(unwrapped, r#"{"Draw":{"radius":1}}"#);
+        assert_eq!(
+            unwrap_value_root(r#"{"value": "Clear"}"#.into(), false),
+            r#""Clear""#
+        );
+
+        // Anything else is passed through for the parser to report on
+        assert_eq!(unwrap_value_root(r#""Clear""#.into(), false), r#""Clear""#);
+        assert_eq!(unwrap_value_root("not json".into(), false), "not json");
+    }
+
+    #[test]
+    fn test_strict_schema_recurses_into_enum_wrapping_enum() {
+        let schema = prepare_strict_schema(&enum_wrapping_enum_schema());
+
+        let draw = &schema["oneOf"][0];
+        assert_eq!(draw["additionalProperties"], serde_json::json!(false));
+        assert_eq!(draw["required"], serde_json::json!(["Draw"]));
+
+        let circle_variant = &draw["properties"]["Draw"]["oneOf"][0];
+        assert_eq!(
+            circle_variant["additionalProperties"],
+            serde_json::json!(false)
+        );
+        assert_eq!(circle_variant["required"], serde_json::json!(["Circle"]));
+
+        // Strict mode lists every property of the innermost object as required, in
+        // declaration order
+        let circle = &circle_variant["properties"]["Circle"];
+        assert_eq!(circle["additionalProperties"], serde_json::json!(false));
+        assert_eq!(circle["required"], serde_json::json!(["radius", "label"]));
+
+        // Unit variants stay plain string enums
+        assert!(schema["oneOf"][1].get("additionalProperties").is_none());
+    }
+
+    #[test]
+    fn test_gemini_schema_recurses_into_enum_wrapping_enum() {
+        let schema = prepare_gemini_schema(&enum_wrapping_enum_schema());
+
+        let inner = &schema["oneOf"][0]["properties"]["Draw"];
+        assert!(
+            inner.get("title").is_none(),
+            "nested title should be stripped"
+        );
+
+        // Externally tagged variants are not mistaken for adjacently tagged ones
+        let circle_variant = &inner["oneOf"][0];
+        assert!(circle_variant["properties"]["Circle"].is_object());
+        assert_eq!(circle_variant["required"], serde_json::json!(["Circle"]));
+        assert!(
+            circle_variant["properties"]["Circle"]
+                .get("additionalProperties")
+                .is_none()
+        );
+        assert_eq!(
+            circle_variant["properties"]["Circle"]["required"],
+            serde_json::json!(["radius"])
+        );
+    }
 }
@@ -61,6 +61,16 @@ pub enum ApiErrorKind {
     /// The API service is temporarily down. This is usually transient.
     ServiceUnavailable,
 
+    /// Provider overloaded (HTTP 529, or an `overloaded_error` response)
+    ///
+    /// The provider is temporarily at capacity (Anthropic reports this as
+    /// `overloaded_error`). Unlike a generic server error, the request itself is fine
+    /// and should succeed after backing off.
+    Overloaded {
+        /// How long to wait before retrying (if provided by the API)
+        retry_after: Option<Duration>,
+    },
+
     /// Gateway/proxy error (HTTP 520-524, Cloudflare errors)
     ///
     /// An error occurred at the gateway level. Usually transient.
@@ -84,6 +94,25 @@ pub enum ApiErrorKind {
     /// The request payload (usually the prompt) is too large.
     RequestTooLarge,
 
+    /// Response body larger than the configured `max_response_bytes`
+    ///
+    /// The body was abandoned once it passed the limit rather than buffered in full.
+    ResponseTooLarge {
+        /// The configured limit in bytes
+        limit: usize,
+    },
+
+    /// Prompt exceeds the model's context window (HTTP 400 mentioning context length)
+    ///
+    /// Not retryable as-is: shorten the prompt or switch to a model with a larger
+    /// context window.
+    ContextLengthExceeded {
+        /// The model's context window in tokens (if stated by the API)
+        limit: Option<u64>,
+        /// The number of tokens the request needed (if stated by the API)
+        requested: Option<u64>,
+    },
+
     /// Invalid request (HTTP 400)
     ///
     /// The request was malformed or contained invalid parameters.
@@ -116,6 +145,16 @@ pub enum ApiErrorKind {
         /// Description of what was expected vs received
         details: String,
     },
+
+    /// Output blocked by the provider's safety or content filters
+    ///
+    /// The provider refused to return content for moderation reasons (e.g. a Gemini
+    /// safety block or an OpenAI `content_filter` finish reason). Retrying the same
+    /// prompt is not expected to succeed.
+    ContentFiltered {
+        /// The reason reported by the provider
+        details: String,
+    },
 }
 
 impl ApiErrorKind {
@@ -140,6 +179,7 @@ impl ApiErrorKind {
             self,
             ApiErrorKind::RateLimited { .. }
                 | ApiErrorKind::ServiceUnavailable
+                | ApiErrorKind::Overloaded { .. }
                 | ApiErrorKind::GatewayError { .. }
                 | ApiErrorKind::ServerError { .. }
         )
@@ -155,6 +195,10 @@ impl ApiErrorKind {
                 Some(retry_after.unwrap_or(Duration::from_secs(5)))
             }
             ApiErrorKind::ServiceUnavailable => Some(Duration::from_secs(2)),
+            // Overload clears more slowly than a one-off failure, so back off longer
+            ApiErrorKind::Overloaded { retry_after } => {
+                Some(retry_after.unwrap_or(Duration::from_secs(10)))
+            }
             ApiErrorKind::GatewayError { .. } => Some(Duration::from_secs(1)),
             ApiErrorKind::ServerError { .. } => Some(Duration::from_secs(2)),
             _ => None,
@@ -187,6 +231,12 @@ impl ApiErrorKind {
                     provider_name
                 )
             }
+            ApiErrorKind::Overloaded { .. } => {
+                format!(
+                    "{} is temporarily overloaded. Please try again shortly.",
+                    provider_name
+                )
+            }
             ApiErrorKind::GatewayError { code } => {
                 format!(
                     "Gateway error ({}). This is usually transient - please retry.",
@@ -206,6 +256,22 @@ impl ApiErrorKind {
             ApiErrorKind::RequestTooLarge => {
                 "Request too large. Try reducing the prompt length or max_tokens.".to_string()
             }
+            ApiErrorKind::ResponseTooLarge { limit } => {
+                format!(
+                    "{} response exceeded the {} byte limit. Raise max_response_bytes if this is expected.",
+                    provider_name, limit
+                )
+            }
+            ApiErrorKind::ContextLengthExceeded { limit, requested } => {
+                let mut msg = "Prompt exceeds the model's context window".to_string();
+                match (requested, limit) {
+                    (Some(r), Some(l)) => msg.push_str(&format!(" ({} > {} tokens)", r, l)),
+                    (None, Some(l)) => msg.push_str(&format!(" ({} tokens)", l)),
+                    _ => {}
+                }
+                msg.push_str(". Shorten the prompt or use a model with a larger context window.");
+                msg
+            }
             ApiErrorKind::BadRequest { details } => {
                 format!("Invalid request: {}", details)
             }
@@ -224,6 +290,12 @@ impl ApiErrorKind {
                     provider_name, details
                 )
             }
+            ApiErrorKind::ContentFiltered { details } => {
+                format!(
+                    "{} blocked the response due to content filtering: {}",
+                    provider_name, details
+                )
+            }
         }
     }
 }
@@ -240,16 +312,38 @@ impl std::fmt::Display for ApiErrorKind {
             }
             ApiErrorKind::InvalidModel { model, .. } => write!(f, "Invalid model: {}", model),
             ApiErrorKind::ServiceUnavailable => write!(f, "Service unavailable"),
+            ApiErrorKind::Overloaded { retry_after } => {
+                write!(f, "Overloaded")?;
+                if let Some(d) = retry_after {
+                    write!(f, " (retry after {}s)", d.as_secs())?;
+                }
+                Ok(())
+            }
             ApiErrorKind::GatewayError { code } => write!(f, "Gateway error ({})", code),
             ApiErrorKind::AuthenticationFailed => write!(f, "Authentication failed"),
             ApiErrorKind::PermissionDenied => write!(f, "Permission denied"),
             ApiErrorKind::RequestTooLarge => write!(f, "Request too large"),
+            ApiErrorKind::ResponseTooLarge { limit } => {
+                write!(f, "Response too large (limit {} bytes)", limit)
+            }
+            ApiErrorKind::ContextLengthExceeded { limit, requested } => {
+                write!(f, "Context length exceeded")?;
+                match (requested, limit) {
+                    (Some(r), Some(l)) => write!(f, " ({} > {} tokens)", r, l),
+                    (None, Some(l)) => write!(f, " (limit {} tokens)", l),
+                    (Some(r), None) => write!(f, " ({} tokens requested)", r),
+                    (None, None) => Ok(()),
+                }
+            }
             ApiErrorKind::BadRequest { details } => write!(f, "Bad request: {}", details),
             ApiErrorKind::ServerError { code } => write!(f, "Server error ({})", code),
             ApiErrorKind::Other { code, message } => write!(f, "API error ({}): {}", code, message),
             ApiErrorKind::UnexpectedResponse { details } => {
                 write!(f, "Unexpected response: {}", details)
             }
+            ApiErrorKind::ContentFiltered { details } => {
+                write!(f, "Content filtered: {}", details)
+            }
         }
     }
 }
@@ -312,6 +406,15 @@ pub enum RStructorError {
     #[error("Timeout error")]
     Timeout,
 
+    /// The client's cumulative spending limit has been reached
+    #[error("Budget exceeded: spent ${spent_usd:.4} of ${limit_usd:.4} limit")]
+    BudgetExceeded {
+        /// Total spent so far in USD
+        spent_usd: f64,
+        /// The configured ceiling in USD
+        limit_usd: f64,
+    },
+
     /// HTTP client error (from reqwest)
     #[error("HTTP client error: {0}")]
     HttpError(#[from] reqwest::Error),
@@ -416,6 +519,16 @@ impl PartialEq for RStructorError {
             (Self::SchemaError(a), Self::SchemaError(b)) => a == b,
             (Self::SerializationError(a), Self::SerializationError(b)) => a == b,
             (Self::Timeout, Self::Timeout) => true,
+            (
+                Self::BudgetExceeded {
+                    spent_usd: s1,
+                    limit_usd: l1,
+                },
+                Self::BudgetExceeded {
+                    spent_usd: s2,
+                    limit_usd: l2,
+                },
+            ) => s1 == s2 && l1 == l2,
             // HttpError and JsonError don't implement PartialEq, so we always return false
             (Self::HttpError(_), Self::HttpError(_)) => false,
             (Self::JsonError(_), Self::JsonError(_)) => false,
@@ -0,0 +1,283 @@
+//! Regression testing for extraction quality.
+//!
+//! [`evaluate`] runs a set of prompts with known answers through a client and reports
+//! which cases came back exactly as expected, along with which fields differed. Run it
+//! before and after changing a prompt, schema description or model to see what moved.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use rstructor::eval::{EvalCase, evaluate};
+//! use rstructor::{Instructor, OpenAIClient};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Instructor, Serialize, Deserialize, Debug)]
+//! struct Movie {
+//!     title: String,
+//!     year: u16,
+//! }
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let client = OpenAIClient::from_env()?;
+//! let cases = vec![
+//!     EvalCase::new(
+//!         "Nolan's 2010 dream heist film",
+//!         Movie { title: "Inception".into(), year: 2010 },
+//!     ),
+//!     EvalCase::new(
+//!         "The 1999 film where Neo takes the red pill",
+//!         Movie { title: "The Matrix".into(), year: 1999 },
+//!     ),
+//! ];
+//!
+//! let report = evaluate(&client, &cases).await;
+//! println!("accuracy: {:.0}%", report.accuracy() * 100.0);
+//! for (field, count) in &report.field_mismatches {
+//!     println!("{field}: wrong in {count} case(s)");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tracing::{debug, info};
+
+use crate::backend::LLMClient;
+use crate::error::{RStructorError, Result};
+use crate::model::Instructor;
+
+/// A prompt paired with the value it should produce.
+#[derive(Debug, Clone)]
+pub struct EvalCase<T> {
+    pub prompt: String,
+    pub expected: T,
+}
+
+impl<T> EvalCase<T> {
+    pub fn new(prompt: impl Into<String>, expected: T) -> Self {
+        Self {
+            prompt: prompt.into(),
+            expected,
+        }
+    }
+}
+
+/// A value that differs between the expected and actual output.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldMismatch {
+    /// Location of the value, e.g. `$.cast[0].name` (`$` is the root)
+    pub path: String,
+    /// The expected value (`null` if it was missing from the expected output)
+    pub expected: Value,
+    /// The actual value (`null` if it was missing from the actual output)
+    pub actual: Value,
+}
+
+/// The outcome of a single case.
+#[derive(Debug)]
+pub struct CaseResult {
+    pub prompt: String,
+    /// Values that differed from the expected output; empty if the case passed
+    pub mismatches: Vec<FieldMismatch>,
+    /// Set when `materialize` failed, in which case nothing was compared
+    pub error: Option<RStructorError>,
+}
+
+impl CaseResult {
+    /// Whether the output matched the expected value exactly.
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.mismatches.is_empty()
+    }
+}
+
+/// Results of an [`evaluate`] run.
+#[derive(Debug, Default)]
+pub struct EvalReport {
+    /// One result per case, in the order the cases were given
+    pub cases: Vec<CaseResult>,
+    /// How many cases got each field wrong, keyed by path with array indices replaced
+    /// by `*` (e.g. `$.cast[*].name`), so an element that's wrong at different positions
+    /// in different cases is counted together. A case counts once per path however many
+    /// elements it got wrong; the concrete paths are in [`CaseResult::mismatches`].
+    pub field_mismatches: BTreeMap<String, usize>,
+}
+
+impl EvalReport {
+    /// Number of cases whose output matched exactly.
+    pub fn passed(&self) -> usize {
+        self.cases.iter().filter(|case| case.passed()).count()
+    }
+
+    /// Number of cases that mismatched or errored.
+    pub fn failed(&self) -> usize {
+        self.cases.len() - self.passed()
+    }
+
+    /// Number of cases where `materialize` returned an error.
+    pub fn errors(&self) -> usize {
+        self.cases
+            .iter()
+            .filter(|case| case.error.is_some())
+            .count()
+    }
+
+    /// Fraction of cases that passed, from 0.0 to 1.0 (0.0 for an empty run).
+    pub fn accuracy(&self) -> f64 {
+        if self.cases.is_empty() {
+            return 0.0;
+        }
+        self.passed() as f64 / self.cases.len() as f64
+    }
+}
+
+/// Run every case through `client` and compare the output with the expected value.
+///
+/// Cases run one at a time so rate limits and budgets behave as they would in a
+/// single-threaded caller. Values are compared through their JSON serialization (see
+/// [`diff_json`]), so the comparison follows serde renames. Validation retries happen as
+/// usual; a case whose `materialize` call fails is recorded with its error and counts as
+/// failed.
+pub async fn evaluate<C, T>(client: &C, cases: &[EvalCase<T>]) -> EvalReport
+where
+    C: LLMClient + Sync,
+    T: Instructor + Serialize + DeserializeOwned + Send + 'static,
+{
+    let mut report = EvalReport::default();
+
+    for case in cases {
+        let outcome = client
+            .materialize::<T>(&case.prompt)
+            .await
+            .and_then(|actual| compare(&case.expected, &actual));
+        let result = match outcome {
+            Ok(mismatches) => {
+                let fields: BTreeSet<String> = mismatches
+                    .iter()
+                    .map(|mismatch| field_pattern(&mismatch.path))
+                    .collect();
+                for field in fields {
+                    *report.field_mismatches.entry(field).or_default() += 1;
+                }
+                CaseResult {
+                    prompt: case.prompt.clone(),
+                    mismatches,
+                    error: None,
+                }
+            }
+            Err(e) => CaseResult {
+                prompt: case.prompt.clone(),
+                mismatches: Vec::new(),
+                error: Some(e),
+            },
+        };
+        debug!(
+            prompt = %result.prompt,
+            passed = result.passed(),
+            mismatches = result.mismatches.len(),
+            "Evaluated case"
+        );
+        report.cases.push(result);
+    }
+
+    info!(
+        cases = report.cases.len(),
+        passed = report.passed(),
+        errors = report.errors(),
+        "Evaluation finished"
+    );
+    report
+}
+
+/// A mismatch path with its array indices replaced by `*`: `$.cast[2].name` becomes
+/// `$.cast[*].name`.
+fn field_pattern(path: &str) -> String {
+    let mut pattern = String::with_capacity(path.len());
+    let mut rest = path;
+    while let Some(start) = rest.find('[') {
+        pattern.push_str(&rest[..=start]);
+        rest = &rest[start + 1..];
+        match rest.find(']') {
+            Some(end) if end > 0 && rest[..end].bytes().all(|b| b.is_ascii_digit()) => {
+                pattern.push('*');
+                rest = &rest[end..];
+            }
+            _ => {}
+        }
+    }
+    pattern.push_str(rest);
+    pattern
+}
+
+fn compare<T: Serialize>(expected: &T, actual: &T) -> Result<Vec<FieldMismatch>> {
+    Ok(diff_json(
+        &serde_json::to_value(expected)?,
+        &serde_json::to_value(actual)?,
+    ))
+}
+
+/// List the values that differ between two JSON documents.
+///
+/// Objects are compared key by key and arrays index by index, so a mismatch is reported
+/// at the deepest path where the documents disagree. A key missing on one side shows up
+/// as `null` on that side; since `null` and a missing key are indistinguishable here, an
+/// optional field serialized as `null` matches one that was omitted.
+///
+/// # Example
+///
+/// ```
+/// use rstructor::eval::diff_json;
+/// use serde_json::json;
+///
+/// let expected = json!({"title": "Inception", "cast": [{"name": "Leonardo DiCaprio"}]});
+/// let actual = json!({"title": "Inception", "cast": [{"name": "Leo DiCaprio"}]});
+///
+/// let diffs = diff_json(&expected, &actual);
+/// assert_eq!(diffs.len(), 1);
+/// assert_eq!(diffs[0].path, "$.cast[0].name");
+/// ```
+pub fn diff_json(expected: &Value, actual: &Value) -> Vec<FieldMismatch> {
+    let mut mismatches = Vec::new();
+    diff_at("$".to_string(), expected, actual, &mut mismatches);
+    mismatches
+}
+
+fn diff_at(path: String, expected: &Value, actual: &Value, out: &mut Vec<FieldMismatch>) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, expected_value) in expected {
+                let actual_value = actual.get(key).unwrap_or(&Value::Null);
+                diff_at(
+                    format!("{}.{}", path, key),
+                    expected_value,
+                    actual_value,
+                    out,
+                );
+            }
+            for (key, actual_value) in actual {
+                if !expected.contains_key(key) {
+                    diff_at(format!("{}.{}", path, key), &Value::Null, actual_value, out);
+                }
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) => {
+            for i in 0..expected.len().max(actual.len()) {
+                diff_at(
+                    format!("{}[{}]", path, i),
+                    expected.get(i).unwrap_or(&Value::Null),
+                    actual.get(i).unwrap_or(&Value::Null),
+                    out,
+                );
+            }
+        }
+        _ if expected != actual => out.push(FieldMismatch {
+            path,
+            expected: expected.clone(),
+            actual: actual.clone(),
+        }),
+        _ => {}
+    }
+}
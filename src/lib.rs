@@ -43,15 +43,20 @@
 /// ```
 mod backend;
 pub mod error;
+pub mod eval;
 #[cfg(feature = "logging")]
 pub mod logging;
 pub mod model;
 pub mod schema;
+pub mod streaming;
 
 // Re-exports for convenience
 pub use error::{ApiErrorKind, RStructorError, Result};
-pub use model::Instructor;
-pub use schema::{CustomTypeSchema, Schema, SchemaBuilder, SchemaType};
+pub use model::{Instructor, InstructorExt};
+pub use schema::{
+    CustomTypeSchema, Schema, SchemaBuilder, SchemaTarget, SchemaTransform, SchemaTransformKind,
+    SchemaType,
+};
 
 #[cfg(feature = "openai")]
 pub use backend::openai::{Model as OpenAIModel, OpenAIClient};
@@ -65,6 +70,12 @@ pub use backend::gemini::{GeminiClient, Model as GeminiModel};
 #[cfg(feature = "grok")]
 pub use backend::grok::{GrokClient, Model as GrokModel};
 
+#[cfg(feature = "mistral")]
+pub use backend::mistral::{MistralClient, Model as MistralModel};
+
+#[cfg(feature = "ollama")]
+pub use backend::ollama::{Model as OllamaModel, OllamaClient};
+
 #[cfg(feature = "derive")]
 pub use rstructor_derive::Instructor;
 
@@ -72,5 +83,7 @@ pub use backend::LLMClient;
 pub use backend::ModelInfo;
 pub use backend::ThinkingLevel;
 pub use backend::{
-    ChatMessage, ChatRole, GenerateResult, MaterializeResult, MediaFile, TokenUsage,
+    BatchItem, Budget, BudgetReservation, ChatMessage, ChatRole, GenerateResult, MaterializeResult,
+    MediaFile, ModelPricing, PricingTable, RequestOptions, RetryPolicy, TokenUsage, UsageTotals,
+    UsageTracker,
 };
@@ -131,8 +131,98 @@ pub trait Instructor: SchemaType + DeserializeOwned + Serialize {
     fn validate(&self) -> Result<()> {
         Ok(())
     }
+
+    /// Optional clean-up applied after validation succeeds
+    ///
+    /// This method is called automatically by `materialize` once the data returned
+    /// by the LLM has passed [`validate`](Self::validate), so it can trim strings,
+    /// normalize casing or fill in derived fields in one place. Returning an error
+    /// is treated like a validation failure. The default implementation does nothing.
+    ///
+    /// With the derive macro, point the `normalize` attribute at a function:
+    ///
+    /// ```
+    /// use rstructor::{Instructor, InstructorExt};
+    /// use serde::{Serialize, Deserialize};
+    ///
+    /// #[derive(Instructor, Serialize, Deserialize, Debug)]
+    /// #[llm(normalize = "normalize_tag")]
+    /// struct Tag {
+    ///     name: String,
+    /// }
+    ///
+    /// fn normalize_tag(tag: &mut Tag) -> rstructor::Result<()> {
+    ///     tag.name = tag.name.trim().to_lowercase();
+    ///     Ok(())
+    /// }
+    ///
+    /// let tag = Tag::from_json(r#"{"name": "  Rust "}"#).unwrap();
+    /// assert_eq!(tag.name, "rust");
+    /// ```
+    fn normalize(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Stable hash of this type's schema, see [`Schema::stable_hash`](crate::Schema::stable_hash)
+    ///
+    /// The value is the same across runs and machines, so it can key caches of model
+    /// output or be pinned in a test to detect when a type's schema changes.
+    ///
+    /// ```
+    /// use rstructor::{Instructor, SchemaType};
+    /// use serde::{Serialize, Deserialize};
+    ///
+    /// #[derive(Instructor, Serialize, Deserialize)]
+    /// struct Movie {
+    ///     title: String,
+    /// }
+    ///
+    /// assert_eq!(Movie::schema_hash(), Movie::schema().stable_hash());
+    /// ```
+    fn schema_hash() -> u64
+    where
+        Self: Sized,
+    {
+        Self::schema().stable_hash()
+    }
+}
+
+/// Convenience constructors for [`Instructor`] types that don't involve an LLM.
+///
+/// This is the non-network counterpart to `materialize`: it parses JSON you already
+/// have (from a cache, a file, or an earlier call) and runs the same `validate` and
+/// `normalize` steps.
+/// It is implemented for every `Instructor` type.
+///
+/// # Example
+///
+/// ```
+/// use rstructor::{Instructor, InstructorExt};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize, Debug)]
+/// struct Movie {
+///     title: String,
+///     year: u16,
+/// }
+///
+/// let movie = Movie::from_json(r#"{"title": "Alien", "year": 1979}"#).unwrap();
+/// assert_eq!(movie.title, "Alien");
+///
+/// assert!(Movie::from_json(r#"{"title": "Alien"}"#).is_err());
+/// ```
+pub trait InstructorExt: Instructor + DeserializeOwned {
+    /// Parse a JSON string into `Self`, then run its validation and normalization
+    fn from_json(s: &str) -> Result<Self> {
+        let mut value: Self = serde_json::from_str(s)?;
+        value.validate()?;
+        value.normalize()?;
+        Ok(value)
+    }
 }
 
+impl<T: Instructor + DeserializeOwned> InstructorExt for T {}
+
 // The blanket implementation is removed
 // Instead, the derive macro will handle implementing Instructor for each type
 // This avoids the conflicting implementation errors
@@ -1,3 +1,9 @@
 mod instructor;
+#[cfg(feature = "derive")]
+mod pattern;
+mod union;
 
-pub use instructor::{Instructor, Validatable};
+pub use instructor::{Instructor, InstructorExt, Validatable};
+#[cfg(feature = "derive")]
+#[doc(hidden)]
+pub use pattern::FieldPattern;
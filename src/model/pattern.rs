@@ -0,0 +1,48 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+use tracing::warn;
+
+use crate::error::{RStructorError, Result};
+
+/// A `#[llm(pattern = "...")]` regex, compiled on first use.
+///
+/// The derive macro keeps one of these in a `static` per annotated field and calls
+/// [`check`](Self::check) from `validate()`, so a response that ignores the schema's
+/// `pattern` is retried like any other validation failure.
+#[doc(hidden)]
+pub struct FieldPattern {
+    pattern: &'static str,
+    regex: OnceLock<Option<Regex>>,
+}
+
+impl FieldPattern {
+    pub const fn new(pattern: &'static str) -> Self {
+        Self {
+            pattern,
+            regex: OnceLock::new(),
+        }
+    }
+
+    /// Fail with a `ValidationError` naming `field` if `value` doesn't match.
+    ///
+    /// Like JSON Schema's `pattern`, the regex isn't implicitly anchored. A pattern the
+    /// `regex` crate can't compile is logged and skipped rather than failing every
+    /// response.
+    pub fn check(&self, field: &str, value: &str) -> Result<()> {
+        let regex = self.regex.get_or_init(|| match Regex::new(self.pattern) {
+            Ok(regex) => Some(regex),
+            Err(e) => {
+                warn!(field, pattern = self.pattern, error = %e, "Skipping invalid field pattern");
+                None
+            }
+        });
+        match regex {
+            Some(regex) if !regex.is_match(value) => Err(RStructorError::ValidationError(format!(
+                "{}: {:?} does not match pattern {}",
+                field, value, self.pattern
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
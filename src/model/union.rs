@@ -0,0 +1,86 @@
+/// Define an enum that lets the model choose which of several `Instructor` types to
+/// produce.
+///
+/// `instructor_union!(Name { A, B, C })` expands to an enum with one variant per member
+/// type, each named after and wrapping that type (`Name::A(A)`, ...), deriving
+/// `Instructor`, `Serialize` and `Deserialize`. Its schema is a `oneOf` over the
+/// members, each under its own name, so deserialization lands in the variant the model
+/// picked. Validation and normalization are delegated to the chosen member.
+///
+/// Attributes written before the name (doc comments, `#[derive(Debug)]`,
+/// `#[llm(description = "...")]`) are passed through to the enum. Members must be plain
+/// type names, since they double as variant names; `serde_json` needs to be a
+/// dependency, as with the derive macro.
+///
+/// # Example
+///
+/// ```
+/// use rstructor::{Instructor, SchemaType, instructor_union};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Instructor, Serialize, Deserialize, Debug)]
+/// struct Refund {
+///     order_id: String,
+///     amount: f64,
+/// }
+///
+/// #[derive(Instructor, Serialize, Deserialize, Debug)]
+/// struct Escalation {
+///     reason: String,
+/// }
+///
+/// instructor_union!(
+///     #[derive(Debug)]
+///     #[llm(description = "The action to take for a support ticket")]
+///     pub TicketAction { Refund, Escalation }
+/// );
+///
+/// let schema = TicketAction::schema().to_json();
+/// assert_eq!(schema["oneOf"].as_array().unwrap().len(), 2);
+///
+/// let action: TicketAction =
+///     serde_json::from_str(r#"{"Escalation": {"reason": "Customer is a VIP"}}"#).unwrap();
+/// assert!(matches!(action, TicketAction::Escalation(_)));
+/// ```
+#[cfg(feature = "derive")]
+#[macro_export]
+macro_rules! instructor_union {
+    (
+        $(#[$meta:meta])*
+        $vis:vis $name:ident { $($member:ident),+ $(,)? }
+    ) => {
+        #[derive($crate::Instructor, ::serde::Serialize, ::serde::Deserialize)]
+        #[llm(
+            validate = "Self::__instructor_union_validate",
+            normalize = "Self::__instructor_union_normalize"
+        )]
+        $(#[$meta])*
+        $vis enum $name {
+            $($member($member),)+
+        }
+
+        impl $name {
+            #[doc(hidden)]
+            fn __instructor_union_validate(&self) -> $crate::Result<()> {
+                match self {
+                    $($name::$member(inner) => $crate::Instructor::validate(inner),)+
+                }
+            }
+
+            #[doc(hidden)]
+            fn __instructor_union_normalize(&mut self) -> $crate::Result<()> {
+                match self {
+                    $($name::$member(inner) => $crate::Instructor::normalize(inner),)+
+                }
+            }
+        }
+
+        $(
+            impl ::std::convert::From<$member> for $name {
+                fn from(value: $member) -> Self {
+                    $name::$member(value)
+                }
+            }
+        )+
+    };
+}
@@ -1,16 +1,17 @@
-use serde_json::{Value, json};
-use std::collections::{HashMap, HashSet};
+use serde_json::{Map, Value, json};
 
 use super::Schema;
 
 /// SchemaBuilder helps construct JSON Schema incrementally
+///
+/// Properties and `required` entries keep the order they were added in.
 #[derive(Default)]
 pub struct SchemaBuilder {
     schema_type: String,
     title: Option<String>,
     description: Option<String>,
-    properties: HashMap<String, Value>,
-    required: HashSet<String>,
+    properties: Map<String, Value>,
+    required: Vec<String>,
     examples: Vec<Value>,
 }
 
@@ -51,8 +52,8 @@ impl SchemaBuilder {
     ) -> Self {
         let name = name.into();
         self.properties.insert(name.clone(), property_schema);
-        if required {
-            self.required.insert(name);
+        if required && !self.required.contains(&name) {
+            self.required.push(name);
         }
         self
     }
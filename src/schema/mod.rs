@@ -3,9 +3,11 @@ mod custom_type;
 mod primitives;
 pub use builder::SchemaBuilder;
 pub use custom_type::CustomTypeSchema;
+pub(crate) use primitives::{SIGNED_INTEGER_KEY_PATTERN, UNSIGNED_INTEGER_KEY_PATTERN};
 
 use crate::error::Result;
-use serde_json::Value;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
 use std::fmt::{Display, Formatter, Result as FmtResult};
 
 /// Helper function to call a struct's validate method if it exists
@@ -72,7 +74,14 @@ pub fn call_validate_if_exists<T>(_obj: &T) -> Result<()> {
 /// let json = schema.to_json();
 /// assert_eq!(json["title"], "Person");
 /// ```
-#[derive(Debug, Clone)]
+///
+/// `Schema` serializes transparently as its inner JSON value, so it can be embedded in
+/// larger configuration structs or cached to disk.
+///
+/// Two schemas are equal when their JSON values are equal; object key order is ignored,
+/// so `assert_eq!` on schemas isn't affected by how maps happen to be ordered.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct Schema {
     pub schema: Value,
 }
@@ -90,6 +99,52 @@ impl Schema {
         &self.schema
     }
 
+    /// The root `title`, if the schema has one
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rstructor::Schema;
+    ///
+    /// let schema = Schema::builder()
+    ///     .title("Person")
+    ///     .description("A person with a name")
+    ///     .property("name", serde_json::json!({"type": "string"}), true)
+    ///     .property("nickname", serde_json::json!({"type": "string"}), false)
+    ///     .build();
+    ///
+    /// assert_eq!(schema.title(), Some("Person"));
+    /// assert_eq!(schema.description(), Some("A person with a name"));
+    /// assert_eq!(schema.property_names(), vec!["name", "nickname"]);
+    /// assert_eq!(schema.required_fields(), vec!["name"]);
+    /// ```
+    pub fn title(&self) -> Option<&str> {
+        self.schema.get("title").and_then(Value::as_str)
+    }
+
+    /// The root `description`, if the schema has one
+    pub fn description(&self) -> Option<&str> {
+        self.schema.get("description").and_then(Value::as_str)
+    }
+
+    /// Names of the root object's properties (empty for non-object schemas)
+    pub fn property_names(&self) -> Vec<&str> {
+        self.schema
+            .get("properties")
+            .and_then(Value::as_object)
+            .map(|props| props.keys().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// Names listed in the root object's `required` array
+    pub fn required_fields(&self) -> Vec<&str> {
+        self.schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|required| required.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default()
+    }
+
     /// Get the JSON representation of this schema
     ///
     /// Returns the schema as-is without enhancement to prevent stack overflow
@@ -101,6 +156,25 @@ impl Schema {
         self.schema.clone()
     }
 
+    /// Take the schema's JSON value without cloning it
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rstructor::Schema;
+    /// use serde_json::{Value, json};
+    ///
+    /// let schema = Schema::new(json!({"type": "string"}));
+    /// assert_eq!(schema.clone().into_value(), json!({"type": "string"}));
+    ///
+    /// // Or through `From`/`Into`
+    /// let value: Value = schema.into();
+    /// assert_eq!(value["type"], "string");
+    /// ```
+    pub fn into_value(self) -> Value {
+        self.schema
+    }
+
     // Format the schema as a pretty-printed JSON string
     pub fn to_pretty_json(&self) -> String {
         // Get the schema with array enhancements
@@ -112,10 +186,450 @@ impl Schema {
         })
     }
 
+    /// Schema for a JSON array of `T`, i.e. `Vec<T>`.
+    ///
+    /// Definitions the item schema carries for recursive types (`$defs`/`definitions`)
+    /// are moved up to the array so their `$ref`s still resolve from the root.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rstructor::{Instructor, Schema};
+    /// use serde::{Serialize, Deserialize};
+    ///
+    /// #[derive(Instructor, Serialize, Deserialize)]
+    /// struct Movie {
+    ///     title: String,
+    /// }
+    ///
+    /// let schema = Schema::for_array_of::<Movie>().to_json();
+    /// assert_eq!(schema["type"], "array");
+    /// assert_eq!(schema["items"]["properties"]["title"]["type"], "string");
+    /// ```
+    pub fn for_array_of<T: SchemaType>() -> Schema {
+        let mut items = T::schema().into_value();
+        let mut schema = serde_json::Map::new();
+        schema.insert("type".to_string(), Value::String("array".to_string()));
+        if let Some(items_obj) = items.as_object_mut() {
+            for key in ["$defs", "definitions"] {
+                if let Some(defs) = items_obj.remove(key) {
+                    schema.insert(key.to_string(), defs);
+                }
+            }
+        }
+        schema.insert("items".to_string(), items);
+        Schema::new(Value::Object(schema))
+    }
+
     /// Create a schema builder for an object type
     pub fn builder() -> SchemaBuilder {
         SchemaBuilder::object()
     }
+
+    /// Convert this schema into OpenAPI 3.1 `components/schemas` entries.
+    ///
+    /// The returned object maps component names to schemas and can be merged
+    /// directly into an OpenAPI document's `components.schemas` section. The root
+    /// schema is stored under `name`; any `$defs` (used for recursive types) are
+    /// hoisted to sibling components and `$ref`s are rewritten from `#/$defs/...`
+    /// to `#/components/schemas/...`. Legacy `nullable: true` markers are converted
+    /// to OpenAPI 3.1 type arrays (e.g. `["string", "null"]`), or to an `anyOf` with
+    /// `{"type": "null"}` for schemas without a `type` such as `$ref`s. Property names
+    /// and example, default, const and enum values are copied through unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rstructor::Schema;
+    /// use serde_json::json;
+    ///
+    /// let schema = Schema::new(json!({
+    ///     "$defs": {
+    ///         "Node": {
+    ///             "type": "object",
+    ///             "properties": {
+    ///                 "children": {"type": "array", "items": {"$ref": "#/$defs/Node"}}
+    ///             }
+    ///         }
+    ///     },
+    ///     "$ref": "#/$defs/Node"
+    /// }));
+    ///
+    /// let components = schema.to_openapi("Node");
+    /// assert_eq!(
+    ///     components["Node"]["properties"]["children"]["items"]["$ref"],
+    ///     "#/components/schemas/Node"
+    /// );
+    /// ```
+    pub fn to_openapi(&self, name: &str) -> Value {
+        let mut root = self.schema.clone();
+        let mut components = serde_json::Map::new();
+
+        // Hoist $defs into sibling components
+        if let Some(obj) = root.as_object_mut() {
+            obj.remove("$schema");
+            for key in ["$defs", "definitions"] {
+                if let Some(Value::Object(defs)) = obj.remove(key) {
+                    for (def_name, def_schema) in defs {
+                        components.insert(def_name, def_schema);
+                    }
+                }
+            }
+        }
+
+        // A root that is only a $ref to a def of the same name collapses into that def
+        let root_ref = root
+            .as_object()
+            .filter(|obj| obj.len() == 1)
+            .and_then(|obj| obj.get("$ref"))
+            .and_then(|r| r.as_str())
+            .and_then(|r| r.strip_prefix("#/$defs/"))
+            .map(str::to_string);
+        match root_ref {
+            Some(def_name) if def_name == name && components.contains_key(name) => {}
+            _ => {
+                components.insert(name.to_string(), root);
+            }
+        }
+
+        let mut result = Value::Object(components);
+        rewrite_for_openapi(&mut result);
+        result
+    }
+
+    /// Transform this schema into the shape a provider's structured-output API accepts.
+    ///
+    /// These are the same transforms the built-in clients apply before sending a
+    /// request, so custom clients targeting OpenAI-compatible or Gemini-compatible
+    /// endpoints can build conforming requests without reimplementing them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rstructor::{Schema, SchemaTarget};
+    /// use serde_json::json;
+    ///
+    /// let schema = Schema::new(json!({
+    ///     "type": "object",
+    ///     "properties": { "name": { "type": "string" } }
+    /// }));
+    ///
+    /// let strict = schema.prepare_for(SchemaTarget::OpenAIStrict);
+    /// assert_eq!(strict["additionalProperties"], false);
+    /// assert_eq!(strict["required"], json!(["name"]));
+    ///
+    /// assert_eq!(schema.prepare_for(SchemaTarget::Raw), schema.to_json());
+    /// ```
+    pub fn prepare_for(&self, target: SchemaTarget) -> Value {
+        match target {
+            SchemaTarget::OpenAIStrict => crate::backend::prepare_strict_schema(self),
+            SchemaTarget::Gemini => crate::backend::prepare_gemini_schema(self),
+            SchemaTarget::Raw => self.to_json(),
+        }
+    }
+
+    /// Like [`prepare_for`](Self::prepare_for), but also list every change made.
+    ///
+    /// Use this when a type behaves differently on one provider than another: the
+    /// report shows which keywords were stripped, which `$ref`s were inlined, and which
+    /// tuples, maps and enum variants were rewritten, each with its location in the
+    /// prepared schema.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rstructor::{Schema, SchemaTarget, SchemaTransformKind};
+    /// use serde_json::json;
+    ///
+    /// let schema = Schema::new(json!({
+    ///     "type": "object",
+    ///     "title": "Person",
+    ///     "properties": { "name": { "type": "string", "examples": ["Ada"] } },
+    ///     "required": ["name"]
+    /// }));
+    ///
+    /// let (prepared, transforms) = schema.prepare_for_with_report(SchemaTarget::Gemini);
+    /// assert_eq!(prepared, schema.prepare_for(SchemaTarget::Gemini));
+    /// for transform in &transforms {
+    ///     println!("{}", transform); // e.g. "#/properties/name: stripped `examples`"
+    /// }
+    /// assert!(transforms.iter().any(|t| t.path == "#/properties/name"
+    ///     && t.kind == SchemaTransformKind::KeywordStripped { keyword: "examples".into() }));
+    /// ```
+    pub fn prepare_for_with_report(&self, target: SchemaTarget) -> (Value, Vec<SchemaTransform>) {
+        match target {
+            SchemaTarget::OpenAIStrict => crate::backend::prepare_strict_schema_with_report(self),
+            SchemaTarget::Gemini => crate::backend::prepare_gemini_schema_with_report(self),
+            SchemaTarget::Raw => (self.to_json(), Vec::new()),
+        }
+    }
+
+    /// A hash of the schema that is stable across runs, platforms and Rust versions
+    ///
+    /// The schema is hashed in canonical form (object keys sorted, no whitespace) with
+    /// 64-bit FNV-1a, so the value only changes when the schema itself does. Use it as a
+    /// cache key, or pin it in a test to catch accidental schema changes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rstructor::Schema;
+    /// use serde_json::json;
+    ///
+    /// let a = Schema::new(json!({"type": "object", "title": "Person"}));
+    /// let b = Schema::new(json!({"title": "Person", "type": "object"}));
+    /// assert_eq!(a.stable_hash(), b.stable_hash());
+    ///
+    /// let c = Schema::new(json!({"type": "object", "title": "Company"}));
+    /// assert_ne!(a.stable_hash(), c.stable_hash());
+    /// ```
+    pub fn stable_hash(&self) -> u64 {
+        let mut canonical = String::new();
+        write_canonical_json(&self.schema, &mut canonical);
+        fnv1a_64(canonical.as_bytes())
+    }
+
+    /// Whether two schemas describe the same structure, ignoring object key order
+    ///
+    /// Equivalent to `==`; array order (e.g. `required`, `enum`) still matters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rstructor::Schema;
+    /// use serde_json::json;
+    ///
+    /// let a = Schema::new(json!({"type": "object", "required": ["name"]}));
+    /// let b = Schema::new(json!({"required": ["name"], "type": "object"}));
+    /// assert!(a.structurally_eq(&b));
+    ///
+    /// let c = Schema::new(json!({"type": "object", "required": []}));
+    /// assert!(!a.structurally_eq(&c));
+    /// ```
+    pub fn structurally_eq(&self, other: &Schema) -> bool {
+        self.schema == other.schema
+    }
+}
+
+/// Serialize `value` with object keys sorted, independent of map ordering
+fn write_canonical_json(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_unstable_by_key(|(key, _)| *key);
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical_json(value, out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical_json(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+/// 64-bit FNV-1a, chosen because its output is fixed by specification
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Provider schema dialect accepted by [`Schema::prepare_for`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaTarget {
+    /// OpenAI strict structured outputs (also used for Anthropic): every object gets
+    /// `additionalProperties: false` and lists all of its properties as required, with
+    /// previously optional properties (struct or enum variant fields) made nullable
+    OpenAIStrict,
+    /// Gemini `responseSchema`: keywords Gemini rejects, such as `examples`,
+    /// `additionalProperties` and `title`, are removed, and properties missing from
+    /// `required` are marked `nullable`
+    Gemini,
+    /// The schema exactly as generated
+    Raw,
+}
+
+/// A change made to a schema by [`Schema::prepare_for_with_report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaTransform {
+    /// Where the change was made, as a JSON Pointer fragment into the prepared schema
+    /// (`#` is the root, `#/properties/name` a property)
+    pub path: String,
+    pub kind: SchemaTransformKind,
+}
+
+/// What a [`SchemaTransform`] did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaTransformKind {
+    /// A keyword the provider rejects was removed
+    KeywordStripped { keyword: String },
+    /// A `$ref` was replaced with a copy of the definition it points to
+    RefInlined { reference: String },
+    /// A recursive `$ref` past the inlining depth limit became a bare object
+    RecursionTruncated { reference: String },
+    /// A tuple's `prefixItems` became a plain array of the union of its item types,
+    /// losing the per-position types and the length
+    TupleFlattened { len: usize },
+    /// A map (an object described only by an `additionalProperties` schema) got
+    /// placeholder properties with these keys
+    MapRewritten { keys: Vec<String> },
+    /// An adjacently tagged enum variant's content object was merged into the variant
+    AdjacentlyTaggedFlattened { content_key: String },
+    /// An optional property was made nullable; in strict mode it's also required
+    MadeNullable,
+    /// `additionalProperties: false` was added to an object
+    AdditionalPropertiesClosed,
+}
+
+impl std::fmt::Display for SchemaTransform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.path)?;
+        match &self.kind {
+            SchemaTransformKind::KeywordStripped { keyword } => {
+                write!(f, "stripped `{}`", keyword)
+            }
+            SchemaTransformKind::RefInlined { reference } => write!(f, "inlined {}", reference),
+            SchemaTransformKind::RecursionTruncated { reference } => {
+                write!(f, "truncated recursive {}", reference)
+            }
+            SchemaTransformKind::TupleFlattened { len } => {
+                write!(f, "flattened {}-element tuple into an array", len)
+            }
+            SchemaTransformKind::MapRewritten { keys } => {
+                write!(f, "rewrote map with placeholder keys [{}]", keys.join(", "))
+            }
+            SchemaTransformKind::AdjacentlyTaggedFlattened { content_key } => {
+                write!(f, "merged `{}` into the enum variant", content_key)
+            }
+            SchemaTransformKind::MadeNullable => write!(f, "made nullable"),
+            SchemaTransformKind::AdditionalPropertiesClosed => {
+                write!(f, "added `additionalProperties: false`")
+            }
+        }
+    }
+}
+
+/// Rewrite each component schema's `$ref`s and `nullable` markers for OpenAPI 3.1
+fn rewrite_for_openapi(components: &mut Value) {
+    if let Value::Object(components) = components {
+        for schema in components.values_mut() {
+            rewrite_schema_for_openapi(schema);
+        }
+    }
+}
+
+/// Keywords whose value maps names to subschemas, rather than being a schema itself
+const SCHEMA_MAP_KEYWORDS: [&str; 5] = [
+    "properties",
+    "patternProperties",
+    "$defs",
+    "definitions",
+    "dependentSchemas",
+];
+
+/// Keywords holding instance data, which must be copied through untouched
+const DATA_KEYWORDS: [&str; 5] = ["example", "examples", "default", "const", "enum"];
+
+/// Rewrite one schema, recursing only into positions that hold subschemas
+fn rewrite_schema_for_openapi(schema: &mut Value) {
+    let Value::Object(obj) = schema else {
+        return;
+    };
+
+    if let Some(Value::String(reference)) = obj.get_mut("$ref") {
+        for prefix in ["#/$defs/", "#/definitions/"] {
+            if let Some(def_name) = reference.strip_prefix(prefix) {
+                *reference = format!("#/components/schemas/{}", def_name);
+                break;
+            }
+        }
+    }
+
+    if matches!(obj.get("nullable"), Some(Value::Bool(_)))
+        && obj.remove("nullable") == Some(Value::Bool(true))
+    {
+        make_nullable_for_openapi(obj);
+    }
+
+    for (key, child) in obj.iter_mut() {
+        if DATA_KEYWORDS.contains(&key.as_str()) {
+            continue;
+        }
+        if SCHEMA_MAP_KEYWORDS.contains(&key.as_str()) {
+            if let Value::Object(subschemas) = child {
+                for subschema in subschemas.values_mut() {
+                    rewrite_schema_for_openapi(subschema);
+                }
+            }
+            continue;
+        }
+        match child {
+            Value::Object(_) => rewrite_schema_for_openapi(child),
+            Value::Array(items) => items.iter_mut().for_each(rewrite_schema_for_openapi),
+            _ => {}
+        }
+    }
+}
+
+/// Express a legacy `nullable: true` the OpenAPI 3.1 way: `null` joins the `type`
+/// (and `enum`, if any), or, for schemas without a `type` such as a `$ref`, the
+/// schema becomes one branch of an `anyOf` with `{"type": "null"}`.
+fn make_nullable_for_openapi(obj: &mut serde_json::Map<String, Value>) {
+    let null_type = || json!({ "type": "null" });
+    match obj.get_mut("type") {
+        Some(Value::String(ty)) => {
+            let ty = std::mem::take(ty);
+            obj.insert("type".to_string(), json!([ty, "null"]));
+        }
+        Some(Value::Array(types)) => {
+            if !types.iter().any(|t| t == "null") {
+                types.push(Value::String("null".into()));
+            }
+        }
+        Some(_) => {}
+        None => {
+            if let Some(Value::Array(branches)) = obj.get_mut("anyOf") {
+                if !branches.contains(&null_type()) {
+                    branches.push(null_type());
+                }
+            } else {
+                // Annotations stay on the outer schema; everything else becomes a branch
+                let mut branch = std::mem::take(obj);
+                for key in ["title", "description"] {
+                    if let Some(annotation) = branch.remove(key) {
+                        obj.insert(key.to_string(), annotation);
+                    }
+                }
+                obj.insert(
+                    "anyOf".to_string(),
+                    Value::Array(vec![Value::Object(branch), null_type()]),
+                );
+            }
+            return;
+        }
+    }
+    if let Some(Value::Array(values)) = obj.get_mut("enum")
+        && !values.contains(&Value::Null)
+    {
+        values.push(Value::Null);
+    }
 }
 
 // Display implementation for Schema
@@ -130,6 +644,12 @@ impl Display for Schema {
     }
 }
 
+impl From<Schema> for Value {
+    fn from(schema: Schema) -> Self {
+        schema.into_value()
+    }
+}
+
 /// SchemaType trait defines a type that can be converted to a JSON Schema.
 ///
 /// This trait is implemented for types that can generate a JSON Schema representation
@@ -201,6 +721,10 @@ impl Display for Schema {
 /// // let json = schema.to_json();
 /// // assert_eq!(json["properties"]["name"]["description"], "Person's name");
 /// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` does not implement `SchemaType`",
+    note = "derive `Instructor` for it, implement `SchemaType` by hand, or for `uuid::Uuid` enable rstructor's `uuid` feature"
+)]
 pub trait SchemaType {
     /// Generate a JSON Schema representation of this type
     fn schema() -> Schema;
@@ -212,6 +736,91 @@ pub trait SchemaType {
     fn schema_name() -> Option<String> {
         None
     }
+
+    /// Names of the properties the schema lists, including those inlined from its own
+    /// flattened fields, used by the derive macro to catch `#[serde(flatten)]` fields
+    /// whose properties collide
+    #[doc(hidden)]
+    const PROPERTY_NAMES: &'static [&'static str] = &[];
+
+    /// Whether `#[serde(flatten)]` can inline the schema into a parent: false for
+    /// schemas without top-level `properties` (primitives, sequences, enums and
+    /// self-referential structs), which would silently contribute nothing
+    #[doc(hidden)]
+    const FLATTENABLE: bool = true;
+}
+
+/// Fail const evaluation with `message` unless the flattened type is `flattenable`.
+#[doc(hidden)]
+pub const fn assert_flattenable(flattenable: bool, message: &'static str) {
+    if !flattenable {
+        panic!("{}", message);
+    }
+}
+
+/// Total number of names in `parts`, the length for [`concat_property_names`].
+#[doc(hidden)]
+pub const fn property_names_len(parts: &[&[&str]]) -> usize {
+    let mut len = 0;
+    let mut i = 0;
+    while i < parts.len() {
+        len += parts[i].len();
+        i += 1;
+    }
+    len
+}
+
+/// Concatenate `parts` into one array, so a derived `PROPERTY_NAMES` can include the
+/// names inlined from its flattened fields.
+#[doc(hidden)]
+pub const fn concat_property_names<const N: usize>(parts: &[&[&'static str]]) -> [&'static str; N] {
+    let mut names = [""; N];
+    let mut n = 0;
+    let mut i = 0;
+    while i < parts.len() {
+        let mut j = 0;
+        while j < parts[i].len() {
+            names[n] = parts[i][j];
+            n += 1;
+            j += 1;
+        }
+        i += 1;
+    }
+    names
+}
+
+/// Fail const evaluation with `message` if `a` and `b` share a name.
+///
+/// The derive macro calls this in a `const` item for each pair of flattened fields, so
+/// colliding properties are a compile error.
+#[doc(hidden)]
+pub const fn assert_disjoint_properties(a: &[&str], b: &[&str], message: &'static str) {
+    let mut i = 0;
+    while i < a.len() {
+        let mut j = 0;
+        while j < b.len() {
+            if const_str_eq(a[i], b[j]) {
+                panic!("{}", message);
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+}
+
+const fn const_str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
 }
 
 #[cfg(test)]
@@ -14,6 +14,9 @@ impl<T: SchemaType> SchemaType for Box<T> {
     fn schema_name() -> Option<String> {
         T::schema_name()
     }
+
+    const PROPERTY_NAMES: &'static [&'static str] = T::PROPERTY_NAMES;
+    const FLATTENABLE: bool = T::FLATTENABLE;
 }
 
 // ============================================================================
@@ -35,13 +38,21 @@ impl SchemaType for Value {
 // HashMap<String, V> - Objects with dynamic keys
 // ============================================================================
 
+/// Canonical schema for string-keyed maps.
+///
+/// Maps are described purely by `additionalProperties`, with no placeholder keys.
+/// Providers that can't express dynamic keys (Gemini) get example keys added when the
+/// request schema is prepared, so other providers always see the clean map schema.
+fn string_map_schema<V: SchemaType>() -> Schema {
+    Schema::new(json!({
+        "type": "object",
+        "additionalProperties": V::schema().into_value()
+    }))
+}
+
 impl<V: SchemaType> SchemaType for HashMap<String, V> {
     fn schema() -> Schema {
-        let value_schema = V::schema().to_json();
-        Schema::new(json!({
-            "type": "object",
-            "additionalProperties": value_schema
-        }))
+        string_map_schema::<V>()
     }
 
     fn schema_name() -> Option<String> {
@@ -50,14 +61,10 @@ impl<V: SchemaType> SchemaType for HashMap<String, V> {
     }
 }
 
-// Also implement for HashMap with other string-like keys that serialize to strings
+// Also implement for BTreeMap, which serializes identically
 impl<V: SchemaType> SchemaType for std::collections::BTreeMap<String, V> {
     fn schema() -> Schema {
-        let value_schema = V::schema().to_json();
-        Schema::new(json!({
-            "type": "object",
-            "additionalProperties": value_schema
-        }))
+        string_map_schema::<V>()
     }
 
     fn schema_name() -> Option<String> {
@@ -66,6 +73,91 @@ impl<V: SchemaType> SchemaType for std::collections::BTreeMap<String, V> {
     }
 }
 
+// IndexMap keeps insertion order but has the same JSON shape
+#[cfg(feature = "indexmap")]
+impl<V: SchemaType, S> SchemaType for indexmap::IndexMap<String, V, S> {
+    fn schema() -> Schema {
+        string_map_schema::<V>()
+    }
+
+    fn schema_name() -> Option<String> {
+        let value_name = V::schema_name().unwrap_or_else(|| "Unknown".to_string());
+        Some(format!("IndexMap<String, {}>", value_name))
+    }
+}
+
+// ============================================================================
+// Integer-keyed maps - Objects whose keys are numbers written as strings
+// ============================================================================
+
+/// `propertyNames` pattern for keys of signed integer maps (the derive emits the same)
+pub(crate) const SIGNED_INTEGER_KEY_PATTERN: &str = r"^-?\d+$";
+/// `propertyNames` pattern for keys of unsigned integer maps
+pub(crate) const UNSIGNED_INTEGER_KEY_PATTERN: &str = r"^\d+$";
+
+/// Schema for maps with integer keys.
+///
+/// JSON object keys are always strings, so serde writes `{1: "a"}` as `{"1": "a"}` and
+/// parses the key back from the string. `propertyNames` tells the model the keys must
+/// be integers.
+fn integer_key_map_schema<V: SchemaType>(pattern: &str) -> Schema {
+    Schema::new(json!({
+        "type": "object",
+        "propertyNames": {
+            "type": "string",
+            "pattern": pattern
+        },
+        "additionalProperties": V::schema().into_value()
+    }))
+}
+
+macro_rules! impl_integer_key_map_schema {
+    ($pattern:expr => $($key:ty),+) => {
+        $(
+            impl<V: SchemaType> SchemaType for HashMap<$key, V> {
+                fn schema() -> Schema {
+                    integer_key_map_schema::<V>($pattern)
+                }
+
+                fn schema_name() -> Option<String> {
+                    let value_name = V::schema_name().unwrap_or_else(|| "Unknown".to_string());
+                    Some(format!("HashMap<{}, {}>", stringify!($key), value_name))
+                }
+            }
+
+            impl<V: SchemaType> SchemaType for std::collections::BTreeMap<$key, V> {
+                fn schema() -> Schema {
+                    integer_key_map_schema::<V>($pattern)
+                }
+
+                fn schema_name() -> Option<String> {
+                    let value_name = V::schema_name().unwrap_or_else(|| "Unknown".to_string());
+                    Some(format!("BTreeMap<{}, {}>", stringify!($key), value_name))
+                }
+            }
+
+            #[cfg(feature = "indexmap")]
+            impl<V: SchemaType, S> SchemaType for indexmap::IndexMap<$key, V, S> {
+                fn schema() -> Schema {
+                    integer_key_map_schema::<V>($pattern)
+                }
+
+                fn schema_name() -> Option<String> {
+                    let value_name = V::schema_name().unwrap_or_else(|| "Unknown".to_string());
+                    Some(format!("IndexMap<{}, {}>", stringify!($key), value_name))
+                }
+            }
+        )+
+    };
+}
+
+impl_integer_key_map_schema!(
+    SIGNED_INTEGER_KEY_PATTERN => i8, i16, i32, i64, i128, isize
+);
+impl_integer_key_map_schema!(
+    UNSIGNED_INTEGER_KEY_PATTERN => u8, u16, u32, u64, u128, usize
+);
+
 // ============================================================================
 // Tuples - Fixed-length arrays with typed elements
 // ============================================================================
@@ -76,7 +168,7 @@ macro_rules! impl_tuple_schema {
         impl<$($T: SchemaType),+> SchemaType for ($($T,)+) {
             fn schema() -> Schema {
                 let items = vec![
-                    $($T::schema().to_json()),+
+                    $($T::schema().into_value()),+
                 ];
                 let count = items.len();
                 Schema::new(json!({
@@ -93,6 +185,8 @@ macro_rules! impl_tuple_schema {
                 ];
                 Some(format!("({})", names.join(", ")))
             }
+
+            const FLATTENABLE: bool = false;
         }
     };
 }
@@ -123,6 +217,8 @@ impl SchemaType for String {
     fn schema_name() -> Option<String> {
         Some("String".to_string())
     }
+
+    const FLATTENABLE: bool = false;
 }
 
 impl SchemaType for &str {
@@ -133,6 +229,24 @@ impl SchemaType for &str {
     fn schema_name() -> Option<String> {
         Some("str".to_string())
     }
+
+    const FLATTENABLE: bool = false;
+}
+
+impl SchemaType for char {
+    fn schema() -> Schema {
+        Schema::new(json!({
+            "type": "string",
+            "minLength": 1,
+            "maxLength": 1
+        }))
+    }
+
+    fn schema_name() -> Option<String> {
+        Some("char".to_string())
+    }
+
+    const FLATTENABLE: bool = false;
 }
 
 impl SchemaType for bool {
@@ -143,6 +257,8 @@ impl SchemaType for bool {
     fn schema_name() -> Option<String> {
         Some("bool".to_string())
     }
+
+    const FLATTENABLE: bool = false;
 }
 
 // Integer types
@@ -157,6 +273,8 @@ macro_rules! impl_integer_schema {
                 fn schema_name() -> Option<String> {
                     Some(stringify!($ty).to_string())
                 }
+
+                const FLATTENABLE: bool = false;
             }
         )+
     };
@@ -178,6 +296,8 @@ macro_rules! impl_float_schema {
                 fn schema_name() -> Option<String> {
                     Some(stringify!($ty).to_string())
                 }
+
+                const FLATTENABLE: bool = false;
             }
         )+
     };
@@ -191,7 +311,7 @@ impl_float_schema!(f32, f64);
 
 impl<T: SchemaType> SchemaType for Vec<T> {
     fn schema() -> Schema {
-        let item_schema = T::schema().to_json();
+        let item_schema = T::schema().into_value();
         Schema::new(json!({
             "type": "array",
             "items": item_schema
@@ -202,6 +322,8 @@ impl<T: SchemaType> SchemaType for Vec<T> {
         let item_name = T::schema_name().unwrap_or_else(|| "Unknown".to_string());
         Some(format!("Vec<{}>", item_name))
     }
+
+    const FLATTENABLE: bool = false;
 }
 
 // ============================================================================
@@ -219,6 +341,9 @@ impl<T: SchemaType> SchemaType for Option<T> {
         let inner_name = T::schema_name().unwrap_or_else(|| "Unknown".to_string());
         Some(format!("Option<{}>", inner_name))
     }
+
+    const PROPERTY_NAMES: &'static [&'static str] = T::PROPERTY_NAMES;
+    const FLATTENABLE: bool = T::FLATTENABLE;
 }
 
 // ============================================================================
@@ -227,7 +352,7 @@ impl<T: SchemaType> SchemaType for Option<T> {
 
 impl<T: SchemaType> SchemaType for std::collections::HashSet<T> {
     fn schema() -> Schema {
-        let item_schema = T::schema().to_json();
+        let item_schema = T::schema().into_value();
         Schema::new(json!({
             "type": "array",
             "items": item_schema,
@@ -239,11 +364,13 @@ impl<T: SchemaType> SchemaType for std::collections::HashSet<T> {
         let item_name = T::schema_name().unwrap_or_else(|| "Unknown".to_string());
         Some(format!("HashSet<{}>", item_name))
     }
+
+    const FLATTENABLE: bool = false;
 }
 
 impl<T: SchemaType> SchemaType for std::collections::BTreeSet<T> {
     fn schema() -> Schema {
-        let item_schema = T::schema().to_json();
+        let item_schema = T::schema().into_value();
         Schema::new(json!({
             "type": "array",
             "items": item_schema,
@@ -255,6 +382,100 @@ impl<T: SchemaType> SchemaType for std::collections::BTreeSet<T> {
         let item_name = T::schema_name().unwrap_or_else(|| "Unknown".to_string());
         Some(format!("BTreeSet<{}>", item_name))
     }
+
+    const FLATTENABLE: bool = false;
+}
+
+// ============================================================================
+// chrono date/time types - ISO-8601 strings
+// ============================================================================
+
+/// Schema shared by the chrono types; matches what the derive emits for date fields.
+fn date_time_schema() -> Schema {
+    Schema::new(json!({
+        "type": "string",
+        "format": "date-time",
+        "description": "ISO-8601 formatted date and time"
+    }))
+}
+
+impl<Tz: chrono::TimeZone> SchemaType for chrono::DateTime<Tz> {
+    fn schema() -> Schema {
+        date_time_schema()
+    }
+
+    fn schema_name() -> Option<String> {
+        Some("DateTime".to_string())
+    }
+
+    const FLATTENABLE: bool = false;
+}
+
+impl SchemaType for chrono::NaiveDateTime {
+    fn schema() -> Schema {
+        date_time_schema()
+    }
+
+    fn schema_name() -> Option<String> {
+        Some("NaiveDateTime".to_string())
+    }
+
+    const FLATTENABLE: bool = false;
+}
+
+impl SchemaType for chrono::NaiveDate {
+    fn schema() -> Schema {
+        // Serializes as a bare date (YYYY-MM-DD), not a timestamp
+        Schema::new(json!({
+            "type": "string",
+            "format": "date",
+            "description": "ISO-8601 formatted date"
+        }))
+    }
+
+    fn schema_name() -> Option<String> {
+        Some("NaiveDate".to_string())
+    }
+
+    const FLATTENABLE: bool = false;
+}
+
+impl SchemaType for chrono::NaiveTime {
+    fn schema() -> Schema {
+        Schema::new(json!({
+            "type": "string",
+            "format": "time",
+            "description": "ISO-8601 formatted time of day"
+        }))
+    }
+
+    fn schema_name() -> Option<String> {
+        Some("NaiveTime".to_string())
+    }
+
+    const FLATTENABLE: bool = false;
+}
+
+// ============================================================================
+// uuid::Uuid - Hyphenated UUID strings
+// ============================================================================
+
+/// Derived `Uuid` fields delegate here too, so they need the `uuid` feature.
+#[cfg(feature = "uuid")]
+impl SchemaType for uuid::Uuid {
+    fn schema() -> Schema {
+        Schema::new(json!({
+            "type": "string",
+            "format": "uuid",
+            "description": "UUID identifier string"
+        }))
+    }
+
+    fn schema_name() -> Option<String> {
+        Some("Uuid".to_string())
+    }
+
+    const FLATTENABLE: bool = false;
 }
 
 #[cfg(test)]
@@ -284,6 +505,19 @@ mod tests {
         assert_eq!(json["additionalProperties"]["type"], "integer");
     }
 
+    #[test]
+    fn test_map_schemas_share_canonical_shape() {
+        let expected = serde_json::json!({
+            "type": "object",
+            "additionalProperties": { "type": "integer" }
+        });
+        assert_eq!(<HashMap<String, i32>>::schema().to_json(), expected);
+        assert_eq!(
+            <std::collections::BTreeMap<String, i32>>::schema().to_json(),
+            expected
+        );
+    }
+
     #[test]
     fn test_tuple_schema() {
         let schema = <(i32, String)>::schema();
@@ -311,4 +545,22 @@ mod tests {
         assert_eq!(json["additionalProperties"]["type"], "array");
         assert_eq!(json["additionalProperties"]["items"]["type"], "string");
     }
+
+    #[test]
+    fn test_date_time_schemas_in_containers() {
+        type Timestamp = chrono::DateTime<chrono::Utc>;
+        assert_eq!(Timestamp::schema().to_json()["format"], "date-time");
+        assert_eq!(
+            <HashMap<String, Timestamp>>::schema().to_json()["additionalProperties"]["format"],
+            "date-time"
+        );
+        assert_eq!(
+            <(Timestamp, String)>::schema().to_json()["prefixItems"][0]["format"],
+            "date-time"
+        );
+        assert_eq!(
+            <Vec<chrono::NaiveDate>>::schema().to_json()["items"]["format"],
+            "date"
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use super::{Schema, SchemaBuilder};
+use super::{Schema, SchemaBuilder, SchemaTarget};
 use serde_json::json;
 
 #[test]
@@ -82,3 +82,287 @@ fn test_schema_builder() {
     assert!(required.iter().any(|v| v == "name"));
     assert!(required.iter().any(|v| v == "age"));
 }
+
+#[test]
+fn test_schema_to_openapi() {
+    let schema = Schema::new(json!({
+        "type": "object",
+        "title": "Order",
+        "properties": {
+            "note": { "type": "string", "nullable": true },
+            "address": { "$ref": "#/$defs/Address" }
+        },
+        "$defs": {
+            "Address": { "type": "object" }
+        }
+    }));
+
+    let components = schema.to_openapi("Order");
+
+    // The root schema takes the component name and $defs are hoisted
+    assert!(components["Order"]["$defs"].is_null());
+    assert_eq!(
+        components["Order"]["properties"]["address"]["$ref"],
+        "#/components/schemas/Address"
+    );
+    assert_eq!(components["Address"]["type"], "object");
+    assert_eq!(
+        components["Order"]["properties"]["note"]["type"],
+        json!(["string", "null"])
+    );
+    assert!(components["Order"]["properties"]["note"]["nullable"].is_null());
+}
+
+#[test]
+fn test_schema_to_openapi_leaves_names_and_data_alone() {
+    let schema = Schema::new(json!({
+        "type": "object",
+        "properties": {
+            "nullable": { "type": "boolean" },
+            "settings": {
+                "type": "object",
+                "default": { "nullable": true, "$ref": "#/$defs/Keep" },
+                "examples": [{ "nullable": false }]
+            },
+            "mode": { "const": { "nullable": true } }
+        },
+        "required": ["nullable"]
+    }));
+
+    let components = schema.to_openapi("Config");
+    let properties = &components["Config"]["properties"];
+    assert_eq!(properties["nullable"], json!({ "type": "boolean" }));
+    assert_eq!(
+        properties["settings"]["default"],
+        json!({ "nullable": true, "$ref": "#/$defs/Keep" })
+    );
+    assert_eq!(
+        properties["settings"]["examples"],
+        json!([{ "nullable": false }])
+    );
+    assert_eq!(properties["mode"]["const"], json!({ "nullable": true }));
+}
+
+#[test]
+fn test_schema_to_openapi_nullable_without_type() {
+    let schema = Schema::new(json!({
+        "type": "object",
+        "properties": {
+            "address": {
+                "$ref": "#/$defs/Address",
+                "description": "Shipping address",
+                "nullable": true
+            },
+            "choice": {
+                "anyOf": [{ "type": "string" }, { "type": "integer" }],
+                "nullable": true
+            },
+            "size": { "type": "string", "enum": ["S", "M"], "nullable": true }
+        },
+        "$defs": { "Address": { "type": "object" } }
+    }));
+
+    let components = schema.to_openapi("Order");
+    let properties = &components["Order"]["properties"];
+    assert_eq!(
+        properties["address"],
+        json!({
+            "description": "Shipping address",
+            "anyOf": [
+                { "$ref": "#/components/schemas/Address" },
+                { "type": "null" }
+            ]
+        })
+    );
+    assert_eq!(
+        properties["choice"]["anyOf"],
+        json!([{ "type": "string" }, { "type": "integer" }, { "type": "null" }])
+    );
+    assert_eq!(properties["size"]["type"], json!(["string", "null"]));
+    assert_eq!(properties["size"]["enum"], json!(["S", "M", null]));
+}
+
+#[test]
+fn test_schema_serde_round_trip() {
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Cached {
+        name: String,
+        schema: Schema,
+    }
+
+    let schema = SchemaBuilder::object()
+        .title("Person")
+        .property("name", json!({"type": "string"}), true)
+        .build();
+    let cached = Cached {
+        name: "person".to_string(),
+        schema,
+    };
+
+    // The schema serializes as its plain JSON value
+    let value = serde_json::to_value(&cached).unwrap();
+    assert_eq!(value["schema"]["title"], "Person");
+    assert_eq!(value["schema"]["required"], json!(["name"]));
+
+    let restored: Cached = serde_json::from_value(value).unwrap();
+    assert_eq!(restored.schema.to_json(), cached.schema.to_json());
+}
+
+#[test]
+fn test_prepare_for_targets() {
+    let schema = SchemaBuilder::object()
+        .title("Person")
+        .property(
+            "address",
+            json!({
+                "type": "object",
+                "properties": { "city": { "type": "string", "examples": ["Paris"] } }
+            }),
+            true,
+        )
+        .build();
+
+    let strict = schema.prepare_for(SchemaTarget::OpenAIStrict);
+    assert_eq!(strict["additionalProperties"], false);
+    assert_eq!(
+        strict["properties"]["address"]["additionalProperties"],
+        false
+    );
+    assert_eq!(strict["properties"]["address"]["required"], json!(["city"]));
+
+    let gemini = schema.prepare_for(SchemaTarget::Gemini);
+    assert!(gemini.get("title").is_none());
+    assert!(
+        gemini["properties"]["address"]["properties"]["city"]
+            .get("examples")
+            .is_none()
+    );
+
+    assert_eq!(schema.prepare_for(SchemaTarget::Raw), schema.to_json());
+}
+
+#[test]
+fn test_schema_metadata_accessors() {
+    let schema = Schema::new(json!({
+        "type": "object",
+        "title": "Movie",
+        "description": "A film",
+        "properties": {
+            "title": { "type": "string" },
+            "year": { "type": "integer" }
+        },
+        "required": ["title"]
+    }));
+    assert_eq!(schema.title(), Some("Movie"));
+    assert_eq!(schema.description(), Some("A film"));
+    assert_eq!(schema.property_names(), vec!["title", "year"]);
+    assert_eq!(schema.required_fields(), vec!["title"]);
+
+    let bare = Schema::new(json!({ "type": "string" }));
+    assert_eq!(bare.title(), None);
+    assert_eq!(bare.description(), None);
+    assert!(bare.property_names().is_empty());
+    assert!(bare.required_fields().is_empty());
+}
+
+#[test]
+fn test_stable_hash_ignores_key_order() {
+    let schema = Schema::new(json!({
+        "type": "object",
+        "properties": {
+            "name": { "type": "string", "description": "Full name" },
+            "tags": { "type": "array", "items": { "type": "string" } }
+        },
+        "required": ["name"]
+    }));
+    let reordered = Schema::new(json!({
+        "required": ["name"],
+        "properties": {
+            "tags": { "items": { "type": "string" }, "type": "array" },
+            "name": { "description": "Full name", "type": "string" }
+        },
+        "type": "object"
+    }));
+    assert_eq!(schema.stable_hash(), reordered.stable_hash());
+
+    // Array order is meaningful, so it changes the hash
+    let changed = Schema::new(json!({
+        "type": "object",
+        "properties": {
+            "name": { "type": "string", "description": "Full name" },
+            "tags": { "type": "array", "items": { "type": "string" } }
+        },
+        "required": ["name", "tags"]
+    }));
+    assert_ne!(schema.stable_hash(), changed.stable_hash());
+
+    // Pinned so any change to the canonical form or hash function is caught
+    assert_eq!(
+        Schema::new(json!({ "type": "string" })).stable_hash(),
+        3_968_812_916_696_199_474
+    );
+}
+
+#[test]
+fn test_schema_equality_ignores_key_order() {
+    let schema = Schema::new(json!({
+        "type": "object",
+        "properties": {
+            "name": { "type": "string", "description": "Full name" },
+            "age": { "type": "integer" }
+        },
+        "required": ["name"]
+    }));
+    let reordered = Schema::new(json!({
+        "required": ["name"],
+        "properties": {
+            "age": { "type": "integer" },
+            "name": { "description": "Full name", "type": "string" }
+        },
+        "type": "object"
+    }));
+    assert_eq!(schema, reordered);
+    assert!(schema.structurally_eq(&reordered));
+
+    let changed = Schema::new(json!({
+        "type": "object",
+        "properties": {
+            "name": { "type": "string" },
+            "age": { "type": "integer" }
+        },
+        "required": ["name"]
+    }));
+    assert_ne!(schema, changed);
+    assert!(!schema.structurally_eq(&changed));
+}
+
+#[test]
+fn test_for_array_of_hoists_definitions() {
+    struct Node;
+    impl super::SchemaType for Node {
+        fn schema() -> Schema {
+            Schema::new(json!({
+                "$defs": {
+                    "Node": {
+                        "type": "object",
+                        "properties": {
+                            "children": {"type": "array", "items": {"$ref": "#/$defs/Node"}}
+                        }
+                    }
+                },
+                "$ref": "#/$defs/Node"
+            }))
+        }
+    }
+
+    let schema = Schema::for_array_of::<Node>().into_value();
+    assert_eq!(schema["type"], "array");
+    assert_eq!(schema["items"], json!({"$ref": "#/$defs/Node"}));
+    assert!(schema["$defs"]["Node"].is_object());
+
+    let strings = Schema::for_array_of::<String>().into_value();
+    assert_eq!(
+        strings,
+        json!({"type": "array", "items": {"type": "string"}})
+    );
+}
@@ -0,0 +1,350 @@
+//! Callbacks that fire as soon as a given field of a streamed JSON document completes.
+//!
+//! [`JsonPathWatcher`] consumes text a chunk at a time, as it arrives from a streaming
+//! response, and calls the callback registered for a path the moment the value at that
+//! path is fully decoded, without waiting for the rest of the document. A UI can show a
+//! title while the model is still writing a long summary after it.
+//!
+//! Feed it the deltas from [`LLMClient::materialize_stream`](crate::LLMClient::materialize_stream),
+//! or from any other stream of text, and use [`collect_stream`] to parse the complete
+//! response once the stream ends.
+//!
+//! # Example
+//!
+//! ```
+//! use rstructor::streaming::JsonPathWatcher;
+//! use std::sync::{Arc, Mutex};
+//!
+//! let seen = Arc::new(Mutex::new(Vec::new()));
+//! let titles = seen.clone();
+//! let mut watcher = JsonPathWatcher::new()
+//!     .on("$.title", move |value| titles.lock().unwrap().push(value.clone()));
+//!
+//! watcher.feed(r#"{"title": "Incep"#);
+//! assert!(seen.lock().unwrap().is_empty());
+//! watcher.feed(r#"tion", "summary": "A thief who"#);
+//! assert_eq!(seen.lock().unwrap()[0], "Inception"); // fired before the summary ended
+//! ```
+
+use futures::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tracing::{trace, warn};
+
+use crate::backend::{parse_and_validate_response, unwrap_value_root, wrap_non_object_root};
+use crate::error::{RStructorError, Result};
+use crate::model::Instructor;
+
+type Callback<'a> = Box<dyn FnMut(&Value) + Send + 'a>;
+
+/// Open container in the document being decoded
+enum Frame {
+    Object {
+        start: usize,
+        key: Option<String>,
+        after_colon: bool,
+    },
+    Array {
+        start: usize,
+        index: usize,
+    },
+}
+
+/// Incrementally decodes a JSON document, firing callbacks keyed by path.
+///
+/// Paths use the same form as [`eval::diff_json`](crate::eval::diff_json): `$` is the
+/// root, `.name` selects an object key and `[2]` an array element. `[*]` matches any
+/// index, so `$.cast[*]` fires once per cast member as each one completes. Callbacks
+/// receive the decoded value, which for objects and arrays is the whole subtree.
+///
+/// Anything before the first `{` or `[` (such as a Markdown code fence) is skipped, and
+/// so is anything after the root value closes.
+pub struct JsonPathWatcher<'a> {
+    callbacks: Vec<(String, Callback<'a>)>,
+    buffer: String,
+    /// Byte offset in `buffer` up to which input has been scanned
+    scanned: usize,
+    started: bool,
+    done: bool,
+    stack: Vec<Frame>,
+    /// Start of the string being read, and whether it's an object key
+    string: Option<(usize, bool)>,
+    escaped: bool,
+    /// Start of the number or literal (`true`, `false`, `null`) being read
+    scalar: Option<usize>,
+}
+
+impl Default for JsonPathWatcher<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> JsonPathWatcher<'a> {
+    pub fn new() -> Self {
+        Self {
+            callbacks: Vec::new(),
+            buffer: String::new(),
+            scanned: 0,
+            started: false,
+            done: false,
+            stack: Vec::new(),
+            string: None,
+            escaped: false,
+            scalar: None,
+        }
+    }
+
+    /// Register `callback` to run when the value at `path` completes.
+    ///
+    /// Several callbacks may watch the same path; they run in registration order.
+    pub fn on<F>(mut self, path: impl Into<String>, callback: F) -> Self
+    where
+        F: FnMut(&Value) + Send + 'a,
+    {
+        self.callbacks.push((path.into(), Box::new(callback)));
+        self
+    }
+
+    /// Consume the next chunk of the document, firing callbacks for any watched values
+    /// it completes.
+    pub fn feed(&mut self, chunk: &str) {
+        if self.done {
+            return;
+        }
+        self.buffer.push_str(chunk);
+        while self.scanned < self.buffer.len() && !self.done {
+            let c = self.buffer[self.scanned..].chars().next().unwrap();
+            let i = self.scanned;
+            self.scanned += c.len_utf8();
+            self.scan(i, c);
+        }
+    }
+
+    /// Whether the root value has closed.
+    pub fn is_complete(&self) -> bool {
+        self.done
+    }
+
+    /// Everything fed so far.
+    pub fn text(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Deserialize the completed document.
+    ///
+    /// Fails with `RStructorError::SerializationError` if the root value hasn't closed.
+    pub fn finish<T: DeserializeOwned>(self) -> Result<T> {
+        if !self.done {
+            return Err(RStructorError::SerializationError(
+                "Streamed JSON ended before the root value was complete".to_string(),
+            ));
+        }
+        let root = self.buffer[self.root_start()..self.scanned].to_string();
+        Ok(serde_json::from_str(&root)?)
+    }
+
+    fn root_start(&self) -> usize {
+        self.buffer.find(['{', '[']).unwrap_or(0)
+    }
+
+    fn scan(&mut self, i: usize, c: char) {
+        if !self.started {
+            if c != '{' && c != '[' {
+                return;
+            }
+            self.started = true;
+        }
+
+        if let Some((start, is_key)) = self.string {
+            if self.escaped {
+                self.escaped = false;
+            } else if c == '\\' {
+                self.escaped = true;
+            } else if c == '"' {
+                self.string = None;
+                if is_key {
+                    let key = serde_json::from_str::<String>(&self.buffer[start..=i]).ok();
+                    if let Some(Frame::Object { key: slot, .. }) = self.stack.last_mut() {
+                        *slot = key;
+                    }
+                } else {
+                    self.complete(start, i + 1);
+                }
+            }
+            return;
+        }
+
+        if let Some(start) = self.scalar {
+            if !matches!(c, ',' | '}' | ']') && !c.is_whitespace() {
+                return;
+            }
+            self.scalar = None;
+            self.complete(start, i);
+        }
+
+        match c {
+            '"' => {
+                let is_key = matches!(
+                    self.stack.last(),
+                    Some(Frame::Object {
+                        after_colon: false,
+                        ..
+                    })
+                );
+                self.string = Some((i, is_key));
+            }
+            '{' => self.stack.push(Frame::Object {
+                start: i,
+                key: None,
+                after_colon: false,
+            }),
+            '[' => self.stack.push(Frame::Array { start: i, index: 0 }),
+            '}' | ']' => {
+                let start = match self.stack.pop() {
+                    Some(Frame::Object { start, .. } | Frame::Array { start, .. }) => start,
+                    None => return,
+                };
+                self.complete(start, i + 1);
+                if self.stack.is_empty() {
+                    self.done = true;
+                }
+            }
+            ':' => {
+                if let Some(Frame::Object { after_colon, .. }) = self.stack.last_mut() {
+                    *after_colon = true;
+                }
+            }
+            ',' => match self.stack.last_mut() {
+                Some(Frame::Object {
+                    key, after_colon, ..
+                }) => {
+                    *key = None;
+                    *after_colon = false;
+                }
+                Some(Frame::Array { index, .. }) => *index += 1,
+                None => {}
+            },
+            c if c.is_whitespace() => {}
+            _ => self.scalar = Some(i),
+        }
+    }
+
+    /// A value spanning `buffer[start..end]` just closed at the current path
+    fn complete(&mut self, start: usize, end: usize) {
+        let path = self.current_path();
+        if !self
+            .callbacks
+            .iter()
+            .any(|(pattern, _)| path_matches(pattern, &path))
+        {
+            return;
+        }
+        let value = match serde_json::from_str::<Value>(&self.buffer[start..end]) {
+            Ok(value) => value,
+            Err(e) => {
+                warn!(path = %path, error = %e, "Skipping watched value that isn't valid JSON");
+                return;
+            }
+        };
+        trace!(path = %path, "Watched value completed");
+        for (pattern, callback) in &mut self.callbacks {
+            if path_matches(pattern, &path) {
+                callback(&value);
+            }
+        }
+    }
+
+    fn current_path(&self) -> String {
+        let mut path = "$".to_string();
+        for frame in &self.stack {
+            match frame {
+                Frame::Object { key: Some(key), .. } => {
+                    path.push('.');
+                    path.push_str(key);
+                }
+                Frame::Object { key: None, .. } => {}
+                Frame::Array { index, .. } => path.push_str(&format!("[{}]", index)),
+            }
+        }
+        path
+    }
+}
+
+/// Whether a concrete path like `$.cast[3].name` matches `pattern`, where `[*]` in the
+/// pattern stands for any index
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let mut pattern = pattern;
+    let mut path = path;
+    loop {
+        match pattern.find("[*]") {
+            None => return pattern == path,
+            Some(wildcard) => {
+                let (literal, rest) = pattern.split_at(wildcard);
+                let Some(after_literal) = path.strip_prefix(literal) else {
+                    return false;
+                };
+                let Some(after_bracket) = after_literal.strip_prefix('[') else {
+                    return false;
+                };
+                let Some(close) = after_bracket.find(']') else {
+                    return false;
+                };
+                if close == 0 || !after_bracket[..close].bytes().all(|b| b.is_ascii_digit()) {
+                    return false;
+                }
+                pattern = &rest[3..];
+                path = &after_bracket[close + 1..];
+            }
+        }
+    }
+}
+
+/// Accumulate a stream of text deltas and parse the whole response as `T`.
+///
+/// Meant for the stream returned by
+/// [`LLMClient::materialize_stream`](crate::LLMClient::materialize_stream): the first
+/// error in the stream is returned as is, and the complete text is then parsed,
+/// validated and normalized like a [`materialize`](crate::LLMClient::materialize)
+/// response. A failure here isn't retried.
+///
+/// # Example
+///
+/// ```no_run
+/// # use rstructor::{LLMClient, OpenAIClient, Instructor};
+/// # use serde::{Serialize, Deserialize};
+/// # #[derive(Instructor, Serialize, Deserialize)]
+/// # struct Movie { title: String }
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use futures::StreamExt;
+/// use rstructor::streaming::collect_stream;
+///
+/// let client = OpenAIClient::from_env()?;
+/// let stream = client.materialize_stream::<Movie>("Describe Inception").await?;
+/// let stream = stream.inspect(|delta| {
+///     if let Ok(text) = delta {
+///         print!("{}", text);
+///     }
+/// });
+/// let movie: Movie = collect_stream(stream).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn collect_stream<T, S>(stream: S) -> Result<T>
+where
+    T: Instructor + DeserializeOwned,
+    S: Stream<Item = Result<String>>,
+{
+    let mut stream = std::pin::pin!(stream);
+    let mut text = String::new();
+    while let Some(delta) = stream.next().await {
+        text.push_str(&delta?);
+    }
+    trace!(len = text.len(), "Collected streamed response");
+
+    // Non-object roots are streamed inside the `{"value": ...}` wrapper some providers need
+    if wrap_non_object_root(&mut T::schema().to_json()) {
+        text = unwrap_value_root(text, false);
+    }
+    parse_and_validate_response::<T>(&text, false).map_err(|(err, _)| err)
+}
@@ -0,0 +1,68 @@
+//! Tests for Anthropic's forced tool-use mode, using a local stub of the Messages API.
+
+#![cfg(feature = "anthropic")]
+
+mod common;
+
+use common::serve_once;
+use rstructor::{AnthropicClient, Instructor, LLMClient};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Movie {
+    title: String,
+    year: u16,
+}
+
+/// A Messages API response with the given content blocks
+fn message(content: Value) -> Value {
+    json!({
+        "model": "claude-sonnet-4-5-20250929",
+        "content": content,
+        "usage": { "input_tokens": 10, "output_tokens": 5 }
+    })
+}
+
+#[tokio::test]
+async fn test_force_tool_use_reads_tool_input() {
+    let (base_url, requests) = serve_once(message(json!([{
+        "type": "tool_use",
+        "id": "toolu_01",
+        "name": "structured_output",
+        "input": { "title": "Inception", "year": 2010 }
+    }])));
+    let client = AnthropicClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .force_tool_use(true);
+
+    let movie: Movie = client.materialize("Describe Inception").await.unwrap();
+    assert_eq!(movie.title, "Inception");
+    assert_eq!(movie.year, 2010);
+
+    let captured = requests.recv().unwrap();
+    assert!(captured.header("anthropic-beta").is_none());
+    let request = captured.body;
+    assert!(request.get("output_format").is_none());
+    assert_eq!(request["tool_choice"]["type"], "tool");
+    assert_eq!(request["tool_choice"]["name"], "structured_output");
+    let tool = &request["tools"][0];
+    assert_eq!(tool["name"], "structured_output");
+    assert!(tool["input_schema"]["properties"]["year"].is_object());
+}
+
+#[tokio::test]
+async fn test_force_tool_use_without_tool_block_fails() {
+    let (base_url, _requests) = serve_once(message(
+        json!([{ "type": "text", "text": "I can't do that." }]),
+    ));
+    let client = AnthropicClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .force_tool_use(true)
+        .no_retries();
+
+    let result = client.materialize::<Movie>("Describe Inception").await;
+    assert!(result.is_err());
+}
@@ -0,0 +1,81 @@
+//! Tests for `#[llm(min_items, max_items, unique_items)]` on array and set fields.
+
+use std::collections::{BTreeSet, HashSet};
+
+use rstructor::{Instructor, SchemaTarget, SchemaType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Article {
+    #[llm(description = "Topic tags", min_items = 3, max_items = 5)]
+    tags: Vec<String>,
+
+    #[llm(max_items = 2, unique_items)]
+    authors: Option<Vec<String>>,
+
+    #[llm(min_items = 1)]
+    keywords: HashSet<String>,
+
+    #[llm(max_items = 4, unique_items = true)]
+    regions: BTreeSet<String>,
+
+    #[llm(unique_items = false)]
+    ratings: Vec<u8>,
+
+    links: Vec<String>,
+}
+
+#[test]
+fn test_item_bounds_on_vec() {
+    let schema_json = Article::schema().to_json();
+    let tags = &schema_json["properties"]["tags"];
+
+    assert_eq!(tags["type"], "array");
+    assert_eq!(tags["items"]["type"], "string");
+    assert_eq!(tags["minItems"], 3);
+    assert_eq!(tags["maxItems"], 5);
+    assert_eq!(tags["description"], "Topic tags");
+    assert!(tags.get("uniqueItems").is_none());
+}
+
+#[test]
+fn test_item_bounds_on_optional_vec() {
+    let schema_json = Article::schema().to_json();
+    let authors = &schema_json["properties"]["authors"];
+
+    assert_eq!(authors["maxItems"], 2);
+    assert_eq!(authors["uniqueItems"], true);
+    assert!(authors.get("minItems").is_none());
+}
+
+#[test]
+fn test_item_bounds_on_sets() {
+    let schema_json = Article::schema().to_json();
+    let props = &schema_json["properties"];
+
+    assert_eq!(props["keywords"]["minItems"], 1);
+    assert_eq!(props["keywords"]["uniqueItems"], true);
+    assert_eq!(props["regions"]["maxItems"], 4);
+    assert_eq!(props["regions"]["uniqueItems"], true);
+}
+
+#[test]
+fn test_unannotated_arrays_unchanged() {
+    let schema_json = Article::schema().to_json();
+    let props = &schema_json["properties"];
+
+    for field in ["ratings", "links"] {
+        assert!(props[field].get("minItems").is_none(), "{}", field);
+        assert!(props[field].get("maxItems").is_none(), "{}", field);
+        assert!(props[field].get("uniqueItems").is_none(), "{}", field);
+    }
+}
+
+#[test]
+fn test_item_bounds_survive_provider_preparation() {
+    for target in [SchemaTarget::OpenAIStrict, SchemaTarget::Gemini] {
+        let schema = Article::schema().prepare_for(target);
+        assert_eq!(schema["properties"]["tags"]["minItems"], 3, "{:?}", target);
+        assert_eq!(schema["properties"]["tags"]["maxItems"], 5, "{:?}", target);
+    }
+}
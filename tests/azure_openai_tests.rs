@@ -0,0 +1,127 @@
+//! Tests for `OpenAIClient::azure`, using a local stub of an Azure OpenAI resource.
+
+#![cfg(feature = "openai")]
+
+mod common;
+
+use common::serve;
+use rstructor::{Instructor, LLMClient, OpenAIClient};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+struct Movie {
+    title: String,
+    year: u16,
+}
+
+fn completion(content: &str) -> Value {
+    json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "model": "gpt-4o-2024-11-20",
+        "choices": [{
+            "index": 0,
+            "message": { "role": "assistant", "content": content },
+            "finish_reason": "stop"
+        }],
+        "usage": { "prompt_tokens": 25, "completion_tokens": 8, "total_tokens": 33 }
+    })
+}
+
+fn client(endpoint: &str) -> OpenAIClient {
+    OpenAIClient::azure(endpoint, "prod-chat", "2024-10-21", "azure-key").unwrap()
+}
+
+#[tokio::test]
+async fn test_materialize_uses_deployment_url_and_api_key_header() {
+    let (endpoint, rx) = serve(vec![completion(r#"{"title": "Inception", "year": 2010}"#)]);
+    let movie: Movie = client(&endpoint)
+        .model("gpt-4o")
+        .materialize("Describe Inception")
+        .await
+        .unwrap();
+    assert_eq!(movie.year, 2010);
+
+    let captured = rx.recv().unwrap();
+    assert!(captured.request_line.starts_with(
+        "POST /openai/deployments/prod-chat/chat/completions?api-version=2024-10-21 "
+    ));
+    assert_eq!(captured.header("api-key"), Some("azure-key"));
+    assert_eq!(captured.header("authorization"), None);
+    assert_eq!(captured.body["response_format"]["type"], "json_schema");
+    assert_eq!(
+        captured.body["response_format"]["json_schema"]["strict"],
+        true
+    );
+}
+
+#[tokio::test]
+async fn test_model_defaults_to_deployment_name() {
+    let (endpoint, rx) = serve(vec![completion(r#"{"title": "Inception", "year": 2010}"#)]);
+    let _: Movie = client(&format!("{}/", endpoint))
+        .materialize("Describe Inception")
+        .await
+        .unwrap();
+
+    let captured = rx.recv().unwrap();
+    // The trailing slash on the endpoint is dropped
+    assert!(
+        captured
+            .request_line
+            .starts_with("POST /openai/deployments/prod-chat/chat/completions?")
+    );
+    assert_eq!(captured.body["model"], "prod-chat");
+    // An unrecognised deployment name gets no reasoning parameters
+    assert!(captured.body.get("reasoning_effort").is_none());
+}
+
+#[tokio::test]
+async fn test_generate_uses_deployment_url() {
+    let (endpoint, rx) = serve(vec![completion("Hello there")]);
+    let text = client(&endpoint).generate("Say hello").await.unwrap();
+    assert_eq!(text, "Hello there");
+
+    let captured = rx.recv().unwrap();
+    assert!(captured.request_line.starts_with(
+        "POST /openai/deployments/prod-chat/chat/completions?api-version=2024-10-21 "
+    ));
+    assert_eq!(captured.header("api-key"), Some("azure-key"));
+}
+
+#[tokio::test]
+async fn test_list_models_uses_resource_models_endpoint() {
+    let (endpoint, rx) = serve(vec![json!({
+        "object": "list",
+        "data": [
+            { "id": "gpt-4o", "object": "model" },
+            { "id": "text-embedding-3-large", "object": "model" }
+        ]
+    })]);
+    let models = client(&endpoint).list_models().await.unwrap();
+    assert_eq!(
+        models.iter().map(|m| m.id.as_str()).collect::<Vec<_>>(),
+        vec!["gpt-4o"]
+    );
+
+    let captured = rx.recv().unwrap();
+    assert!(
+        captured
+            .request_line
+            .starts_with("GET /openai/models?api-version=2024-10-21 ")
+    );
+    assert_eq!(captured.header("api-key"), Some("azure-key"));
+}
+
+#[test]
+fn test_empty_key_is_rejected() {
+    assert!(
+        OpenAIClient::azure(
+            "https://my-resource.openai.azure.com",
+            "prod-chat",
+            "2024-10-21",
+            ""
+        )
+        .is_err()
+    );
+}
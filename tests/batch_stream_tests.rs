@@ -0,0 +1,247 @@
+//! Tests for streaming batch materialization and usage tracking.
+//!
+//! These tests use an in-memory client so they run without network access.
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use rstructor::{
+    ChatMessage, GenerateResult, Instructor, LLMClient, MaterializeResult, ModelInfo,
+    RStructorError, RequestOptions, Result, TokenUsage,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+struct Echo {
+    text: String,
+}
+
+/// Client that echoes the prompt back and reports one token per character, priced at
+/// $1 per million tokens.
+///
+/// Prompts starting with "slow" yield to the runtime a few times first, so they finish
+/// after prompts started later.
+struct EchoClient;
+
+#[async_trait]
+impl LLMClient for EchoClient {
+    async fn materialize<T>(&self, prompt: &str) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        Ok(self.materialize_with_metadata::<T>(prompt).await?.data)
+    }
+
+    async fn materialize_with_metadata<T>(&self, prompt: &str) -> Result<MaterializeResult<T>>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        if prompt == "fail" {
+            return Err(RStructorError::ValidationError("rejected".to_string()));
+        }
+        if prompt.starts_with("slow") {
+            for _ in 0..10 {
+                tokio::task::yield_now().await;
+            }
+        }
+        let data: T = serde_json::from_value(serde_json::json!({ "text": prompt }))?;
+        let usage = TokenUsage::new("echo", prompt.len() as u64, 1).with_pricing(1.0, 1.0);
+        Ok(MaterializeResult::new(data, Some(usage)))
+    }
+
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        Ok(prompt.to_string())
+    }
+
+    async fn generate_with_metadata(&self, prompt: &str) -> Result<GenerateResult> {
+        Ok(GenerateResult::new(prompt.to_string(), None))
+    }
+
+    fn from_env() -> Result<Self> {
+        Ok(EchoClient)
+    }
+
+    async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        Ok(Vec::new())
+    }
+}
+
+#[tokio::test]
+async fn test_materialize_many_stream_yields_every_item() {
+    let client = EchoClient;
+    let prompts = ["one", "fail", "three"];
+
+    let mut items: Vec<_> = client
+        .materialize_many_stream::<Echo>(&prompts, 2)
+        .collect()
+        .await;
+    items.sort_by_key(|item| item.index);
+
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0].result.as_ref().unwrap().text, "one");
+    assert!(items[1].result.is_err());
+    assert!(items[1].usage.is_none());
+    assert_eq!(items[2].result.as_ref().unwrap().text, "three");
+}
+
+#[tokio::test]
+async fn test_materialize_many_stream_running_totals() {
+    let client = EchoClient;
+    let prompts = ["ab", "cde", "f"];
+
+    let items: Vec<_> = client
+        .materialize_many_stream::<Echo>(&prompts, 1)
+        .collect()
+        .await;
+
+    // Totals only ever grow and the final item reflects the whole batch
+    let totals: Vec<u64> = items.iter().map(|i| i.totals.input_tokens).collect();
+    assert!(totals.windows(2).all(|w| w[0] <= w[1]));
+    let last = items.last().unwrap().totals;
+    assert_eq!(last.requests, 3);
+    assert_eq!(last.input_tokens, 6);
+    assert_eq!(last.output_tokens, 3);
+
+    let cost = last.cost_usd.unwrap();
+    assert!((cost - 9.0 / 1_000_000.0).abs() < 1e-12);
+}
+
+#[tokio::test]
+async fn test_materialize_many_stream_can_stop_early() {
+    let client = EchoClient;
+    let prompts = ["a", "b", "c", "d"];
+
+    let mut stream = client.materialize_many_stream::<Echo>(&prompts, 1);
+    let first = stream.next().await.unwrap();
+    assert_eq!(first.totals.requests, 1);
+    drop(stream);
+}
+
+#[tokio::test]
+async fn test_materialize_many_keeps_input_order() {
+    let client = EchoClient;
+    let prompts = ["slow one", "fail", "three", "slow four", "five"];
+
+    // The slow prompts complete last, but results line up with the prompts
+    let arrival: Vec<usize> = client
+        .materialize_many_stream::<Echo>(&prompts, 3)
+        .map(|item| item.index)
+        .collect()
+        .await;
+    assert_ne!(arrival, vec![0, 1, 2, 3, 4]);
+
+    let results = client.materialize_many::<Echo>(&prompts, 3).await;
+    assert_eq!(results.len(), 5);
+    assert_eq!(results[0].as_ref().unwrap().text, "slow one");
+    assert!(results[1].is_err());
+    assert_eq!(results[2].as_ref().unwrap().text, "three");
+    assert_eq!(results[3].as_ref().unwrap().text, "slow four");
+    assert_eq!(results[4].as_ref().unwrap().text, "five");
+}
+
+#[tokio::test]
+async fn test_materialize_many_handles_empty_input_and_zero_concurrency() {
+    let client = EchoClient;
+    assert!(client.materialize_many::<Echo>(&[], 4).await.is_empty());
+
+    let results = client.materialize_many::<Echo>(&["a", "b"], 0).await;
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[1].as_ref().unwrap().text, "b");
+}
+
+#[tokio::test]
+async fn test_default_generate_with_messages_single_turn_only() {
+    let client = EchoClient;
+
+    let result = client
+        .generate_with_messages(&[ChatMessage::user("hi")])
+        .await
+        .unwrap();
+    assert_eq!(result.text, "hi");
+
+    let messages = [ChatMessage::system("be brief"), ChatMessage::user("hi")];
+    assert!(client.generate_with_messages(&messages).await.is_err());
+}
+
+#[tokio::test]
+async fn test_default_opts_methods_accept_only_empty_options() {
+    let client = EchoClient;
+
+    let echo: Echo = client
+        .materialize_opts("hi", &RequestOptions::new())
+        .await
+        .unwrap();
+    assert_eq!(echo.text, "hi");
+
+    let options = RequestOptions::new().with_temperature(0.5);
+    assert!(client.generate_opts("hi", &options).await.is_err());
+}
+
+#[tokio::test]
+async fn test_default_materialize_with_examples_needs_empty_examples() {
+    let client = EchoClient;
+
+    let echo: Echo = client.materialize_with_examples("hi", &[]).await.unwrap();
+    assert_eq!(echo.text, "hi");
+
+    let examples = vec![(
+        "hello".to_string(),
+        Echo {
+            text: "hello".to_string(),
+        },
+    )];
+    assert!(
+        client
+            .materialize_with_examples::<Echo>("hi", &examples)
+            .await
+            .is_err()
+    );
+}
+
+#[test]
+fn test_default_materialize_request_body_is_unsupported() {
+    assert!(EchoClient.materialize_request_body::<Echo>("hi").is_err());
+}
+
+#[test]
+fn test_default_estimate_prompt_tokens_counts_the_prompt() {
+    // Without a request preview only the prompt is counted, at four bytes per token
+    assert_eq!(EchoClient.estimate_prompt_tokens::<Echo>("abcdefghi"), 3);
+}
+
+#[derive(Serialize)]
+struct Ticket {
+    subject: String,
+    priority: u8,
+}
+
+#[tokio::test]
+async fn test_materialize_from_sends_serialized_input() {
+    let ticket = Ticket {
+        subject: "Login broken".to_string(),
+        priority: 1,
+    };
+    let expected = serde_json::to_string_pretty(&ticket).unwrap();
+
+    let echo: Echo = EchoClient.materialize_from(&ticket).await.unwrap();
+    assert_eq!(echo.text, expected);
+
+    let echo: Echo = EchoClient
+        .materialize_from_with_instruction("Triage this ticket", &ticket)
+        .await
+        .unwrap();
+    assert_eq!(echo.text, format!("Triage this ticket\n\n{}", expected));
+
+    let text = EchoClient.generate_from(&["a", "b"][..]).await.unwrap();
+    assert_eq!(text, "[\n  \"a\",\n  \"b\"\n]");
+}
+
+#[tokio::test]
+async fn test_materialize_stream_is_unsupported_by_default() {
+    let err = EchoClient
+        .materialize_stream::<Echo>("hello")
+        .await
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("Streaming is not supported"));
+}
@@ -0,0 +1,161 @@
+//! Tests for the client spending limit.
+//!
+//! The budget is checked before any request is sent, so an exhausted budget can be
+//! exercised without network access; calls that go through use a local stub of the
+//! OpenAI API.
+
+mod common;
+
+use rstructor::{
+    AnthropicClient, Budget, GeminiClient, GrokClient, Instructor, LLMClient, OpenAIClient,
+    PricingTable, RStructorError, TokenUsage,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Barrier};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Answer {
+    value: String,
+}
+
+fn pricing() -> PricingTable {
+    PricingTable::new()
+        .with_model("gpt-5.2", 1.75, 14.00)
+        .with_model("claude-sonnet-4-5", 3.00, 15.00)
+}
+
+#[tokio::test]
+async fn test_exhausted_budget_blocks_materialize() {
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .budget(0.01, &pricing());
+    client
+        .current_budget()
+        .unwrap()
+        .record(Some(&TokenUsage::new("gpt-5.2", 10_000, 0)));
+
+    let err = client.materialize::<Answer>("hello").await.unwrap_err();
+    assert!(matches!(err, RStructorError::BudgetExceeded { .. }));
+    assert!(!err.is_retryable());
+}
+
+#[tokio::test]
+async fn test_exhausted_budget_blocks_generate() {
+    let pricing = pricing();
+    let anthropic = AnthropicClient::new("test-key")
+        .unwrap()
+        .budget(0.0, &pricing);
+    let gemini = GeminiClient::new("test-key").unwrap().budget(0.0, &pricing);
+    let grok = GrokClient::new("test-key").unwrap().budget(0.0, &pricing);
+
+    for result in [
+        anthropic.generate("hello").await,
+        gemini.generate("hello").await,
+        grok.generate("hello").await,
+    ] {
+        assert!(matches!(result, Err(RStructorError::BudgetExceeded { .. })));
+    }
+}
+
+#[test]
+fn test_budget_shared_across_clones() {
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .budget(1.0, &pricing());
+    let budget = client.current_budget().unwrap();
+    let shared = budget.clone();
+
+    shared.record(Some(&TokenUsage::new("claude-sonnet-4-5", 100_000, 0)));
+    assert!((budget.spent_usd() - 0.3).abs() < 1e-9);
+    assert!((budget.remaining_usd() - 0.7).abs() < 1e-9);
+
+    // Unknown models aren't counted
+    budget.record(Some(&TokenUsage::new("mystery-model", 1_000_000, 0)));
+    assert!((budget.spent_usd() - 0.3).abs() < 1e-9);
+}
+
+#[test]
+fn test_budget_exceeded_message() {
+    let err = RStructorError::BudgetExceeded {
+        spent_usd: 1.5,
+        limit_usd: 1.0,
+    };
+    assert_eq!(
+        err.to_string(),
+        "Budget exceeded: spent $1.5000 of $1.0000 limit"
+    );
+}
+
+#[tokio::test]
+async fn test_call_that_would_cross_the_ceiling_is_refused() {
+    // $1 per thousand output tokens, so `max_tokens(1000)` reserves about $1 a call
+    let pricing = PricingTable::new().with_model("gpt-4o", 0.0, 1000.0);
+    let (base_url, _requests) = common::serve_once(common::completion(r#"{"value": "ok"}"#));
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o")
+        .max_tokens(1000)
+        .no_retries()
+        .budget(1.5, &pricing);
+
+    // $0.60 is spent, and a call that could cost $1 more would cross $1.50
+    client
+        .current_budget()
+        .unwrap()
+        .record(Some(&TokenUsage::new("gpt-4o", 0, 600)));
+    let err = client.materialize::<Answer>("hello").await.unwrap_err();
+    assert!(matches!(err, RStructorError::BudgetExceeded { .. }));
+
+    // With room for it, the call goes through and is charged its actual usage
+    let client = client.budget(1.5, &pricing);
+    let answer: Answer = client.materialize("hello").await.unwrap();
+    assert_eq!(answer.value, "ok");
+    let budget = client.current_budget().unwrap();
+    assert_eq!(budget.reserved_usd(), 0.0);
+    assert!((budget.spent_usd() - 0.005).abs() < 1e-9);
+}
+
+#[test]
+fn test_concurrent_checks_reserve_within_the_ceiling() {
+    let pricing = PricingTable::new().with_model("gpt-4o", 0.0, 1000.0);
+    let budget = Budget::new(2.5, &pricing);
+    let estimate = TokenUsage::new("gpt-4o", 0, 1000);
+
+    // Every thread checks before any of them records
+    let barrier = Arc::new(Barrier::new(8));
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let budget = budget.clone();
+            let estimate = estimate.clone();
+            let barrier = Arc::clone(&barrier);
+            std::thread::spawn(move || {
+                let reservation = budget.check(&estimate);
+                barrier.wait();
+                reservation.is_ok()
+            })
+        })
+        .collect();
+    let passed = handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .filter(|&ok| ok)
+        .count();
+    assert_eq!(passed, 2);
+
+    // Reservations dropped without recording a cost (failed calls) are released
+    assert_eq!(budget.reserved_usd(), 0.0);
+    assert_eq!(budget.spent_usd(), 0.0);
+}
+
+#[test]
+fn test_reservation_settles_to_actual_cost() {
+    let pricing = PricingTable::new().with_model("gpt-4o", 0.0, 1000.0);
+    let budget = Budget::new(2.5, &pricing);
+
+    let reservation = budget.check(&TokenUsage::new("gpt-4o", 0, 1000)).unwrap();
+    assert!((budget.reserved_usd() - 1.0).abs() < 1e-9);
+    reservation.record(Some(&TokenUsage::new("gpt-4o", 0, 250)));
+    assert_eq!(budget.reserved_usd(), 0.0);
+    assert!((budget.spent_usd() - 0.25).abs() < 1e-9);
+}
@@ -0,0 +1,157 @@
+//! Local stubs of the provider APIs, shared by the integration tests.
+//!
+//! Each test binary pulls this in with `mod common;` and uses only some of it.
+#![allow(dead_code)]
+
+use serde_json::{Value, json};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+
+/// A request received by the stub
+pub struct Captured {
+    /// e.g. `POST /chat/completions HTTP/1.1`
+    pub request_line: String,
+    /// Header names (lowercased) and values
+    pub headers: Vec<(String, String)>,
+    /// The JSON body, or `Value::Null` if there was none
+    pub body: Value,
+}
+
+impl Captured {
+    /// Value of the first header called `name` (lowercase)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// A canned response for the stub to send
+pub struct Reply {
+    status: String,
+    content_type: String,
+    headers: Vec<String>,
+    body: String,
+    content_length: bool,
+}
+
+impl Reply {
+    /// Respond with `status` (e.g. `200 OK`) and `body` as `content_type`
+    pub fn new(status: &str, content_type: &str, body: impl Into<String>) -> Self {
+        Self {
+            status: status.to_string(),
+            content_type: content_type.to_string(),
+            headers: Vec::new(),
+            body: body.into(),
+            content_length: true,
+        }
+    }
+
+    /// `200 OK` with a JSON body
+    pub fn json(body: Value) -> Self {
+        Self::new("200 OK", "application/json", body.to_string())
+    }
+
+    /// An event stream, which runs until the connection closes
+    pub fn event_stream(status: &str, body: impl Into<String>) -> Self {
+        Self::new(status, "text/event-stream", body).without_content_length()
+    }
+
+    /// Add a header, given as a `Name: value` line
+    pub fn header(mut self, line: &str) -> Self {
+        self.headers.push(line.to_string());
+        self
+    }
+
+    /// Leave out `Content-Length`, so the body runs until the connection closes
+    pub fn without_content_length(mut self) -> Self {
+        self.content_length = false;
+        self
+    }
+
+    fn to_http(&self) -> String {
+        let mut head = format!(
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\n",
+            self.status, self.content_type
+        );
+        for header in &self.headers {
+            head.push_str(header);
+            head.push_str("\r\n");
+        }
+        if self.content_length {
+            head.push_str(&format!("Content-Length: {}\r\n", self.body.len()));
+        }
+        format!("{}Connection: close\r\n\r\n{}", head, self.body)
+    }
+}
+
+impl From<Value> for Reply {
+    fn from(body: Value) -> Self {
+        Reply::json(body)
+    }
+}
+
+/// Answer each request with the next of `replies`, sending back every request received.
+///
+/// Returns the stub's base URL and the channel of captured requests.
+pub fn serve<R: Into<Reply>>(replies: Vec<R>) -> (String, mpsc::Receiver<Captured>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let replies: Vec<Reply> = replies.into_iter().map(Into::into).collect();
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        for reply in replies {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut headers = Vec::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    headers.push((name.to_ascii_lowercase(), value.trim().to_string()));
+                }
+            }
+            let content_length = headers
+                .iter()
+                .find(|(n, _)| n == "content-length")
+                .map_or(0, |(_, v)| v.parse().unwrap());
+            let mut request = vec![0u8; content_length];
+            reader.read_exact(&mut request).unwrap();
+            // Tests that don't look at the requests drop the receiver
+            let _ = tx.send(Captured {
+                request_line: request_line.trim_end().to_string(),
+                headers,
+                body: serde_json::from_slice(&request).unwrap_or(Value::Null),
+            });
+
+            // The client may hang up once it has seen enough
+            let _ = reader.into_inner().write_all(reply.to_http().as_bytes());
+        }
+    });
+    (base_url, rx)
+}
+
+/// Answer one request with `reply`
+pub fn serve_once(reply: impl Into<Reply>) -> (String, mpsc::Receiver<Captured>) {
+    serve(vec![reply.into()])
+}
+
+/// An OpenAI-style chat completion whose message content is `content`
+pub fn completion(content: &str) -> Value {
+    json!({
+        "model": "gpt-4o",
+        "choices": [{
+            "message": { "role": "assistant", "content": content },
+            "finish_reason": "stop"
+        }],
+        "usage": { "prompt_tokens": 10, "completion_tokens": 5 }
+    })
+}
@@ -102,6 +102,27 @@ mod container_attributes_tests {
         field: String,
     }
 
+    // Test enum with a most likely variant
+    #[derive(Instructor, Serialize, Deserialize, Debug)]
+    #[llm(example = "Normal")]
+    #[serde(rename_all = "snake_case")]
+    enum EnumWithExample {
+        #[llm(description = "Needs attention today")]
+        Urgent,
+        #[llm(description = "Routine request (the usual case)")]
+        Normal,
+        LowPriority,
+    }
+
+    // Test enum whose example names the serialized value
+    #[derive(Instructor, Serialize, Deserialize, Debug)]
+    #[llm(example = "low_priority")]
+    #[serde(rename_all = "snake_case")]
+    enum EnumWithSerializedExample {
+        Urgent,
+        LowPriority,
+    }
+
     // Enum without attributes
     #[derive(Instructor, Serialize, Deserialize, Debug)]
     enum EnumWithoutAttributes {
@@ -234,6 +255,27 @@ mod container_attributes_tests {
         assert_eq!(examples[1], "Value2");
     }
 
+    // Tests for enum with a single example
+    #[test]
+    fn test_enum_with_example() {
+        let schema_json = EnumWithExample::schema().to_json();
+
+        // The example uses the serialized value, not the Rust variant name
+        assert_eq!(schema_json["example"], "normal");
+        assert!(schema_json.get("examples").is_none());
+        assert_eq!(schema_json["enum"].as_array().unwrap().len(), 3);
+
+        let schema_json = EnumWithSerializedExample::schema().to_json();
+        assert_eq!(schema_json["example"], "low_priority");
+
+        assert!(
+            EnumWithoutAttributes::schema()
+                .to_json()
+                .get("example")
+                .is_none()
+        );
+    }
+
     // Tests for enum with multiple attributes
     #[test]
     fn test_enum_with_multiple_attrs() {
@@ -0,0 +1,53 @@
+//! Tests for `#[llm(content_media_type, content_encoding)]` on string fields.
+
+use rstructor::{Instructor, SchemaTarget, SchemaType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Attachment {
+    #[llm(
+        description = "The image bytes",
+        content_media_type = "image/png",
+        content_encoding = "base64"
+    )]
+    data: String,
+
+    #[llm(content_media_type = "application/json")]
+    manifest: Option<String>,
+
+    // Not a string, so the annotations are ignored
+    #[llm(content_media_type = "text/plain")]
+    size: u64,
+
+    name: String,
+}
+
+#[test]
+fn test_content_annotations_on_string_fields() {
+    let schema_json = Attachment::schema().to_json();
+    let props = &schema_json["properties"];
+
+    assert_eq!(props["data"]["type"], "string");
+    assert_eq!(props["data"]["contentMediaType"], "image/png");
+    assert_eq!(props["data"]["contentEncoding"], "base64");
+    assert_eq!(props["data"]["description"], "The image bytes");
+
+    assert_eq!(props["manifest"]["contentMediaType"], "application/json");
+    assert!(props["manifest"].get("contentEncoding").is_none());
+
+    assert!(props["size"].get("contentMediaType").is_none());
+    assert!(props["name"].get("contentMediaType").is_none());
+}
+
+#[test]
+fn test_content_annotations_stripped_for_gemini() {
+    let schema = Attachment::schema().prepare_for(SchemaTarget::Gemini);
+    let data = &schema["properties"]["data"];
+    assert_eq!(data["type"], "string");
+    assert!(data.get("contentMediaType").is_none());
+    assert!(data.get("contentEncoding").is_none());
+
+    // Other targets keep them
+    let strict = Attachment::schema().prepare_for(SchemaTarget::OpenAIStrict);
+    assert_eq!(strict["properties"]["data"]["contentEncoding"], "base64");
+}
@@ -0,0 +1,159 @@
+//! Tests that chrono date fields carry their date `format` in every position.
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use rstructor::{Instructor, SchemaType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Schedule {
+    checkpoints: HashMap<String, DateTime<Utc>>,
+    window: (DateTime<Utc>, String),
+    holidays: Vec<NaiveDate>,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+enum Change {
+    Moved {
+        at: DateTime<Utc>,
+        deadlines: HashMap<String, DateTime<Utc>>,
+        span: (DateTime<Utc>, String),
+        history: Vec<DateTime<Utc>>,
+    },
+    Cancelled,
+}
+
+#[test]
+fn test_struct_date_fields_in_containers() {
+    let schema_json = Schedule::schema().to_json();
+    let props = &schema_json["properties"];
+
+    assert_eq!(
+        props["checkpoints"]["additionalProperties"]["format"],
+        "date-time"
+    );
+    assert_eq!(props["window"]["prefixItems"][0]["type"], "string");
+    assert_eq!(props["window"]["prefixItems"][0]["format"], "date-time");
+    assert!(props["window"]["prefixItems"][1].get("format").is_none());
+    assert_eq!(props["holidays"]["items"]["format"], "date");
+}
+
+#[test]
+fn test_enum_variant_date_fields_in_containers() {
+    let schema_json = Change::schema().to_json();
+    let moved = schema_json["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|variant| variant["properties"].get("Moved").is_some())
+        .expect("Moved variant");
+    let props = &moved["properties"]["Moved"]["properties"];
+
+    assert_eq!(props["at"]["format"], "date-time");
+    assert_eq!(
+        props["deadlines"]["additionalProperties"]["format"],
+        "date-time"
+    );
+    assert_eq!(props["span"]["prefixItems"][0]["format"], "date-time");
+    assert_eq!(props["history"]["items"]["format"], "date-time");
+}
+
+#[test]
+fn test_date_schema_type_matches_derive() {
+    let schema_json = Schedule::schema().to_json();
+    assert_eq!(
+        schema_json["properties"]["checkpoints"]["additionalProperties"],
+        <DateTime<Utc>>::schema().to_json()
+    );
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Spellings {
+    short: DateTime<Utc>,
+    qualified: chrono::DateTime<chrono::Utc>,
+    day: NaiveDate,
+}
+
+#[test]
+fn test_field_schemas_do_not_depend_on_path_spelling() {
+    let schema_json = Spellings::schema().to_json();
+    let props = &schema_json["properties"];
+
+    assert_eq!(props["short"], <DateTime<Utc>>::schema().to_json());
+    assert_eq!(props["qualified"], props["short"]);
+    assert_eq!(props["day"], NaiveDate::schema().to_json());
+    assert_eq!(props["day"]["format"], "date");
+}
+
+/// A user type that happens to share a name with a chrono type
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Date {
+    day: u8,
+    month: u8,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Appointment {
+    on: Date,
+}
+
+#[test]
+fn test_user_type_named_date_keeps_its_schema() {
+    let schema_json = Appointment::schema().to_json();
+    let on = &schema_json["properties"]["on"];
+
+    assert_eq!(on["type"], "object");
+    assert!(on.get("format").is_none());
+    assert!(on["properties"].get("month").is_some());
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Shift {
+    starts: NaiveTime,
+    breaks: Vec<NaiveTime>,
+    clock_ins: HashMap<String, NaiveDateTime>,
+    logged_at: DateTime<Local>,
+}
+
+#[test]
+fn test_chrono_schema_type_impls() {
+    for schema in [
+        <DateTime<Utc>>::schema(),
+        <DateTime<Local>>::schema(),
+        NaiveDateTime::schema(),
+    ] {
+        let schema_json = schema.to_json();
+        assert_eq!(schema_json["type"], "string");
+        assert_eq!(schema_json["format"], "date-time");
+    }
+
+    let time = NaiveTime::schema().to_json();
+    assert_eq!(time["type"], "string");
+    assert_eq!(time["format"], "time");
+}
+
+#[test]
+fn test_chrono_types_in_generic_containers() {
+    let list = <Vec<DateTime<Utc>>>::schema().to_json();
+    assert_eq!(list["type"], "array");
+    assert_eq!(list["items"]["format"], "date-time");
+
+    let map = <HashMap<String, NaiveDateTime>>::schema().to_json();
+    assert_eq!(map["type"], "object");
+    assert_eq!(map["additionalProperties"]["format"], "date-time");
+}
+
+#[test]
+fn test_naive_time_fields() {
+    let schema_json = Shift::schema().to_json();
+    let props = &schema_json["properties"];
+
+    assert_eq!(props["starts"]["type"], "string");
+    assert_eq!(props["starts"]["format"], "time");
+    assert_eq!(props["breaks"]["items"]["format"], "time");
+    assert_eq!(
+        props["clock_ins"]["additionalProperties"]["format"],
+        "date-time"
+    );
+    assert_eq!(props["logged_at"]["format"], "date-time");
+}
@@ -203,4 +203,100 @@ mod derive_macro_tests {
         assert_eq!(double_quotes[1], "item2");
         assert_eq!(double_quotes[2], "item3");
     }
+
+    // Field schemas delegate to the standalone SchemaType impls
+    #[derive(Instructor, Serialize, Deserialize, Debug)]
+    struct DelegatedFields {
+        #[llm(description = "Unique tags")]
+        tags: std::collections::HashSet<String>,
+
+        initial: char,
+
+        pairs: Vec<(String, f64)>,
+
+        parent: Option<Box<DelegatedFields>>,
+    }
+
+    #[test]
+    fn test_field_schemas_delegate_to_schema_type() {
+        let schema_json = DelegatedFields::schema().to_json();
+        let defs = &schema_json["$defs"]["DelegatedFields"];
+        let props = defs["properties"].as_object().unwrap();
+
+        // Sets keep uniqueItems from the HashSet impl, overlaid with the field description
+        assert_eq!(props["tags"]["type"], "array");
+        assert_eq!(props["tags"]["uniqueItems"], true);
+        assert_eq!(props["tags"]["description"], "Unique tags");
+
+        assert_eq!(props["initial"], char::schema().to_json());
+        assert_eq!(props["pairs"], <Vec<(String, f64)>>::schema().to_json());
+
+        // Direct self-references use $ref instead of recursing
+        assert_eq!(props["parent"]["$ref"], "#/$defs/DelegatedFields");
+    }
+
+    // Schema defaults communicated to the model
+    #[derive(Instructor, Serialize, Deserialize, Debug)]
+    struct WithDefaults {
+        #[llm(description = "Result page size", default = 20)]
+        page_size: u32,
+
+        #[llm(default = "en", example = "fr")]
+        language: String,
+
+        #[llm(default = true)]
+        include_archived: Option<bool>,
+
+        #[llm(default = ["a", "b"])]
+        tags: Vec<String>,
+
+        no_default: f64,
+    }
+
+    #[test]
+    fn test_llm_default_attribute() {
+        let schema_json = WithDefaults::schema().to_json();
+        let props = &schema_json["properties"];
+
+        assert_eq!(props["page_size"]["default"], 20);
+        assert_eq!(props["page_size"]["description"], "Result page size");
+        assert_eq!(props["language"]["default"], "en");
+        assert_eq!(props["language"]["example"], "fr");
+        assert_eq!(props["include_archived"]["default"], true);
+        assert_eq!(props["tags"]["default"], serde_json::json!(["a", "b"]));
+        assert!(props["no_default"].get("default").is_none());
+    }
+
+    #[derive(Instructor, Serialize, Deserialize, Debug)]
+    struct PostalAddress {
+        street: String,
+        city: String,
+    }
+
+    // Field titles used as human-readable labels
+    #[derive(Instructor, Serialize, Deserialize, Debug)]
+    struct WithTitles {
+        #[llm(title = "Full name", description = "The customer's full legal name")]
+        name: String,
+
+        #[llm(title = "Delivery address")]
+        address: Option<PostalAddress>,
+
+        untitled: u8,
+    }
+
+    #[test]
+    fn test_llm_field_title_attribute() {
+        let schema_json = WithTitles::schema().to_json();
+        let props = &schema_json["properties"];
+
+        assert_eq!(props["name"]["title"], "Full name");
+        assert_eq!(
+            props["name"]["description"],
+            "The customer's full legal name"
+        );
+        // The field title replaces the nested type's own title
+        assert_eq!(props["address"]["title"], "Delivery address");
+        assert!(props["untitled"].get("title").is_none());
+    }
 }
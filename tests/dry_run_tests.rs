@@ -0,0 +1,112 @@
+//! Tests for dry-run mode, which synthesizes values from the schema instead of
+//! calling the provider.
+
+#![cfg(all(feature = "openai", feature = "anthropic", feature = "gemini"))]
+
+use chrono::{DateTime, Utc};
+use rstructor::{AnthropicClient, GeminiClient, Instructor, LLMClient, OpenAIClient};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+enum Status {
+    Draft,
+    Published,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+enum Channel {
+    Email { address: String },
+    Phone(String),
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Author {
+    name: String,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+#[llm(validate = "reject_everything")]
+struct Article {
+    title: String,
+    word_count: u32,
+    rating: f64,
+    featured: bool,
+    subtitle: Option<String>,
+    tags: Vec<String>,
+    status: Status,
+    contact: Channel,
+    author: Author,
+    published_at: DateTime<Utc>,
+    metadata: HashMap<String, String>,
+}
+
+fn reject_everything(_: &Article) -> rstructor::Result<()> {
+    Err(rstructor::RStructorError::ValidationError(
+        "placeholders should skip validation".to_string(),
+    ))
+}
+
+/// Nothing listens here, so any request that escapes dry-run mode fails
+const UNREACHABLE: &str = "http://127.0.0.1:1";
+
+#[tokio::test]
+async fn test_dry_run_fills_schema_with_placeholders() {
+    let client = OpenAIClient::new("unused")
+        .unwrap()
+        .base_url(UNREACHABLE)
+        .dry_run(true);
+
+    let article: Article = client.materialize("Write an article").await.unwrap();
+    assert_eq!(article.title, "");
+    assert_eq!(article.word_count, 0);
+    assert_eq!(article.rating, 0.0);
+    assert!(!article.featured);
+    assert!(article.subtitle.is_none());
+    assert!(article.tags.is_empty());
+    assert_eq!(article.status, Status::Draft);
+    assert_eq!(
+        article.contact,
+        Channel::Email {
+            address: String::new()
+        }
+    );
+    assert_eq!(article.author.name, "");
+    assert_eq!(article.published_at, DateTime::<Utc>::UNIX_EPOCH);
+    assert!(article.metadata.is_empty());
+}
+
+#[tokio::test]
+async fn test_dry_run_works_for_every_provider() {
+    let anthropic = AnthropicClient::new("unused")
+        .unwrap()
+        .base_url(UNREACHABLE)
+        .dry_run(true);
+    let result = anthropic
+        .materialize_with_metadata::<Author>("Name an author")
+        .await
+        .unwrap();
+    assert_eq!(result.data.name, "");
+    assert!(result.usage.is_none());
+
+    let gemini = GeminiClient::new("unused")
+        .unwrap()
+        .base_url(UNREACHABLE)
+        .dry_run(true);
+    let status: Status = gemini.materialize("Pick a status").await.unwrap();
+    assert_eq!(status, Status::Draft);
+    assert_eq!(gemini.generate("Say hello").await.unwrap(), "");
+}
+
+#[tokio::test]
+async fn test_dry_run_lists_are_empty() {
+    let client = OpenAIClient::new("unused")
+        .unwrap()
+        .base_url(UNREACHABLE)
+        .dry_run(true);
+    let authors: Vec<Author> = client
+        .materialize_many_items("List the authors")
+        .await
+        .unwrap();
+    assert!(authors.is_empty());
+}
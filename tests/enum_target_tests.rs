@@ -0,0 +1,89 @@
+//! Tests for materializing enums as the top-level type, using a local stub of the
+//! OpenAI API.
+
+#![cfg(feature = "openai")]
+
+mod common;
+
+use common::{completion, serve_once};
+use rstructor::{Instructor, LLMClient, OpenAIClient};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+enum Shape {
+    Circle { radius: f64 },
+    Rectangle { width: f64, height: f64 },
+    Empty,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+enum Sentiment {
+    Positive,
+    Negative,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Review {
+    sentiment: Sentiment,
+}
+
+fn client(base_url: String) -> OpenAIClient {
+    OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o")
+        .no_retries()
+}
+
+#[tokio::test]
+async fn test_data_enum_target_is_wrapped_in_object() {
+    let (base_url, requests) = serve_once(completion(r#"{"value": {"Circle": {"radius": 2.5}}}"#));
+
+    let shape: Shape = client(base_url).materialize("A circle").await.unwrap();
+    assert_eq!(shape, Shape::Circle { radius: 2.5 });
+
+    let request = requests.recv().unwrap().body;
+    let schema = &request["response_format"]["json_schema"]["schema"];
+    assert_eq!(schema["type"], "object");
+    assert_eq!(schema["required"], json!(["value"]));
+    assert_eq!(schema["additionalProperties"], json!(false));
+    assert!(schema["properties"]["value"]["oneOf"].is_array());
+}
+
+#[tokio::test]
+async fn test_unit_variant_of_data_enum_target() {
+    let (base_url, requests) = serve_once(completion(r#"{"value": "Empty"}"#));
+
+    let shape: Shape = client(base_url).materialize("Nothing").await.unwrap();
+    assert_eq!(shape, Shape::Empty);
+    requests.recv().unwrap();
+}
+
+#[tokio::test]
+async fn test_simple_enum_target_is_wrapped_in_object() {
+    let (base_url, requests) = serve_once(completion(r#"{"value": "Negative"}"#));
+
+    let sentiment: Sentiment = client(base_url).materialize("Awful").await.unwrap();
+    assert_eq!(sentiment, Sentiment::Negative);
+
+    let request = requests.recv().unwrap().body;
+    let schema = &request["response_format"]["json_schema"]["schema"];
+    assert_eq!(
+        schema["properties"]["value"]["enum"],
+        json!(["Positive", "Negative"])
+    );
+}
+
+#[tokio::test]
+async fn test_struct_target_is_not_wrapped() {
+    let (base_url, requests) = serve_once(completion(r#"{"sentiment": "Positive"}"#));
+
+    let review: Review = client(base_url).materialize("Great").await.unwrap();
+    assert_eq!(review.sentiment, Sentiment::Positive);
+
+    let request = requests.recv().unwrap().body;
+    let schema = &request["response_format"]["json_schema"]["schema"];
+    assert!(schema["properties"].get("value").is_none());
+    assert!(schema["properties"]["sentiment"].is_object());
+}
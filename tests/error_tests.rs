@@ -58,6 +58,7 @@ mod error_tests {
             .is_retryable()
         );
         assert!(ApiErrorKind::ServiceUnavailable.is_retryable());
+        assert!(ApiErrorKind::Overloaded { retry_after: None }.is_retryable());
         assert!(ApiErrorKind::GatewayError { code: 520 }.is_retryable());
         assert!(ApiErrorKind::GatewayError { code: 521 }.is_retryable());
         assert!(ApiErrorKind::GatewayError { code: 522 }.is_retryable());
@@ -70,6 +71,7 @@ mod error_tests {
         assert!(!ApiErrorKind::AuthenticationFailed.is_retryable());
         assert!(!ApiErrorKind::PermissionDenied.is_retryable());
         assert!(!ApiErrorKind::RequestTooLarge.is_retryable());
+        assert!(!ApiErrorKind::ResponseTooLarge { limit: 1024 }.is_retryable());
         assert!(
             !ApiErrorKind::BadRequest {
                 details: "test".into()
@@ -96,6 +98,19 @@ mod error_tests {
             }
             .is_retryable()
         );
+        assert!(
+            !ApiErrorKind::ContentFiltered {
+                details: "test".into()
+            }
+            .is_retryable()
+        );
+        assert!(
+            !ApiErrorKind::ContextLengthExceeded {
+                limit: Some(8192),
+                requested: Some(9000)
+            }
+            .is_retryable()
+        );
     }
 
     #[test]
@@ -114,6 +129,17 @@ mod error_tests {
             Some(Duration::from_secs(42))
         );
         assert!(ApiErrorKind::ServiceUnavailable.retry_delay().is_some());
+        assert_eq!(
+            ApiErrorKind::Overloaded { retry_after: None }.retry_delay(),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            ApiErrorKind::Overloaded {
+                retry_after: Some(Duration::from_secs(7))
+            }
+            .retry_delay(),
+            Some(Duration::from_secs(7))
+        );
         assert!(
             ApiErrorKind::GatewayError { code: 520 }
                 .retry_delay()
@@ -181,10 +207,22 @@ mod error_tests {
                 "new",
             ),
             (ApiErrorKind::ServiceUnavailable, "temporarily unavailable"),
+            (ApiErrorKind::Overloaded { retry_after: None }, "overloaded"),
             (ApiErrorKind::GatewayError { code: 520 }, "520"),
             (ApiErrorKind::AuthenticationFailed, "API_KEY"),
             (ApiErrorKind::PermissionDenied, "Permission denied"),
             (ApiErrorKind::RequestTooLarge, "too large"),
+            (
+                ApiErrorKind::ResponseTooLarge { limit: 1024 },
+                "max_response_bytes",
+            ),
+            (
+                ApiErrorKind::ContextLengthExceeded {
+                    limit: Some(8192),
+                    requested: Some(9000),
+                },
+                "9000 > 8192",
+            ),
             (
                 ApiErrorKind::BadRequest {
                     details: "invalid param".into(),
@@ -205,6 +243,12 @@ mod error_tests {
                 },
                 "empty array",
             ),
+            (
+                ApiErrorKind::ContentFiltered {
+                    details: "finish reason SAFETY".into(),
+                },
+                "content filtering",
+            ),
         ];
 
         for (kind, expected_substr) in test_cases {
@@ -236,10 +280,23 @@ mod error_tests {
                 suggestion: Some("alt".into()),
             },
             ApiErrorKind::ServiceUnavailable,
+            ApiErrorKind::Overloaded { retry_after: None },
+            ApiErrorKind::Overloaded {
+                retry_after: Some(Duration::from_secs(10)),
+            },
             ApiErrorKind::GatewayError { code: 520 },
             ApiErrorKind::AuthenticationFailed,
             ApiErrorKind::PermissionDenied,
             ApiErrorKind::RequestTooLarge,
+            ApiErrorKind::ResponseTooLarge { limit: 1024 },
+            ApiErrorKind::ContextLengthExceeded {
+                limit: None,
+                requested: None,
+            },
+            ApiErrorKind::ContextLengthExceeded {
+                limit: Some(8192),
+                requested: Some(9000),
+            },
             ApiErrorKind::BadRequest {
                 details: "test".into(),
             },
@@ -251,6 +308,9 @@ mod error_tests {
             ApiErrorKind::UnexpectedResponse {
                 details: "test".into(),
             },
+            ApiErrorKind::ContentFiltered {
+                details: "test".into(),
+            },
         ];
 
         for variant in variants {
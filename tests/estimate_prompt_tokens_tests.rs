@@ -0,0 +1,100 @@
+//! Tests for pre-flight token estimates, computed from request previews.
+
+#![cfg(all(feature = "openai", feature = "mistral"))]
+
+use rstructor::{Instructor, LLMClient, MistralClient, OpenAIClient};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Title {
+    title: String,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Film {
+    #[llm(description = "Original release title")]
+    title: String,
+    #[llm(description = "Year of the theatrical release")]
+    year: u16,
+    #[llm(description = "Directors credited on the film")]
+    directors: Vec<String>,
+    #[llm(description = "Main cast members, in billing order")]
+    cast: Vec<String>,
+    #[llm(description = "One-paragraph plot summary without spoilers")]
+    summary: String,
+}
+
+fn openai() -> OpenAIClient {
+    OpenAIClient::new("test-key").unwrap().model("gpt-4o")
+}
+
+#[test]
+fn test_estimate_grows_with_prompt() {
+    let short = openai().estimate_prompt_tokens::<Title>("Name a movie");
+    let long = openai().estimate_prompt_tokens::<Title>(&"Name a movie. ".repeat(100));
+    assert!(long > short + 100);
+}
+
+#[test]
+fn test_estimate_includes_schema() {
+    // OpenAI carries the schema in `response_format`, Mistral in a system message
+    let prompt = "Describe Inception";
+    assert!(
+        openai().estimate_prompt_tokens::<Film>(prompt)
+            > openai().estimate_prompt_tokens::<Title>(prompt) + 20
+    );
+    let mistral = MistralClient::new("test-key").unwrap();
+    assert!(
+        mistral.estimate_prompt_tokens::<Film>(prompt)
+            > mistral.estimate_prompt_tokens::<Title>(prompt) + 20
+    );
+}
+
+#[test]
+fn test_estimate_includes_system_prompts() {
+    let instruction = "You are a meticulous film archivist. ".repeat(20);
+    let plain = openai().estimate_prompt_tokens::<Title>("Name a movie");
+    let with_system = openai()
+        .system_prompt(instruction)
+        .estimate_prompt_tokens::<Title>("Name a movie");
+    assert!(with_system > plain + 100);
+}
+
+#[cfg(not(feature = "tokenizer"))]
+#[test]
+fn test_estimate_without_tokenizer_is_four_bytes_per_token() {
+    let client = openai();
+    let body = client
+        .materialize_request_body::<Film>("Describe Inception")
+        .unwrap();
+    assert_eq!(
+        client.estimate_prompt_tokens::<Film>("Describe Inception"),
+        body.to_string().len().div_ceil(4)
+    );
+}
+
+#[cfg(feature = "tokenizer")]
+#[test]
+fn test_tokenizer_counts_openai_requests_exactly() {
+    let client = openai();
+    let body = client
+        .materialize_request_body::<Film>("Describe Inception")
+        .unwrap()
+        .to_string();
+    let bpe = tiktoken_rs::bpe_for_model("gpt-4o").unwrap();
+    assert_eq!(
+        client.estimate_prompt_tokens::<Film>("Describe Inception"),
+        bpe.encode_with_special_tokens(&body).len()
+    );
+
+    // Models without a known encoding keep the byte-based estimate
+    let mistral = MistralClient::new("test-key").unwrap();
+    let body = mistral
+        .materialize_request_body::<Film>("Describe Inception")
+        .unwrap()
+        .to_string();
+    assert_eq!(
+        mistral.estimate_prompt_tokens::<Film>("Describe Inception"),
+        body.len().div_ceil(4)
+    );
+}
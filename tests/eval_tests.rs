@@ -0,0 +1,140 @@
+//! Tests for the `eval` module: `diff_json` and `evaluate` against dry-run clients.
+
+use rstructor::Instructor;
+use rstructor::eval::{EvalCase, FieldMismatch, diff_json};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Instructor, Serialize, Deserialize, Debug, Clone)]
+struct Movie {
+    title: String,
+    year: u16,
+    cast: Vec<String>,
+}
+
+fn movie(title: &str, year: u16) -> Movie {
+    Movie {
+        title: title.to_string(),
+        year,
+        cast: Vec::new(),
+    }
+}
+
+#[test]
+fn test_diff_json_reports_deepest_paths() {
+    let expected = json!({
+        "title": "Inception",
+        "year": 2010,
+        "cast": ["Leonardo DiCaprio", "Elliot Page"],
+        "studio": {"name": "Warner Bros."}
+    });
+    let actual = json!({
+        "title": "Inception",
+        "year": 2011,
+        "cast": ["Leonardo DiCaprio"],
+        "studio": {"name": "Legendary"},
+        "rating": 8.8
+    });
+
+    let diffs = diff_json(&expected, &actual);
+    let paths: Vec<&str> = diffs.iter().map(|d| d.path.as_str()).collect();
+    assert_eq!(paths, ["$.year", "$.cast[1]", "$.studio.name", "$.rating"]);
+    assert_eq!(
+        diffs[1],
+        FieldMismatch {
+            path: "$.cast[1]".to_string(),
+            expected: json!("Elliot Page"),
+            actual: json!(null),
+        }
+    );
+}
+
+#[test]
+fn test_diff_json_identical_and_root_values() {
+    let value = json!({"a": [1, {"b": null}]});
+    assert!(diff_json(&value, &value).is_empty());
+
+    // A missing key and an explicit null are treated alike
+    assert!(diff_json(&json!({"a": null}), &json!({})).is_empty());
+
+    let diffs = diff_json(&json!("yes"), &json!(["yes"]));
+    assert_eq!(diffs.len(), 1);
+    assert_eq!(diffs[0].path, "$");
+}
+
+#[cfg(feature = "openai")]
+mod evaluate {
+    use super::*;
+    use rstructor::OpenAIClient;
+    use rstructor::eval::evaluate;
+
+    #[tokio::test]
+    async fn test_report_counts_passes_and_field_mismatches() {
+        // Dry-run output is the schema placeholder: empty strings, zeros, empty arrays
+        let client = OpenAIClient::new("unused").unwrap().dry_run(true);
+        let cases = vec![
+            EvalCase::new("blank", movie("", 0)),
+            EvalCase::new("Inception", movie("Inception", 2010)),
+            EvalCase::new("Untitled 2010 film", movie("", 2010)),
+        ];
+
+        let report = evaluate(&client, &cases).await;
+        assert_eq!(report.cases.len(), 3);
+        assert_eq!(report.passed(), 1);
+        assert_eq!(report.failed(), 2);
+        assert_eq!(report.errors(), 0);
+        assert!((report.accuracy() - 1.0 / 3.0).abs() < 1e-9);
+
+        assert!(report.cases[0].passed());
+        assert_eq!(report.cases[1].prompt, "Inception");
+        assert_eq!(report.cases[1].mismatches.len(), 2);
+        assert_eq!(report.field_mismatches["$.year"], 2);
+        assert_eq!(report.field_mismatches["$.title"], 1);
+        assert!(!report.field_mismatches.contains_key("$.cast"));
+    }
+
+    #[tokio::test]
+    async fn test_field_mismatches_group_array_elements() {
+        let client = OpenAIClient::new("unused").unwrap().dry_run(true);
+        let with_cast = |cast: &[&str]| Movie {
+            cast: cast.iter().map(|name| name.to_string()).collect(),
+            ..movie("", 0)
+        };
+        let cases = vec![
+            EvalCase::new(
+                "two leads",
+                with_cast(&["Leonardo DiCaprio", "Elliot Page"]),
+            ),
+            EvalCase::new("one lead", with_cast(&["Keanu Reeves"])),
+        ];
+
+        let report = evaluate(&client, &cases).await;
+        assert_eq!(report.cases[0].mismatches.len(), 2);
+        assert_eq!(report.cases[0].mismatches[1].path, "$.cast[1]");
+        assert_eq!(report.field_mismatches.len(), 1);
+        assert_eq!(report.field_mismatches["$.cast[*]"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_calls_are_recorded_as_errors() {
+        let client = OpenAIClient::new("test-key")
+            .unwrap()
+            .base_url("http://127.0.0.1:1")
+            .no_retries();
+        let cases = vec![EvalCase::new("Inception", movie("Inception", 2010))];
+
+        let report = evaluate(&client, &cases).await;
+        assert_eq!(report.errors(), 1);
+        assert_eq!(report.accuracy(), 0.0);
+        assert!(report.cases[0].error.is_some());
+        assert!(report.field_mismatches.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_empty_run() {
+        let client = OpenAIClient::new("unused").unwrap().dry_run(true);
+        let report = evaluate::<_, Movie>(&client, &[]).await;
+        assert_eq!(report.passed(), 0);
+        assert_eq!(report.accuracy(), 0.0);
+    }
+}
@@ -0,0 +1,63 @@
+//! Tests for typed few-shot examples, using a local stub of the OpenAI API.
+
+#![cfg(feature = "openai")]
+
+mod common;
+
+use common::{completion, serve_once};
+use rstructor::{Instructor, LLMClient, OpenAIClient};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Sentiment {
+    label: String,
+    score: f32,
+}
+
+#[tokio::test]
+async fn test_materialize_with_examples_sends_example_turns() {
+    let (base_url, requests) = serve_once(completion(r#"{"label": "negative", "score": 0.1}"#));
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o")
+        .no_retries();
+
+    let examples = vec![
+        (
+            "I loved it".to_string(),
+            Sentiment {
+                label: "positive".to_string(),
+                score: 0.5,
+            },
+        ),
+        (
+            "It was fine".to_string(),
+            Sentiment {
+                label: "neutral".to_string(),
+                score: 0.25,
+            },
+        ),
+    ];
+    let sentiment: Sentiment = client
+        .materialize_with_examples("The ending ruined it", &examples)
+        .await
+        .unwrap();
+    assert_eq!(sentiment.label, "negative");
+
+    let request = requests.recv().unwrap().body;
+    let messages = request["messages"].as_array().unwrap();
+    let roles: Vec<&str> = messages
+        .iter()
+        .map(|m| m["role"].as_str().unwrap())
+        .collect();
+    assert_eq!(roles, ["user", "assistant", "user", "assistant", "user"]);
+    assert_eq!(messages[0]["content"], "I loved it");
+    assert_eq!(
+        serde_json::from_str::<Value>(messages[1]["content"].as_str().unwrap()).unwrap(),
+        json!({"label": "positive", "score": 0.5})
+    );
+    assert_eq!(messages[2]["content"], "It was fine");
+    assert_eq!(messages[4]["content"], "The ending ruined it");
+}
@@ -0,0 +1,50 @@
+//! Schemas keep fields in declaration order, which providers follow when generating.
+
+use rstructor::{Instructor, SchemaTarget, SchemaType};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Verdict {
+    #[llm(description = "Step-by-step reasoning, written before the conclusion")]
+    reasoning: String,
+    conclusion: String,
+    confidence: f32,
+    notes: Option<String>,
+    appeal: bool,
+}
+
+fn property_names(schema: &serde_json::Value) -> Vec<&str> {
+    schema["properties"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect()
+}
+
+#[test]
+fn test_schema_properties_follow_declaration_order() {
+    let schema = Verdict::schema().to_json();
+    assert_eq!(
+        property_names(&schema),
+        ["reasoning", "conclusion", "confidence", "notes", "appeal"]
+    );
+    assert_eq!(
+        schema["required"],
+        json!(["reasoning", "conclusion", "confidence", "appeal"])
+    );
+}
+
+#[test]
+fn test_strict_schema_required_follows_declaration_order() {
+    let schema = Verdict::schema().prepare_for(SchemaTarget::OpenAIStrict);
+    assert_eq!(
+        property_names(&schema),
+        ["reasoning", "conclusion", "confidence", "notes", "appeal"]
+    );
+    assert_eq!(
+        schema["required"],
+        json!(["reasoning", "conclusion", "confidence", "notes", "appeal"])
+    );
+}
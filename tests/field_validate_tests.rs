@@ -0,0 +1,101 @@
+use rstructor::{Instructor, InstructorExt, RStructorError};
+use serde::{Deserialize, Serialize};
+
+fn check_email(email: &str) -> rstructor::Result<()> {
+    if !email.contains('@') {
+        return Err(RStructorError::ValidationError(format!(
+            "'{}' is not an email address",
+            email
+        )));
+    }
+    Ok(())
+}
+
+fn check_age(age: &u32) -> rstructor::Result<()> {
+    if *age > 150 {
+        return Err(RStructorError::ValidationError(format!(
+            "{} is not a realistic age",
+            age
+        )));
+    }
+    Ok(())
+}
+
+fn check_nickname(nickname: &Option<String>) -> rstructor::Result<()> {
+    match nickname {
+        Some(name) if name.is_empty() => Err(RStructorError::ValidationError(
+            "must not be empty when present".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn validate_user(user: &User) -> rstructor::Result<()> {
+    if user.email_address.ends_with("@example.com") {
+        return Err(RStructorError::ValidationError(
+            "example.com addresses are not allowed".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[llm(validate = "validate_user")]
+struct User {
+    #[llm(validate = "check_email")]
+    email_address: String,
+    #[llm(validate = "check_age")]
+    age: u32,
+    #[llm(validate = "check_nickname")]
+    nickname: Option<String>,
+}
+
+fn user(email: &str, age: u32, nickname: Option<&str>) -> User {
+    User {
+        email_address: email.to_string(),
+        age,
+        nickname: nickname.map(str::to_string),
+    }
+}
+
+fn validation_message(result: rstructor::Result<()>) -> String {
+    match result {
+        Err(RStructorError::ValidationError(msg)) => msg,
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_field_validators_pass() {
+    assert!(user("ada@lovelace.org", 36, Some("Ada")).validate().is_ok());
+    assert!(user("ada@lovelace.org", 36, None).validate().is_ok());
+}
+
+#[test]
+fn test_field_validator_error_names_field() {
+    let msg = validation_message(user("ada", 36, None).validate());
+    // The schema name (after rename_all) is used, since that's what the LLM sees
+    assert_eq!(msg, "emailAddress: 'ada' is not an email address");
+
+    let msg = validation_message(user("ada@lovelace.org", 200, None).validate());
+    assert_eq!(msg, "age: 200 is not a realistic age");
+
+    let msg = validation_message(user("ada@lovelace.org", 36, Some("")).validate());
+    assert_eq!(msg, "nickname: must not be empty when present");
+}
+
+#[test]
+fn test_field_validators_run_before_container_validator() {
+    let msg = validation_message(user("ada@example.com", 200, None).validate());
+    assert_eq!(msg, "age: 200 is not a realistic age");
+
+    let msg = validation_message(user("ada@example.com", 36, None).validate());
+    assert_eq!(msg, "example.com addresses are not allowed");
+}
+
+#[test]
+fn test_field_validator_runs_on_parse() {
+    let err = User::from_json(r#"{"emailAddress": "ada", "age": 36}"#).unwrap_err();
+    assert!(err.to_string().contains("emailAddress: 'ada'"));
+}
@@ -0,0 +1,77 @@
+//! Tests for raw multi-turn generation, using local stubs of the provider APIs.
+
+#![cfg(all(feature = "openai", feature = "anthropic"))]
+
+mod common;
+
+use common::serve_once;
+use rstructor::{AnthropicClient, ChatMessage, LLMClient, OpenAIClient};
+use serde_json::json;
+
+fn conversation() -> Vec<ChatMessage> {
+    vec![
+        ChatMessage::system("You are a concise travel guide."),
+        ChatMessage::user("Name one museum in Paris."),
+        ChatMessage::assistant("The Louvre."),
+        ChatMessage::user("When was it founded?"),
+    ]
+}
+
+#[tokio::test]
+async fn test_openai_generate_with_messages_sends_history() {
+    let (base_url, requests) = serve_once(json!({
+        "model": "gpt-4o",
+        "choices": [{
+            "message": { "role": "assistant", "content": "1793." },
+            "finish_reason": "stop"
+        }],
+        "usage": { "prompt_tokens": 30, "completion_tokens": 3 }
+    }));
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o");
+
+    let result = client
+        .generate_with_messages(&conversation())
+        .await
+        .unwrap();
+    assert_eq!(result.text, "1793.");
+    assert_eq!(result.usage.unwrap().input_tokens, 30);
+
+    let request = requests.recv().unwrap().body;
+    let roles: Vec<&str> = request["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["role"].as_str().unwrap())
+        .collect();
+    assert_eq!(roles, ["system", "user", "assistant", "user"]);
+    assert!(request.get("response_format").is_none());
+}
+
+#[tokio::test]
+async fn test_anthropic_generate_with_messages_uses_system_field() {
+    let (base_url, requests) = serve_once(json!({
+        "model": "claude-sonnet-4-5-20250929",
+        "content": [{ "type": "text", "text": "1793." }],
+        "usage": { "input_tokens": 30, "output_tokens": 3 }
+    }));
+    let client = AnthropicClient::new("test-key").unwrap().base_url(base_url);
+
+    let result = client
+        .generate_with_messages(&conversation())
+        .await
+        .unwrap();
+    assert_eq!(result.text, "1793.");
+
+    let request = requests.recv().unwrap().body;
+    assert_eq!(request["system"], "You are a concise travel guide.");
+    let roles: Vec<&str> = request["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["role"].as_str().unwrap())
+        .collect();
+    assert_eq!(roles, ["user", "assistant", "user"]);
+}
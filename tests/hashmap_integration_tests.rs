@@ -1,7 +1,7 @@
 // tests/hashmap_integration_tests.rs
 #[cfg(test)]
 mod hashmap_tests {
-    use rstructor::{GeminiClient, Instructor, LLMClient};
+    use rstructor::{GeminiClient, Instructor, LLMClient, SchemaTarget, SchemaType};
     use serde::{Deserialize, Serialize};
     use std::collections::HashMap;
     use std::env;
@@ -27,6 +27,123 @@ mod hashmap_tests {
         user_data: HashMap<String, Metadata>,
     }
 
+    #[derive(Instructor, Serialize, Deserialize, Debug)]
+    struct ComposedCollections {
+        #[llm(description = "One score map per round")]
+        rounds: Vec<HashMap<String, i32>>,
+
+        #[llm(description = "Map of player names to their scores")]
+        history: HashMap<String, Vec<i32>>,
+
+        #[llm(description = "Grid of cells")]
+        grid: Vec<Vec<u8>>,
+    }
+
+    #[test]
+    fn test_composed_collection_schemas() {
+        let schema = ComposedCollections::schema().to_json();
+        let props = &schema["properties"];
+
+        assert_eq!(props["rounds"]["type"], "array");
+        assert_eq!(props["rounds"]["items"]["type"], "object");
+        assert_eq!(
+            props["rounds"]["items"]["additionalProperties"]["type"],
+            "integer"
+        );
+
+        assert_eq!(props["history"]["type"], "object");
+        assert_eq!(props["history"]["additionalProperties"]["type"], "array");
+        assert_eq!(
+            props["history"]["additionalProperties"]["items"]["type"],
+            "integer"
+        );
+
+        assert_eq!(props["grid"]["items"]["type"], "array");
+        assert_eq!(props["grid"]["items"]["items"]["type"], "integer");
+
+        // The derive output should match the standalone SchemaType impls
+        assert_eq!(
+            props["rounds"]["items"],
+            <HashMap<String, i32> as SchemaType>::schema().to_json()
+        );
+        assert_eq!(
+            props["history"]["additionalProperties"],
+            <Vec<i32> as SchemaType>::schema().to_json()
+        );
+    }
+
+    #[derive(Instructor, Serialize, Deserialize, Debug)]
+    struct BoundedMaps {
+        #[llm(
+            description = "Scores by player",
+            min_properties = 1,
+            max_properties = 10
+        )]
+        scores: HashMap<String, u32>,
+
+        #[llm(min_properties = 2)]
+        aliases: Option<HashMap<String, String>>,
+
+        unbounded: HashMap<String, bool>,
+    }
+
+    #[test]
+    fn test_strict_schema_keeps_map_value_schemas() {
+        let schema = ComplexMap::schema().prepare_for(SchemaTarget::OpenAIStrict);
+        assert_eq!(schema["additionalProperties"], false);
+
+        // The map keeps its value schema rather than being closed off with `false`
+        let user_data = &schema["properties"]["user_data"];
+        assert_eq!(user_data["type"], "object");
+        let metadata = &user_data["additionalProperties"];
+        assert_eq!(metadata["type"], "object");
+        assert_eq!(metadata["additionalProperties"], false);
+        assert_eq!(metadata["required"], serde_json::json!(["score", "tags"]));
+
+        let schema = Inventory::schema().prepare_for(SchemaTarget::OpenAIStrict);
+        let stock_counts = &schema["properties"]["stock_counts"];
+        assert_eq!(stock_counts["additionalProperties"]["type"], "integer");
+    }
+
+    #[test]
+    fn test_map_cardinality_attributes() {
+        let schema = BoundedMaps::schema().to_json();
+        let props = &schema["properties"];
+
+        assert_eq!(props["scores"]["minProperties"], 1);
+        assert_eq!(props["scores"]["maxProperties"], 10);
+        assert_eq!(props["scores"]["description"], "Scores by player");
+        assert_eq!(props["aliases"]["minProperties"], 2);
+        assert!(props["aliases"].get("maxProperties").is_none());
+        assert!(props["unbounded"].get("minProperties").is_none());
+    }
+
+    #[test]
+    fn test_map_fields_have_no_placeholder_properties() {
+        let inventory = Inventory::schema().to_json();
+        let complex = ComplexMap::schema().to_json();
+
+        for map_schema in [
+            &inventory["properties"]["categories"],
+            &inventory["properties"]["stock_counts"],
+            &complex["properties"]["user_data"],
+        ] {
+            let mut keys: Vec<&str> = map_schema
+                .as_object()
+                .unwrap()
+                .keys()
+                .map(String::as_str)
+                .collect();
+            keys.sort_unstable();
+            assert_eq!(
+                keys,
+                ["additionalProperties", "description", "type"],
+                "map schema should be additionalProperties-only: {}",
+                map_schema
+            );
+        }
+    }
+
     #[tokio::test]
     async fn test_gemini_hashmap_nested_vec() {
         let api_key = env::var("GEMINI_API_KEY").expect("GEMINI_API_KEY must be set");
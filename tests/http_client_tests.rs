@@ -0,0 +1,203 @@
+//! Tests for `with_http_client` and custom headers, using local stubs of the provider APIs.
+
+#![cfg(all(feature = "openai", feature = "anthropic", feature = "ollama"))]
+
+mod common;
+
+use common::{completion, serve};
+use reqwest::header::{HeaderMap, HeaderValue};
+use rstructor::{AnthropicClient, Instructor, LLMClient, OllamaClient, OpenAIClient};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::Duration;
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+struct Movie {
+    title: String,
+    year: u16,
+}
+
+/// An HTTP client that tags every request with `x-team: search`
+fn tagged_http_client() -> reqwest::Client {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-team", HeaderValue::from_static("search"));
+    reqwest::Client::builder()
+        .default_headers(headers)
+        .user_agent("movie-indexer/1.0")
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_openai_sends_requests_through_injected_client() {
+    let (base_url, rx) = serve(vec![completion(r#"{"title": "Heat", "year": 1995}"#)]);
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o")
+        .with_http_client(tagged_http_client());
+
+    let movie: Movie = client.materialize("Describe Heat").await.unwrap();
+    assert_eq!(movie.year, 1995);
+
+    let captured = rx.recv().unwrap();
+    assert_eq!(captured.header("x-team"), Some("search"));
+    assert_eq!(captured.header("user-agent"), Some("movie-indexer/1.0"));
+    // Per-request auth is still added on top of the client's defaults
+    assert_eq!(captured.header("authorization"), Some("Bearer test-key"));
+}
+
+#[tokio::test]
+async fn test_anthropic_sends_requests_through_injected_client() {
+    let (base_url, rx) = serve(vec![json!({
+        "model": "claude-sonnet-4-5-20250929",
+        "content": [{ "type": "text", "text": "Hello" }],
+        "usage": { "input_tokens": 5, "output_tokens": 1 }
+    })]);
+    let client = AnthropicClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .with_http_client(tagged_http_client());
+
+    assert_eq!(client.generate("Say hello").await.unwrap(), "Hello");
+
+    let captured = rx.recv().unwrap();
+    assert_eq!(captured.header("x-team"), Some("search"));
+    assert_eq!(captured.header("x-api-key"), Some("test-key"));
+}
+
+#[tokio::test]
+async fn test_ollama_sends_requests_through_injected_client() {
+    let (base_url, rx) = serve(vec![json!({
+        "model": "llama3.2",
+        "message": { "role": "assistant", "content": "Hello" },
+        "done": true
+    })]);
+    let client = OllamaClient::new()
+        .base_url(base_url)
+        .with_http_client(tagged_http_client());
+
+    assert_eq!(client.generate("Say hello").await.unwrap(), "Hello");
+    assert_eq!(rx.recv().unwrap().header("x-team"), Some("search"));
+}
+
+#[tokio::test]
+async fn test_timeout_afterwards_replaces_injected_client() {
+    let (base_url, rx) = serve(vec![completion("Hello")]);
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o")
+        .with_http_client(tagged_http_client())
+        .timeout(Duration::from_secs(30));
+
+    client.generate("Say hello").await.unwrap();
+    assert_eq!(rx.recv().unwrap().header("x-team"), None);
+}
+
+#[tokio::test]
+async fn test_custom_headers_are_sent_with_every_request() {
+    let (base_url, rx) = serve(vec![
+        completion(r#"{"title": "Heat", "year": "1995"}"#),
+        completion(r#"{"title": "Heat", "year": 1995}"#),
+    ]);
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o")
+        .header("X-Org-Id", "research")
+        .header("X-Route", "eu-west")
+        .header("X-Route", "us-east");
+
+    let _: Movie = client.materialize("Describe Heat").await.unwrap();
+
+    // The validation retry carries them too
+    for _ in 0..2 {
+        let captured = rx.recv().unwrap();
+        assert_eq!(captured.header("x-org-id"), Some("research"));
+        assert_eq!(captured.header("x-route"), Some("us-east"));
+        assert_eq!(
+            captured
+                .headers
+                .iter()
+                .filter(|(n, _)| n == "x-route")
+                .count(),
+            1
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_custom_headers_do_not_override_client_headers() {
+    let (base_url, rx) = serve(vec![completion("Hello")]);
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o")
+        .header("Authorization", "Bearer someone-else")
+        .header("Content-Type", "text/plain")
+        .header("X-Org-Id", "research");
+
+    client.generate("Say hello").await.unwrap();
+
+    let captured = rx.recv().unwrap();
+    assert_eq!(captured.header("x-org-id"), Some("research"));
+    let values = |name: &str| {
+        captured
+            .headers
+            .iter()
+            .filter(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(values("authorization"), vec!["Bearer test-key"]);
+    assert_eq!(values("content-type"), vec!["application/json"]);
+}
+
+#[tokio::test]
+async fn test_anthropic_sends_header_map() {
+    let (base_url, rx) = serve(vec![json!({
+        "model": "claude-sonnet-4-5-20250929",
+        "content": [{ "type": "text", "text": "Hello" }],
+        "usage": { "input_tokens": 5, "output_tokens": 1 }
+    })]);
+    let mut extra = HeaderMap::new();
+    extra.insert("x-org-id", HeaderValue::from_static("research"));
+    extra.insert("x-api-key", HeaderValue::from_static("someone-else"));
+    let client = AnthropicClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .headers(extra);
+
+    client.generate("Say hello").await.unwrap();
+
+    let captured = rx.recv().unwrap();
+    assert_eq!(captured.header("x-org-id"), Some("research"));
+    assert_eq!(captured.header("x-api-key"), Some("test-key"));
+    assert_eq!(
+        captured
+            .headers
+            .iter()
+            .filter(|(n, _)| n == "x-api-key")
+            .count(),
+        1
+    );
+}
+
+#[tokio::test]
+async fn test_invalid_header_is_ignored() {
+    let (base_url, rx) = serve(vec![completion("Hello")]);
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o")
+        .header("X Org", "research")
+        .header("X-Note", "line\nbreak")
+        .header("X-Org-Id", "research");
+
+    client.generate("Say hello").await.unwrap();
+
+    let captured = rx.recv().unwrap();
+    assert_eq!(captured.header("x-org-id"), Some("research"));
+    assert_eq!(captured.header("x-note"), None);
+}
@@ -0,0 +1,78 @@
+//! Tests that `IndexMap` fields get the same map schema as `HashMap`.
+
+use indexmap::IndexMap;
+use rstructor::{Instructor, SchemaType};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Scores {
+    value: u32,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Leaderboard {
+    ordered: IndexMap<String, u32>,
+    plain: HashMap<String, u32>,
+    #[llm(description = "Per-player details, in rank order")]
+    details: indexmap::IndexMap<String, Scores>,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+enum Update {
+    Ranks { ranks: IndexMap<String, u32> },
+    Cleared,
+}
+
+#[test]
+fn test_index_map_field_matches_hash_map() {
+    let schema_json = Leaderboard::schema().to_json();
+    let props = &schema_json["properties"];
+    assert_eq!(props["ordered"], props["plain"]);
+    assert_eq!(props["ordered"]["additionalProperties"]["type"], "integer");
+
+    assert_eq!(props["details"]["type"], "object");
+    assert_eq!(
+        props["details"]["additionalProperties"]["properties"]["value"]["type"],
+        "integer"
+    );
+}
+
+#[test]
+fn test_index_map_in_enum_variant() {
+    let schema_json = Update::schema().to_json();
+    let ranks = schema_json["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find_map(|variant| variant["properties"].get("Ranks"))
+        .expect("Ranks variant");
+    assert_eq!(
+        ranks["properties"]["ranks"]["additionalProperties"]["type"],
+        "integer"
+    );
+}
+
+#[test]
+fn test_index_map_keeps_response_order() {
+    let board: Leaderboard =
+        serde_json::from_str(r#"{"ordered": {"zoe": 3, "adam": 1}, "plain": {}, "details": {}}"#)
+            .unwrap();
+    assert_eq!(
+        board.ordered.keys().collect::<Vec<_>>(),
+        vec!["zoe", "adam"]
+    );
+}
+
+#[cfg(feature = "indexmap")]
+#[test]
+fn test_index_map_schema_type_matches_hash_map() {
+    assert_eq!(
+        <IndexMap<String, Scores>>::schema().to_json(),
+        <HashMap<String, Scores>>::schema().to_json()
+    );
+    assert_eq!(
+        <IndexMap<String, Scores>>::schema_name().as_deref(),
+        Some("IndexMap<String, Scores>")
+    );
+}
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod llm_model_tests {
-    use rstructor::{Instructor, RStructorError, Schema, SchemaType};
+    use rstructor::{Instructor, InstructorExt, RStructorError, Schema, SchemaType};
     use serde::{Deserialize, Serialize};
     use serde_json::json;
 
@@ -169,4 +169,31 @@ mod llm_model_tests {
         // Test that schema_name returns the expected value
         assert_eq!(TestModel::schema_name(), Some("TestModel".to_string()));
     }
+
+    #[test]
+    fn test_from_json_parses_and_validates() {
+        let model = TestModel::from_json(r#"{"name": "Alice", "age": 30, "active": true}"#)
+            .expect("valid JSON should parse");
+        assert_eq!(
+            model,
+            TestModel {
+                name: "Alice".to_string(),
+                age: 30,
+                active: true,
+            }
+        );
+
+        // Validation runs after parsing
+        let result = TestModel::from_json(r#"{"name": "Bob", "age": 17, "active": true}"#);
+        assert_eq!(
+            result,
+            Err(RStructorError::ValidationError(
+                "Age must be at least 18".to_string()
+            ))
+        );
+
+        // Malformed JSON surfaces as a JSON error
+        let result = TestModel::from_json("{not json");
+        assert!(matches!(result, Err(RStructorError::JsonError(_))));
+    }
 }
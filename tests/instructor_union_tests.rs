@@ -0,0 +1,109 @@
+//! Tests for `instructor_union!`, which lets the model pick one of several types.
+
+use rstructor::{Instructor, RStructorError, SchemaType, instructor_union};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+#[llm(description = "Refund part or all of an order")]
+#[llm(validate = "validate_refund")]
+struct Refund {
+    order_id: String,
+    amount: f64,
+}
+
+fn validate_refund(refund: &Refund) -> rstructor::Result<()> {
+    if refund.amount <= 0.0 {
+        return Err(RStructorError::ValidationError(
+            "amount must be positive".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+#[llm(normalize = "normalize_escalation")]
+struct Escalation {
+    reason: String,
+}
+
+fn normalize_escalation(escalation: &mut Escalation) -> rstructor::Result<()> {
+    escalation.reason = escalation.reason.trim().to_string();
+    Ok(())
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+enum Priority {
+    Low,
+    High,
+}
+
+instructor_union!(
+    /// What to do with a support ticket
+    #[derive(Debug, PartialEq)]
+    #[llm(description = "The action to take for a support ticket")]
+    pub TicketAction { Refund, Escalation, Priority }
+);
+
+#[test]
+fn test_union_schema_is_one_of_member_schemas() {
+    let schema_json = TicketAction::schema().to_json();
+    assert_eq!(
+        schema_json["description"],
+        "The action to take for a support ticket"
+    );
+
+    let one_of = schema_json["oneOf"].as_array().unwrap();
+    assert_eq!(one_of.len(), 3);
+    assert_eq!(
+        one_of[0]["properties"]["Refund"]["properties"]["amount"]["type"],
+        "number"
+    );
+    assert_eq!(one_of[0]["required"], serde_json::json!(["Refund"]));
+    assert_eq!(
+        one_of[1]["properties"]["Escalation"]["properties"]["reason"]["type"],
+        "string"
+    );
+    assert_eq!(
+        one_of[2]["properties"]["Priority"]["enum"],
+        serde_json::json!(["Low", "High"])
+    );
+}
+
+#[test]
+fn test_union_deserializes_into_chosen_member() {
+    let action: TicketAction =
+        serde_json::from_str(r#"{"Refund": {"order_id": "A-17", "amount": 12.5}}"#).unwrap();
+    assert_eq!(
+        action,
+        TicketAction::Refund(Refund {
+            order_id: "A-17".to_string(),
+            amount: 12.5
+        })
+    );
+
+    let action: TicketAction = serde_json::from_str(r#"{"Priority": "High"}"#).unwrap();
+    assert_eq!(action, TicketAction::Priority(Priority::High));
+
+    assert!(serde_json::from_str::<TicketAction>(r#"{"Unknown": {}}"#).is_err());
+}
+
+#[test]
+fn test_union_delegates_validation_and_normalization() {
+    let bad = TicketAction::from(Refund {
+        order_id: "A-17".to_string(),
+        amount: -1.0,
+    });
+    assert!(bad.validate().is_err());
+
+    let mut escalation = TicketAction::from(Escalation {
+        reason: "  VIP customer ".to_string(),
+    });
+    assert!(escalation.validate().is_ok());
+    escalation.normalize().unwrap();
+    assert_eq!(
+        escalation,
+        TicketAction::Escalation(Escalation {
+            reason: "VIP customer".to_string()
+        })
+    );
+}
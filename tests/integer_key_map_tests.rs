@@ -0,0 +1,132 @@
+//! Tests for maps keyed by integers, which JSON writes as strings.
+
+use std::collections::{BTreeMap, HashMap};
+
+use rstructor::{Instructor, SchemaTarget, SchemaType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+enum Status {
+    Open,
+    Closed,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+struct Tracker {
+    #[llm(description = "Status by ticket number")]
+    tickets: HashMap<u32, Status>,
+    offsets: BTreeMap<i64, String>,
+    labels: HashMap<String, String>,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+enum Update {
+    Bulk { changes: HashMap<u64, String> },
+    Clear,
+}
+
+#[test]
+fn test_integer_key_map_schema_type() {
+    let unsigned = <HashMap<u32, String>>::schema().to_json();
+    assert_eq!(unsigned["type"], "object");
+    assert_eq!(unsigned["propertyNames"]["type"], "string");
+    assert_eq!(unsigned["propertyNames"]["pattern"], r"^\d+$");
+    assert_eq!(unsigned["additionalProperties"]["type"], "string");
+
+    let signed = <BTreeMap<i64, bool>>::schema().to_json();
+    assert_eq!(signed["propertyNames"]["pattern"], r"^-?\d+$");
+    assert_eq!(signed["additionalProperties"]["type"], "boolean");
+
+    assert_eq!(
+        <HashMap<u32, String>>::schema_name().as_deref(),
+        Some("HashMap<u32, String>")
+    );
+
+    // String keys are unconstrained
+    assert!(
+        <HashMap<String, String>>::schema()
+            .to_json()
+            .get("propertyNames")
+            .is_none()
+    );
+}
+
+#[test]
+fn test_derived_integer_key_fields() {
+    let schema_json = Tracker::schema().to_json();
+    let props = &schema_json["properties"];
+
+    assert_eq!(props["tickets"]["propertyNames"]["pattern"], r"^\d+$");
+    assert_eq!(
+        props["tickets"]["additionalProperties"],
+        Status::schema().to_json()
+    );
+    assert_eq!(props["tickets"]["description"], "Status by ticket number");
+    assert_eq!(
+        props["offsets"],
+        <BTreeMap<i64, String>>::schema().to_json()
+    );
+    assert!(props["labels"].get("propertyNames").is_none());
+}
+
+#[test]
+fn test_derived_integer_key_fields_in_enum_variants() {
+    let schema_json = Update::schema().to_json();
+    let bulk = schema_json["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find_map(|variant| variant["properties"].get("Bulk"))
+        .expect("Bulk variant");
+    let changes = &bulk["properties"]["changes"];
+    assert_eq!(changes["propertyNames"]["pattern"], r"^\d+$");
+    assert_eq!(changes["additionalProperties"]["type"], "string");
+}
+
+#[test]
+fn test_integer_keys_round_trip_as_strings() {
+    let tracker = Tracker {
+        tickets: HashMap::from([(7, Status::Open)]),
+        offsets: BTreeMap::from([(-3, "behind".to_string())]),
+        labels: HashMap::new(),
+    };
+    let json = serde_json::to_value(&tracker).unwrap();
+    assert_eq!(json["tickets"]["7"], "Open");
+    assert_eq!(json["offsets"]["-3"], "behind");
+
+    let parsed: Tracker = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed, tracker);
+}
+
+#[test]
+fn test_gemini_uses_numeric_placeholder_keys() {
+    let schema = Tracker::schema().prepare_for(SchemaTarget::Gemini);
+    let tickets = &schema["properties"]["tickets"];
+
+    assert!(tickets.get("propertyNames").is_none());
+    let keys: Vec<&str> = tickets["properties"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    assert_eq!(keys, vec!["1", "2", "3"]);
+    let description = tickets["description"].as_str().unwrap();
+    assert!(
+        description.starts_with("Status by ticket number. "),
+        "{}",
+        description
+    );
+    assert!(
+        description.contains("integers written as strings"),
+        "{}",
+        description
+    );
+
+    // String-keyed maps keep the generic placeholders
+    assert!(
+        schema["properties"]["labels"]["properties"]
+            .get("key1")
+            .is_some()
+    );
+}
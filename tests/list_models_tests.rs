@@ -0,0 +1,65 @@
+//! Tests for OpenAI model listing against a local stub server.
+
+#![cfg(feature = "openai")]
+
+mod common;
+
+use common::{Reply, serve_once};
+use rstructor::{LLMClient, OpenAIClient};
+use std::time::Duration;
+
+/// Serve a single `/models` response, then stop listening.
+fn serve_models_once(body: &str) -> String {
+    serve_once(Reply::new("200 OK", "application/json", body)).0
+}
+
+const MODELS: &str = r#"{"data": [
+    {"id": "gpt-4o"},
+    {"id": "gpt-4o-mini-tts"},
+    {"id": "gpt-4o-transcribe"},
+    {"id": "gpt-image-1"},
+    {"id": "o4-mini"},
+    {"id": "chatgpt-4o-latest"},
+    {"id": "text-embedding-3-small"},
+    {"id": "omni-moderation-latest"},
+    {"id": "dall-e-3"}
+]}"#;
+
+#[tokio::test]
+async fn test_list_models_filters_non_chat_models() {
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(serve_models_once(MODELS));
+
+    let ids: Vec<String> = client
+        .list_models()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|m| m.id)
+        .collect();
+    assert_eq!(ids, ["gpt-4o", "o4-mini", "chatgpt-4o-latest"]);
+}
+
+#[tokio::test]
+async fn test_list_models_cache() {
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(serve_models_once(MODELS))
+        .cache_models(Duration::from_secs(60));
+
+    let first = client.list_models().await.unwrap();
+    // The stub server only answers once, so this must come from the cache
+    let second = client.list_models().await.unwrap();
+    assert_eq!(first, second);
+}
+
+#[tokio::test]
+async fn test_list_models_without_cache_refetches() {
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(serve_models_once(MODELS));
+
+    client.list_models().await.unwrap();
+    assert!(client.list_models().await.is_err());
+}
@@ -0,0 +1,99 @@
+//! Tests for extracting a list of items without a wrapper type, using a local stub of
+//! the OpenAI API.
+
+#![cfg(feature = "openai")]
+
+mod common;
+
+use common::serve;
+use rstructor::{Instructor, LLMClient, OpenAIClient, RStructorError};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+#[llm(validate = "validate_person")]
+struct Person {
+    name: String,
+    role: String,
+}
+
+fn validate_person(person: &Person) -> rstructor::Result<()> {
+    if person.name.trim().is_empty() {
+        return Err(RStructorError::ValidationError(
+            "name must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn completion(content: Value) -> Value {
+    json!({
+        "model": "gpt-4o",
+        "choices": [{
+            "message": { "role": "assistant", "content": content.to_string() },
+            "finish_reason": "stop"
+        }],
+        "usage": { "prompt_tokens": 10, "completion_tokens": 5 }
+    })
+}
+
+#[tokio::test]
+async fn test_materialize_many_items_unwraps_the_array() {
+    let (base_url, requests) = serve(vec![completion(json!({
+        "items": [
+            { "name": "Ada", "role": "engineer" },
+            { "name": "Bo", "role": "designer" }
+        ]
+    }))]);
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o");
+
+    let people: Vec<Person> = client
+        .materialize_many_items("Ada is an engineer and Bo a designer")
+        .await
+        .unwrap();
+    assert_eq!(people.len(), 2);
+    assert_eq!(people[1].name, "Bo");
+
+    let request = requests.recv().unwrap().body;
+    let format = &request["response_format"]["json_schema"];
+    assert_eq!(format["name"], "PersonList");
+    assert_eq!(format["schema"]["required"], json!(["items"]));
+    let items = &format["schema"]["properties"]["items"];
+    assert_eq!(items["type"], "array");
+    assert_eq!(items["items"]["properties"]["role"]["type"], "string");
+}
+
+#[tokio::test]
+async fn test_materialize_many_items_retries_invalid_item() {
+    let (base_url, requests) = serve(vec![
+        completion(json!({
+            "items": [
+                { "name": "Ada", "role": "engineer" },
+                { "name": " ", "role": "designer" }
+            ]
+        })),
+        completion(json!({ "items": [{ "name": "Ada", "role": "engineer" }] })),
+    ]);
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o");
+
+    let people: Vec<Person> = client
+        .materialize_many_items("Who is mentioned?")
+        .await
+        .unwrap();
+    assert_eq!(people.len(), 1);
+
+    // The retry feedback names the offending item
+    let _first = requests.recv().unwrap().body;
+    let retry = requests.recv().unwrap().body;
+    let feedback = retry["messages"].as_array().unwrap().last().unwrap()["content"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert!(feedback.contains("items[1]"), "{}", feedback);
+}
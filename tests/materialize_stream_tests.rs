@@ -0,0 +1,298 @@
+//! Tests for `materialize_stream` and `collect_stream`, using local stubs of the provider
+//! streaming APIs.
+
+#![cfg(all(feature = "openai", feature = "gemini"))]
+
+mod common;
+
+use common::{Captured, Reply, serve_once};
+use futures::StreamExt;
+use rstructor::streaming::collect_stream;
+use rstructor::{ApiErrorKind, GeminiClient, Instructor, LLMClient, OpenAIClient, PricingTable};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::sync::mpsc;
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+struct Movie {
+    title: String,
+    year: u16,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+enum Color {
+    Red,
+    Green,
+}
+
+/// Answer one request with `status` and an event-stream `body`, sending back what was
+/// received.
+fn stream_once(status: &str, body: String) -> (String, mpsc::Receiver<Captured>) {
+    serve_once(Reply::event_stream(status, body))
+}
+
+/// An event stream with one `data:` event per payload
+fn sse(events: &[Value]) -> String {
+    events
+        .iter()
+        .map(|e| format!("data: {}\r\n\r\n", e))
+        .collect()
+}
+
+fn openai_events(deltas: &[&str]) -> String {
+    let mut events = vec![
+        json!({ "choices": [{ "delta": { "role": "assistant", "content": "" }, "finish_reason": null }] }),
+    ];
+    events.extend(
+        deltas
+            .iter()
+            .map(|d| json!({ "choices": [{ "delta": { "content": d }, "finish_reason": null }] })),
+    );
+    events.push(json!({ "choices": [{ "delta": {}, "finish_reason": "stop" }] }));
+    sse(&events) + "data: [DONE]\r\n\r\n"
+}
+
+fn gemini_events(deltas: &[&str]) -> String {
+    let events: Vec<Value> = deltas
+        .iter()
+        .map(|d| json!({ "candidates": [{ "content": { "parts": [{ "text": d }], "role": "model" } }] }))
+        .collect();
+    sse(&events)
+}
+
+fn openai_client(base_url: String) -> OpenAIClient {
+    OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o")
+}
+
+fn gemini_client(base_url: String) -> GeminiClient {
+    GeminiClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gemini-2.5-flash")
+}
+
+#[tokio::test]
+async fn test_openai_stream_yields_deltas_in_order() {
+    let (base_url, rx) = stream_once(
+        "200 OK",
+        openai_events(&[r#"{"title": "Inc"#, r#"eption", "#, r#""year": 2010}"#]),
+    );
+    let deltas: Vec<String> = openai_client(base_url)
+        .materialize_stream::<Movie>("Describe Inception")
+        .await
+        .unwrap()
+        .map(|delta| delta.unwrap())
+        .collect()
+        .await;
+
+    assert_eq!(
+        deltas,
+        vec![r#"{"title": "Inc"#, r#"eption", "#, r#""year": 2010}"#]
+    );
+    let captured = rx.recv().unwrap();
+    assert!(captured.request_line.starts_with("POST /chat/completions "));
+    assert_eq!(captured.body["stream"], true);
+    assert_eq!(captured.body["response_format"]["type"], "json_schema");
+}
+
+#[tokio::test]
+async fn test_openai_stream_collects_into_value() {
+    let (base_url, _rx) = stream_once(
+        "200 OK",
+        openai_events(&[r#"{"title": "Inception","#, r#" "year": 2010}"#]),
+    );
+    let stream = openai_client(base_url)
+        .materialize_stream::<Movie>("Describe Inception")
+        .await
+        .unwrap();
+    let movie: Movie = collect_stream(stream).await.unwrap();
+    assert_eq!(
+        movie,
+        Movie {
+            title: "Inception".to_string(),
+            year: 2010
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_collect_stream_unwraps_enum_root() {
+    // OpenAI needs an object root, so the enum arrives inside `{"value": ...}`
+    let (base_url, _rx) = stream_once("200 OK", openai_events(&[r#"{"value":"#, r#""Green"}"#]));
+    let stream = openai_client(base_url)
+        .materialize_stream::<Color>("Pick a color")
+        .await
+        .unwrap();
+    let color: Color = collect_stream(stream).await.unwrap();
+    assert_eq!(color, Color::Green);
+}
+
+#[tokio::test]
+async fn test_collect_stream_reports_invalid_json() {
+    let (base_url, _rx) = stream_once("200 OK", openai_events(&[r#"{"title": "Incep"#]));
+    let stream = openai_client(base_url)
+        .materialize_stream::<Movie>("Describe Inception")
+        .await
+        .unwrap();
+    let err = collect_stream::<Movie, _>(stream).await.unwrap_err();
+    assert!(err.to_string().contains("Failed to parse response as JSON"));
+}
+
+#[tokio::test]
+async fn test_error_status_is_classified_before_streaming() {
+    let body = json!({ "error": { "message": "Rate limit exceeded" } }).to_string();
+    let (base_url, _rx) = stream_once("429 Too Many Requests", body);
+    let err = openai_client(base_url)
+        .materialize_stream::<Movie>("Describe Inception")
+        .await
+        .err()
+        .unwrap();
+    assert!(matches!(
+        err.api_error_kind(),
+        Some(ApiErrorKind::RateLimited { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_openai_content_filter_ends_stream_with_error() {
+    let events = sse(&[
+        json!({ "choices": [{ "delta": { "content": "{\"title\": " }, "finish_reason": null }] }),
+        json!({ "choices": [{ "delta": {}, "finish_reason": "content_filter" }] }),
+    ]);
+    let (base_url, _rx) = stream_once("200 OK", events);
+    let items: Vec<_> = openai_client(base_url)
+        .materialize_stream::<Movie>("Describe Inception")
+        .await
+        .unwrap()
+        .collect()
+        .await;
+
+    assert_eq!(items.len(), 2);
+    assert!(items[0].is_ok());
+    assert!(matches!(
+        items[1].as_ref().unwrap_err().api_error_kind(),
+        Some(ApiErrorKind::ContentFiltered { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_gemini_stream_collects_into_value() {
+    let (base_url, rx) = stream_once(
+        "200 OK",
+        gemini_events(&[r#"{"title": "Inception", "#, r#""year": 2010}"#]),
+    );
+    let stream = gemini_client(base_url)
+        .materialize_stream::<Movie>("Describe Inception")
+        .await
+        .unwrap();
+    let movie: Movie = collect_stream(stream).await.unwrap();
+    assert_eq!(movie.title, "Inception");
+
+    let captured = rx.recv().unwrap();
+    assert!(
+        captured.request_line.starts_with(
+            "POST /models/gemini-2.5-flash:streamGenerateContent?alt=sse&key=test-key "
+        )
+    );
+    assert!(captured.body["generation_config"]["response_schema"].is_object());
+}
+
+#[tokio::test]
+async fn test_gemini_blocked_stream_ends_with_error() {
+    let events = sse(&[json!({ "promptFeedback": { "blockReason": "SAFETY" } })]);
+    let (base_url, _rx) = stream_once("200 OK", events);
+    let stream = gemini_client(base_url)
+        .materialize_stream::<Movie>("Describe Inception")
+        .await
+        .unwrap();
+    let err = collect_stream::<Movie, _>(stream).await.unwrap_err();
+    assert!(matches!(
+        err.api_error_kind(),
+        Some(ApiErrorKind::ContentFiltered { .. })
+    ));
+}
+
+#[tokio::test]
+async fn test_dry_run_streams_placeholder() {
+    let client = OpenAIClient::new("test-key").unwrap().dry_run(true);
+    let stream = client
+        .materialize_stream::<Movie>("Describe Inception")
+        .await
+        .unwrap();
+    let movie: Movie = collect_stream(stream).await.unwrap();
+    assert_eq!(movie.year, 0);
+}
+
+#[tokio::test]
+async fn test_openai_stream_is_charged_to_budget() {
+    let mut events = openai_events(&[r#"{"title": "Inception", "year": 2010}"#]);
+    let usage = json!({
+        "model": "gpt-4o",
+        "choices": [],
+        "usage": { "prompt_tokens": 1_000_000, "completion_tokens": 100_000, "total_tokens": 1_100_000 }
+    });
+    events = events.replace(
+        "data: [DONE]",
+        &format!("data: {}\r\n\r\ndata: [DONE]", usage),
+    );
+    let (base_url, rx) = stream_once("200 OK", events);
+    let pricing = PricingTable::new().with_model("gpt-4o", 2.50, 10.00);
+    let client = openai_client(base_url).budget(10.0, &pricing);
+
+    let stream = client
+        .materialize_stream::<Movie>("Describe Inception")
+        .await
+        .unwrap();
+    let movie: Movie = collect_stream(stream).await.unwrap();
+    assert_eq!(movie.year, 2010);
+
+    let captured = rx.recv().unwrap();
+    assert_eq!(captured.body["stream_options"]["include_usage"], true);
+    let spent = client.current_budget().unwrap().spent_usd();
+    assert!((spent - 3.5).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_openai_stream_without_budget_does_not_request_usage() {
+    let (base_url, rx) = stream_once(
+        "200 OK",
+        openai_events(&[r#"{"title": "Inception", "year": 2010}"#]),
+    );
+    let stream = openai_client(base_url)
+        .materialize_stream::<Movie>("Describe Inception")
+        .await
+        .unwrap();
+    let _: Movie = collect_stream(stream).await.unwrap();
+    assert!(rx.recv().unwrap().body.get("stream_options").is_none());
+}
+
+#[tokio::test]
+async fn test_gemini_stream_is_charged_once_to_budget() {
+    // Usage metadata is cumulative and repeated on every chunk
+    let events = sse(&[
+        json!({
+            "candidates": [{ "content": { "parts": [{ "text": r#"{"title": "Inception","# }] } }],
+            "usageMetadata": { "promptTokenCount": 1_000_000, "candidatesTokenCount": 50_000, "totalTokenCount": 1_050_000 }
+        }),
+        json!({
+            "candidates": [{ "content": { "parts": [{ "text": r#" "year": 2010}"# }] }, "finishReason": "STOP" }],
+            "usageMetadata": { "promptTokenCount": 1_000_000, "candidatesTokenCount": 100_000, "totalTokenCount": 1_100_000 }
+        }),
+    ]);
+    let (base_url, _rx) = stream_once("200 OK", events);
+    let pricing = PricingTable::new().with_model("gemini-2.5-flash", 0.30, 2.50);
+    let client = gemini_client(base_url).budget(10.0, &pricing);
+
+    let stream = client
+        .materialize_stream::<Movie>("Describe Inception")
+        .await
+        .unwrap();
+    let movie: Movie = collect_stream(stream).await.unwrap();
+    assert_eq!(movie.year, 2010);
+
+    let spent = client.current_budget().unwrap().spent_usd();
+    assert!((spent - 0.55).abs() < 1e-9);
+}
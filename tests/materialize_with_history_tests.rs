@@ -0,0 +1,189 @@
+//! Tests for `materialize_with_history`, using local stubs of the provider APIs.
+
+mod common;
+
+use async_trait::async_trait;
+use common::serve;
+use rstructor::{
+    ApiErrorKind, ChatMessage, GenerateResult, Instructor, LLMClient, MaterializeResult, ModelInfo,
+    Result,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+struct Order {
+    item: String,
+    quantity: u32,
+}
+
+fn completion(content: &str) -> Value {
+    json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "model": "gpt-4o",
+        "choices": [{
+            "index": 0,
+            "message": { "role": "assistant", "content": content },
+            "finish_reason": "stop"
+        }],
+        "usage": { "prompt_tokens": 20, "completion_tokens": 6, "total_tokens": 26 }
+    })
+}
+
+fn conversation() -> Vec<ChatMessage> {
+    vec![
+        ChatMessage::user("I'd like two lattes"),
+        ChatMessage::assistant(r#"{"item": "latte", "quantity": 2}"#),
+        ChatMessage::user("Actually, make that three"),
+    ]
+}
+
+/// Roles and contents of a chat-completions style `messages` array
+fn turns(body: &Value) -> Vec<(String, String)> {
+    body["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| {
+            (
+                m["role"].as_str().unwrap().to_string(),
+                m["content"].as_str().unwrap_or_default().to_string(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(feature = "openai")]
+#[tokio::test]
+async fn test_openai_sends_the_whole_conversation() {
+    let (base_url, rx) = serve(vec![completion(r#"{"item": "latte", "quantity": 3}"#)]);
+    let client = rstructor::OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o")
+        .system_prompt("You take coffee orders.");
+
+    let order: Order = client
+        .materialize_with_history(&conversation())
+        .await
+        .unwrap();
+    assert_eq!(order.quantity, 3);
+
+    let roles: Vec<String> = turns(&rx.recv().unwrap().body)
+        .into_iter()
+        .map(|(role, _)| role)
+        .collect();
+    assert_eq!(roles, vec!["system", "user", "assistant", "user"]);
+}
+
+#[cfg(feature = "openai")]
+#[tokio::test]
+async fn test_retry_appends_feedback_after_the_conversation() {
+    let (base_url, rx) = serve(vec![
+        completion(r#"{"item": "latte", "quantity": "three"}"#),
+        completion(r#"{"item": "latte", "quantity": 3}"#),
+    ]);
+    let client = rstructor::OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o");
+
+    let history = conversation();
+    let order: Order = client.materialize_with_history(&history).await.unwrap();
+    assert_eq!(order.quantity, 3);
+    assert_eq!(history.len(), 3);
+
+    let first = turns(&rx.recv().unwrap().body);
+    let retry = turns(&rx.recv().unwrap().body);
+    assert_eq!(first.len(), 3);
+    assert_eq!(retry.len(), 5);
+    assert_eq!(&retry[..3], &first[..]);
+    assert_eq!(retry[3].0, "assistant");
+    assert!(retry[3].1.contains(r#""three""#));
+    assert_eq!(retry[4].0, "user");
+}
+
+#[cfg(feature = "anthropic")]
+#[tokio::test]
+async fn test_anthropic_merges_consecutive_user_turns() {
+    let (base_url, rx) = serve(vec![json!({
+        "model": "claude-sonnet-4-5-20250929",
+        "content": [{ "type": "text", "text": r#"{"item": "mocha", "quantity": 1}"# }],
+        "usage": { "input_tokens": 10, "output_tokens": 5 }
+    })]);
+    let client = rstructor::AnthropicClient::new("test-key")
+        .unwrap()
+        .base_url(base_url);
+
+    let messages = [
+        ChatMessage::user("One mocha, please."),
+        ChatMessage::user("No whipped cream."),
+    ];
+    let order: Order = client.materialize_with_history(&messages).await.unwrap();
+    assert_eq!(order.item, "mocha");
+
+    let request = rx.recv().unwrap().body;
+    let sent = request["messages"].as_array().unwrap();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0]["role"], "user");
+}
+
+/// A client that only implements the required methods
+struct FixedClient;
+
+#[async_trait]
+impl LLMClient for FixedClient {
+    async fn materialize<T>(&self, prompt: &str) -> Result<T>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        Ok(self.materialize_with_metadata::<T>(prompt).await?.data)
+    }
+
+    async fn materialize_with_metadata<T>(&self, prompt: &str) -> Result<MaterializeResult<T>>
+    where
+        T: Instructor + DeserializeOwned + Send + 'static,
+    {
+        let data: T = serde_json::from_value(json!({ "item": prompt, "quantity": 1 }))?;
+        Ok(MaterializeResult::new(data, None))
+    }
+
+    async fn generate(&self, prompt: &str) -> Result<String> {
+        Ok(prompt.to_string())
+    }
+
+    async fn generate_with_metadata(&self, prompt: &str) -> Result<GenerateResult> {
+        Ok(GenerateResult::new(prompt.to_string(), None))
+    }
+
+    fn from_env() -> Result<Self> {
+        Ok(FixedClient)
+    }
+
+    async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+        Ok(Vec::new())
+    }
+}
+
+#[tokio::test]
+async fn test_default_accepts_a_single_user_message() {
+    let order: Order = FixedClient
+        .materialize_with_history(&[ChatMessage::user("tea")])
+        .await
+        .unwrap();
+    assert_eq!(order.item, "tea");
+}
+
+#[tokio::test]
+async fn test_default_rejects_longer_conversations() {
+    let err = FixedClient
+        .materialize_with_history::<Order>(&conversation())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.api_error_kind(),
+        Some(ApiErrorKind::BadRequest { .. })
+    ));
+}
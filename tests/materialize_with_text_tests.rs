@@ -0,0 +1,121 @@
+//! Tests for `materialize_with_text`, which extracts a value from a plain text
+//! completion, using a local stub of the OpenAI API.
+
+#![cfg(feature = "openai")]
+
+mod common;
+
+use common::{completion, serve_once};
+use rstructor::{Instructor, LLMClient, OpenAIClient, RStructorError};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+#[llm(validate = "validate_estimate")]
+struct Estimate {
+    city: String,
+    population: u64,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+enum Verdict {
+    Plausible,
+    Implausible,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Notes {
+    summary: Option<String>,
+    #[serde(default)]
+    sources: Vec<String>,
+}
+
+fn validate_estimate(estimate: &Estimate) -> rstructor::Result<()> {
+    if estimate.population == 0 {
+        return Err(RStructorError::ValidationError(
+            "population must be positive".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn client(base_url: String) -> OpenAIClient {
+    OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o")
+}
+
+#[tokio::test]
+async fn test_returns_value_and_surrounding_prose() {
+    let text = "Census data [2021] is a few years old, so treat this as approximate.\n\n\
+                ```json\n{\"city\": \"Lisbon\", \"population\": 545000}\n```\n\
+                Metro area figures are much higher.";
+    let (base_url, requests) = serve_once(completion(text));
+
+    let (estimate, raw): (Estimate, String) = client(base_url)
+        .materialize_with_text("Estimate the population of Lisbon")
+        .await
+        .unwrap();
+    assert_eq!(estimate.city, "Lisbon");
+    assert_eq!(estimate.population, 545000);
+    assert_eq!(raw, text);
+
+    // The schema travels in the prompt rather than as a response format
+    let request = requests.recv().unwrap().body;
+    assert!(request.get("response_format").is_none());
+    let prompt = request["messages"][0]["content"].as_str().unwrap();
+    assert!(prompt.starts_with("Estimate the population of Lisbon"));
+    assert!(prompt.contains("\"population\""));
+}
+
+#[tokio::test]
+async fn test_response_without_matching_json_is_an_error() {
+    let (base_url, _requests) = serve_once(completion(
+        "I can't give an estimate without more context {sorry}.",
+    ));
+    let err = client(base_url)
+        .materialize_with_text::<Estimate>("Estimate the population of Lisbon")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RStructorError::ValidationError(_)));
+}
+
+#[tokio::test]
+async fn test_extracted_value_is_validated() {
+    let (base_url, _requests) = serve_once(completion(
+        r#"Nobody lives there: {"city": "Atlantis", "population": 0}"#,
+    ));
+    let err = client(base_url)
+        .materialize_with_text::<Estimate>("Estimate the population of Atlantis")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("population must be positive"));
+}
+
+#[tokio::test]
+async fn test_enum_target_is_wrapped_and_unwrapped() {
+    let (base_url, requests) = serve_once(completion(
+        "Lisbon's census puts it near that figure.\n```json\n{\"value\": \"Plausible\"}\n```",
+    ));
+    let (verdict, _raw): (Verdict, String) = client(base_url)
+        .materialize_with_text("Is 545000 plausible for Lisbon?")
+        .await
+        .unwrap();
+    assert_eq!(verdict, Verdict::Plausible);
+
+    let request = requests.recv().unwrap().body;
+    let prompt = request["messages"][0]["content"].as_str().unwrap();
+    assert!(prompt.contains("\"value\""));
+}
+
+#[tokio::test]
+async fn test_nested_value_of_a_mismatched_answer_is_not_used() {
+    // `summary` has the wrong type, and the nested `{}` would fit `Notes` on its own
+    let (base_url, _requests) =
+        serve_once(completion(r#"Here you go: {"summary": 3, "details": {}}"#));
+    let err = client(base_url)
+        .materialize_with_text::<Notes>("Summarize the article")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, RStructorError::ValidationError(_)));
+}
@@ -0,0 +1,110 @@
+//! Tests for `max_response_bytes`, using a local stub of the OpenAI API.
+
+#![cfg(feature = "openai")]
+
+mod common;
+
+use common::{Reply, completion, serve_once};
+use rstructor::{ApiErrorKind, Instructor, LLMClient, OpenAIClient, RStructorError};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Movie {
+    title: String,
+}
+
+fn client(base_url: String, max_bytes: usize) -> OpenAIClient {
+    OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o")
+        .max_response_bytes(max_bytes)
+}
+
+fn assert_too_large(err: RStructorError, expected_limit: usize) {
+    match err.api_error_kind() {
+        Some(ApiErrorKind::ResponseTooLarge { limit }) => assert_eq!(*limit, expected_limit),
+        other => panic!("expected ResponseTooLarge, got {:?}", other),
+    }
+    assert!(!err.is_retryable());
+}
+
+#[tokio::test]
+async fn test_response_within_limit_is_read() {
+    let (base_url, _) = serve_once(completion(r#"{"title": "Inception"}"#));
+    let movie: Movie = client(base_url, 64 * 1024)
+        .materialize("Describe Inception")
+        .await
+        .unwrap();
+    assert_eq!(movie.title, "Inception");
+}
+
+#[tokio::test]
+async fn test_declared_length_over_limit_is_rejected() {
+    let (base_url, _) = serve_once(completion(&"a".repeat(10_000)));
+    let err = client(base_url, 1024)
+        .generate("Say a lot")
+        .await
+        .unwrap_err();
+    assert_too_large(err, 1024);
+}
+
+#[tokio::test]
+async fn test_undeclared_length_is_capped_while_reading() {
+    let (base_url, _) =
+        serve_once(Reply::json(completion(&"a".repeat(10_000))).without_content_length());
+    let err = client(base_url, 1024)
+        .materialize::<Movie>("Describe Inception")
+        .await
+        .unwrap_err();
+    assert_too_large(err, 1024);
+}
+
+#[tokio::test]
+async fn test_oversized_error_body_keeps_its_status() {
+    let body = serde_json::json!({
+        "error": { "message": "Rate limit reached", "padding": "a".repeat(10_000) }
+    });
+    let (base_url, _) = serve_once(
+        Reply::new(
+            "429 Too Many Requests",
+            "application/json",
+            body.to_string(),
+        )
+        .header("Retry-After: 7"),
+    );
+    let err = client(base_url, 1024)
+        .no_retries()
+        .generate("Say a lot")
+        .await
+        .unwrap_err();
+    match err.api_error_kind() {
+        Some(ApiErrorKind::RateLimited { retry_after }) => {
+            assert_eq!(*retry_after, Some(std::time::Duration::from_secs(7)))
+        }
+        other => panic!("expected RateLimited, got {:?}", other),
+    }
+    assert!(err.is_retryable());
+}
+
+#[tokio::test]
+async fn test_oversized_unavailable_body_stays_retryable() {
+    let (base_url, _) = serve_once(
+        Reply::new(
+            "503 Service Unavailable",
+            "text/html",
+            "<p>down</p>".repeat(1_000),
+        )
+        .without_content_length(),
+    );
+    let err = client(base_url, 1024)
+        .no_retries()
+        .generate("Say a lot")
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.api_error_kind(),
+        Some(ApiErrorKind::ServiceUnavailable)
+    ));
+    assert!(err.is_retryable());
+}
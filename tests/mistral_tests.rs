@@ -0,0 +1,145 @@
+//! Tests for the Mistral client, using a local stub of the Mistral API.
+
+#![cfg(feature = "mistral")]
+
+mod common;
+
+use common::serve;
+use rstructor::{Instructor, LLMClient, MistralClient};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+struct Movie {
+    title: String,
+    year: u16,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+enum Sentiment {
+    Positive,
+    Negative,
+}
+
+fn completion(content: &str) -> Value {
+    json!({
+        "id": "cmpl-1",
+        "object": "chat.completion",
+        "model": "mistral-small-latest",
+        "choices": [{
+            "index": 0,
+            "message": { "role": "assistant", "content": content },
+            "finish_reason": "stop"
+        }],
+        "usage": { "prompt_tokens": 30, "completion_tokens": 9, "total_tokens": 39 }
+    })
+}
+
+fn client(base_url: String) -> MistralClient {
+    MistralClient::new("test-key").unwrap().base_url(base_url)
+}
+
+#[tokio::test]
+async fn test_materialize_uses_json_object_mode() {
+    let (base_url, rx) = serve(vec![completion(r#"{"title": "Inception", "year": 2010}"#)]);
+    let result = client(base_url)
+        .materialize_with_metadata::<Movie>("Describe Inception")
+        .await
+        .unwrap();
+
+    assert_eq!(result.data.title, "Inception");
+    let usage = result.usage.unwrap();
+    assert_eq!((usage.input_tokens, usage.output_tokens), (30, 9));
+    assert_eq!(usage.model, "mistral-small-latest");
+
+    let captured = rx.recv().unwrap();
+    assert!(captured.request_line.starts_with("POST /chat/completions "));
+    assert_eq!(captured.header("authorization"), Some("Bearer test-key"));
+    let request = captured.body;
+    assert_eq!(request["response_format"], json!({ "type": "json_object" }));
+    assert_eq!(request["messages"][0]["role"], "system");
+    assert!(
+        request["messages"][0]["content"]
+            .as_str()
+            .unwrap()
+            .contains(r#""year""#)
+    );
+    assert_eq!(request["messages"][1]["content"], "Describe Inception");
+}
+
+#[tokio::test]
+async fn test_invalid_response_is_retried_with_feedback() {
+    let (base_url, rx) = serve(vec![
+        completion(r#"{"title": "Inception", "year": "2010"}"#),
+        completion(r#"{"title": "Inception", "year": 2010}"#),
+    ]);
+    let result = client(base_url)
+        .materialize_with_metadata::<Movie>("Describe Inception")
+        .await
+        .unwrap();
+    assert_eq!(result.data.year, 2010);
+    assert_eq!(result.attempts, 2);
+
+    let _ = rx.recv().unwrap();
+    let retry = rx.recv().unwrap().body;
+    let roles: Vec<&str> = retry["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["role"].as_str().unwrap())
+        .collect();
+    assert_eq!(roles, ["system", "user", "assistant", "user"]);
+}
+
+#[tokio::test]
+async fn test_enum_root_is_wrapped_in_object() {
+    let (base_url, _rx) = serve(vec![completion(r#"{"value": "Positive"}"#)]);
+    let sentiment: Sentiment = client(base_url)
+        .materialize("What a wonderful film")
+        .await
+        .unwrap();
+    assert_eq!(sentiment, Sentiment::Positive);
+}
+
+#[tokio::test]
+async fn test_generate_sends_no_response_format() {
+    let (base_url, rx) = serve(vec![completion("Bonjour !")]);
+    let text = client(base_url)
+        .model("mistral-large-latest")
+        .generate("Say hello in French")
+        .await
+        .unwrap();
+    assert_eq!(text, "Bonjour !");
+
+    let request = rx.recv().unwrap().body;
+    assert!(request.get("response_format").is_none());
+    assert_eq!(request["model"], "mistral-large-latest");
+}
+
+#[tokio::test]
+async fn test_list_models_skips_non_chat_models() {
+    let (base_url, rx) = serve(vec![json!({
+        "object": "list",
+        "data": [
+            {
+                "id": "mistral-large-latest",
+                "name": "mistral-large-2411",
+                "description": "Top-tier reasoning model",
+                "capabilities": { "completion_chat": true }
+            },
+            { "id": "mistral-embed", "capabilities": { "completion_chat": false } },
+            { "id": "ft:open-mistral-7b:abc" }
+        ]
+    })]);
+    let models = client(base_url).list_models().await.unwrap();
+
+    let ids: Vec<&str> = models.iter().map(|m| m.id.as_str()).collect();
+    assert_eq!(ids, ["mistral-large-latest", "ft:open-mistral-7b:abc"]);
+    assert_eq!(models[0].name.as_deref(), Some("mistral-large-2411"));
+    assert!(rx.recv().unwrap().request_line.starts_with("GET /models "));
+}
+
+#[test]
+fn test_new_rejects_empty_api_key() {
+    assert!(MistralClient::new("").is_err());
+}
@@ -1,6 +1,9 @@
 #[cfg(test)]
 mod tests {
-    use rstructor::{AnthropicModel, GeminiModel, GrokModel, OpenAIModel};
+    use rstructor::{
+        AnthropicModel, ApiErrorKind, GeminiModel, GrokModel, MistralModel, OllamaModel,
+        OpenAIModel,
+    };
     use std::str::FromStr;
 
     #[test]
@@ -105,9 +108,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_mistral_model_from_string() {
+        // Test known model
+        let model = MistralModel::from_string("mistral-large-latest");
+        assert_eq!(model, MistralModel::MistralLarge);
+        assert_eq!(MistralModel::Codestral.as_str(), "codestral-latest");
+
+        // Pinned versions are custom models
+        let model = MistralModel::from_str("mistral-large-2411").unwrap();
+        assert_eq!(
+            model,
+            MistralModel::Custom("mistral-large-2411".to_string())
+        );
+    }
+
+    #[test]
+    fn test_ollama_model_from_string() {
+        // Test known model
+        let model = OllamaModel::from_string("qwen2.5");
+        assert_eq!(model, OllamaModel::Qwen25);
+
+        // Tags are custom models
+        let model: OllamaModel = "llama3.2:1b".into();
+        assert_eq!(model, OllamaModel::Custom("llama3.2:1b".to_string()));
+        assert_eq!(model.as_str(), "llama3.2:1b");
+    }
+
     #[test]
     fn test_model_as_str_with_custom() {
         let model = OpenAIModel::Custom("my-custom-model".to_string());
         assert_eq!(model.as_str(), "my-custom-model");
     }
+
+    #[test]
+    fn test_try_known_rejects_unknown_models() {
+        assert_eq!(OpenAIModel::try_known("o1").unwrap(), OpenAIModel::O1);
+        assert_eq!(
+            GrokModel::try_known("grok-3-mini").unwrap(),
+            GrokModel::Grok3Mini
+        );
+
+        let err = AnthropicModel::try_known("claude-opus-4.1").unwrap_err();
+        assert!(matches!(
+            err.api_error_kind(),
+            Some(ApiErrorKind::InvalidModel { model, .. }) if model == "claude-opus-4.1"
+        ));
+        assert!(GeminiModel::try_known("gemini-9-ultra").is_err());
+
+        // The permissive path still accepts anything
+        assert!(matches!(
+            GeminiModel::from_str("gemini-9-ultra").unwrap(),
+            GeminiModel::Custom(_)
+        ));
+    }
 }
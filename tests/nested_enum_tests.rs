@@ -284,4 +284,52 @@ mod nested_enum_tests {
             }
         }
     }
+
+    // ====== Newtype variant wrapping a data-carrying enum ======
+
+    #[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+    enum Shape {
+        Circle { radius: f64 },
+        Square(f64),
+        Empty,
+    }
+
+    #[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+    enum DrawCommand {
+        Draw(Shape),
+        Clear,
+    }
+
+    #[test]
+    fn test_newtype_variant_wrapping_enum_schema() {
+        let schema_json = DrawCommand::schema().to_json();
+        let variants = schema_json["oneOf"].as_array().unwrap();
+
+        let draw_variant = variants
+            .iter()
+            .find(|v| v.get("properties").and_then(|p| p.get("Draw")).is_some())
+            .expect("Draw variant should be present");
+
+        // The wrapped enum's oneOf is embedded inside the variant property
+        let inner_variants = draw_variant["properties"]["Draw"]["oneOf"]
+            .as_array()
+            .expect("inner enum should be a oneOf");
+        assert_eq!(inner_variants.len(), 3);
+        assert!(
+            inner_variants
+                .iter()
+                .any(|v| v["properties"]["Circle"]["properties"]["radius"]["type"] == "number")
+        );
+    }
+
+    #[test]
+    fn test_newtype_variant_wrapping_enum_deserialization() {
+        let json = serde_json::json!({ "Draw": { "Circle": { "radius": 2.5 } } });
+        let command: DrawCommand = serde_json::from_value(json).unwrap();
+        assert_eq!(command, DrawCommand::Draw(Shape::Circle { radius: 2.5 }));
+
+        let json = serde_json::json!({ "Draw": "Empty" });
+        let command: DrawCommand = serde_json::from_value(json).unwrap();
+        assert_eq!(command, DrawCommand::Draw(Shape::Empty));
+    }
 }
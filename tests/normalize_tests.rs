@@ -0,0 +1,117 @@
+//! Tests for the `normalize` hook that runs after validation.
+
+mod common;
+
+use rstructor::{Instructor, InstructorExt, RStructorError};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+#[llm(validate = "validate_contact", normalize = "normalize_contact")]
+struct Contact {
+    name: String,
+    email: String,
+    #[serde(default)]
+    domain: String,
+}
+
+fn validate_contact(contact: &Contact) -> rstructor::Result<()> {
+    if !contact.email.contains('@') {
+        return Err(RStructorError::ValidationError(format!(
+            "Invalid email: {}",
+            contact.email
+        )));
+    }
+    Ok(())
+}
+
+fn normalize_contact(contact: &mut Contact) -> rstructor::Result<()> {
+    contact.name = contact.name.trim().to_string();
+    contact.email = contact.email.trim().to_lowercase();
+    contact.domain = contact
+        .email
+        .split_once('@')
+        .map(|(_, domain)| domain.to_string())
+        .unwrap_or_default();
+    Ok(())
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+#[llm(normalize = "reject_empty")]
+struct Label {
+    text: String,
+}
+
+fn reject_empty(label: &mut Label) -> rstructor::Result<()> {
+    label.text = label.text.trim().to_string();
+    if label.text.is_empty() {
+        return Err(RStructorError::ValidationError(
+            "Label cannot be blank".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Plain {
+    text: String,
+}
+
+#[test]
+fn test_normalize_cleans_up_value() {
+    let contact =
+        Contact::from_json(r#"{"name": "  Ada Lovelace ", "email": " Ada@Example.COM"}"#).unwrap();
+    assert_eq!(contact.name, "Ada Lovelace");
+    assert_eq!(contact.email, "ada@example.com");
+    assert_eq!(contact.domain, "example.com");
+}
+
+#[test]
+fn test_normalize_skipped_when_validation_fails() {
+    let err = Contact::from_json(r#"{"name": "Ada", "email": "not-an-email"}"#).unwrap_err();
+    assert!(err.to_string().contains("Invalid email"));
+}
+
+#[test]
+fn test_normalize_error_is_returned() {
+    assert_eq!(Label::from_json(r#"{"text": " ok "}"#).unwrap().text, "ok");
+    let err = Label::from_json(r#"{"text": "   "}"#).unwrap_err();
+    assert!(err.to_string().contains("Label cannot be blank"));
+}
+
+#[test]
+fn test_default_normalize_is_noop() {
+    let mut plain = Plain::from_json(r#"{"text": "  as is  "}"#).unwrap();
+    assert!(plain.normalize().is_ok());
+    assert_eq!(plain.text, "  as is  ");
+}
+
+#[cfg(feature = "openai")]
+mod materialize {
+    use super::Contact;
+    use crate::common::serve_once;
+    use rstructor::{LLMClient, OpenAIClient};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_materialize_returns_normalized_value() {
+        let content = r#"{"name": " Grace Hopper ", "email": "GRACE@navy.mil "}"#;
+        let (base_url, _) = serve_once(json!({
+            "model": "gpt-4o",
+            "choices": [{
+                "message": { "role": "assistant", "content": content },
+                "finish_reason": "stop"
+            }],
+            "usage": { "prompt_tokens": 10, "completion_tokens": 5 }
+        }));
+        let client = OpenAIClient::new("test-key")
+            .unwrap()
+            .base_url(base_url)
+            .model("gpt-4o")
+            .no_retries();
+
+        let contact: Contact = client.materialize("Who wrote COBOL?").await.unwrap();
+        assert_eq!(contact.name, "Grace Hopper");
+        assert_eq!(contact.email, "grace@navy.mil");
+        assert_eq!(contact.domain, "navy.mil");
+    }
+}
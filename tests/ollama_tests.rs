@@ -0,0 +1,168 @@
+//! Tests for the Ollama client, using a local stub of the Ollama API.
+
+#![cfg(feature = "ollama")]
+
+mod common;
+
+use common::serve;
+use rstructor::{Instructor, LLMClient, OllamaClient, RequestOptions};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+struct Movie {
+    title: String,
+    year: u16,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+enum Sentiment {
+    Positive,
+    Negative,
+}
+
+fn chat_response(content: &str) -> Value {
+    json!({
+        "model": "llama3.2",
+        "created_at": "2025-01-01T00:00:00Z",
+        "message": { "role": "assistant", "content": content },
+        "done": true,
+        "done_reason": "stop",
+        "prompt_eval_count": 42,
+        "eval_count": 7
+    })
+}
+
+fn client(base_url: String) -> OllamaClient {
+    OllamaClient::new().base_url(base_url)
+}
+
+#[tokio::test]
+async fn test_materialize_uses_json_mode_with_schema_in_prompt() {
+    let (base_url, rx) = serve(vec![chat_response(
+        r#"{"title": "Inception", "year": 2010}"#,
+    )]);
+    let result = client(base_url)
+        .materialize_with_metadata::<Movie>("Describe Inception")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        result.data,
+        Movie {
+            title: "Inception".to_string(),
+            year: 2010
+        }
+    );
+    let usage = result.usage.unwrap();
+    assert_eq!((usage.input_tokens, usage.output_tokens), (42, 7));
+
+    let captured = rx.recv().unwrap();
+    assert!(captured.request_line.starts_with("POST /api/chat "));
+    let request = captured.body;
+    assert_eq!(request["format"], "json");
+    assert_eq!(request["stream"], false);
+    assert_eq!(request["model"], "llama3.2");
+    assert_eq!(request["messages"][0]["role"], "system");
+    assert!(
+        request["messages"][0]["content"]
+            .as_str()
+            .unwrap()
+            .contains(r#""title""#)
+    );
+    assert_eq!(request["messages"][1]["content"], "Describe Inception");
+}
+
+#[tokio::test]
+async fn test_invalid_response_is_retried_with_feedback() {
+    let (base_url, rx) = serve(vec![
+        chat_response(r#"{"title": "Inception"}"#),
+        chat_response(r#"{"title": "Inception", "year": 2010}"#),
+    ]);
+    let movie: Movie = client(base_url)
+        .materialize("Describe Inception")
+        .await
+        .unwrap();
+    assert_eq!(movie.year, 2010);
+
+    let _ = rx.recv().unwrap();
+    let retry = rx.recv().unwrap().body;
+    let messages = retry["messages"].as_array().unwrap();
+    assert_eq!(messages.len(), 4);
+    assert_eq!(messages[2]["role"], "assistant");
+    assert_eq!(messages[3]["role"], "user");
+}
+
+#[tokio::test]
+async fn test_enum_root_is_wrapped_in_object() {
+    let (base_url, rx) = serve(vec![chat_response(r#"{"value": "Negative"}"#)]);
+    let sentiment: Sentiment = client(base_url)
+        .materialize("The plot dragged on forever")
+        .await
+        .unwrap();
+    assert_eq!(sentiment, Sentiment::Negative);
+
+    let request = rx.recv().unwrap().body;
+    assert!(
+        request["messages"][0]["content"]
+            .as_str()
+            .unwrap()
+            .contains(r#""value""#)
+    );
+}
+
+#[tokio::test]
+async fn test_generate_sends_options_without_json_mode() {
+    let (base_url, rx) = serve(vec![chat_response("Hello!")]);
+    let options = RequestOptions::new().with_max_tokens(16).with_stop(["\n"]);
+    let text = client(base_url)
+        .model("qwen2.5:14b")
+        .generate_opts("Say hello", &options)
+        .await
+        .unwrap();
+    assert_eq!(text, "Hello!");
+
+    let request = rx.recv().unwrap().body;
+    assert!(request.get("format").is_none());
+    assert_eq!(request["model"], "qwen2.5:14b");
+    assert_eq!(request["options"]["num_predict"], 16);
+    assert_eq!(request["options"]["stop"], json!(["\n"]));
+    assert_eq!(request["options"]["temperature"], 0.0);
+}
+
+#[tokio::test]
+async fn test_list_models_reads_tags() {
+    let (base_url, rx) = serve(vec![json!({
+        "models": [
+            { "name": "llama3.2:latest", "details": { "parameter_size": "3.2B" } },
+            { "name": "qwen2.5:14b" }
+        ]
+    })]);
+    let models = client(base_url).list_models().await.unwrap();
+
+    let ids: Vec<&str> = models.iter().map(|m| m.id.as_str()).collect();
+    assert_eq!(ids, ["llama3.2:latest", "qwen2.5:14b"]);
+    assert_eq!(models[0].description.as_deref(), Some("3.2B parameters"));
+    assert!(
+        rx.recv()
+            .unwrap()
+            .request_line
+            .starts_with("GET /api/tags ")
+    );
+}
+
+#[test]
+fn test_request_body_preview() {
+    let body = OllamaClient::new()
+        .system_prompt("Be terse.")
+        .materialize_request_body::<Movie>("Describe Inception")
+        .unwrap();
+    let roles: Vec<&str> = body["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["role"].as_str().unwrap())
+        .collect();
+    assert_eq!(roles, ["system", "system", "user"]);
+    assert_eq!(body["format"], "json");
+}
@@ -0,0 +1,98 @@
+//! Tests for enums with a catch-all `#[llm(other)]` variant.
+
+use rstructor::{Instructor, SchemaTarget, SchemaType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Genre {
+    Action,
+    Comedy,
+    Drama,
+    #[llm(other)]
+    #[serde(untagged)]
+    Other(String),
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+#[llm(description = "Payment method used at checkout")]
+enum PaymentMethod {
+    Card,
+    Cash,
+    #[llm(other, description = "The method's name, if it isn't card or cash")]
+    #[serde(untagged)]
+    Other(String),
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Movie {
+    title: String,
+    genre: Genre,
+}
+
+#[test]
+fn test_other_schema_prefers_known_values() {
+    let schema_json = Genre::schema().to_json();
+    assert_eq!(schema_json["title"], "Genre");
+
+    let any_of = schema_json["anyOf"].as_array().unwrap();
+    assert_eq!(any_of.len(), 2);
+    assert_eq!(any_of[0]["type"], "string");
+    assert_eq!(
+        any_of[0]["enum"],
+        serde_json::json!(["action", "comedy", "drama"])
+    );
+    assert_eq!(any_of[1]["type"], "string");
+    assert!(any_of[1].get("enum").is_none());
+    assert_eq!(
+        any_of[1]["description"],
+        "Any other value, only when none of the listed values fit"
+    );
+}
+
+#[test]
+fn test_other_schema_uses_variant_description() {
+    let schema_json = PaymentMethod::schema().to_json();
+    assert_eq!(
+        schema_json["description"],
+        "Payment method used at checkout"
+    );
+    assert_eq!(
+        schema_json["anyOf"][1]["description"],
+        "The method's name, if it isn't card or cash"
+    );
+}
+
+#[test]
+fn test_unknown_values_deserialize_into_other() {
+    let known: Genre = serde_json::from_str(r#""comedy""#).unwrap();
+    assert_eq!(known, Genre::Comedy);
+
+    let unknown: Genre = serde_json::from_str(r#""western""#).unwrap();
+    assert_eq!(unknown, Genre::Other("western".to_string()));
+
+    // The fallback serializes back to the bare string
+    assert_eq!(
+        serde_json::to_string(&Genre::Other("noir".to_string())).unwrap(),
+        r#""noir""#
+    );
+
+    let movie: Movie =
+        serde_json::from_str(r#"{"title": "Unforgiven", "genre": "western"}"#).unwrap();
+    assert_eq!(movie.genre, Genre::Other("western".to_string()));
+}
+
+#[test]
+fn test_other_enum_as_struct_field() {
+    let schema_json = Movie::schema().to_json();
+    assert_eq!(
+        schema_json["properties"]["genre"]["anyOf"][0]["enum"],
+        serde_json::json!(["action", "comedy", "drama"])
+    );
+
+    // Strict preparation leaves both branches intact
+    let strict = Movie::schema().prepare_for(SchemaTarget::OpenAIStrict);
+    let any_of = strict["properties"]["genre"]["anyOf"].as_array().unwrap();
+    assert_eq!(any_of.len(), 2);
+    assert_eq!(any_of[1]["type"], "string");
+}
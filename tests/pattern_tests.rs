@@ -0,0 +1,80 @@
+//! Tests for `#[llm(pattern = "...")]` on string fields.
+
+use rstructor::{Instructor, InstructorExt, RStructorError, SchemaTarget, SchemaType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Product {
+    #[llm(description = "Stock keeping unit", pattern = r"^[A-Z]{3}-\d{4}$")]
+    sku_code: String,
+
+    #[llm(pattern = r"^\+?[0-9 ]+$")]
+    support_phone: Option<String>,
+
+    name: String,
+}
+
+fn product(sku: &str, phone: Option<&str>) -> Product {
+    Product {
+        sku_code: sku.to_string(),
+        support_phone: phone.map(str::to_string),
+        name: "Widget".to_string(),
+    }
+}
+
+fn validation_message(result: rstructor::Result<()>) -> String {
+    match result {
+        Err(RStructorError::ValidationError(msg)) => msg,
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_pattern_in_schema() {
+    let schema_json = Product::schema().to_json();
+    let props = &schema_json["properties"];
+
+    assert_eq!(props["skuCode"]["pattern"], r"^[A-Z]{3}-\d{4}$");
+    assert_eq!(props["skuCode"]["description"], "Stock keeping unit");
+    assert_eq!(props["supportPhone"]["pattern"], r"^\+?[0-9 ]+$");
+    assert!(props["name"].get("pattern").is_none());
+}
+
+#[test]
+fn test_pattern_survives_provider_preparation() {
+    for target in [SchemaTarget::OpenAIStrict, SchemaTarget::Gemini] {
+        let schema = Product::schema().prepare_for(target);
+        assert_eq!(
+            schema["properties"]["skuCode"]["pattern"], r"^[A-Z]{3}-\d{4}$",
+            "{:?}",
+            target
+        );
+    }
+}
+
+#[test]
+fn test_matching_values_pass_validation() {
+    assert!(product("ABC-1234", Some("+1 555 0100")).validate().is_ok());
+    // Absent optional fields aren't checked
+    assert!(product("ABC-1234", None).validate().is_ok());
+}
+
+#[test]
+fn test_non_matching_value_fails_validation() {
+    let msg = validation_message(product("abc-12", None).validate());
+    assert_eq!(
+        msg,
+        r#"skuCode: "abc-12" does not match pattern ^[A-Z]{3}-\d{4}$"#
+    );
+
+    let msg = validation_message(product("ABC-1234", Some("call us")).validate());
+    assert!(msg.starts_with("supportPhone:"), "{}", msg);
+}
+
+#[test]
+fn test_pattern_checked_on_parse() {
+    let err = Product::from_json(r#"{"skuCode": "A1", "name": "Widget"}"#).unwrap_err();
+    assert!(matches!(err, RStructorError::ValidationError(_)));
+    assert!(err.to_string().contains("skuCode"));
+}
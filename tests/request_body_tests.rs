@@ -0,0 +1,150 @@
+//! Tests for previewing request bodies without sending them.
+
+use rstructor::{Instructor, LLMClient};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+#[llm(description = "A movie")]
+struct Movie {
+    #[llm(description = "Title of the movie", example = "Inception")]
+    title: String,
+    year: Option<u16>,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+enum Sentiment {
+    Positive,
+    Negative,
+}
+
+#[cfg(feature = "openai")]
+#[test]
+fn test_openai_request_body() {
+    let client = rstructor::OpenAIClient::new("test-key")
+        .unwrap()
+        .model("gpt-4o")
+        .service_tier("flex");
+    let body = client
+        .materialize_request_body::<Movie>("Name a movie")
+        .unwrap();
+
+    assert_eq!(body["model"], "gpt-4o");
+    assert_eq!(body["service_tier"], "flex");
+    assert_eq!(body["messages"][0]["role"], "user");
+    assert_eq!(body["messages"][0]["content"], "Name a movie");
+
+    let schema = &body["response_format"]["json_schema"]["schema"];
+    assert_eq!(body["response_format"]["json_schema"]["name"], "Movie");
+    assert_eq!(schema["additionalProperties"], false);
+    assert_eq!(schema["required"], serde_json::json!(["title", "year"]));
+}
+
+#[cfg(feature = "openai")]
+#[test]
+fn test_openai_request_body_wraps_enum_root() {
+    let client = rstructor::OpenAIClient::new("test-key").unwrap();
+    let body = client
+        .materialize_request_body::<Sentiment>("How was it?")
+        .unwrap();
+
+    let schema = &body["response_format"]["json_schema"]["schema"];
+    assert_eq!(schema["type"], "object");
+    assert!(schema["properties"]["value"]["enum"].is_array());
+}
+
+#[cfg(feature = "openai")]
+#[test]
+fn test_openai_request_body_with_predicted_output() {
+    let previous = Movie {
+        title: "Inception".to_string(),
+        year: Some(2010),
+    };
+    let client = rstructor::OpenAIClient::new("test-key")
+        .unwrap()
+        .model("gpt-4.1")
+        .predicted_output(&previous);
+    let body = client
+        .materialize_request_body::<Movie>("Fix the year")
+        .unwrap();
+
+    assert_eq!(body["prediction"]["type"], "content");
+    let content = body["prediction"]["content"].as_str().unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(content).unwrap(),
+        serde_json::json!({"title": "Inception", "year": 2010})
+    );
+
+    // Wrapped roots are predicted inside the wrapper
+    let client = rstructor::OpenAIClient::new("test-key")
+        .unwrap()
+        .predicted_output(Sentiment::Positive);
+    let body = client
+        .materialize_request_body::<Sentiment>("How was it?")
+        .unwrap();
+    assert_eq!(body["prediction"]["content"], r#"{"value":"Positive"}"#);
+
+    // No prediction unless asked for
+    let body = rstructor::OpenAIClient::new("test-key")
+        .unwrap()
+        .materialize_request_body::<Movie>("Name a movie")
+        .unwrap();
+    assert!(body.get("prediction").is_none());
+}
+
+#[cfg(feature = "anthropic")]
+#[test]
+fn test_anthropic_request_body() {
+    let client = rstructor::AnthropicClient::new("test-key").unwrap();
+    let body = client
+        .materialize_request_body::<Movie>("Name a movie")
+        .unwrap();
+
+    assert_eq!(body["messages"][0]["role"], "user");
+    assert_eq!(body["output_format"]["type"], "json_schema");
+    assert_eq!(
+        body["output_format"]["schema"]["additionalProperties"],
+        false
+    );
+    assert!(body.get("tools").is_none());
+
+    // Forced tool use moves the schema into the tool definition
+    let body = client
+        .force_tool_use(true)
+        .materialize_request_body::<Movie>("Name a movie")
+        .unwrap();
+    assert!(body.get("output_format").is_none());
+    assert_eq!(body["tools"][0]["input_schema"]["type"], "object");
+}
+
+#[cfg(feature = "gemini")]
+#[test]
+fn test_gemini_request_body() {
+    let client = rstructor::GeminiClient::new("test-key").unwrap();
+    let body = client
+        .materialize_request_body::<Movie>("Name a movie")
+        .unwrap();
+
+    let config = &body["generation_config"];
+    assert_eq!(config["response_mime_type"], "application/json");
+    // Gemini doesn't accept `additionalProperties` or `title`
+    let schema = &config["response_schema"];
+    assert!(schema.get("additionalProperties").is_none());
+    assert!(schema.get("title").is_none());
+    // Option fields are nullable, required ones are not
+    assert_eq!(schema["properties"]["year"]["nullable"], true);
+    assert!(schema["properties"]["title"].get("nullable").is_none());
+    assert_eq!(body["contents"][0]["parts"][0]["text"], "Name a movie");
+}
+
+#[cfg(feature = "grok")]
+#[test]
+fn test_grok_request_body() {
+    let client = rstructor::GrokClient::new("test-key").unwrap();
+    let body = client
+        .materialize_request_body::<Movie>("Name a movie")
+        .unwrap();
+
+    let schema = &body["response_format"]["json_schema"]["schema"];
+    assert_eq!(schema["additionalProperties"], false);
+    assert!(body.get("service_tier").is_none());
+}
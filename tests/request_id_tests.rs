@@ -0,0 +1,79 @@
+//! Tests that provider request ids are surfaced on results, using local stubs of the
+//! provider APIs.
+
+#![cfg(all(feature = "openai", feature = "anthropic", feature = "gemini"))]
+
+mod common;
+
+use common::{Reply, completion, serve_once};
+use rstructor::{AnthropicClient, GeminiClient, Instructor, LLMClient, OpenAIClient};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Movie {
+    title: String,
+}
+
+#[tokio::test]
+async fn test_openai_materialize_reports_request_id() {
+    let (base_url, _) = serve_once(
+        Reply::json(completion(r#"{"title": "Inception"}"#)).header("x-request-id: req_abc123"),
+    );
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o");
+
+    let result = client
+        .materialize_with_metadata::<Movie>("Describe Inception")
+        .await
+        .unwrap();
+    assert_eq!(result.data.title, "Inception");
+    assert_eq!(result.request_id.as_deref(), Some("req_abc123"));
+}
+
+#[tokio::test]
+async fn test_openai_generate_without_header_has_no_request_id() {
+    let (base_url, _) = serve_once(completion("Hello"));
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o");
+
+    let result = client.generate_with_metadata("Say hello").await.unwrap();
+    assert_eq!(result.text, "Hello");
+    assert!(result.request_id.is_none());
+}
+
+#[tokio::test]
+async fn test_anthropic_generate_reports_request_id() {
+    let (base_url, _) = serve_once(
+        Reply::json(json!({
+            "model": "claude-sonnet-4-5-20250929",
+            "content": [{ "type": "text", "text": "Hello" }],
+            "usage": { "input_tokens": 10, "output_tokens": 1 }
+        }))
+        .header("request-id: req_011CAnthropic"),
+    );
+    let client = AnthropicClient::new("test-key").unwrap().base_url(base_url);
+
+    let result = client.generate_with_metadata("Say hello").await.unwrap();
+    assert_eq!(result.request_id.as_deref(), Some("req_011CAnthropic"));
+}
+
+#[tokio::test]
+async fn test_gemini_generate_reports_response_id() {
+    let (base_url, _) = serve_once(json!({
+        "candidates": [{
+            "content": { "role": "model", "parts": [{ "text": "Hello" }] },
+            "finishReason": "STOP"
+        }],
+        "responseId": "gemini-response-1"
+    }));
+    let client = GeminiClient::new("test-key").unwrap().base_url(base_url);
+
+    let result = client.generate_with_metadata("Say hello").await.unwrap();
+    assert_eq!(result.text, "Hello");
+    assert_eq!(result.request_id.as_deref(), Some("gemini-response-1"));
+}
@@ -0,0 +1,196 @@
+//! Tests for per-call request options, using a local stub of the OpenAI API.
+
+#![cfg(feature = "openai")]
+
+mod common;
+
+use common::{completion, serve_once};
+use rstructor::{Instructor, LLMClient, OpenAIClient, RequestOptions};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Movie {
+    title: String,
+}
+
+#[tokio::test]
+async fn test_materialize_opts_overrides_client_config() {
+    let (base_url, requests) = serve_once(completion(r#"{"title": "Inception"}"#));
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o")
+        .temperature(0.0)
+        .max_tokens(100);
+
+    let options = RequestOptions::new()
+        .with_temperature(0.5)
+        .with_top_p(0.9)
+        .with_stop(["END"]);
+    let movie: Movie = client
+        .materialize_opts("Describe Inception", &options)
+        .await
+        .unwrap();
+    assert_eq!(movie.title, "Inception");
+
+    let request = requests.recv().unwrap().body;
+    assert_eq!(request["temperature"], 0.5);
+    assert_eq!(request["top_p"].as_f64().unwrap() as f32, 0.9);
+    assert_eq!(request["stop"], json!(["END"]));
+    // Fields not overridden fall back to the client configuration
+    assert_eq!(request["max_tokens"], 100);
+}
+
+#[tokio::test]
+async fn test_stop_option_replaces_client_stop_sequences() {
+    let (base_url, requests) = serve_once(completion("Hello"));
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o")
+        .stop_sequences(["END"]);
+
+    let options = RequestOptions::new().with_stop(["STOP"]);
+    client.generate_opts("Say hello", &options).await.unwrap();
+    assert_eq!(requests.recv().unwrap().body["stop"], json!(["STOP"]));
+
+    // An empty override sends no stop sequences at all
+    let (base_url, requests) = serve_once(completion("Hello"));
+    let client = client.base_url(base_url);
+    let options = RequestOptions::new().with_stop(Vec::<String>::new());
+    client.generate_opts("Say hello", &options).await.unwrap();
+    assert!(requests.recv().unwrap().body.get("stop").is_none());
+}
+
+#[tokio::test]
+async fn test_top_p_option_replaces_client_top_p() {
+    let (base_url, requests) = serve_once(completion("Hello"));
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o")
+        .top_p(0.5)
+        .frequency_penalty(0.5);
+
+    let options = RequestOptions::new().with_top_p(0.9);
+    client.generate_opts("Say hello", &options).await.unwrap();
+    let request = requests.recv().unwrap().body;
+    assert_eq!(request["top_p"].as_f64().unwrap() as f32, 0.9);
+    assert_eq!(request["frequency_penalty"], 0.5);
+}
+
+#[tokio::test]
+async fn test_top_p_option_is_clamped() {
+    let (base_url, requests) = serve_once(completion("Hello"));
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o");
+
+    let options = RequestOptions::new().with_top_p(1.5);
+    client.generate_opts("Say hello", &options).await.unwrap();
+    assert_eq!(requests.recv().unwrap().body["top_p"], 1.0);
+}
+
+#[tokio::test]
+async fn test_generate_opts_leaves_unset_fields_out() {
+    let (base_url, requests) = serve_once(completion("Hello"));
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o");
+
+    let options = RequestOptions::new().with_max_tokens(20);
+    let text = client.generate_opts("Say hello", &options).await.unwrap();
+    assert_eq!(text, "Hello");
+
+    let request = requests.recv().unwrap().body;
+    assert_eq!(request["max_tokens"], 20);
+    assert!(request.get("top_p").is_none());
+    assert!(request.get("stop").is_none());
+    assert!(request.get("service_tier").is_none());
+}
+
+#[tokio::test]
+async fn test_base_url_override_routes_single_request() {
+    let (canary_url, requests) = serve_once(completion(r#"{"title": "Inception"}"#));
+    // Nothing listens on the client's own endpoint, so only the override can succeed
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url("http://127.0.0.1:1")
+        .model("gpt-4o");
+
+    let options = RequestOptions::new().with_base_url(canary_url);
+    let movie: Movie = client
+        .materialize_opts("Name a movie", &options)
+        .await
+        .unwrap();
+    assert_eq!(movie.title, "Inception");
+    assert_eq!(requests.recv().unwrap().body["model"], "gpt-4o");
+}
+
+#[cfg(feature = "anthropic")]
+#[tokio::test]
+async fn test_anthropic_generate_opts_base_url_override() {
+    let (canary_url, requests) = serve_once(json!({
+        "model": "claude-sonnet-4-5-20250929",
+        "content": [{ "type": "text", "text": "Hello" }],
+        "usage": { "input_tokens": 10, "output_tokens": 1 }
+    }));
+    let client = rstructor::AnthropicClient::new("test-key")
+        .unwrap()
+        .base_url("http://127.0.0.1:1");
+
+    let options = RequestOptions::new().with_base_url(canary_url);
+    let text = client.generate_opts("Say hello", &options).await.unwrap();
+    assert_eq!(text, "Hello");
+    assert!(requests.recv().is_ok());
+}
+
+#[tokio::test]
+async fn test_reasoning_models_send_max_completion_tokens() {
+    let (base_url, requests) = serve_once(completion("Hello"));
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("o3-mini")
+        .max_tokens(50);
+
+    client.generate("Say hello").await.unwrap();
+
+    let request = requests.recv().unwrap().body;
+    assert_eq!(request["max_completion_tokens"], 50);
+    assert!(request.get("max_tokens").is_none());
+}
+
+#[tokio::test]
+async fn test_service_tier_is_sent_when_set() {
+    let (base_url, requests) = serve_once(completion(r#"{"title": "Inception"}"#));
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o")
+        .service_tier("flex");
+
+    let movie: Movie = client.materialize("Name a movie").await.unwrap();
+    assert_eq!(movie.title, "Inception");
+
+    let request = requests.recv().unwrap().body;
+    assert_eq!(request["service_tier"], "flex");
+}
+
+#[cfg(feature = "grok")]
+#[tokio::test]
+async fn test_grok_service_tier_is_sent_when_set() {
+    let (base_url, requests) = serve_once(completion("Hello"));
+    let client = rstructor::GrokClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .service_tier("flex");
+
+    client.generate("Say hello").await.unwrap();
+
+    let request = requests.recv().unwrap().body;
+    assert_eq!(request["service_tier"], "flex");
+}
@@ -0,0 +1,50 @@
+//! Tests for `#[llm(require_descriptions)]`. Missing descriptions are compile errors,
+//! covered by the derive's `compile_fail` doc test; these check that fully described
+//! types are unaffected.
+
+use rstructor::{Instructor, SchemaType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+#[llm(require_descriptions)]
+struct Shipment {
+    #[llm(description = "Carrier tracking number")]
+    tracking_number: String,
+    #[llm(description = "Gross weight in kilograms")]
+    weight_kg: f64,
+    // Not part of the schema, so it needs no description
+    #[llm(skip_if_none)]
+    internal_ref: Option<String>,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+#[llm(require_descriptions)]
+enum Delivery {
+    Courier {
+        #[llm(description = "Name of the courier company")]
+        company: String,
+    },
+    Pickup,
+}
+
+#[test]
+fn test_described_types_compile_unchanged() {
+    let schema_json = Shipment::schema().to_json();
+    assert_eq!(
+        schema_json["properties"]["weight_kg"]["description"],
+        "Gross weight in kilograms"
+    );
+    assert!(schema_json["properties"].get("internal_ref").is_none());
+
+    let schema_json = Delivery::schema().to_json();
+    let courier = schema_json["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find_map(|variant| variant["properties"].get("Courier"))
+        .expect("Courier variant");
+    assert_eq!(
+        courier["properties"]["company"]["description"],
+        "Name of the courier company"
+    );
+}
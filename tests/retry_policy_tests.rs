@@ -0,0 +1,82 @@
+//! Tests for `RetryPolicy` and the `retry_policy` client builder.
+
+mod common;
+
+use rstructor::RetryPolicy;
+use std::time::Duration;
+
+#[test]
+fn test_default_backs_off_exponentially() {
+    let policy = RetryPolicy::default().with_jitter(false);
+    assert_eq!(policy.backoff(0), Duration::from_millis(500));
+    assert_eq!(policy.backoff(1), Duration::from_secs(1));
+    assert_eq!(policy.backoff(2), Duration::from_secs(2));
+    assert_eq!(policy.backoff(10), Duration::from_secs(30));
+    assert_eq!(policy.backoff(usize::MAX), Duration::from_secs(30));
+}
+
+#[test]
+fn test_jitter_only_shortens_the_wait() {
+    let policy = RetryPolicy::new().with_base_delay(Duration::from_secs(4));
+    for _ in 0..100 {
+        let delay = policy.backoff(0);
+        assert!(delay <= Duration::from_secs(4));
+        assert!(delay >= Duration::from_secs(3));
+    }
+}
+
+#[test]
+fn test_fixed_policy_is_constant() {
+    let policy = RetryPolicy::fixed(Duration::from_millis(250));
+    assert!(!policy.jitter);
+    for retry in 0..5 {
+        assert_eq!(policy.backoff(retry), Duration::from_millis(250));
+    }
+}
+
+#[test]
+fn test_multiplier_below_one_shrinks_the_wait() {
+    let policy = RetryPolicy::fixed(Duration::from_secs(1))
+        .with_max_delay(Duration::from_secs(10))
+        .with_multiplier(0.5);
+    assert_eq!(policy.backoff(1), Duration::from_millis(500));
+}
+
+#[cfg(feature = "openai")]
+mod client {
+    use crate::common::{completion, serve};
+    use rstructor::{Instructor, LLMClient, OpenAIClient, RetryPolicy};
+    use serde::{Deserialize, Serialize};
+    use std::time::{Duration, Instant};
+
+    #[derive(Instructor, Serialize, Deserialize, Debug)]
+    struct Movie {
+        title: String,
+        year: u16,
+    }
+
+    #[tokio::test]
+    async fn test_client_uses_configured_policy() {
+        let (base_url, _) = serve(vec![
+            completion(r#"{"title": "Heat", "year": "1995"}"#),
+            completion(r#"{"title": "Heat", "year": "1995"}"#),
+            completion(r#"{"title": "Heat", "year": 1995}"#),
+        ]);
+        let client = OpenAIClient::new("test-key")
+            .unwrap()
+            .base_url(base_url)
+            .model("gpt-4o")
+            .retry_policy(RetryPolicy::fixed(Duration::from_millis(100)));
+
+        let start = Instant::now();
+        let result = client
+            .materialize_with_metadata::<Movie>("Describe Heat")
+            .await
+            .unwrap();
+        assert_eq!(result.attempts, 3);
+        // Two fixed waits, well short of the default 500ms + 1s
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_millis(200));
+        assert!(elapsed < Duration::from_millis(1500));
+    }
+}
@@ -0,0 +1,119 @@
+//! Tests for the `top_p` and penalty builders, checked against request previews.
+
+#![cfg(all(
+    feature = "openai",
+    feature = "anthropic",
+    feature = "gemini",
+    feature = "grok",
+    feature = "mistral"
+))]
+
+use rstructor::{
+    AnthropicClient, GeminiClient, GrokClient, Instructor, LLMClient, MistralClient, OpenAIClient,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Title {
+    title: String,
+}
+
+fn body(client: &impl LLMClient) -> Value {
+    client
+        .materialize_request_body::<Title>("Name a movie")
+        .unwrap()
+}
+
+fn float(value: &Value) -> f32 {
+    value.as_f64().unwrap() as f32
+}
+
+#[test]
+fn test_openai_compatible_clients_send_top_p_and_penalties() {
+    let requests = [
+        body(
+            &OpenAIClient::new("test-key")
+                .unwrap()
+                .model("gpt-4o")
+                .top_p(0.9)
+                .frequency_penalty(0.5)
+                .presence_penalty(-0.25),
+        ),
+        body(
+            &GrokClient::new("test-key")
+                .unwrap()
+                .top_p(0.9)
+                .frequency_penalty(0.5)
+                .presence_penalty(-0.25),
+        ),
+        body(
+            &MistralClient::new("test-key")
+                .unwrap()
+                .top_p(0.9)
+                .frequency_penalty(0.5)
+                .presence_penalty(-0.25),
+        ),
+    ];
+    for request in requests {
+        assert_eq!(float(&request["top_p"]), 0.9);
+        assert_eq!(float(&request["frequency_penalty"]), 0.5);
+        assert_eq!(float(&request["presence_penalty"]), -0.25);
+    }
+}
+
+#[test]
+fn test_unset_parameters_are_left_out() {
+    let request = body(&OpenAIClient::new("test-key").unwrap().model("gpt-4o"));
+    assert!(request.get("top_p").is_none());
+    assert!(request.get("frequency_penalty").is_none());
+    assert!(request.get("presence_penalty").is_none());
+}
+
+#[test]
+fn test_values_are_clamped_to_documented_ranges() {
+    let request = body(
+        &OpenAIClient::new("test-key")
+            .unwrap()
+            .model("gpt-4o")
+            .top_p(1.5)
+            .frequency_penalty(3.0)
+            .presence_penalty(-5.0),
+    );
+    assert_eq!(float(&request["top_p"]), 1.0);
+    assert_eq!(float(&request["frequency_penalty"]), 2.0);
+    assert_eq!(float(&request["presence_penalty"]), -2.0);
+
+    let request = body(&GrokClient::new("test-key").unwrap().top_p(-0.1));
+    assert_eq!(float(&request["top_p"]), 0.0);
+}
+
+#[test]
+fn test_gemini_sends_top_p_in_generation_config() {
+    let request = body(&GeminiClient::new("test-key").unwrap().top_p(0.8));
+    assert_eq!(float(&request["generation_config"]["topP"]), 0.8);
+    assert!(request["generation_config"].get("top_p").is_none());
+}
+
+#[test]
+fn test_anthropic_sends_top_p() {
+    let request = body(&AnthropicClient::new("test-key").unwrap().top_p(0.8));
+    assert_eq!(float(&request["top_p"]), 0.8);
+}
+
+#[test]
+fn test_reasoning_models_leave_out_sampling_params() {
+    for model in ["o3-mini", "gpt-5.2"] {
+        let request = body(
+            &OpenAIClient::new("test-key")
+                .unwrap()
+                .model(model)
+                .top_p(0.9)
+                .frequency_penalty(0.5)
+                .presence_penalty(0.5),
+        );
+        assert!(request.get("top_p").is_none(), "{model}");
+        assert!(request.get("frequency_penalty").is_none(), "{model}");
+        assert!(request.get("presence_penalty").is_none(), "{model}");
+    }
+}
@@ -0,0 +1,59 @@
+//! Tests for per-call schema overrides, using a local stub of the OpenAI API.
+
+#![cfg(feature = "openai")]
+
+mod common;
+
+use common::{completion, serve_once};
+use rstructor::{Instructor, LLMClient, OpenAIClient, Schema};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Movie {
+    title: String,
+}
+
+#[tokio::test]
+async fn test_materialize_with_schema_sends_override() {
+    let (base_url, requests) = serve_once(completion(r#"{"title": "Inception"}"#));
+    let client = OpenAIClient::new("test-key").unwrap().base_url(base_url);
+
+    let schema = Schema::new(json!({
+        "type": "object",
+        "properties": {
+            "title": { "type": "string", "description": "Original release title" }
+        },
+        "required": ["title"]
+    }));
+    let movie: Movie = client
+        .materialize_with_schema("Describe Inception", schema)
+        .await
+        .unwrap();
+    assert_eq!(movie.title, "Inception");
+
+    let request = requests.recv().unwrap().body;
+    let sent = &request["response_format"]["json_schema"]["schema"];
+    assert_eq!(
+        sent["properties"]["title"]["description"],
+        "Original release title"
+    );
+}
+
+#[tokio::test]
+async fn test_materialize_with_schema_still_validates_into_t() {
+    let (base_url, _requests) = serve_once(completion(r#"{"name": "Inception"}"#));
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .no_retries();
+
+    let schema = Schema::new(json!({
+        "type": "object",
+        "properties": { "name": { "type": "string" } }
+    }));
+    let result = client
+        .materialize_with_schema::<Movie>("Describe Inception", schema)
+        .await;
+    assert!(result.is_err());
+}
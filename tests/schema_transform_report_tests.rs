@@ -0,0 +1,131 @@
+//! Tests for `Schema::prepare_for_with_report`, which lists the changes made while
+//! preparing a schema for a provider.
+
+use rstructor::{Instructor, Schema, SchemaTarget, SchemaTransformKind, SchemaType};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Listing {
+    #[llm(description = "Street address", example = "1 Main St")]
+    address: String,
+    nickname: Option<String>,
+    coordinates: (f64, f64, String),
+    scores: HashMap<String, u8>,
+}
+
+fn kinds_at(transforms: &[rstructor::SchemaTransform], path: &str) -> Vec<SchemaTransformKind> {
+    transforms
+        .iter()
+        .filter(|t| t.path == path)
+        .map(|t| t.kind.clone())
+        .collect()
+}
+
+#[test]
+fn test_report_matches_prepare_for() {
+    let schema = Listing::schema();
+    for target in [
+        SchemaTarget::OpenAIStrict,
+        SchemaTarget::Gemini,
+        SchemaTarget::Raw,
+    ] {
+        let (prepared, _) = schema.prepare_for_with_report(target);
+        assert_eq!(prepared, schema.prepare_for(target));
+    }
+    assert!(
+        schema
+            .prepare_for_with_report(SchemaTarget::Raw)
+            .1
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_gemini_report() {
+    let (_, transforms) = Listing::schema().prepare_for_with_report(SchemaTarget::Gemini);
+
+    assert!(
+        kinds_at(&transforms, "#").contains(&SchemaTransformKind::KeywordStripped {
+            keyword: "title".to_string()
+        })
+    );
+    assert_eq!(
+        kinds_at(&transforms, "#/properties/address"),
+        [SchemaTransformKind::KeywordStripped {
+            keyword: "example".to_string()
+        }]
+    );
+    assert_eq!(
+        kinds_at(&transforms, "#/properties/nickname"),
+        [SchemaTransformKind::MadeNullable]
+    );
+    assert!(
+        kinds_at(&transforms, "#/properties/coordinates")
+            .contains(&SchemaTransformKind::TupleFlattened { len: 3 })
+    );
+    assert!(
+        kinds_at(&transforms, "#/properties/scores")
+            .iter()
+            .any(|kind| matches!(
+                kind,
+                SchemaTransformKind::MapRewritten { keys } if keys.len() == 3
+            ))
+    );
+
+    let rendered: Vec<String> = transforms.iter().map(|t| t.to_string()).collect();
+    assert!(rendered.contains(&"#/properties/nickname: made nullable".to_string()));
+}
+
+#[test]
+fn test_strict_report() {
+    let (_, transforms) = Listing::schema().prepare_for_with_report(SchemaTarget::OpenAIStrict);
+
+    assert_eq!(
+        kinds_at(&transforms, "#"),
+        [SchemaTransformKind::AdditionalPropertiesClosed]
+    );
+    assert_eq!(
+        kinds_at(&transforms, "#/properties/nickname"),
+        [SchemaTransformKind::MadeNullable]
+    );
+    // Maps keep their value schema rather than being closed
+    assert!(kinds_at(&transforms, "#/properties/scores").is_empty());
+}
+
+#[test]
+fn test_gemini_report_lists_inlined_and_truncated_refs() {
+    let schema = Schema::new(json!({
+        "$ref": "#/$defs/Node",
+        "$defs": {
+            "Node": {
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "child": { "$ref": "#/$defs/Node" }
+                },
+                "required": ["name"]
+            }
+        }
+    }));
+    let (_, transforms) = schema.prepare_for_with_report(SchemaTarget::Gemini);
+
+    assert!(
+        kinds_at(&transforms, "#").contains(&SchemaTransformKind::RefInlined {
+            reference: "#/$defs/Node".to_string()
+        })
+    );
+    assert!(kinds_at(&transforms, "#/properties/child").contains(
+        &SchemaTransformKind::RefInlined {
+            reference: "#/$defs/Node".to_string()
+        }
+    ));
+    // Past the depth limit the remaining reference is dropped
+    let deepest = format!("#{}", "/properties/child".repeat(4));
+    assert!(
+        kinds_at(&transforms, &deepest).contains(&SchemaTransformKind::KeywordStripped {
+            keyword: "$ref".to_string()
+        })
+    );
+}
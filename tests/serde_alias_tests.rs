@@ -0,0 +1,82 @@
+//! Tests for `#[llm(document_aliases)]`, which lists `#[serde(alias)]` names in field
+//! descriptions.
+
+use rstructor::{Instructor, SchemaType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+#[llm(document_aliases)]
+struct Contact {
+    #[llm(description = "Full name of the person")]
+    #[serde(alias = "full_name", alias = "fullName")]
+    name: String,
+
+    #[serde(alias = "mail")]
+    email: Option<String>,
+
+    phone: String,
+}
+
+// Aliases are only documented when asked for
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct LegacyContact {
+    #[llm(description = "Full name of the person")]
+    #[serde(alias = "full_name")]
+    name: String,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+#[llm(document_aliases)]
+enum Lookup {
+    ByEmail {
+        #[serde(alias = "mail")]
+        #[llm(description = "Email address to search for")]
+        email: String,
+    },
+    ById(u64),
+}
+
+#[test]
+fn test_aliases_appended_to_descriptions() {
+    let schema_json = Contact::schema().to_json();
+    let props = &schema_json["properties"];
+
+    assert_eq!(
+        props["name"]["description"],
+        "Full name of the person (also accepts: full_name, fullName)"
+    );
+    assert_eq!(props["email"]["description"], "Also accepts: mail");
+    assert!(props["phone"].get("description").is_none());
+
+    // The canonical name is still the only property
+    assert!(props.get("full_name").is_none());
+}
+
+#[test]
+fn test_aliases_not_documented_by_default() {
+    let schema_json = LegacyContact::schema().to_json();
+    assert_eq!(
+        schema_json["properties"]["name"]["description"],
+        "Full name of the person"
+    );
+}
+
+#[test]
+fn test_aliases_in_enum_variant_fields() {
+    let schema_json = Lookup::schema().to_json();
+    let by_email = &schema_json["oneOf"][0]["properties"]["ByEmail"];
+    assert_eq!(
+        by_email["properties"]["email"]["description"],
+        "Email address to search for (also accepts: mail)"
+    );
+}
+
+#[test]
+fn test_aliases_still_deserialize() {
+    let contact: Contact = serde_json::from_str(
+        r#"{"fullName": "Ada Lovelace", "mail": "ada@example.com", "phone": "1"}"#,
+    )
+    .unwrap();
+    assert_eq!(contact.name, "Ada Lovelace");
+    assert_eq!(contact.email.as_deref(), Some("ada@example.com"));
+}
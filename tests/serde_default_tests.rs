@@ -0,0 +1,129 @@
+//! Tests for `#[serde(default)]` fields, which are listed in the schema but not required.
+
+#[cfg(feature = "openai")]
+mod common;
+
+use rstructor::{Instructor, InstructorExt, SchemaType};
+use serde::{Deserialize, Serialize};
+
+fn default_currency() -> String {
+    "USD".to_string()
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Invoice {
+    number: String,
+
+    #[serde(default)]
+    tags: Vec<String>,
+
+    #[serde(default = "default_currency")]
+    currency: String,
+
+    #[llm(description = "Line count")]
+    #[serde(default, rename = "lineCount")]
+    line_count: u32,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+enum Payment {
+    Card {
+        last4: String,
+        #[serde(default)]
+        saved: bool,
+    },
+    Cash,
+}
+
+fn required(schema: &serde_json::Value) -> Vec<&str> {
+    schema["required"]
+        .as_array()
+        .map(|required| required.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_default_fields_listed_but_not_required() {
+    let schema_json = Invoice::schema().to_json();
+    let props = schema_json["properties"].as_object().unwrap();
+
+    assert!(props.contains_key("tags"));
+    assert!(props.contains_key("currency"));
+    // Other serde attributes on the same field still apply
+    assert_eq!(props["lineCount"]["description"], "Line count");
+    assert_eq!(required(&schema_json), vec!["number"]);
+}
+
+#[test]
+fn test_default_fields_in_enum_variants() {
+    let schema_json = Payment::schema().to_json();
+    let card = schema_json["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find_map(|variant| variant["properties"].get("Card"))
+        .expect("Card variant");
+
+    assert!(card["properties"].get("saved").is_some());
+    assert_eq!(required(card), vec!["last4"]);
+}
+
+#[cfg(feature = "openai")]
+#[tokio::test]
+async fn test_strict_null_for_default_field_round_trips() {
+    use rstructor::{LLMClient, OpenAIClient, SchemaTarget};
+
+    // Strict mode requires every key, so the model may answer `null` for a defaulted one
+    let (base_url, requests) = common::serve_once(common::completion(
+        r#"{"number": "INV-1", "tags": null, "currency": null, "lineCount": null}"#,
+    ));
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .no_retries();
+
+    let invoice: Invoice = client.materialize("Read the invoice").await.unwrap();
+    assert_eq!(invoice.number, "INV-1");
+    assert!(invoice.tags.is_empty());
+    assert_eq!(invoice.currency, "USD");
+    assert_eq!(invoice.line_count, 0);
+
+    let request = requests.recv().unwrap().body;
+    let schema = &request["response_format"]["json_schema"]["schema"];
+    assert_eq!(required(schema).len(), 4);
+    assert_eq!(
+        schema,
+        &Invoice::schema().prepare_for(SchemaTarget::OpenAIStrict)
+    );
+}
+
+#[test]
+fn test_missing_default_fields_parse() {
+    let invoice = Invoice::from_json(r#"{"number": "INV-1"}"#).unwrap();
+    assert!(invoice.tags.is_empty());
+    assert_eq!(invoice.currency, "USD");
+    assert_eq!(invoice.line_count, 0);
+}
+
+#[cfg(feature = "openai")]
+#[tokio::test]
+async fn test_strict_null_for_default_variant_field_parses() {
+    use rstructor::{LLMClient, OpenAIClient};
+
+    let (base_url, _requests) = common::serve_once(common::completion(
+        r#"{"value": {"Card": {"last4": "4242", "saved": null}}}"#,
+    ));
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .no_retries();
+
+    let payment: Payment = client.materialize("Pay by card").await.unwrap();
+    match payment {
+        Payment::Card { last4, saved } => {
+            assert_eq!(last4, "4242");
+            assert!(!saved);
+        }
+        Payment::Cash => panic!("expected a card payment"),
+    }
+}
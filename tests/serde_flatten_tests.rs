@@ -0,0 +1,114 @@
+//! Tests for `#[serde(flatten)]`, which inlines a nested type's properties.
+
+use std::collections::HashMap;
+
+use rstructor::{Instructor, InstructorExt, SchemaTarget, SchemaType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+struct Metadata {
+    #[llm(description = "Who made the request")]
+    requested_by: String,
+    trace_id: Option<String>,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+struct Audit {
+    created_at: String,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug, PartialEq)]
+struct CreateUser {
+    name: String,
+    #[serde(flatten)]
+    metadata: Metadata,
+    #[serde(flatten)]
+    audit: Option<Audit>,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Labels {
+    kind: String,
+    #[serde(flatten)]
+    extra: HashMap<String, String>,
+}
+
+fn required(schema: &serde_json::Value) -> Vec<&str> {
+    schema["required"]
+        .as_array()
+        .map(|required| required.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_flattened_properties_inlined() {
+    let schema_json = CreateUser::schema().to_json();
+    let props = schema_json["properties"].as_object().unwrap();
+
+    let keys: Vec<&str> = props.keys().map(String::as_str).collect();
+    assert_eq!(keys, vec!["name", "requested_by", "trace_id", "created_at"]);
+    assert!(!props.contains_key("metadata"));
+    assert_eq!(props["requested_by"]["description"], "Who made the request");
+}
+
+#[test]
+fn test_flattened_required_merged() {
+    let schema_json = CreateUser::schema().to_json();
+    // Fields of an optional flattened type aren't required
+    assert_eq!(required(&schema_json), vec!["name", "requested_by"]);
+}
+
+#[test]
+fn test_flattened_map_allows_extra_keys() {
+    let schema_json = Labels::schema().to_json();
+    assert!(schema_json["properties"].get("extra").is_none());
+    assert_eq!(schema_json["additionalProperties"]["type"], "string");
+    assert_eq!(required(&schema_json), vec!["kind"]);
+}
+
+#[test]
+fn test_flattened_schema_matches_serde_shape() {
+    let user = CreateUser::from_json(
+        r#"{"name": "Ada", "requested_by": "admin", "trace_id": null, "created_at": "2024-01-01"}"#,
+    )
+    .unwrap();
+    assert_eq!(user.metadata.requested_by, "admin");
+    assert_eq!(user.audit.unwrap().created_at, "2024-01-01");
+
+    let strict = CreateUser::schema().prepare_for(SchemaTarget::OpenAIStrict);
+    assert!(strict["properties"].get("requested_by").is_some());
+    assert!(strict["properties"].get("metadata").is_none());
+}
+
+#[test]
+fn test_property_names_exposed() {
+    assert_eq!(Metadata::PROPERTY_NAMES, &["requested_by", "trace_id"]);
+    // Flattened fields contribute the names they inline
+    assert_eq!(
+        CreateUser::PROPERTY_NAMES,
+        &["name", "requested_by", "trace_id", "created_at"]
+    );
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Envelope {
+    id: String,
+    #[serde(flatten)]
+    request: CreateUser,
+}
+
+#[test]
+fn test_nested_flatten_properties_inlined() {
+    let schema_json = Envelope::schema().to_json();
+    let keys: Vec<&str> = schema_json["properties"]
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    assert_eq!(
+        keys,
+        vec!["id", "name", "requested_by", "trace_id", "created_at"]
+    );
+    assert_eq!(Envelope::PROPERTY_NAMES.len(), 5);
+}
@@ -276,6 +276,182 @@ mod serde_rename_tests {
         assert!(enum_values.iter().any(|v| v == "VERY_HIGH"));
     }
 
+    // =====================================================================
+    // rename_all parity between structs and simple enums
+    // =====================================================================
+
+    /// Property names in the schema, sorted
+    fn schema_properties<T: SchemaType>() -> Vec<String> {
+        let mut names: Vec<String> = T::schema().to_json()["properties"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Keys serde actually produces for a value, sorted
+    fn serde_keys<T: Serialize>(value: &T) -> Vec<String> {
+        let mut names: Vec<String> = serde_json::to_value(value)
+            .unwrap()
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Enum values in the schema, in declaration order
+    fn schema_enum_values<T: SchemaType>() -> Vec<String> {
+        T::schema().to_json()["enum"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_str().unwrap().to_string())
+            .collect()
+    }
+
+    /// Values serde actually produces for each variant
+    fn serde_values<T: Serialize>(variants: &[T]) -> Vec<String> {
+        variants
+            .iter()
+            .map(|v| {
+                serde_json::to_value(v)
+                    .unwrap()
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[derive(Instructor, Serialize, Deserialize, Debug, Default)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    struct ScreamingSnakeStruct {
+        first_name: String,
+        account_id: u32,
+        is_active: bool,
+    }
+
+    #[derive(Instructor, Serialize, Deserialize, Debug)]
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    enum ScreamingSnakeStatus {
+        InProgress,
+        OnHold,
+        Done,
+    }
+
+    #[test]
+    fn test_rename_all_screaming_snake_case_matches_serde() {
+        assert_eq!(
+            schema_properties::<ScreamingSnakeStruct>(),
+            vec!["ACCOUNT_ID", "FIRST_NAME", "IS_ACTIVE"]
+        );
+        assert_eq!(
+            schema_properties::<ScreamingSnakeStruct>(),
+            serde_keys(&ScreamingSnakeStruct::default())
+        );
+
+        let variants = [
+            ScreamingSnakeStatus::InProgress,
+            ScreamingSnakeStatus::OnHold,
+            ScreamingSnakeStatus::Done,
+        ];
+        assert_eq!(
+            schema_enum_values::<ScreamingSnakeStatus>(),
+            vec!["IN_PROGRESS", "ON_HOLD", "DONE"]
+        );
+        assert_eq!(
+            schema_enum_values::<ScreamingSnakeStatus>(),
+            serde_values(&variants)
+        );
+    }
+
+    #[derive(Instructor, Serialize, Deserialize, Debug, Default)]
+    #[serde(rename_all = "kebab-case")]
+    struct KebabStruct {
+        first_name: String,
+        account_id: u32,
+        is_active: bool,
+    }
+
+    #[derive(Instructor, Serialize, Deserialize, Debug)]
+    #[serde(rename_all = "kebab-case")]
+    enum KebabStatus {
+        InProgress,
+        OnHold,
+        Done,
+    }
+
+    #[test]
+    fn test_rename_all_kebab_case_matches_serde() {
+        assert_eq!(
+            schema_properties::<KebabStruct>(),
+            vec!["account-id", "first-name", "is-active"]
+        );
+        assert_eq!(
+            schema_properties::<KebabStruct>(),
+            serde_keys(&KebabStruct::default())
+        );
+
+        let variants = [
+            KebabStatus::InProgress,
+            KebabStatus::OnHold,
+            KebabStatus::Done,
+        ];
+        assert_eq!(
+            schema_enum_values::<KebabStatus>(),
+            vec!["in-progress", "on-hold", "done"]
+        );
+        assert_eq!(schema_enum_values::<KebabStatus>(), serde_values(&variants));
+    }
+
+    #[derive(Instructor, Serialize, Deserialize, Debug, Default)]
+    #[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+    struct ScreamingKebabStruct {
+        first_name: String,
+        account_id: u32,
+        is_active: bool,
+    }
+
+    #[derive(Instructor, Serialize, Deserialize, Debug)]
+    #[serde(rename_all = "SCREAMING-KEBAB-CASE")]
+    enum ScreamingKebabStatus {
+        InProgress,
+        OnHold,
+        Done,
+    }
+
+    #[test]
+    fn test_rename_all_screaming_kebab_case_matches_serde() {
+        assert_eq!(
+            schema_properties::<ScreamingKebabStruct>(),
+            vec!["ACCOUNT-ID", "FIRST-NAME", "IS-ACTIVE"]
+        );
+        assert_eq!(
+            schema_properties::<ScreamingKebabStruct>(),
+            serde_keys(&ScreamingKebabStruct::default())
+        );
+
+        let variants = [
+            ScreamingKebabStatus::InProgress,
+            ScreamingKebabStatus::OnHold,
+            ScreamingKebabStatus::Done,
+        ];
+        assert_eq!(
+            schema_enum_values::<ScreamingKebabStatus>(),
+            vec!["IN-PROGRESS", "ON-HOLD", "DONE"]
+        );
+        assert_eq!(
+            schema_enum_values::<ScreamingKebabStatus>(),
+            serde_values(&variants)
+        );
+    }
+
     // =====================================================================
     // Complex enum (with data) rename tests
     // =====================================================================
@@ -0,0 +1,109 @@
+//! Tests for `#[serde(skip)]`, `#[serde(skip_deserializing)]` and
+//! `#[serde(skip_serializing[_if])]` in schema generation.
+
+use rstructor::{Instructor, InstructorExt, SchemaTarget, SchemaType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+#[llm(require_descriptions)]
+struct Order {
+    #[llm(description = "Order number")]
+    number: String,
+
+    // Skipped fields need no description
+    #[serde(skip)]
+    cache_key: u64,
+
+    #[serde(skip_deserializing)]
+    computed_total: f64,
+
+    #[llm(description = "Free-form notes")]
+    #[serde(skip_serializing_if = "String::is_empty", default)]
+    notes: String,
+
+    #[llm(description = "Gift message")]
+    #[serde(skip_serializing, default)]
+    gift_message: String,
+
+    #[llm(description = "Coupon code")]
+    coupon: Option<String>,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+enum Event {
+    Placed {
+        id: String,
+        #[serde(skip)]
+        internal: bool,
+        #[serde(skip_serializing_if = "Vec::is_empty", default)]
+        items: Vec<String>,
+    },
+    Cancelled,
+}
+
+fn required(schema: &serde_json::Value) -> Vec<&str> {
+    schema["required"]
+        .as_array()
+        .map(|required| required.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_skipped_fields_left_out_of_schema() {
+    let schema_json = Order::schema().to_json();
+    let props = schema_json["properties"].as_object().unwrap();
+
+    assert!(!props.contains_key("cache_key"));
+    assert!(!props.contains_key("computed_total"));
+    assert!(!required(&schema_json).contains(&"cache_key"));
+    assert!(!required(&schema_json).contains(&"computed_total"));
+    assert!(props.contains_key("number"));
+}
+
+#[test]
+fn test_skip_serializing_fields_are_optional() {
+    let schema_json = Order::schema().to_json();
+    let props = schema_json["properties"].as_object().unwrap();
+
+    assert!(props.contains_key("notes"));
+    assert!(props.contains_key("gift_message"));
+    assert_eq!(required(&schema_json), vec!["number"]);
+}
+
+#[test]
+fn test_skipped_fields_in_enum_variants() {
+    let schema_json = Event::schema().to_json();
+    let placed = schema_json["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find_map(|variant| variant["properties"].get("Placed"))
+        .expect("Placed variant");
+
+    assert!(placed["properties"].get("internal").is_none());
+    assert!(placed["properties"].get("items").is_some());
+    assert_eq!(required(placed), vec!["id"]);
+}
+
+#[test]
+fn test_strict_schema_excludes_skipped_fields() {
+    let schema = Order::schema().prepare_for(SchemaTarget::OpenAIStrict);
+    let props = schema["properties"].as_object().unwrap();
+    assert!(!props.contains_key("cache_key"));
+    // Strict mode requires every listed property, so the response always deserializes
+    assert_eq!(
+        required(&schema).len(),
+        props.len(),
+        "{}",
+        serde_json::to_string_pretty(&schema).unwrap()
+    );
+}
+
+#[test]
+fn test_response_without_skipped_fields_parses() {
+    let order = Order::from_json(r#"{"number": "A-1", "notes": "", "coupon": null}"#).unwrap();
+    assert_eq!(order.number, "A-1");
+    assert_eq!(order.cache_key, 0);
+    assert_eq!(order.computed_total, 0.0);
+    assert!(order.gift_message.is_empty());
+}
@@ -0,0 +1,68 @@
+//! Tests for `#[llm(skip_if_none)]`, which leaves optional fields out of the schema.
+
+use rstructor::{Instructor, SchemaTarget, SchemaType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct Invoice {
+    number: String,
+    due_date: Option<String>,
+    #[llm(skip_if_none, description = "Only filled in by the billing team")]
+    internal_notes: Option<String>,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+enum Payment {
+    Card {
+        last_four: String,
+        #[llm(skip_if_none)]
+        issuer_reference: Option<String>,
+    },
+    Cash,
+}
+
+#[test]
+fn test_skipped_field_is_absent_from_schema() {
+    let schema_json = Invoice::schema().to_json();
+    let props = schema_json["properties"].as_object().unwrap();
+    assert!(props.contains_key("number"));
+    assert!(props.contains_key("dueDate"));
+    assert!(!props.contains_key("internalNotes"));
+    assert_eq!(schema_json["required"], serde_json::json!(["number"]));
+
+    // Strict preparation doesn't bring it back
+    let strict = Invoice::schema().prepare_for(SchemaTarget::OpenAIStrict);
+    assert!(strict["properties"].get("internalNotes").is_none());
+    assert_eq!(strict["required"], serde_json::json!(["number", "dueDate"]));
+}
+
+#[test]
+fn test_skipped_field_deserializes_as_none() {
+    let invoice: Invoice = serde_json::from_str(r#"{"number": "INV-7"}"#).unwrap();
+    assert_eq!(invoice.number, "INV-7");
+    assert!(invoice.internal_notes.is_none());
+}
+
+#[test]
+fn test_skipped_field_in_enum_variant() {
+    let schema_json = Payment::schema().to_json();
+    let card = schema_json["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|variant| variant["properties"].get("Card").is_some())
+        .expect("Card variant");
+    let fields = &card["properties"]["Card"]["properties"];
+    assert!(fields.get("last_four").is_some());
+    assert!(fields.get("issuer_reference").is_none());
+
+    let payment: Payment = serde_json::from_str(r#"{"Card": {"last_four": "4242"}}"#).unwrap();
+    assert!(matches!(
+        payment,
+        Payment::Card {
+            issuer_reference: None,
+            ..
+        }
+    ));
+}
@@ -0,0 +1,89 @@
+//! Tests for the `stop_sequences` client builder, checked against request previews.
+
+#![cfg(all(
+    feature = "openai",
+    feature = "anthropic",
+    feature = "gemini",
+    feature = "grok",
+    feature = "mistral",
+    feature = "ollama"
+))]
+
+use rstructor::{
+    AnthropicClient, GeminiClient, GrokClient, Instructor, LLMClient, MistralClient, OllamaClient,
+    OpenAIClient,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Title {
+    title: String,
+}
+
+fn body(client: &impl LLMClient) -> Value {
+    client
+        .materialize_request_body::<Title>("Name a movie")
+        .unwrap()
+}
+
+#[test]
+fn test_stop_sequences_use_each_provider_field() {
+    let stop = ["END", "\n\n"];
+    let expected = json!(["END", "\n\n"]);
+
+    let openai = OpenAIClient::new("test-key")
+        .unwrap()
+        .model("gpt-4o")
+        .stop_sequences(stop);
+    assert_eq!(body(&openai)["stop"], expected);
+
+    let grok = GrokClient::new("test-key").unwrap().stop_sequences(stop);
+    assert_eq!(body(&grok)["stop"], expected);
+
+    let mistral = MistralClient::new("test-key").unwrap().stop_sequences(stop);
+    assert_eq!(body(&mistral)["stop"], expected);
+
+    let ollama = OllamaClient::new().stop_sequences(stop);
+    assert_eq!(body(&ollama)["options"]["stop"], expected);
+
+    let anthropic = AnthropicClient::new("test-key")
+        .unwrap()
+        .stop_sequences(stop);
+    assert_eq!(body(&anthropic)["stop_sequences"], expected);
+
+    let gemini = GeminiClient::new("test-key").unwrap().stop_sequences(stop);
+    assert_eq!(
+        body(&gemini)["generation_config"]["stopSequences"],
+        expected
+    );
+}
+
+#[test]
+fn test_no_stop_sequences_are_left_out() {
+    let openai = body(&OpenAIClient::new("test-key").unwrap());
+    assert!(openai.get("stop").is_none());
+
+    let anthropic = body(&AnthropicClient::new("test-key").unwrap());
+    assert!(anthropic.get("stop_sequences").is_none());
+
+    let gemini = body(&GeminiClient::new("test-key").unwrap());
+    assert!(gemini["generation_config"].get("stopSequences").is_none());
+
+    // Clearing them again drops the field too
+    let ollama = OllamaClient::new()
+        .stop_sequences(["END"])
+        .stop_sequences(Vec::<String>::new());
+    assert!(body(&ollama)["options"].get("stop").is_none());
+}
+
+#[test]
+fn test_openai_reasoning_models_leave_out_stop() {
+    for model in ["o3", "o4-mini", "gpt-5.2"] {
+        let client = OpenAIClient::new("test-key")
+            .unwrap()
+            .model(model)
+            .stop_sequences(["END"]);
+        assert!(body(&client).get("stop").is_none(), "{model}");
+    }
+}
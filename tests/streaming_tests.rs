@@ -0,0 +1,113 @@
+//! Tests for `JsonPathWatcher`, which fires callbacks as streamed JSON values complete.
+
+use rstructor::streaming::JsonPathWatcher;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex};
+
+type Log = Arc<Mutex<Vec<(String, Value)>>>;
+
+/// Watch `paths`, logging each firing with its pattern.
+fn watcher(paths: &[&str]) -> (JsonPathWatcher<'static>, Log) {
+    let log: Log = Arc::default();
+    let mut watcher = JsonPathWatcher::new();
+    for path in paths {
+        let log = log.clone();
+        let path = path.to_string();
+        watcher = watcher.on(path.clone(), move |value| {
+            log.lock().unwrap().push((path.clone(), value.clone()))
+        });
+    }
+    (watcher, log)
+}
+
+const DOCUMENT: &str = r#"{
+    "title": "Inception",
+    "year": 2010,
+    "cast": [
+        {"name": "Leonardo DiCaprio", "role": "Cobb"},
+        {"name": "Elliot Page", "role": "Ariadne"}
+    ],
+    "summary": "A thief who steals corporate secrets \"through\" dreams."
+}"#;
+
+#[test]
+fn test_fires_each_path_as_soon_as_it_completes() {
+    let (mut watcher, log) = watcher(&["$.title", "$.year", "$.summary"]);
+
+    watcher.feed(r#"{"title": "Incep"#);
+    assert!(log.lock().unwrap().is_empty());
+    watcher.feed(r#"tion", "year": 20"#);
+    assert_eq!(log.lock().unwrap().len(), 1);
+    // A number isn't complete until something follows it
+    watcher.feed("10");
+    assert_eq!(log.lock().unwrap().len(), 1);
+    watcher.feed(r#", "summary": "Dreams"#);
+    assert_eq!(log.lock().unwrap()[1], ("$.year".to_string(), json!(2010)));
+    watcher.feed(r#""}"#);
+
+    let log = log.lock().unwrap();
+    assert_eq!(log[0], ("$.title".to_string(), json!("Inception")));
+    assert_eq!(log[2], ("$.summary".to_string(), json!("Dreams")));
+    assert!(watcher.is_complete());
+}
+
+#[test]
+fn test_wildcards_nested_values_and_whole_document() {
+    let (mut watcher, log) = watcher(&["$.cast[*].name", "$.cast[1]", "$.cast", "$"]);
+    // Feed one character at a time to exercise every split point
+    for c in DOCUMENT.chars() {
+        watcher.feed(&c.to_string());
+    }
+
+    let log = log.lock().unwrap();
+    let order: Vec<&str> = log.iter().map(|(path, _)| path.as_str()).collect();
+    assert_eq!(
+        order,
+        [
+            "$.cast[*].name",
+            "$.cast[*].name",
+            "$.cast[1]",
+            "$.cast",
+            "$"
+        ]
+    );
+    assert_eq!(log[0].1, "Leonardo DiCaprio");
+    assert_eq!(log[1].1, "Elliot Page");
+    assert_eq!(log[2].1, json!({"name": "Elliot Page", "role": "Ariadne"}));
+    assert_eq!(log[3].1.as_array().unwrap().len(), 2);
+    assert_eq!(
+        log[4].1["summary"],
+        "A thief who steals corporate secrets \"through\" dreams."
+    );
+}
+
+#[test]
+fn test_skips_text_around_the_document() {
+    let (mut watcher, log) = watcher(&["$.title"]);
+    watcher.feed("Here you go:\n```json\n{\"title\": \"Up\"}\n```\n{\"title\": \"Ignored\"}");
+
+    assert_eq!(*log.lock().unwrap(), [("$.title".to_string(), json!("Up"))]);
+    assert!(watcher.is_complete());
+}
+
+#[derive(Deserialize, Debug)]
+struct Movie {
+    title: String,
+    year: u16,
+}
+
+#[test]
+fn test_finish_deserializes_the_document() {
+    let mut watcher = JsonPathWatcher::new();
+    watcher.feed("```json\n{\"title\": \"Heat\", ");
+    watcher.feed("\"year\": 1995}\n```");
+    let movie: Movie = watcher.finish().unwrap();
+    assert_eq!(movie.title, "Heat");
+    assert_eq!(movie.year, 1995);
+
+    let mut truncated = JsonPathWatcher::new();
+    truncated.feed(r#"{"title": "Heat""#);
+    assert!(!truncated.is_complete());
+    assert!(truncated.finish::<Movie>().is_err());
+}
@@ -0,0 +1,155 @@
+//! Tests that optional fields stay nullable when schemas are prepared for strict mode.
+
+use rstructor::{Instructor, SchemaTarget, SchemaType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+enum Event {
+    Meeting {
+        title: String,
+        location: Option<String>,
+    },
+    Reminder(String),
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Calendar {
+    owner: String,
+    timezone: Option<String>,
+    next_event: Event,
+}
+
+fn meeting_fields(schema: &serde_json::Value) -> &serde_json::Value {
+    schema["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|variant| variant["properties"].get("Meeting").is_some())
+        .expect("Meeting variant")
+        .get("properties")
+        .and_then(|props| props.get("Meeting"))
+        .unwrap()
+}
+
+#[test]
+fn test_enum_variant_optional_field_is_nullable_under_strict() {
+    let strict = Event::schema().prepare_for(SchemaTarget::OpenAIStrict);
+    let meeting = meeting_fields(&strict);
+
+    let required = meeting["required"].as_array().unwrap();
+    assert!(required.contains(&serde_json::json!("title")));
+    assert!(required.contains(&serde_json::json!("location")));
+    assert_eq!(meeting["additionalProperties"], false);
+
+    assert_eq!(meeting["properties"]["title"]["type"], "string");
+    assert_eq!(
+        meeting["properties"]["location"]["type"],
+        serde_json::json!(["string", "null"])
+    );
+}
+
+#[test]
+fn test_struct_optional_field_is_nullable_under_strict() {
+    let strict = Calendar::schema().prepare_for(SchemaTarget::OpenAIStrict);
+    let props = &strict["properties"];
+    assert_eq!(props["owner"]["type"], "string");
+    assert_eq!(
+        props["timezone"]["type"],
+        serde_json::json!(["string", "null"])
+    );
+    assert_eq!(strict["required"].as_array().unwrap().len(), 3);
+
+    // The nested enum's variant gets the same treatment
+    let meeting = meeting_fields(&props["next_event"]);
+    assert_eq!(
+        meeting["properties"]["location"]["type"],
+        serde_json::json!(["string", "null"])
+    );
+}
+
+#[test]
+fn test_optional_fields_untouched_for_other_targets() {
+    let schema = Event::schema().prepare_for(SchemaTarget::Gemini);
+    let meeting = meeting_fields(&schema);
+    assert_eq!(meeting["properties"]["location"]["type"], "string");
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Venue {
+    name: String,
+    capacity: Option<u32>,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Session {
+    title: String,
+    speaker: Option<String>,
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Conference {
+    name: String,
+    venue: Option<Venue>,
+    tags: Option<Vec<String>>,
+    sessions: Vec<Session>,
+}
+
+#[test]
+fn test_nested_optional_object_is_nullable_under_strict() {
+    let strict = Conference::schema().prepare_for(SchemaTarget::OpenAIStrict);
+    let venue = &strict["properties"]["venue"];
+
+    assert_eq!(venue["type"], serde_json::json!(["object", "null"]));
+    assert_eq!(venue["additionalProperties"], false);
+    assert_eq!(venue["required"], serde_json::json!(["name", "capacity"]));
+    assert_eq!(venue["properties"]["name"]["type"], "string");
+    assert_eq!(
+        venue["properties"]["capacity"]["type"],
+        serde_json::json!(["integer", "null"])
+    );
+}
+
+#[test]
+fn test_optional_array_is_nullable_under_strict() {
+    let strict = Conference::schema().prepare_for(SchemaTarget::OpenAIStrict);
+    let props = &strict["properties"];
+
+    assert_eq!(props["tags"]["type"], serde_json::json!(["array", "null"]));
+    assert_eq!(props["tags"]["items"]["type"], "string");
+
+    // A required array stays non-null, but optional fields of its items are nullable
+    assert_eq!(props["sessions"]["type"], "array");
+    let session = &props["sessions"]["items"];
+    assert_eq!(session["required"], serde_json::json!(["title", "speaker"]));
+    assert_eq!(
+        session["properties"]["speaker"]["type"],
+        serde_json::json!(["string", "null"])
+    );
+    assert_eq!(
+        strict["required"],
+        serde_json::json!(["name", "venue", "tags", "sessions"])
+    );
+}
+
+#[test]
+fn test_schema_without_required_is_all_nullable_under_strict() {
+    // Hand-written schemas may omit `required`, which makes every property optional
+    let schema = rstructor::Schema::new(serde_json::json!({
+        "type": "object",
+        "properties": {
+            "nickname": {"type": "string"},
+            "age": {"type": "integer"}
+        }
+    }));
+    let strict = schema.prepare_for(SchemaTarget::OpenAIStrict);
+
+    assert_eq!(strict["required"], serde_json::json!(["nickname", "age"]));
+    assert_eq!(
+        strict["properties"]["nickname"]["type"],
+        serde_json::json!(["string", "null"])
+    );
+    assert_eq!(
+        strict["properties"]["age"]["type"],
+        serde_json::json!(["integer", "null"])
+    );
+}
@@ -0,0 +1,76 @@
+//! Tests for `#[llm(format = "...")]` on string fields.
+
+use chrono::{DateTime, Utc};
+use rstructor::{Instructor, SchemaTarget, SchemaTransformKind, SchemaType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Server {
+    #[llm(description = "Admin contact", format = "email")]
+    contact: String,
+
+    #[llm(format = "hostname", pattern = r"^[a-z0-9.-]+$")]
+    host: String,
+
+    #[llm(format = "ipv4")]
+    address: Option<String>,
+
+    #[llm(format = "date-time")]
+    booted_at: String,
+
+    started: DateTime<Utc>,
+
+    name: String,
+}
+
+#[test]
+fn test_format_in_schema() {
+    let schema_json = Server::schema().to_json();
+    let props = &schema_json["properties"];
+
+    assert_eq!(props["contact"]["type"], "string");
+    assert_eq!(props["contact"]["format"], "email");
+    assert_eq!(props["contact"]["description"], "Admin contact");
+    assert_eq!(props["host"]["format"], "hostname");
+    assert_eq!(props["host"]["pattern"], r"^[a-z0-9.-]+$");
+    assert_eq!(props["address"]["format"], "ipv4");
+    assert!(props["name"].get("format").is_none());
+}
+
+#[test]
+fn test_format_kept_for_openai_strict() {
+    let schema = Server::schema().prepare_for(SchemaTarget::OpenAIStrict);
+    let props = &schema["properties"];
+    assert_eq!(props["contact"]["format"], "email");
+    assert_eq!(props["address"]["format"], "ipv4");
+}
+
+#[test]
+fn test_gemini_keeps_only_supported_formats() {
+    let (schema, transforms) = Server::schema().prepare_for_with_report(SchemaTarget::Gemini);
+    let props = &schema["properties"];
+
+    assert!(props["contact"].get("format").is_none());
+    assert!(props["host"].get("format").is_none());
+    assert!(props["address"].get("format").is_none());
+    // Other keywords on the same field are untouched
+    assert_eq!(props["host"]["pattern"], r"^[a-z0-9.-]+$");
+    assert_eq!(props["booted_at"]["format"], "date-time");
+    assert_eq!(props["started"]["format"], "date-time");
+
+    let stripped: Vec<&str> = transforms
+        .iter()
+        .filter(|t| {
+            matches!(&t.kind, SchemaTransformKind::KeywordStripped { keyword } if keyword == "format")
+        })
+        .map(|t| t.path.as_str())
+        .collect();
+    assert_eq!(
+        stripped,
+        vec![
+            "#/properties/contact",
+            "#/properties/host",
+            "#/properties/address"
+        ]
+    );
+}
@@ -0,0 +1,57 @@
+//! Tests for `#[llm(min_length, max_length)]` on string fields.
+
+use rstructor::{Instructor, SchemaTarget, SchemaType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Post {
+    #[llm(description = "Post body", min_length = 1, max_length = 280)]
+    text: String,
+
+    #[llm(max_length = 60)]
+    headline: Option<String>,
+
+    #[llm(min_length = 3)]
+    author: String,
+
+    tag: String,
+}
+
+#[test]
+fn test_length_bounds_on_string_fields() {
+    let schema_json = Post::schema().to_json();
+    let props = &schema_json["properties"];
+
+    assert_eq!(props["text"]["type"], "string");
+    assert_eq!(props["text"]["minLength"], 1);
+    assert_eq!(props["text"]["maxLength"], 280);
+    assert_eq!(props["text"]["description"], "Post body");
+
+    assert_eq!(props["headline"]["maxLength"], 60);
+    assert!(props["headline"].get("minLength").is_none());
+
+    assert_eq!(props["author"]["minLength"], 3);
+    assert!(props["author"].get("maxLength").is_none());
+
+    assert!(props["tag"].get("minLength").is_none());
+    assert!(props["tag"].get("maxLength").is_none());
+}
+
+#[test]
+fn test_length_bounds_survive_provider_preparation() {
+    for target in [SchemaTarget::OpenAIStrict, SchemaTarget::Gemini] {
+        let (schema, transforms) = Post::schema().prepare_for_with_report(target);
+        let props = &schema["properties"];
+        assert_eq!(props["text"]["minLength"], 1, "{:?}", target);
+        assert_eq!(props["text"]["maxLength"], 280, "{:?}", target);
+        assert_eq!(props["headline"]["maxLength"], 60, "{:?}", target);
+        assert!(
+            !transforms
+                .iter()
+                .any(|t| t.to_string().contains("minLength") || t.to_string().contains("maxLength")),
+            "{:?}: {:?}",
+            target,
+            transforms
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Tests for layered client-level system prompts, checked through request previews.
+
+#[cfg(feature = "anthropic")]
+mod common;
+
+#[cfg(feature = "anthropic")]
+use common::serve;
+use rstructor::{Instructor, LLMClient};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "anthropic")]
+use serde_json::{Value, json};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Movie {
+    title: String,
+}
+
+#[cfg(feature = "anthropic")]
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Film {
+    title: String,
+    year: u16,
+}
+
+const BASE: &str = "You are a meticulous data extraction assistant.";
+const TASK: &str = "Only consider films released before 2000.";
+
+#[cfg(feature = "openai")]
+#[test]
+fn test_openai_sends_one_system_message_per_prompt() {
+    let client = rstructor::OpenAIClient::new("test-key")
+        .unwrap()
+        .model("gpt-4o")
+        .system_prompt(BASE)
+        .system_prompt(TASK);
+    let body = client
+        .materialize_request_body::<Movie>("Name a movie")
+        .unwrap();
+
+    let messages = body["messages"].as_array().unwrap();
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[0]["role"], "system");
+    assert_eq!(messages[0]["content"], BASE);
+    assert_eq!(messages[1]["role"], "system");
+    assert_eq!(messages[1]["content"], TASK);
+    assert_eq!(messages[2]["role"], "user");
+    assert_eq!(messages[2]["content"], "Name a movie");
+}
+
+#[cfg(feature = "anthropic")]
+#[test]
+fn test_anthropic_joins_system_prompts() {
+    let client = rstructor::AnthropicClient::new("test-key")
+        .unwrap()
+        .system_prompt(BASE)
+        .system_prompt(TASK);
+    let body = client
+        .materialize_request_body::<Movie>("Name a movie")
+        .unwrap();
+
+    assert_eq!(body["system"], format!("{}\n\n{}", BASE, TASK));
+    let messages = body["messages"].as_array().unwrap();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0]["role"], "user");
+}
+
+#[cfg(feature = "gemini")]
+#[test]
+fn test_gemini_joins_system_prompts_into_system_instruction() {
+    let client = rstructor::GeminiClient::new("test-key")
+        .unwrap()
+        .system_prompt(BASE)
+        .system_prompt(TASK);
+    let body = client
+        .materialize_request_body::<Movie>("Name a movie")
+        .unwrap();
+
+    assert_eq!(
+        body["system_instruction"]["parts"][0]["text"],
+        format!("{}\n\n{}", BASE, TASK)
+    );
+    let contents = body["contents"].as_array().unwrap();
+    assert_eq!(contents.len(), 1);
+    assert_eq!(contents[0]["role"], "user");
+}
+
+#[cfg(feature = "openai")]
+#[test]
+fn test_no_system_prompt_by_default() {
+    let client = rstructor::OpenAIClient::new("test-key")
+        .unwrap()
+        .model("gpt-4o");
+    let body = client
+        .materialize_request_body::<Movie>("Name a movie")
+        .unwrap();
+    assert_eq!(body["messages"].as_array().unwrap().len(), 1);
+}
+
+#[cfg(feature = "mistral")]
+#[test]
+fn test_mistral_sends_system_prompts_after_schema_instruction() {
+    let client = rstructor::MistralClient::new("test-key")
+        .unwrap()
+        .system_prompt(BASE)
+        .system_prompt(TASK);
+    let body = client
+        .materialize_request_body::<Movie>("Name a movie")
+        .unwrap();
+
+    let messages = body["messages"].as_array().unwrap();
+    let roles: Vec<&str> = messages
+        .iter()
+        .map(|m| m["role"].as_str().unwrap())
+        .collect();
+    assert_eq!(roles, vec!["system", "system", "system", "user"]);
+    assert_eq!(messages[1]["content"], BASE);
+    assert_eq!(messages[2]["content"], TASK);
+}
+
+#[cfg(feature = "ollama")]
+#[test]
+fn test_ollama_sends_system_prompts_after_schema_instruction() {
+    let client = rstructor::OllamaClient::new().system_prompt(BASE);
+    let body = client
+        .materialize_request_body::<Movie>("Name a movie")
+        .unwrap();
+
+    let messages = body["messages"].as_array().unwrap();
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[1]["role"], "system");
+    assert_eq!(messages[1]["content"], BASE);
+    assert_eq!(messages[2]["role"], "user");
+}
+
+/// A Messages API response holding `text`
+#[cfg(feature = "anthropic")]
+fn message(text: &str) -> Value {
+    json!({
+        "model": "claude-sonnet-4-5-20250929",
+        "content": [{ "type": "text", "text": text }],
+        "usage": { "input_tokens": 10, "output_tokens": 5 }
+    })
+}
+
+#[cfg(feature = "anthropic")]
+#[tokio::test]
+async fn test_system_prompt_stays_out_of_retry_turns() {
+    let (base_url, rx) = serve(vec![
+        message(r#"{"title": "Heat", "year": "1995"}"#),
+        message(r#"{"title": "Heat", "year": 1995}"#),
+    ]);
+    let client = rstructor::AnthropicClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .system_prompt(BASE);
+    let film: Film = client.materialize("Name a movie").await.unwrap();
+    assert_eq!(film.year, 1995);
+
+    rx.recv().unwrap();
+    let retry = rx.recv().unwrap().body;
+    assert_eq!(retry["system"], BASE);
+    let roles: Vec<&str> = retry["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|m| m["role"].as_str().unwrap())
+        .collect();
+    assert_eq!(roles, vec!["user", "assistant", "user"]);
+}
@@ -247,6 +247,26 @@ mod timeout_tests {
         // (We can't access config directly, but default behavior means no timeout)
     }
 
+    #[cfg(feature = "openai")]
+    #[tokio::test]
+    async fn test_deadline_bounds_whole_call() {
+        // A local listener that accepts connections but never responds, so each
+        // request would hang without the deadline
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let client = OpenAIClient::new("test-key")
+            .expect("Failed to create OpenAI client")
+            .base_url(base_url)
+            .max_retries(5)
+            .deadline(Duration::from_millis(200));
+
+        let started = std::time::Instant::now();
+        let result = client.materialize::<TestStruct>("test").await;
+        assert!(matches!(result, Err(RStructorError::Timeout)));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
     // Note: Tests that make actual API calls with reasonable timeouts are intentionally
     // omitted here to keep unit tests fast. The timeout functionality is already well-covered
     // by the tests above that verify:
@@ -0,0 +1,93 @@
+//! Tests for date fields serialized as Unix timestamps.
+
+use chrono::{DateTime, TimeZone, Utc};
+use rstructor::{Instructor, SchemaType};
+use serde::{Deserialize, Serialize};
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Event {
+    #[llm(description = "When the event was created")]
+    #[serde(with = "chrono::serde::ts_seconds")]
+    created_at: DateTime<Utc>,
+
+    #[serde(with = "chrono::serde::ts_milliseconds_option", default)]
+    updated_at: Option<DateTime<Utc>>,
+
+    #[serde(
+        serialize_with = "chrono::serde::ts_seconds::serialize",
+        deserialize_with = "chrono::serde::ts_seconds::deserialize",
+        rename = "startsAt"
+    )]
+    starts_at: DateTime<Utc>,
+
+    // Default serde format stays an ISO-8601 string
+    published_at: DateTime<Utc>,
+
+    #[llm(as_timestamp)]
+    #[serde(with = "epoch")]
+    expires_at: DateTime<Utc>,
+}
+
+/// A custom helper the derive cannot recognize by name
+mod epoch {
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(dt: &DateTime<Utc>, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_i64(dt.timestamp())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<DateTime<Utc>, D::Error> {
+        let secs = i64::deserialize(d)?;
+        DateTime::from_timestamp(secs, 0).ok_or_else(|| serde::de::Error::custom("out of range"))
+    }
+}
+
+#[test]
+fn test_timestamp_fields_are_integers() {
+    let schema_json = Event::schema().to_json();
+    let props = &schema_json["properties"];
+
+    assert_eq!(props["created_at"]["type"], "integer");
+    assert!(props["created_at"].get("format").is_none());
+    assert_eq!(
+        props["created_at"]["description"],
+        "When the event was created (Unix timestamp in seconds)"
+    );
+
+    assert_eq!(props["updated_at"]["type"], "integer");
+    assert_eq!(
+        props["updated_at"]["description"],
+        "Unix timestamp in milliseconds"
+    );
+
+    // serialize_with paths are recognized and a later rename still applies
+    assert_eq!(props["startsAt"]["type"], "integer");
+
+    assert_eq!(props["published_at"]["type"], "string");
+    assert_eq!(props["published_at"]["format"], "date-time");
+
+    assert_eq!(props["expires_at"]["type"], "integer");
+}
+
+#[test]
+fn test_timestamp_schema_matches_serde() {
+    let at = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+    let event = Event {
+        created_at: at,
+        updated_at: Some(at),
+        starts_at: at,
+        published_at: at,
+        expires_at: at,
+    };
+    let value = serde_json::to_value(&event).unwrap();
+    assert!(value["created_at"].is_i64());
+    assert!(value["updated_at"].is_i64());
+    assert!(value["startsAt"].is_i64());
+    assert!(value["expires_at"].is_i64());
+    assert!(value["published_at"].is_string());
+
+    // An integer the schema asks for deserializes back
+    let parsed: Event = serde_json::from_value(value).unwrap();
+    assert_eq!(parsed.created_at, at);
+}
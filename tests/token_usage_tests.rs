@@ -0,0 +1,185 @@
+//! Tests for how token totals are computed, using local stubs of the provider APIs.
+
+#![cfg(all(feature = "openai", feature = "gemini"))]
+
+mod common;
+
+use common::serve_once;
+use rstructor::{
+    GeminiClient, Instructor, LLMClient, MaterializeResult, OpenAIClient, PricingTable, TokenUsage,
+    UsageTracker,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Greeting {
+    text: String,
+}
+
+#[test]
+fn test_total_falls_back_to_sum() {
+    let usage = TokenUsage::new("gpt-4o", 100, 20);
+    assert_eq!(usage.reported_total_tokens, None);
+    assert_eq!(usage.total_tokens(), 120);
+    assert_eq!(usage.with_total_tokens(Some(135)).total_tokens(), 135);
+}
+
+#[test]
+fn test_tracker_totals_match_per_call_totals() {
+    let tracker = UsageTracker::new();
+    let calls = [
+        TokenUsage::new("gemini-2.5-flash", 100, 20).with_total_tokens(Some(150)),
+        TokenUsage::new("gpt-4o", 50, 10),
+    ];
+    for usage in &calls {
+        tracker.record(usage);
+    }
+
+    let totals = tracker.totals();
+    assert_eq!(totals.input_tokens, 150);
+    assert_eq!(totals.output_tokens, 30);
+    assert_eq!(totals.other_tokens, 30);
+    assert_eq!(
+        totals.total_tokens(),
+        calls.iter().map(TokenUsage::total_tokens).sum::<u64>()
+    );
+}
+
+#[test]
+fn test_tracker_sums_known_costs() {
+    let tracker = UsageTracker::new();
+    tracker.record(&TokenUsage::new("llama3.2", 1000, 100));
+    assert_eq!(tracker.totals().cost_usd, None);
+
+    tracker.record(&TokenUsage::new("my-finetune", 2_000_000, 500_000).with_pricing(0.50, 2.00));
+    tracker.record(&TokenUsage::new("gpt-4o-2024-08-06", 1_000_000, 100_000));
+    let totals = tracker.totals();
+    assert_eq!(totals.requests, 3);
+    assert_eq!(totals.cost_usd, Some(5.5));
+
+    tracker.reset();
+    assert_eq!(tracker.totals().cost_usd, None);
+}
+
+#[tokio::test]
+async fn test_openai_reported_total_is_kept() {
+    let (base_url, _) = serve_once(json!({
+        "model": "gpt-4o",
+        "choices": [{
+            "message": { "role": "assistant", "content": "Hello" },
+            "finish_reason": "stop"
+        }],
+        "usage": { "prompt_tokens": 10, "completion_tokens": 5, "total_tokens": 15 }
+    }));
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o");
+
+    let usage = client
+        .generate_with_metadata("Say hello")
+        .await
+        .unwrap()
+        .usage
+        .unwrap();
+    assert_eq!(usage.reported_total_tokens, Some(15));
+    assert_eq!(usage.total_tokens(), 15);
+}
+
+#[tokio::test]
+async fn test_gemini_total_includes_thinking_tokens() {
+    let (base_url, _) = serve_once(json!({
+        "candidates": [{
+            "content": { "role": "model", "parts": [{ "text": "Hello" }] },
+            "finishReason": "STOP"
+        }],
+        "usageMetadata": {
+            "promptTokenCount": 10,
+            "candidatesTokenCount": 5,
+            "thoughtsTokenCount": 40,
+            "totalTokenCount": 55
+        }
+    }));
+    let client = GeminiClient::new("test-key").unwrap().base_url(base_url);
+
+    let usage = client
+        .generate_with_metadata("Say hello")
+        .await
+        .unwrap()
+        .usage
+        .unwrap();
+    assert_eq!(usage.input_tokens, 10);
+    assert_eq!(usage.output_tokens, 5);
+    assert_eq!(usage.total_tokens(), 55);
+}
+
+#[test]
+fn test_cost_from_builtin_pricing() {
+    let usage = TokenUsage::new("gpt-4o", 1_000_000, 1_000_000);
+    assert_eq!(usage.cost_usd(), Some(12.5));
+
+    // Dated snapshots use their undated model's price
+    let snapshot = TokenUsage::new("gpt-4o-mini-2024-07-18", 1_000_000, 1_000_000);
+    assert_eq!(snapshot.cost_usd(), Some(0.75));
+    let claude = TokenUsage::new("claude-sonnet-4-5-20250929", 2_000_000, 0);
+    assert_eq!(claude.cost_usd(), Some(6.0));
+}
+
+#[test]
+fn test_unlisted_variant_is_not_priced_as_its_family() {
+    let pricing = PricingTable::builtin();
+    assert!(pricing.get("o1").is_some());
+    assert_eq!(pricing.get("o1-pro"), None);
+    assert_eq!(pricing.get("o3-deep-research"), None);
+    assert_eq!(pricing.get("o3-deep-research-2025-06-26"), None);
+    // Only a whole date counts as a snapshot suffix
+    assert_eq!(pricing.get("gpt-4o-2024"), None);
+}
+
+#[test]
+fn test_unknown_model_has_no_cost() {
+    assert_eq!(TokenUsage::new("llama3.2", 1000, 100).cost_usd(), None);
+    assert_eq!(PricingTable::builtin().get("llama3.2"), None);
+}
+
+#[test]
+fn test_custom_pricing_overrides_builtin() {
+    let local = TokenUsage::new("llama3.2", 1_000_000, 1_000_000).with_pricing(0.0, 0.0);
+    assert_eq!(local.cost_usd(), Some(0.0));
+
+    let discounted = TokenUsage::new("gpt-4o", 1_000_000, 0).with_pricing(1.25, 5.0);
+    assert_eq!(discounted.cost_usd(), Some(1.25));
+}
+
+#[test]
+fn test_materialize_result_cost() {
+    let without_usage = MaterializeResult::from_data(());
+    assert_eq!(without_usage.cost_usd(), None);
+
+    let with_usage = MaterializeResult::new((), Some(TokenUsage::new("gpt-4o", 400_000, 0)));
+    assert_eq!(with_usage.cost_usd(), Some(1.0));
+}
+
+#[tokio::test]
+async fn test_materialize_reports_cost_for_response_model() {
+    let (base_url, _) = serve_once(json!({
+        "model": "gpt-4o-mini-2024-07-18",
+        "choices": [{
+            "message": { "role": "assistant", "content": "{\"text\": \"Hello\"}" },
+            "finish_reason": "stop"
+        }],
+        "usage": { "prompt_tokens": 1_000_000, "completion_tokens": 0, "total_tokens": 1_000_000 }
+    }));
+    let client = OpenAIClient::new("test-key")
+        .unwrap()
+        .base_url(base_url)
+        .model("gpt-4o-mini");
+
+    let result = client
+        .materialize_with_metadata::<Greeting>("Say hello")
+        .await
+        .unwrap();
+    assert_eq!(result.data.text, "Hello");
+    assert_eq!(result.cost_usd(), Some(0.15));
+}
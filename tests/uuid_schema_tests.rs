@@ -0,0 +1,64 @@
+//! Tests for the `SchemaType` impl on `uuid::Uuid` (behind the `uuid` feature).
+#![cfg(feature = "uuid")]
+
+use std::collections::HashMap;
+
+use rstructor::{Instructor, SchemaType};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+struct Batch {
+    id: Uuid,
+    members: Vec<Uuid>,
+    owners: HashMap<String, Option<Uuid>>,
+}
+
+#[test]
+fn test_uuid_schema() {
+    let schema_json = Uuid::schema().to_json();
+    assert_eq!(schema_json["type"], "string");
+    assert_eq!(schema_json["format"], "uuid");
+}
+
+#[test]
+fn test_vec_of_uuid_schema() {
+    let schema_json = <Vec<Uuid>>::schema().to_json();
+    assert_eq!(schema_json["type"], "array");
+    assert_eq!(schema_json["items"]["format"], "uuid");
+}
+
+#[test]
+fn test_uuid_in_map_values() {
+    let schema_json = <HashMap<String, Option<Uuid>>>::schema().to_json();
+    assert_eq!(schema_json["additionalProperties"]["format"], "uuid");
+}
+
+#[test]
+fn test_uuid_schema_matches_derive() {
+    let schema_json = Batch::schema().to_json();
+    let props = &schema_json["properties"];
+    assert_eq!(props["id"], Uuid::schema().to_json());
+    assert_eq!(props["members"]["items"], Uuid::schema().to_json());
+    assert_eq!(props["owners"]["additionalProperties"]["format"], "uuid");
+}
+
+#[derive(Instructor, Serialize, Deserialize, Debug)]
+enum Lookup {
+    ById { id: Uuid, aliases: Vec<uuid::Uuid> },
+    ByName(String),
+}
+
+#[test]
+fn test_enum_variant_uuid_fields() {
+    let schema_json = Lookup::schema().to_json();
+    let by_id = schema_json["oneOf"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|variant| variant["properties"].get("ById").is_some())
+        .expect("ById variant");
+    let props = &by_id["properties"]["ById"]["properties"];
+    assert_eq!(props["id"]["format"], "uuid");
+    assert_eq!(props["aliases"]["items"]["format"], "uuid");
+}